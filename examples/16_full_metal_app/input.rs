@@ -133,9 +133,13 @@ pub fn start_capture(
     let mut s = SCStream::new(&filter_to_use, &sc_config);
     if !mic_only {
         s.add_output_handler(handler.clone(), SCStreamOutputType::Screen);
-        s.add_output_handler(handler.clone(), SCStreamOutputType::Audio);
+        if sc_config.captures_audio() {
+            s.add_output_handler(handler.clone(), SCStreamOutputType::Audio);
+        }
+    }
+    if sc_config.captures_microphone() {
+        s.add_output_handler(handler, SCStreamOutputType::Microphone);
     }
-    s.add_output_handler(handler, SCStreamOutputType::Microphone);
 
     match s.start_capture() {
         Ok(()) => {
@@ -78,7 +78,7 @@ impl Future for AsyncShareableContentFuture {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         Pin::new(&mut self.inner)
             .poll(cx)
-            .map(|r| r.map_err(SCError::NoShareableContent))
+            .map(crate::shareable_content::content_or_empty)
     }
 }
 
@@ -134,6 +134,7 @@ impl AsyncSCShareableContentOptions {
 
     /// Asynchronously get the shareable content with these options
     pub fn get(self) -> AsyncShareableContentFuture {
+        crate::shareable_content::ensure_core_graphics_initialized();
         let (future, context) = AsyncCompletion::create();
 
         unsafe {
@@ -240,6 +241,12 @@ unsafe impl Sync for AsyncSampleSender {}
 /// Provides async methods for stream lifecycle and frame iteration.
 /// **Executor-agnostic** - works with any async runtime.
 ///
+/// Frames are queued as owned [`CMSampleBuffer`](crate::cm::CMSampleBuffer)
+/// handles, so a frame returned by [`next`](Self::next) stays valid however
+/// long after its capture callback it is consumed. Frames that are still
+/// queued or held count against the stream's `IOSurface` pool; keep
+/// `buffer_capacity` below the configured queue depth.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -675,6 +682,42 @@ extern "C" fn screenshot_output_callback(
     }
 }
 
+/// Capture a single screenshot as a `CGImage` without blocking the executor
+///
+/// Shorthand for [`AsyncSCScreenshotManager::capture_image`]. The Swift
+/// completion wakes the returned future instead of parking a thread.
+///
+/// # Errors
+/// The future resolves to an error if:
+/// - Screen recording permission is not granted
+/// - The capture fails for any reason
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use screencapturekit::async_api::{self, AsyncSCShareableContent};
+/// use screencapturekit::stream::configuration::SCStreamConfiguration;
+/// use screencapturekit::stream::content_filter::SCContentFilter;
+///
+/// let content = AsyncSCShareableContent::get().await?;
+/// let display = &content.displays()[0];
+/// let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+/// let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+///
+/// let image = async_api::capture_image(&filter, &config).await?;
+/// println!("Screenshot: {}x{}", image.width(), image.height());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "macos_14_0")]
+pub fn capture_image(
+    content_filter: &SCContentFilter,
+    configuration: &SCStreamConfiguration,
+) -> AsyncScreenshotFuture<crate::screenshot_manager::CGImage> {
+    AsyncSCScreenshotManager::capture_image(content_filter, configuration)
+}
+
 // ============================================================================
 // AsyncSCContentSharingPicker - Async content sharing picker (macOS 14.0+)
 // ============================================================================
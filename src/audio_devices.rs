@@ -4,6 +4,21 @@
 
 use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
 
+/// A sample rate and channel count an audio input device can deliver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioDeviceFormat {
+    /// Sample rate in Hz
+    pub sample_rate: i32,
+    /// Number of channels
+    pub channels: i32,
+}
+
+impl std::fmt::Display for AudioDeviceFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} Hz, {} ch", self.sample_rate, self.channels)
+    }
+}
+
 /// Represents an audio input device (microphone).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioInputDevice {
@@ -91,4 +106,70 @@ impl AudioInputDevice {
             _ => None,
         }
     }
+
+    /// Find the device with the given unique ID, if it is connected.
+    pub fn with_id(id: &str) -> Option<Self> {
+        Self::list().into_iter().find(|device| device.id == id)
+    }
+
+    /// List the sample rate and channel count combinations the device supports.
+    ///
+    /// Returns an empty list if the device is gone or doesn't report its formats.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use screencapturekit::audio_devices::AudioInputDevice;
+    ///
+    /// if let Some(device) = AudioInputDevice::default_device() {
+    ///     for format in device.supported_formats() {
+    ///         println!("{}: {format}", device.name);
+    ///     }
+    /// }
+    /// ```
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn supported_formats(&self) -> Vec<AudioDeviceFormat> {
+        let Ok(id) = std::ffi::CString::new(self.id.as_str()) else {
+            return Vec::new();
+        };
+        let count = unsafe {
+            crate::ffi::sc_audio_get_input_device_formats(
+                id.as_ptr(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if count <= 0 {
+            return Vec::new();
+        }
+
+        let mut sample_rates = vec![0.0; count as usize];
+        let mut channels = vec![0; count as usize];
+        // The device may have changed between the two calls
+        let written = unsafe {
+            crate::ffi::sc_audio_get_input_device_formats(
+                id.as_ptr(),
+                sample_rates.as_mut_ptr(),
+                channels.as_mut_ptr(),
+                count,
+            )
+        }
+        .clamp(0, count) as usize;
+
+        sample_rates
+            .into_iter()
+            .zip(channels)
+            .take(written)
+            .map(|(sample_rate, channels)| AudioDeviceFormat {
+                // Rates are whole numbers of Hz
+                sample_rate: sample_rate.round() as i32,
+                channels,
+            })
+            .collect()
+    }
 }
@@ -132,6 +132,18 @@ impl CGRect {
         self.width <= 0.0 || self.height <= 0.0
     }
 
+    /// Check if a point lies inside the rect
+    ///
+    /// Like `CGRectContainsPoint`, the minimum edges are inside and the
+    /// maximum edges are not.
+    pub fn contains_point(&self, point: CGPoint) -> bool {
+        !self.is_empty()
+            && point.x >= self.min_x()
+            && point.x < self.max_x()
+            && point.y >= self.min_y()
+            && point.y < self.max_y()
+    }
+
     /// Check if rect is null (both position and size are zero)
     pub const fn is_null(&self) -> bool {
         self.x == 0.0 && self.y == 0.0 && self.width == 0.0 && self.height == 0.0
@@ -345,5 +357,67 @@ impl fmt::Display for CGPoint {
     }
 }
 
+/// RGBA color with components from 0.0 to 1.0
+///
+/// A plain value type, interpreted in the sRGB color space where it is drawn.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::cg::CGColor;
+///
+/// let highlight = CGColor::rgba(1.0, 0.8, 0.0, 0.5);
+/// assert_eq!(CGColor::RED, CGColor::rgb(1.0, 0.0, 0.0));
+/// assert_eq!(highlight.alpha, 0.5);
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CGColor {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+    pub alpha: f64,
+}
+
+impl CGColor {
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    pub const RED: Self = Self::rgb(1.0, 0.0, 0.0);
+    pub const GREEN: Self = Self::rgb(0.0, 1.0, 0.0);
+    pub const BLUE: Self = Self::rgb(0.0, 0.0, 1.0);
+    pub const YELLOW: Self = Self::rgb(1.0, 1.0, 0.0);
+
+    /// Create a color with the given alpha
+    pub const fn rgba(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Create an opaque color
+    pub const fn rgb(red: f64, green: f64, blue: f64) -> Self {
+        Self::rgba(red, green, blue, 1.0)
+    }
+}
+
+impl Default for CGColor {
+    fn default() -> Self {
+        Self::BLACK
+    }
+}
+
+impl fmt::Display for CGColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rgba({}, {}, {}, {})",
+            self.red, self.green, self.blue, self.alpha
+        )
+    }
+}
+
 /// `CGDisplayID` type alias
 pub type CGDisplayID = u32;
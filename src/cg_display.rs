@@ -1,6 +1,10 @@
+use crate::cm::CMTime;
 use crate::error::SCError;
 use crate::screenshot_manager::CGImage;
 
+/// 显示器未报告刷新率时（例如部分可变刷新率面板）假定的刷新率
+const FALLBACK_REFRESH_RATE: f64 = 60.0;
+
 /// CoreGraphics 显示模式（当前模式）
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct DisplayMode {
@@ -38,6 +42,34 @@ impl DisplayMode {
     pub const fn refresh_rate(&self) -> f64 {
         self.refresh_rate
     }
+
+    /// 与该模式刷新率匹配的帧间隔（`1 / refresh_rate`）
+    ///
+    /// 以毫赫兹为时间基，59.94 Hz、120 Hz（ProMotion）等刷新率可得到精确间隔。
+    /// 刷新率为 0（显示器未报告固定刷新率）时回退到 60 Hz。
+    ///
+    /// 可直接传给
+    /// [`SCStreamConfiguration::with_minimum_frame_interval`](crate::stream::configuration::SCStreamConfiguration::with_minimum_frame_interval)：
+    ///
+    /// ```no_run
+    /// use screencapturekit::cg_display::CGDisplay;
+    /// use screencapturekit::stream::configuration::SCStreamConfiguration;
+    ///
+    /// let mode = CGDisplay::new(1).display_mode().unwrap_or_default();
+    /// let config = SCStreamConfiguration::new().with_minimum_frame_interval(&mode.frame_interval());
+    /// ```
+    #[must_use]
+    pub fn frame_interval(&self) -> CMTime {
+        let refresh_rate = if self.refresh_rate > 0.0 {
+            self.refresh_rate
+        } else {
+            FALLBACK_REFRESH_RATE
+        };
+        // 毫赫兹时间基保证小数刷新率精确
+        #[allow(clippy::cast_possible_truncation)]
+        let timescale = (refresh_rate * 1000.0).round() as i32;
+        CMTime::new(1000, timescale)
+    }
 }
 
 /// CoreGraphics 显示设备（基于 CGDirectDisplayID）
@@ -113,6 +145,43 @@ impl CGDisplay {
         }
     }
 
+    /// 获取显示器旋转角度（顺时针，单位：度；0、90、180 或 270）
+    #[must_use]
+    pub fn rotation(&self) -> f64 {
+        unsafe { crate::ffi::cg_display_rotation(self.id) }
+    }
+
+    /// 显示器是否处于镜像组中
+    ///
+    /// 镜像源与镜像目标都会返回 `true`。
+    #[must_use]
+    pub fn is_mirrored(&self) -> bool {
+        unsafe { crate::ffi::cg_display_is_in_mirror_set(self.id) }
+    }
+
+    /// 若该显示器正在镜像另一台显示器，返回被镜像的源显示器
+    ///
+    /// 捕获镜像目标（例如镜像主屏的"显示器 2"）得到的是源显示器的内容，
+    /// 应用可据此提示用户改为选择源显示器：
+    ///
+    /// ```no_run
+    /// use screencapturekit::cg_display::CGDisplay;
+    ///
+    /// let display = CGDisplay::new(2);
+    /// if let Some(source) = display.mirror_source() {
+    ///     eprintln!(
+    ///         "display {} mirrors display {}; capture will show its content",
+    ///         display.id(),
+    ///         source.id()
+    ///     );
+    /// }
+    /// ```
+    #[must_use]
+    pub fn mirror_source(&self) -> Option<Self> {
+        let source = unsafe { crate::ffi::cg_display_mirrors_display(self.id) };
+        (source != 0).then_some(Self::new(source))
+    }
+
     /// 创建当前显示器的 CGImage（适用于低版本截图回退）
     pub fn create_image(&self) -> Option<CGImage> {
         let image_ptr = unsafe { crate::ffi::cg_display_create_image(self.id) };
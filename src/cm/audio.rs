@@ -7,8 +7,10 @@
 //! - [`AudioBuffer`] - Single audio buffer containing sample data
 //! - [`AudioBufferList`] - Collection of audio buffers (typically one per channel)
 //! - [`AudioBufferRef`] - Reference to an audio buffer with convenience methods
+//! - [`AudioChannels`] - PCM samples copied out per channel
 
-use super::ffi;
+use super::{ffi, AudioStreamBasicDescription};
+use crate::error::SCError;
 use std::fmt;
 
 /// Raw audio buffer containing sample data
@@ -251,3 +253,172 @@ impl<'a> Iterator for AudioBufferListIter<'a> {
         }
     }
 }
+
+/// Sample format of PCM audio data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioSampleFormat {
+    /// 32-bit float, nominally in `-1.0..=1.0`
+    F32,
+    /// 16-bit signed integer
+    I16,
+    /// 32-bit signed integer
+    I32,
+}
+
+/// PCM audio copied out of a sample buffer, one vector per channel
+///
+/// Created by [`CMSampleBuffer::audio_channels`](crate::cm::CMSampleBuffer::audio_channels).
+/// The data is always planar: interleaved buffers are split into their
+/// channels, and non-interleaved buffers are taken one channel each, so
+/// `channels[c][i]` is frame `i` of channel `c` either way.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioChannels {
+    /// 32-bit float samples
+    F32(Vec<Vec<f32>>),
+    /// 16-bit integer samples
+    I16(Vec<Vec<i16>>),
+    /// 32-bit integer samples
+    I32(Vec<Vec<i32>>),
+}
+
+impl AudioChannels {
+    /// Sample format the audio was delivered in
+    pub const fn sample_format(&self) -> AudioSampleFormat {
+        match self {
+            Self::F32(_) => AudioSampleFormat::F32,
+            Self::I16(_) => AudioSampleFormat::I16,
+            Self::I32(_) => AudioSampleFormat::I32,
+        }
+    }
+
+    /// Number of channels
+    pub fn channel_count(&self) -> usize {
+        match self {
+            Self::F32(channels) => channels.len(),
+            Self::I16(channels) => channels.len(),
+            Self::I32(channels) => channels.len(),
+        }
+    }
+
+    /// Number of frames, i.e. samples per channel
+    pub fn frame_count(&self) -> usize {
+        match self {
+            Self::F32(channels) => channels.first().map_or(0, Vec::len),
+            Self::I16(channels) => channels.first().map_or(0, Vec::len),
+            Self::I32(channels) => channels.first().map_or(0, Vec::len),
+        }
+    }
+
+    /// The samples as floats in `-1.0..=1.0`, whatever the source format
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_f32(&self) -> Vec<Vec<f32>> {
+        match self {
+            Self::F32(channels) => channels.clone(),
+            Self::I16(channels) => channels
+                .iter()
+                .map(|channel| channel.iter().map(|&s| f32::from(s) / 32_768.0).collect())
+                .collect(),
+            Self::I32(channels) => channels
+                .iter()
+                .map(|channel| {
+                    channel
+                        .iter()
+                        .map(|&s| s as f32 / 2_147_483_648.0)
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Copy linear PCM out of `list` into planar channels
+///
+/// Each buffer holds `number_channels` interleaved channels, which is 1 for
+/// every buffer of a non-interleaved list, so both layouts split the same way.
+pub(super) fn copy_channels(
+    list: &AudioBufferList,
+    format: &AudioStreamBasicDescription,
+) -> Result<AudioChannels, SCError> {
+    let big_endian = format.is_big_endian();
+    let buffers: Vec<(usize, &[u8])> = list
+        .iter()
+        .map(|buffer| (buffer.number_channels as usize, buffer.data()))
+        .collect();
+
+    match (format.is_float(), format.bits_per_channel) {
+        (true, 32) => Ok(AudioChannels::F32(deinterleave(&buffers, |b| {
+            if big_endian {
+                f32::from_be_bytes(b)
+            } else {
+                f32::from_le_bytes(b)
+            }
+        }))),
+        (false, 16) => Ok(AudioChannels::I16(deinterleave(&buffers, |b| {
+            if big_endian {
+                i16::from_be_bytes(b)
+            } else {
+                i16::from_le_bytes(b)
+            }
+        }))),
+        (false, 32) => Ok(AudioChannels::I32(deinterleave(&buffers, |b| {
+            if big_endian {
+                i32::from_be_bytes(b)
+            } else {
+                i32::from_le_bytes(b)
+            }
+        }))),
+        (is_float, bits) => Err(SCError::InvalidBuffer(format!(
+            "Unsupported PCM format: {bits}-bit {}",
+            if is_float { "float" } else { "integer" }
+        ))),
+    }
+}
+
+/// Split each `(channels, bytes)` buffer into its channels, `N` bytes per
+/// sample
+fn deinterleave<T, const N: usize>(
+    buffers: &[(usize, &[u8])],
+    decode: impl Fn([u8; N]) -> T,
+) -> Vec<Vec<T>> {
+    let mut planes = Vec::new();
+    for &(channels, data) in buffers {
+        let channels = channels.max(1);
+        let first = planes.len();
+        planes.extend((0..channels).map(|_| Vec::with_capacity(data.len() / N / channels)));
+        for (index, sample) in data.chunks_exact(N).enumerate() {
+            if let Ok(bytes) = <[u8; N]>::try_from(sample) {
+                planes[first + index % channels].push(decode(bytes));
+            }
+        }
+    }
+    planes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deinterleave_interleaved_stereo() {
+        let data: Vec<u8> = [1i16, -1, 2, -2, 3, -3]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let planes = deinterleave(&[(2, &data)], i16::from_le_bytes);
+        assert_eq!(planes, vec![vec![1, 2, 3], vec![-1, -2, -3]]);
+    }
+
+    #[test]
+    fn test_deinterleave_non_interleaved() {
+        let left: Vec<u8> = [0.5f32, 0.25]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let right: Vec<u8> = [-0.5f32, -0.25]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let planes = deinterleave(&[(1, &left), (1, &right)], f32::from_le_bytes);
+        assert_eq!(planes, vec![vec![0.5, 0.25], vec![-0.5, -0.25]]);
+    }
+}
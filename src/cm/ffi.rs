@@ -14,6 +14,10 @@ extern "C" {
         sample_buffer: *mut std::ffi::c_void,
         out_value: *mut u64,
     ) -> bool;
+    pub fn cm_sample_buffer_get_sequence_number(
+        sample_buffer: *mut std::ffi::c_void,
+        out_value: *mut u64,
+    ) -> bool;
     pub fn cm_sample_buffer_get_scale_factor(
         sample_buffer: *mut std::ffi::c_void,
         out_value: *mut f64,
@@ -50,6 +54,7 @@ extern "C" {
     ) -> bool;
     pub fn cm_sample_buffer_free_dirty_rects(rects_ptr: *mut std::ffi::c_void);
 
+    pub fn cm_sample_buffer_get_host_time(sample_buffer: *mut std::ffi::c_void) -> u64;
     pub fn cm_sample_buffer_get_presentation_timestamp(
         sample_buffer: *mut std::ffi::c_void,
         out_value: *mut i64,
@@ -172,6 +177,10 @@ extern "C" {
     pub fn cm_format_description_get_audio_format_flags(
         format_description: *mut std::ffi::c_void,
     ) -> u32;
+    pub fn cm_format_description_get_audio_stream_basic_description(
+        format_description: *mut std::ffi::c_void,
+        out_description: *mut std::ffi::c_void,
+    ) -> bool;
 
     // Hash functions
     pub fn cm_sample_buffer_hash(sample_buffer: *mut std::ffi::c_void) -> usize;
@@ -286,6 +295,7 @@ extern "C" {
         extra_rows_on_top: *mut usize,
         extra_rows_on_bottom: *mut usize,
     );
+    pub fn cv_pixel_buffer_is_hdr(pixel_buffer: *mut std::ffi::c_void) -> bool;
 
     pub fn cm_sample_buffer_create_for_image_buffer(
         image_buffer: *mut std::ffi::c_void,
@@ -3,6 +3,7 @@
 #![allow(dead_code)]
 
 use super::ffi;
+use crate::utils::four_char_code::FourCharCode;
 use std::fmt;
 
 pub struct CMFormatDescription(*mut std::ffi::c_void);
@@ -44,6 +45,87 @@ pub mod media_types {
     pub const TIMECODE: FourCharCode = FourCharCode::from_bytes(*b"tmcd");
 }
 
+/// Media type of a format description
+///
+/// The typed form of the [`media_types`] constants, returned by
+/// [`CMFormatDescription::media_kind`]. Types without a variant are kept as
+/// [`Raw`](Self::Raw).
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::cm::{media_types, MediaType};
+///
+/// assert_eq!(MediaType::from(media_types::AUDIO), MediaType::Audio);
+/// assert_eq!(MediaType::Video.four_char_code(), media_types::VIDEO);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    /// Video ('vide')
+    Video,
+    /// Audio ('soun')
+    Audio,
+    /// Muxed audio and video ('mux ')
+    Muxed,
+    /// Text or subtitles ('text')
+    Text,
+    /// Closed captions ('clcp')
+    ClosedCaption,
+    /// Timed metadata ('meta')
+    Metadata,
+    /// Timecode ('tmcd')
+    Timecode,
+    /// Any other media type
+    Raw(FourCharCode),
+}
+
+impl MediaType {
+    /// The `FourCharCode` this media type is identified by
+    #[must_use]
+    pub const fn four_char_code(self) -> FourCharCode {
+        match self {
+            Self::Video => media_types::VIDEO,
+            Self::Audio => media_types::AUDIO,
+            Self::Muxed => media_types::MUXED,
+            Self::Text => media_types::TEXT,
+            Self::ClosedCaption => media_types::CLOSED_CAPTION,
+            Self::Metadata => media_types::METADATA,
+            Self::Timecode => media_types::TIMECODE,
+            Self::Raw(code) => code,
+        }
+    }
+}
+
+impl From<FourCharCode> for MediaType {
+    fn from(code: FourCharCode) -> Self {
+        match code {
+            media_types::VIDEO => Self::Video,
+            media_types::AUDIO => Self::Audio,
+            media_types::MUXED => Self::Muxed,
+            media_types::TEXT => Self::Text,
+            media_types::CLOSED_CAPTION => Self::ClosedCaption,
+            media_types::METADATA => Self::Metadata,
+            media_types::TIMECODE => Self::Timecode,
+            other => Self::Raw(other),
+        }
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Video => write!(f, "Video"),
+            Self::Audio => write!(f, "Audio"),
+            Self::Muxed => write!(f, "Muxed"),
+            Self::Text => write!(f, "Text"),
+            Self::ClosedCaption => write!(f, "Closed Caption"),
+            Self::Metadata => write!(f, "Metadata"),
+            Self::Timecode => write!(f, "Timecode"),
+            Self::Raw(code) => write!(f, "{code}"),
+        }
+    }
+}
+
 /// Common codec type constants
 pub mod codec_types {
     use crate::utils::four_char_code::FourCharCode;
@@ -61,6 +143,14 @@ pub mod codec_types {
     pub const PRORES_422: FourCharCode = FourCharCode::from_bytes(*b"apcn");
     /// Apple `ProRes` 4444 ('ap4h')
     pub const PRORES_4444: FourCharCode = FourCharCode::from_bytes(*b"ap4h");
+    /// Apple `ProRes` 422 HQ ('apch')
+    pub const PRORES_422_HQ: FourCharCode = FourCharCode::from_bytes(*b"apch");
+    /// Apple `ProRes` 422 LT ('apcs')
+    pub const PRORES_422_LT: FourCharCode = FourCharCode::from_bytes(*b"apcs");
+    /// Apple `ProRes` 422 Proxy ('apco')
+    pub const PRORES_422_PROXY: FourCharCode = FourCharCode::from_bytes(*b"apco");
+    /// Apple `ProRes` 4444 XQ ('ap4x')
+    pub const PRORES_4444_XQ: FourCharCode = FourCharCode::from_bytes(*b"ap4x");
 
     // Audio codecs
     /// AAC ('aac ')
@@ -75,6 +165,242 @@ pub mod codec_types {
     pub const FLAC: FourCharCode = FourCharCode::from_bytes(*b"flac");
 }
 
+/// Video codec of a format description
+///
+/// The typed form of the video [`codec_types`] constants. Uncompressed frames,
+/// such as those delivered by a stream, carry their pixel format (for example
+/// `'BGRA'`) and are reported as [`Raw`](Self::Raw).
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::cm::{codec_types, VideoCodecType};
+///
+/// assert_eq!(VideoCodecType::from(codec_types::HEVC_2), VideoCodecType::HEVC);
+/// assert!(VideoCodecType::ProRes4444XQ.is_prores());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodecType {
+    /// H.264/AVC
+    H264,
+    /// HEVC/H.265, in either of its sample entry forms
+    HEVC,
+    /// JPEG
+    JPEG,
+    /// Apple `ProRes` 422 Proxy
+    ProRes422Proxy,
+    /// Apple `ProRes` 422 LT
+    ProRes422LT,
+    /// Apple `ProRes` 422
+    ProRes422,
+    /// Apple `ProRes` 422 HQ
+    ProRes422HQ,
+    /// Apple `ProRes` 4444
+    ProRes4444,
+    /// Apple `ProRes` 4444 XQ
+    ProRes4444XQ,
+    /// Any other codec or pixel format
+    Raw(FourCharCode),
+}
+
+impl VideoCodecType {
+    /// Returns `true` for any `ProRes` variant
+    #[must_use]
+    pub const fn is_prores(self) -> bool {
+        matches!(
+            self,
+            Self::ProRes422Proxy
+                | Self::ProRes422LT
+                | Self::ProRes422
+                | Self::ProRes422HQ
+                | Self::ProRes4444
+                | Self::ProRes4444XQ
+        )
+    }
+}
+
+impl From<FourCharCode> for VideoCodecType {
+    fn from(code: FourCharCode) -> Self {
+        match code {
+            codec_types::H264 => Self::H264,
+            codec_types::HEVC | codec_types::HEVC_2 => Self::HEVC,
+            codec_types::JPEG => Self::JPEG,
+            codec_types::PRORES_422_PROXY => Self::ProRes422Proxy,
+            codec_types::PRORES_422_LT => Self::ProRes422LT,
+            codec_types::PRORES_422 => Self::ProRes422,
+            codec_types::PRORES_422_HQ => Self::ProRes422HQ,
+            codec_types::PRORES_4444 => Self::ProRes4444,
+            codec_types::PRORES_4444_XQ => Self::ProRes4444XQ,
+            other => Self::Raw(other),
+        }
+    }
+}
+
+/// Audio codec of a format description
+///
+/// The typed form of the audio [`codec_types`] constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioCodecType {
+    /// Linear PCM, the format of captured audio
+    LinearPCM,
+    /// AAC
+    AAC,
+    /// Apple Lossless
+    ALAC,
+    /// Opus
+    Opus,
+    /// FLAC
+    FLAC,
+    /// Any other codec
+    Raw(FourCharCode),
+}
+
+impl From<FourCharCode> for AudioCodecType {
+    fn from(code: FourCharCode) -> Self {
+        match code {
+            codec_types::LPCM => Self::LinearPCM,
+            codec_types::AAC => Self::AAC,
+            codec_types::ALAC => Self::ALAC,
+            codec_types::OPUS => Self::Opus,
+            codec_types::FLAC => Self::FLAC,
+            other => Self::Raw(other),
+        }
+    }
+}
+
+/// Codec of a format description, by media type
+///
+/// Returned by [`CMFormatDescription::codec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecType {
+    /// A video codec
+    Video(VideoCodecType),
+    /// An audio codec
+    Audio(AudioCodecType),
+    /// The subtype of any other media type
+    Raw(FourCharCode),
+}
+
+/// Layout of Core Audio's `AudioStreamBasicDescription`
+#[repr(C)]
+#[derive(Default)]
+struct RawAudioStreamBasicDescription {
+    sample_rate: f64,
+    format_id: u32,
+    format_flags: u32,
+    bytes_per_packet: u32,
+    frames_per_packet: u32,
+    bytes_per_frame: u32,
+    channels_per_frame: u32,
+    bits_per_channel: u32,
+    reserved: u32,
+}
+
+/// Format of an audio stream, as described by Core Audio's
+/// `AudioStreamBasicDescription`
+///
+/// Returned by [`CMFormatDescription::audio_stream_basic_description`].
+/// Read it from the samples you receive rather than assuming a format:
+/// `ScreenCaptureKit` system audio is usually 48 kHz 32-bit float stereo,
+/// but microphone capture and other sources can differ.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioStreamBasicDescription {
+    /// Frames per second
+    pub sample_rate: f64,
+    /// Audio format, e.g. `lpcm` for linear PCM
+    pub format_id: FourCharCode,
+    /// Format-specific flags (`kAudioFormatFlag*`)
+    pub format_flags: u32,
+    /// Bytes in a packet of audio data
+    pub bytes_per_packet: u32,
+    /// Frames in a packet of audio data
+    pub frames_per_packet: u32,
+    /// Bytes in a frame; for non-interleaved data, in one channel of a frame
+    pub bytes_per_frame: u32,
+    /// Channels in each frame
+    pub channels: u32,
+    /// Bits of sample data per channel
+    pub bits_per_channel: u32,
+}
+
+impl AudioStreamBasicDescription {
+    /// `kAudioFormatFlagIsFloat`
+    pub const FLAG_IS_FLOAT: u32 = 1 << 0;
+    /// `kAudioFormatFlagIsBigEndian`
+    pub const FLAG_IS_BIG_ENDIAN: u32 = 1 << 1;
+    /// `kAudioFormatFlagIsSignedInteger`
+    pub const FLAG_IS_SIGNED_INTEGER: u32 = 1 << 2;
+    /// `kAudioFormatFlagIsPacked`
+    pub const FLAG_IS_PACKED: u32 = 1 << 3;
+    /// `kAudioFormatFlagIsNonInterleaved`
+    pub const FLAG_IS_NON_INTERLEAVED: u32 = 1 << 5;
+
+    /// Whether the data is linear PCM
+    pub fn is_linear_pcm(&self) -> bool {
+        self.format_id == codec_types::LPCM
+    }
+
+    /// Whether samples are floating point
+    pub const fn is_float(&self) -> bool {
+        self.format_flags & Self::FLAG_IS_FLOAT != 0
+    }
+
+    /// Whether samples are signed integers
+    pub const fn is_signed_integer(&self) -> bool {
+        self.format_flags & Self::FLAG_IS_SIGNED_INTEGER != 0
+    }
+
+    /// Whether samples are big-endian
+    pub const fn is_big_endian(&self) -> bool {
+        self.format_flags & Self::FLAG_IS_BIG_ENDIAN != 0
+    }
+
+    /// Whether each channel is in its own buffer
+    pub const fn is_non_interleaved(&self) -> bool {
+        self.format_flags & Self::FLAG_IS_NON_INTERLEAVED != 0
+    }
+}
+
+/// The parts of an audio format an encoder needs up front
+///
+/// A condensed [`AudioStreamBasicDescription`]; see
+/// [`SCStream::audio_format`](crate::stream::SCStream::audio_format) for a
+/// stream's format without inspecting every buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioFormat {
+    /// Frames per second
+    pub sample_rate: f64,
+    /// Channels in each frame
+    pub channels: u32,
+    /// Whether channels are interleaved in a single buffer
+    pub interleaved: bool,
+}
+
+impl From<AudioStreamBasicDescription> for AudioFormat {
+    fn from(asbd: AudioStreamBasicDescription) -> Self {
+        Self {
+            sample_rate: asbd.sample_rate,
+            channels: asbd.channels,
+            interleaved: !asbd.is_non_interleaved(),
+        }
+    }
+}
+
+impl From<RawAudioStreamBasicDescription> for AudioStreamBasicDescription {
+    fn from(raw: RawAudioStreamBasicDescription) -> Self {
+        Self {
+            sample_rate: raw.sample_rate,
+            format_id: FourCharCode::from(raw.format_id),
+            format_flags: raw.format_flags,
+            bytes_per_packet: raw.bytes_per_packet,
+            frames_per_packet: raw.frames_per_packet,
+            bytes_per_frame: raw.bytes_per_frame,
+            channels: raw.channels_per_frame,
+            bits_per_channel: raw.bits_per_channel,
+        }
+    }
+}
+
 impl CMFormatDescription {
     pub fn from_raw(ptr: *mut std::ffi::c_void) -> Option<Self> {
         if ptr.is_null() {
@@ -104,6 +430,26 @@ impl CMFormatDescription {
         crate::utils::four_char_code::FourCharCode::from(self.media_type_raw())
     }
 
+    /// Get the media type as a [`MediaType`]
+    ///
+    /// Convenient for branching in a handler that receives several kinds of
+    /// buffers:
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::{CMSampleBuffer, MediaType};
+    ///
+    /// fn handle(sample: &CMSampleBuffer) {
+    ///     match sample.format_description().map(|f| f.media_kind()) {
+    ///         Some(MediaType::Video) => println!("video frame"),
+    ///         Some(MediaType::Audio) => println!("audio buffer"),
+    ///         other => println!("other: {other:?}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn media_kind(&self) -> MediaType {
+        MediaType::from(self.media_type())
+    }
+
     /// Get the media subtype (codec type) as a raw u32 value
     pub fn media_subtype_raw(&self) -> u32 {
         unsafe { ffi::cm_format_description_get_media_subtype(self.0) }
@@ -171,6 +517,32 @@ impl CMFormatDescription {
         self.media_subtype().display()
     }
 
+    /// Get the codec as a [`CodecType`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::{CMFormatDescription, CodecType, VideoCodecType};
+    ///
+    /// fn describe(format: &CMFormatDescription) -> &'static str {
+    ///     match format.codec() {
+    ///         CodecType::Video(VideoCodecType::H264) => "H.264",
+    ///         CodecType::Video(codec) if codec.is_prores() => "ProRes",
+    ///         CodecType::Video(VideoCodecType::Raw(_)) => "uncompressed video",
+    ///         CodecType::Audio(_) => "audio",
+    ///         _ => "other",
+    ///     }
+    /// }
+    /// ```
+    pub fn codec(&self) -> CodecType {
+        let subtype = self.media_subtype();
+        match self.media_kind() {
+            MediaType::Video => CodecType::Video(VideoCodecType::from(subtype)),
+            MediaType::Audio => CodecType::Audio(AudioCodecType::from(subtype)),
+            _ => CodecType::Raw(subtype),
+        }
+    }
+
     /// Check if the codec is H.264
     pub fn is_h264(&self) -> bool {
         self.media_subtype() == codec_types::H264
@@ -192,10 +564,9 @@ impl CMFormatDescription {
         self.media_subtype() == codec_types::LPCM
     }
 
-    /// Check if the codec is `ProRes`
+    /// Check if the codec is any `ProRes` variant
     pub fn is_prores(&self) -> bool {
-        let subtype = self.media_subtype();
-        subtype == codec_types::PRORES_422 || subtype == codec_types::PRORES_4444
+        VideoCodecType::from(self.media_subtype()).is_prores()
     }
 
     /// Check if the codec is Apple Lossless (ALAC)
@@ -205,6 +576,49 @@ impl CMFormatDescription {
 
     // Audio format description methods
 
+    /// Get the full audio stream format
+    ///
+    /// Reads the stream's actual `AudioStreamBasicDescription`, so the sample
+    /// rate, channel count and sample type can be used to set up an output
+    /// device or resampler without guessing.
+    ///
+    /// Returns `None` if this is not an audio format description.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// fn describe(sample: &CMSampleBuffer) {
+    ///     let Some(asbd) = sample
+    ///         .format_description()
+    ///         .and_then(|format| format.audio_stream_basic_description())
+    ///     else {
+    ///         return;
+    ///     };
+    ///     println!(
+    ///         "{} Hz, {} channels, {}-bit {}",
+    ///         asbd.sample_rate,
+    ///         asbd.channels,
+    ///         asbd.bits_per_channel,
+    ///         if asbd.is_float() { "float" } else { "integer" }
+    ///     );
+    /// }
+    /// ```
+    pub fn audio_stream_basic_description(&self) -> Option<AudioStreamBasicDescription> {
+        if !self.is_audio() {
+            return None;
+        }
+        let mut raw = RawAudioStreamBasicDescription::default();
+        let found = unsafe {
+            ffi::cm_format_description_get_audio_stream_basic_description(
+                self.0,
+                std::ptr::addr_of_mut!(raw).cast(),
+            )
+        };
+        found.then(|| raw.into())
+    }
+
     /// Get the audio sample rate in Hz
     ///
     /// Returns `None` if this is not an audio format description.
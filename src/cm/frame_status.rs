@@ -6,21 +6,30 @@ use std::fmt;
 ///
 /// Indicates the state of a frame captured by `ScreenCaptureKit`.
 /// This maps to Apple's `SCFrameStatus` enum.
+///
+/// Only [`Complete`](Self::Complete) and [`Started`](Self::Started) frames
+/// carry new pixels. An encoder that writes every frame it receives can skip
+/// the others, which otherwise repeat the previous image. There is no status
+/// for dropped frames: the system simply doesn't deliver them. See
+/// [`SCStream::dropped_due_to_backpressure`](crate::stream::SCStream::dropped_due_to_backpressure).
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SCFrameStatus {
-    /// Frame contains complete content
+    /// The frame contains new content
     #[default]
     Complete = 0,
-    /// Frame is idle (no changes)
+    /// Nothing changed since the previous frame
+    ///
+    /// The frame repeats the previous image and has no pixel buffer.
     Idle = 1,
-    /// Frame is blank
+    /// The frame is blank, with nothing to show
     Blank = 2,
-    /// Frame is suspended
+    /// Capture is suspended and the frame has no content
     Suspended = 3,
-    /// Started (first frame)
+    /// The first frame after the stream started, with content
     Started = 4,
-    /// Stopped (last frame)
+    /// The stream stopped; no frames follow
     Stopped = 5,
 }
 
@@ -38,15 +47,28 @@ impl SCFrameStatus {
         }
     }
 
-    /// Returns true if the frame contains actual content
+    /// Returns true if the frame contains new content worth encoding
     pub const fn has_content(self) -> bool {
         matches!(self, Self::Complete | Self::Started)
     }
 
-    /// Returns true if the frame is complete
+    /// Returns true if the status is [`Complete`](Self::Complete)
     pub const fn is_complete(self) -> bool {
         matches!(self, Self::Complete)
     }
+
+    /// Returns true if the status is [`Idle`](Self::Idle)
+    pub const fn is_idle(self) -> bool {
+        matches!(self, Self::Idle)
+    }
+
+    /// Returns true if an encoder can drop the frame without losing content
+    ///
+    /// Idle, blank, suspended and stopped frames repeat the previous image or
+    /// carry none at all. This is the inverse of [`has_content`](Self::has_content).
+    pub const fn is_droppable(self) -> bool {
+        !self.has_content()
+    }
 }
 
 impl fmt::Display for SCFrameStatus {
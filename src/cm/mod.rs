@@ -11,6 +11,7 @@
 //! - [`IOSurface`] - Hardware-accelerated surface
 //! - [`AudioBuffer`] - Audio data buffer
 //! - [`AudioBufferList`] - Collection of audio buffers
+//! - [`AudioChannels`] - Planar PCM samples copied from an audio buffer
 //! - [`SCFrameStatus`] - Status of a captured frame
 
 mod audio;
@@ -26,9 +27,13 @@ mod time;
 // Re-export all public types
 pub use audio::{
     AudioBuffer, AudioBufferList, AudioBufferListIter, AudioBufferListRaw, AudioBufferRef,
+    AudioChannels, AudioSampleFormat,
 };
 pub use block_buffer::CMBlockBuffer;
-pub use format_description::CMFormatDescription;
+pub use format_description::{
+    AudioCodecType, AudioFormat, AudioStreamBasicDescription, CMFormatDescription, CodecType,
+    MediaType, VideoCodecType,
+};
 pub use frame_status::{SCFrameStatus, SCStreamFrameInfoKey};
 pub use iosurface::IOSurface;
 pub use pixel_buffer::{CVPixelBuffer, CVPixelBufferLockGuard, CVPixelBufferPool};
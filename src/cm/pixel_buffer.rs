@@ -2,6 +2,7 @@
 
 use super::ffi;
 use super::IOSurface;
+use crate::FourCharCode;
 use std::fmt;
 
 #[derive(Debug)]
@@ -317,6 +318,95 @@ impl CVPixelBuffer {
         }
     }
 
+    /// Check whether the buffer holds HDR content
+    ///
+    /// Returns `true` when the buffer's transfer function (or the transfer
+    /// function of its attached color space) is PQ (SMPTE ST 2084) or HLG, and
+    /// `false` for SDR content or buffers without color attachments. This
+    /// reflects what the frame actually contains, so a renderer can switch
+    /// between an HDR and a tone-mapping pipeline per frame instead of relying
+    /// on the stream configuration alone.
+    pub fn is_hdr(&self) -> bool {
+        unsafe { ffi::cv_pixel_buffer_is_hdr(self.0) }
+    }
+
+    /// Check whether the frame is (almost) entirely black
+    ///
+    /// Samples a grid of pixels, so the cost does not grow with the frame size.
+    /// Supports BGRA, `l10r` and the 4:2:0 `YCbCr` formats; other formats, and
+    /// buffers that cannot be locked, return `false`. A black frame from a
+    /// window that [plays protected video](crate::shareable_content::SCWindow::is_likely_protected)
+    /// usually means the content was blanked by DRM.
+    pub fn is_black(&self) -> bool {
+        /// Samples per axis
+        const GRID: usize = 32;
+        /// Tolerance for 8-bit channels, allowing for dithering and compression noise
+        const THRESHOLD: u32 = 8;
+
+        let format = FourCharCode::from_u32(self.pixel_format()).as_bytes();
+        // Luma value of black; video range starts at 16
+        let black_luma = match &format {
+            b"420v" => Some(16),
+            b"420f" => Some(0),
+            b"BGRA" | b"l10r" => None,
+            _ => return false,
+        };
+        let Ok(guard) = self.lock_base_address(true) else {
+            return false;
+        };
+
+        let (base, bytes_per_row, width, height) = if black_luma.is_some() {
+            let Some(base) = self.base_address_of_plane(0) else {
+                return false;
+            };
+            (
+                base.cast_const(),
+                self.bytes_per_row_of_plane(0),
+                self.width_of_plane(0),
+                self.height_of_plane(0),
+            )
+        } else {
+            (
+                guard.base_address(),
+                self.bytes_per_row(),
+                self.width(),
+                self.height(),
+            )
+        };
+        if base.is_null() || width == 0 || height == 0 {
+            return false;
+        }
+
+        let (rows, columns) = (GRID.min(height), GRID.min(width));
+        for row in 0..rows {
+            let y = row * height / rows;
+            for column in 0..columns {
+                let x = column * width / columns;
+                // SAFETY: x < width and y < height, and the buffer is locked
+                let dark = unsafe {
+                    let line = base.add(y * bytes_per_row);
+                    match (black_luma, &format) {
+                        (Some(black), _) => u32::from(*line.add(x)) <= black + THRESHOLD,
+                        (None, b"BGRA") => {
+                            let pixel = line.add(x * 4);
+                            (0..3).all(|channel| u32::from(*pixel.add(channel)) <= THRESHOLD)
+                        }
+                        // l10r packs three 10-bit channels into the low 30 bits
+                        _ => {
+                            let pixel = line.add(x * 4).cast::<u32>().read_unaligned();
+                            (0..3).all(|channel| (pixel >> (channel * 10)) & 0x3ff <= THRESHOLD * 4)
+                        }
+                    }
+                };
+                if !dark {
+                    return false;
+                }
+            }
+        }
+        drop(guard);
+        true
+    }
+
     /// Check if the pixel buffer is backed by an `IOSurface`
     pub fn is_backed_by_io_surface(&self) -> bool {
         self.io_surface().is_some()
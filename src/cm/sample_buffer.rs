@@ -2,12 +2,53 @@
 
 use super::ffi;
 use super::{
-    AudioBuffer, AudioBufferList, AudioBufferListRaw, CMBlockBuffer, CMFormatDescription,
-    CMSampleTimingInfo, CMTime, CVPixelBuffer, SCFrameStatus,
+    AudioBuffer, AudioBufferList, AudioBufferListRaw, AudioChannels, CMBlockBuffer,
+    CMFormatDescription, CMSampleTimingInfo, CMTime, CVPixelBuffer, SCFrameStatus,
 };
+use crate::error::SCError;
 use std::fmt;
 
 /// Opaque handle to `CMSampleBuffer`
+///
+/// # Ownership
+///
+/// Every `CMSampleBuffer` owns one retain on the underlying Core Media object,
+/// including the buffers passed to an output handler: the bridge retains each
+/// frame before the callback runs, and the handle releases it when dropped.
+/// A buffer can therefore be moved into a channel, a task or another thread
+/// and stays valid after the callback returns, for as long as the handle
+/// lives. Use [`retained`](Self::retained) (or `clone`) to keep a frame in
+/// several places.
+///
+/// What is *not* tied to the handle's lifetime:
+///
+/// - pointers from [`as_ptr`](Self::as_ptr) and from locked pixel buffers are
+///   only valid while a handle (or lock guard) keeps the object alive
+/// - frames are backed by a small pool of `IOSurface`s, so holding many of
+///   them stalls capture; copy the data out if it has to be kept for long
+///
+/// ```no_run
+/// use screencapturekit::prelude::*;
+/// use std::sync::mpsc;
+///
+/// # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) {
+/// let (tx, rx) = mpsc::sync_channel::<CMSampleBuffer>(4);
+/// let mut stream = SCStream::new(filter, config);
+/// stream.add_output_handler(
+///     move |sample: CMSampleBuffer, _| {
+///         // The handle owns its reference, so it can outlive this callback
+///         let _ = tx.try_send(sample);
+///     },
+///     SCStreamOutputType::Screen,
+/// );
+///
+/// std::thread::spawn(move || {
+///     for sample in rx {
+///         let _ = sample.presentation_timestamp();
+///     }
+/// });
+/// # }
+/// ```
 #[repr(transparent)]
 #[derive(Debug)]
 pub struct CMSampleBuffer(*mut std::ffi::c_void);
@@ -48,6 +89,15 @@ impl CMSampleBuffer {
         self.0
     }
 
+    /// Take an additional reference to this buffer
+    ///
+    /// Equivalent to `clone`, spelled out for code that hands a frame to
+    /// another consumer while keeping it. See [Ownership](Self#ownership).
+    #[must_use]
+    pub fn retained(&self) -> Self {
+        self.clone()
+    }
+
     /// Create a sample buffer for an image buffer (video frame)
     ///
     /// # Arguments
@@ -116,11 +166,29 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Lock the frame's pixels and describe them as a flat [`RawFrame`](crate::output::RawFrame)
+    ///
+    /// Meant for binding layers that re-export frames over another FFI. The
+    /// returned guard keeps the pixel buffer locked read-only; the frame's
+    /// `data_ptr` is valid until the guard is dropped.
+    ///
+    /// Returns `None` for buffers without an image (audio), for planar pixel
+    /// formats such as `420v`, or if the buffer can't be locked. Use
+    /// [`image_buffer`](Self::image_buffer) and its plane accessors for planar
+    /// frames.
+    pub fn to_raw_frame(&self) -> Option<crate::output::RawFrameGuard> {
+        let pts = self.presentation_timestamp();
+        crate::output::RawFrameGuard::new(self.image_buffer()?, pts.value, pts.timescale)
+    }
+
     /// Get the frame status from a sample buffer
     ///
     /// Returns the `SCFrameStatus` attachment from the sample buffer,
     /// indicating whether the frame is complete, idle, blank, etc.
     ///
+    /// Returns `None` when the buffer has no status attachment, as with audio
+    /// buffers, or carries a status this crate doesn't know.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -153,6 +221,34 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Returns true if the frame's status is [`SCFrameStatus::Idle`]
+    ///
+    /// Returns `false` when the buffer has no status attachment.
+    pub fn is_idle_frame(&self) -> bool {
+        self.frame_status().is_some_and(SCFrameStatus::is_idle)
+    }
+
+    /// Returns true if an encoder can skip this frame without losing content
+    ///
+    /// See [`SCFrameStatus::is_droppable`]. Buffers without a status
+    /// attachment, such as audio, are never reported as droppable.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// fn encode(sample: &CMSampleBuffer) {
+    ///     if sample.is_droppable_frame() {
+    ///         return; // Same image as the last frame
+    ///     }
+    ///     // ... hand the pixel buffer to the encoder
+    /// }
+    /// ```
+    pub fn is_droppable_frame(&self) -> bool {
+        self.frame_status().is_some_and(SCFrameStatus::is_droppable)
+    }
+
     /// Get the display time (mach absolute time) from frame info
     ///
     /// This is the time when the frame was displayed on screen.
@@ -167,9 +263,78 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Get the position of this sample among those delivered to its output
+    ///
+    /// Every sample an [`SCStream`](crate::stream::SCStream) receives from the
+    /// system is numbered as it arrives, counting from 0 separately for each
+    /// output type. Samples of one type reach handlers in increasing order, so
+    /// a consumer that passes frames on to other threads can use the number to
+    /// spot frames it lost or reordered: a jump from 41 to 44 means two samples
+    /// went missing in between.
+    ///
+    /// The system does not number frames itself. Frames it never delivers
+    /// (for example because all [`queue_depth`](crate::stream::configuration::SCStreamConfiguration::queue_depth)
+    /// surfaces were still in use) leave no gap; compare presentation
+    /// timestamps against the frame interval to detect those.
+    ///
+    /// Returns `None` for sample buffers that did not come from a stream, such
+    /// as screenshots.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// static NEXT: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// fn handle_frame(sample: &CMSampleBuffer) {
+    ///     if let Some(sequence) = sample.sequence_number() {
+    ///         let expected = NEXT.swap(sequence + 1, Ordering::Relaxed);
+    ///         if sequence > expected {
+    ///             eprintln!("Lost {} frames", sequence - expected);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn sequence_number(&self) -> Option<u64> {
+        unsafe {
+            let mut value: u64 = 0;
+            if ffi::cm_sample_buffer_get_sequence_number(self.0, &mut value) {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+
     /// Get the scale factor (point-to-pixel ratio) from frame info
     ///
     /// This indicates the display's scale factor (e.g., 2.0 for Retina displays).
+    /// Together with [`content_scale`](Self::content_scale) it maps frame
+    /// pixels back to points on the source display: a length of `n` pixels in
+    /// the frame covers `n / content_scale / scale_factor` points.
+    ///
+    /// Returns `None` if the sample has no frame info, for example when it did
+    /// not come from a stream. Fall back to the display mode's pixel and point
+    /// sizes in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cg::CGPoint;
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// /// Frame pixel position to a point offset from the captured content
+    /// fn pixel_to_point(sample: &CMSampleBuffer, pixel: CGPoint) -> Option<CGPoint> {
+    ///     let rect = sample.content_rect()?;
+    ///     let points_per_pixel = 1.0 / (sample.content_scale()? * sample.scale_factor()?);
+    ///     Some(CGPoint::new(
+    ///         (pixel.x - rect.x) * points_per_pixel,
+    ///         (pixel.y - rect.y) * points_per_pixel,
+    ///     ))
+    /// }
+    /// ```
     pub fn scale_factor(&self) -> Option<f64> {
         unsafe {
             let mut value: f64 = 0.0;
@@ -182,6 +347,13 @@ impl CMSampleBuffer {
     }
 
     /// Get the content scale from frame info
+    ///
+    /// This is how much the captured content was scaled to fit the frame: 1.0
+    /// when it is drawn at the source's pixel size, less than 1.0 when it was
+    /// shrunk to fit the configured output size. See
+    /// [`scale_factor`](Self::scale_factor) for mapping pixels to points.
+    ///
+    /// Returns `None` if the sample has no frame info.
     pub fn content_scale(&self) -> Option<f64> {
         unsafe {
             let mut value: f64 = 0.0;
@@ -195,7 +367,29 @@ impl CMSampleBuffer {
 
     /// Get the content rectangle from frame info
     ///
-    /// This is the rectangle of the captured content within the frame.
+    /// This is the rectangle of the captured content within the frame, in
+    /// frame pixels with the origin at the top left. When capturing a window
+    /// the frame is often larger than the window, and everything outside this
+    /// rectangle is padding; crop to it before saving or thumbnailing. For a
+    /// full-display capture it covers the whole frame.
+    ///
+    /// Returns `None` if the sample has no frame info, for example when it did
+    /// not come from a stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// fn padding(sample: &CMSampleBuffer) -> Option<(f64, f64)> {
+    ///     let rect = sample.content_rect()?;
+    ///     let buffer = sample.image_buffer()?;
+    ///     Some((
+    ///         buffer.width() as f64 - rect.width,
+    ///         buffer.height() as f64 - rect.height,
+    ///     ))
+    /// }
+    /// ```
     pub fn content_rect(&self) -> Option<crate::cg::CGRect> {
         unsafe {
             let mut x: f64 = 0.0;
@@ -265,7 +459,10 @@ impl CMSampleBuffer {
     /// Get the dirty rectangles from frame info
     ///
     /// Dirty rectangles indicate areas of the screen that have changed since the last frame.
-    /// This can be used for efficient partial screen updates.
+    /// This can be used for efficient partial screen updates. The rectangles
+    /// are reported as the system attached them, in the coordinate space of
+    /// the frame's pixel buffer; see [`dirty_pixel_rects`](Self::dirty_pixel_rects)
+    /// for a version snapped to whole pixels.
     pub fn dirty_rects(&self) -> Option<Vec<crate::cg::CGRect>> {
         unsafe {
             let mut rects_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
@@ -291,6 +488,42 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Changed regions of the frame as whole-pixel rectangles
+    ///
+    /// Reads the same attachment as [`dirty_rects`](Self::dirty_rects) and
+    /// prepares it for tile-based encoding: each rectangle is rounded outward
+    /// to whole pixels and clipped to the pixel buffer, and empty ones are
+    /// dropped. When any rectangle covers the whole surface, as the system
+    /// reports for the first frame and after resizes, a single rectangle for
+    /// the full buffer is returned.
+    ///
+    /// Returns an empty `Vec` when the buffer has no image or no dirty-rect
+    /// attachment, as with idle frames that repeat the previous content.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// fn send_changed_tiles(sample: &CMSampleBuffer) {
+    ///     for rect in sample.dirty_pixel_rects() {
+    ///         println!("changed: {}x{} at ({}, {})", rect.width, rect.height, rect.x, rect.y);
+    ///     }
+    /// }
+    /// ```
+    pub fn dirty_pixel_rects(&self) -> Vec<crate::cg::CGRect> {
+        let Some(pixel_buffer) = self.image_buffer() else {
+            return Vec::new();
+        };
+        let Some(rects) = self.dirty_rects() else {
+            return Vec::new();
+        };
+        // Buffer dimensions are far below 2^52, so the conversion is exact
+        #[allow(clippy::cast_precision_loss)]
+        let (width, height) = (pixel_buffer.width() as f64, pixel_buffer.height() as f64);
+        pixel_rects(&rects, width, height)
+    }
+
     /// Get the presentation timestamp
     pub fn presentation_timestamp(&self) -> CMTime {
         unsafe {
@@ -314,6 +547,19 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Get the presentation timestamp in `mach_absolute_time` units
+    ///
+    /// The system stamps samples against the host time clock, so this is
+    /// [`presentation_timestamp`](Self::presentation_timestamp) in the clock
+    /// domain used by `AVFoundation` capture devices and Core Audio. Compare it
+    /// directly with their host times, and convert differences with
+    /// [`mach_time_to_duration`](crate::utils::mach_time_to_duration).
+    ///
+    /// Returns 0 if the sample has no valid presentation timestamp.
+    pub fn host_time(&self) -> u64 {
+        unsafe { ffi::cm_sample_buffer_get_host_time(self.0) }
+    }
+
     /// Get the duration of the sample
     pub fn duration(&self) -> CMTime {
         unsafe {
@@ -377,6 +623,54 @@ impl CMSampleBuffer {
         }
     }
 
+    /// Copy the PCM audio in this sample out into one vector per channel
+    ///
+    /// The sample format and layout are read from the stream's
+    /// [`AudioStreamBasicDescription`](crate::cm::AudioStreamBasicDescription)
+    /// rather than assumed:
+    /// system audio is usually 48 kHz 32-bit float, but other sources such as
+    /// the microphone can differ. Interleaved and non-interleaved data both
+    /// come out planar; see [`AudioChannels`](crate::cm::AudioChannels).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::InvalidBuffer`] if the sample holds no audio, the
+    /// audio is not linear PCM, or the sample format is not 32-bit float,
+    /// 16-bit integer or 32-bit integer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMSampleBuffer;
+    ///
+    /// fn mix_down(sample: &CMSampleBuffer) -> Option<Vec<f32>> {
+    ///     let channels = sample.audio_channels().ok()?.to_f32();
+    ///     let count = channels.len() as f32;
+    ///     let frames = channels.first()?.len();
+    ///     Some(
+    ///         (0..frames)
+    ///             .map(|i| channels.iter().map(|c| c[i]).sum::<f32>() / count)
+    ///             .collect(),
+    ///     )
+    /// }
+    /// ```
+    pub fn audio_channels(&self) -> Result<AudioChannels, SCError> {
+        let format = self
+            .format_description()
+            .and_then(|format| format.audio_stream_basic_description())
+            .ok_or_else(|| SCError::InvalidBuffer("Sample has no audio format".to_string()))?;
+        if !format.is_linear_pcm() {
+            return Err(SCError::InvalidBuffer(format!(
+                "Audio is not linear PCM: {}",
+                format.format_id
+            )));
+        }
+        let list = self
+            .audio_buffer_list()
+            .ok_or_else(|| SCError::InvalidBuffer("Sample has no audio buffers".to_string()))?;
+        super::audio::copy_channels(&list, &format)
+    }
+
     /// Get the data buffer (for compressed data)
     pub fn data_buffer(&self) -> Option<CMBlockBuffer> {
         unsafe {
@@ -618,6 +912,15 @@ impl Drop for CMSampleBuffer {
     }
 }
 
+impl Clone for CMSampleBuffer {
+    fn clone(&self) -> Self {
+        unsafe {
+            ffi::cm_sample_buffer_retain(self.0);
+        }
+        Self(self.0)
+    }
+}
+
 unsafe impl Send for CMSampleBuffer {}
 unsafe impl Sync for CMSampleBuffer {}
 
@@ -632,3 +935,86 @@ impl fmt::Display for CMSampleBuffer {
         )
     }
 }
+
+/// Round `rects` outward to whole pixels and clip them to a `width` x `height`
+/// buffer, dropping empty ones; a rectangle covering the whole buffer
+/// replaces all of them
+fn pixel_rects(rects: &[crate::cg::CGRect], width: f64, height: f64) -> Vec<crate::cg::CGRect> {
+    let mut pixel_rects = Vec::with_capacity(rects.len());
+    for rect in rects {
+        let left = rect.x.floor().max(0.0);
+        let top = rect.y.floor().max(0.0);
+        let right = (rect.x + rect.width).ceil().min(width);
+        let bottom = (rect.y + rect.height).ceil().min(height);
+        if right <= left || bottom <= top {
+            continue;
+        }
+        let rect = crate::cg::CGRect::new(left, top, right - left, bottom - top);
+        // Clipped to the buffer, so only the full surface is this large
+        if rect.width >= width && rect.height >= height {
+            return vec![rect];
+        }
+        pixel_rects.push(rect);
+    }
+    pixel_rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cg::CGRect;
+
+    #[test]
+    fn test_pixel_rects_partial() {
+        let rects = [
+            CGRect::new(10.0, 20.0, 30.0, 40.0),
+            CGRect::new(0.0, 0.0, 5.0, 5.0),
+        ];
+        assert_eq!(pixel_rects(&rects, 100.0, 100.0), rects);
+    }
+
+    #[test]
+    fn test_pixel_rects_fractional() {
+        // Rounded outward, so the changed area is never shrunk
+        let rects = [CGRect::new(10.5, 20.25, 5.5, 0.5)];
+        assert_eq!(
+            pixel_rects(&rects, 100.0, 100.0),
+            [CGRect::new(10.0, 20.0, 6.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_pixel_rects_out_of_bounds() {
+        let rects = [
+            // Straddles the top-left and bottom-right corners
+            CGRect::new(-10.0, -5.0, 20.0, 15.0),
+            CGRect::new(90.0, 45.0, 20.0, 20.0),
+            // Entirely outside, or empty once clipped
+            CGRect::new(150.0, 10.0, 10.0, 10.0),
+            CGRect::new(-20.0, 10.0, 20.0, 10.0),
+            CGRect::new(10.0, 10.0, 0.0, 10.0),
+        ];
+        assert_eq!(
+            pixel_rects(&rects, 100.0, 50.0),
+            [
+                CGRect::new(0.0, 0.0, 10.0, 10.0),
+                CGRect::new(90.0, 45.0, 10.0, 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pixel_rects_full_surface() {
+        // A full-surface rectangle, even an oversized one, replaces the rest
+        let rects = [
+            CGRect::new(10.0, 10.0, 5.0, 5.0),
+            CGRect::new(-1.0, -1.0, 102.0, 52.0),
+            CGRect::new(20.0, 20.0, 5.0, 5.0),
+        ];
+        assert_eq!(
+            pixel_rects(&rects, 100.0, 50.0),
+            [CGRect::new(0.0, 0.0, 100.0, 50.0)]
+        );
+        assert!(pixel_rects(&[], 100.0, 50.0).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 //! Core Media time types
 
+use std::cmp::Ordering;
 use std::ffi::c_void;
 use std::fmt;
 
@@ -221,6 +222,93 @@ impl CMTime {
         self.value == other.value && self.timescale == other.timescale
     }
 
+    /// Compare the instants two times represent, like `CMTimeCompare`
+    ///
+    /// Numeric times are rescaled to a common timescale first, so `1/30` and
+    /// `2/60` compare `Equal`. Times in different epochs are ordered by epoch.
+    /// Negative infinity is earlier than every numeric time, and indefinite
+    /// and then positive infinity are later. Returns `None` if either time is
+    /// invalid, or is numeric with a zero timescale but a non-zero value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use screencapturekit::cm::CMTime;
+    ///
+    /// assert_eq!(CMTime::new(1, 30).compare(&CMTime::new(2, 60)), Some(Ordering::Equal));
+    /// assert_eq!(CMTime::new(1, 2).compare(&CMTime::new(600, 1000)), Some(Ordering::Less));
+    /// assert_eq!(CMTime::positive_infinity().compare(&CMTime::new(i64::MAX, 1)), Some(Ordering::Greater));
+    /// assert_eq!(CMTime::INVALID.compare(&CMTime::ZERO), None);
+    /// ```
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        let (a, b) = (self.rank()?, other.rank()?);
+        if a != b {
+            return Some(a.cmp(&b));
+        }
+        if a != 1 {
+            return Some(Ordering::Equal);
+        }
+        if self.epoch != other.epoch {
+            return Some(self.epoch.cmp(&other.epoch));
+        }
+        let (lhs, rhs) = (self.rational()?, other.rational()?);
+        Some((lhs.0 * rhs.1).cmp(&(rhs.0 * lhs.1)))
+    }
+
+    /// The earlier of two times
+    ///
+    /// An invalid operand is ignored, the way `f64::min` ignores NaN; if both
+    /// are invalid the result is invalid. Of two equal instants, `self` is
+    /// returned.
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        match self.compare(&other) {
+            Some(Ordering::Greater) => other,
+            Some(_) => self,
+            None if self.is_valid() => self,
+            None => other,
+        }
+    }
+
+    /// The later of two times
+    ///
+    /// Invalid operands are ignored as in [`min`](Self::min).
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        match self.compare(&other) {
+            Some(Ordering::Less) => other,
+            Some(_) => self,
+            None if self.is_valid() => self,
+            None => other,
+        }
+    }
+
+    /// Position of the time's kind in the ordering, `None` if invalid
+    const fn rank(&self) -> Option<u8> {
+        if !self.is_valid() {
+            None
+        } else if self.is_negative_infinity() {
+            Some(0)
+        } else if self.is_positive_infinity() {
+            Some(3)
+        } else if self.is_indefinite() {
+            Some(2)
+        } else {
+            Some(1)
+        }
+    }
+
+    /// Value and timescale as a fraction with a positive denominator
+    fn rational(&self) -> Option<(i128, i128)> {
+        match self.timescale.cmp(&0) {
+            Ordering::Greater => Some((i128::from(self.value), i128::from(self.timescale))),
+            Ordering::Less => Some((-i128::from(self.value), -i128::from(self.timescale))),
+            Ordering::Equal if self.value == 0 => Some((0, 1)),
+            Ordering::Equal => None,
+        }
+    }
+
     /// Create a time representing positive infinity
     pub const fn positive_infinity() -> Self {
         Self {
@@ -268,6 +356,26 @@ impl Default for CMTime {
     }
 }
 
+/// Orders times by the instant they represent
+///
+/// This follows [`CMTime::compare`], except that two different values for the
+/// same instant (such as `1/30` and `2/60`) are ordered by timescale, then
+/// value, flags and epoch, so the ordering agrees with `==`. Sorting therefore
+/// puts times in chronological order. Invalid times are unordered except
+/// with an identical invalid time.
+impl PartialOrd for CMTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        let key = |t: &Self| (t.timescale, t.value, t.flags, t.epoch);
+        match self.compare(other)? {
+            Ordering::Equal => Some(key(self).cmp(&key(other))),
+            ordering => Some(ordering),
+        }
+    }
+}
+
 impl fmt::Display for CMTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(seconds) = self.as_seconds() {
@@ -9,6 +9,8 @@
 //! |--------|---------|----------|
 //! | `show()` | callback with `SCPickerOutcome` | Get filter + metadata (dimensions, picked content) |
 //! | `show_filter()` | callback with `SCPickerFilterOutcome` | Just get the filter |
+//! | `show_using_style()` | callback with `SCPickerOutcome` | Open on windows, displays or apps |
+//! | `show_for_stream_using_style()` | callback with `SCPickerOutcome` | Same, for a running stream |
 //!
 //! For async/await, use `AsyncSCContentSharingPicker` from the `async_api` module.
 //!
@@ -47,7 +49,9 @@
 //! }
 //! ```
 
+use crate::stream::configuration::SCStreamConfiguration;
 use crate::stream::content_filter::SCContentFilter;
+pub use crate::stream::content_filter::SCShareableContentStyle;
 use std::ffi::c_void;
 
 /// Represents the type of content selected in the picker
@@ -275,8 +279,8 @@ pub struct SCPickerResult {
 }
 
 impl SCPickerResult {
-    /// Create from raw pointer (used by async API)
-    #[cfg(feature = "async")]
+    /// Create from raw pointer (used by async API and `testing`)
+    #[cfg(any(feature = "async", feature = "testing"))]
     #[must_use]
     pub(crate) fn from_ptr(ptr: *const c_void) -> Self {
         Self { ptr }
@@ -345,6 +349,36 @@ impl SCPickerResult {
         (width, height)
     }
 
+    /// Build a stream configuration sized to the picked content
+    ///
+    /// The width and height are set to [`pixel_size`](Self::pixel_size), so the
+    /// output matches the content rect at its native scale factor instead of the
+    /// default configuration size. All other properties keep their defaults and
+    /// can be adjusted with the usual builder methods.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use screencapturekit::content_sharing_picker::*;
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let config = SCContentSharingPickerConfiguration::new();
+    /// SCContentSharingPicker::show(&config, |outcome| {
+    ///     if let SCPickerOutcome::Picked(result) = outcome {
+    ///         let stream_config = result
+    ///             .recommended_configuration()
+    ///             .with_pixel_format(PixelFormat::BGRA);
+    ///         let stream = SCStream::new(&result.filter(), &stream_config);
+    ///     }
+    /// });
+    /// ```
+    #[must_use]
+    pub fn recommended_configuration(&self) -> SCStreamConfiguration {
+        let (width, height) = self.pixel_size();
+        SCStreamConfiguration::new()
+            .with_width(width)
+            .with_height(height)
+    }
+
     /// Get the windows selected by the user
     ///
     /// Returns the picked windows that can be used to create a custom `SCContentFilter`.
@@ -676,15 +710,30 @@ impl SCContentSharingPicker {
 
     /// Show the picker UI with a specific content style
     ///
-    /// Presents the picker pre-filtered to a specific content type.
+    /// Presents the picker pre-focused on one kind of content, e.g. windows
+    /// when sharing a document or displays when presenting.
+    /// [`SCShareableContentStyle::None`] leaves the choice to the system, the
+    /// same as [`show`](Self::show).
     ///
     /// # Arguments
     /// * `config` - The picker configuration
     /// * `style` - The content style to show (Window, Display, Application)
     /// * `callback` - Called with the picker result
+    ///
+    /// # Example
+    /// ```no_run
+    /// use screencapturekit::content_sharing_picker::*;
+    ///
+    /// let config = SCContentSharingPickerConfiguration::new();
+    /// SCContentSharingPicker::show_using_style(&config, SCShareableContentStyle::Window, |outcome| {
+    ///     if let SCPickerOutcome::Picked(result) = outcome {
+    ///         let filter = result.filter();
+    ///     }
+    /// });
+    /// ```
     pub fn show_using_style<F>(
         config: &SCContentSharingPickerConfiguration,
-        style: crate::stream::content_filter::SCShareableContentStyle,
+        style: SCShareableContentStyle,
         callback: F,
     ) where
         F: FnOnce(SCPickerOutcome) + Send + 'static,
@@ -704,6 +753,9 @@ impl SCContentSharingPicker {
 
     /// Show the picker for an existing stream with a specific content style
     ///
+    /// Like [`show_using_style`](Self::show_using_style), but the selection
+    /// replaces the content of `stream`.
+    ///
     /// # Arguments
     /// * `config` - The picker configuration
     /// * `stream` - The stream to update
@@ -712,7 +764,7 @@ impl SCContentSharingPicker {
     pub fn show_for_stream_using_style<F>(
         config: &SCContentSharingPickerConfiguration,
         stream: &crate::stream::SCStream,
-        style: crate::stream::content_filter::SCShareableContentStyle,
+        style: SCShareableContentStyle,
         callback: F,
     ) where
         F: FnOnce(SCPickerOutcome) + Send + 'static,
@@ -3,8 +3,10 @@
 //! This module provides a safe Rust wrapper around GCD (Grand Central Dispatch) queues
 //! that can be used with `ScreenCaptureKit` streams.
 
+use std::collections::HashMap;
 use std::ffi::{c_void, CString};
 use std::fmt;
+use std::sync::{Mutex, PoisonError};
 
 /// Quality of Service levels for dispatch queues
 ///
@@ -36,6 +38,27 @@ pub enum DispatchQoS {
     UserInteractive = 4,
 }
 
+/// How often a dispatch queue drains its autorelease pool
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::dispatch_queue::{DispatchAutoreleaseFrequency, DispatchQoS, DispatchQueue};
+///
+/// let queue = DispatchQueue::new("com.myapp.capture", DispatchQoS::UserInteractive)
+///     .with_autorelease_frequency(DispatchAutoreleaseFrequency::WorkItem);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DispatchAutoreleaseFrequency {
+    /// Use the target queue's behavior (GCD's default)
+    #[default]
+    Inherit = 0,
+    /// Wrap every work item in its own autorelease pool
+    WorkItem = 1,
+    /// Never set up an autorelease pool
+    Never = 2,
+}
+
 /// A wrapper around GCD `DispatchQueue`
 ///
 /// This allows you to provide a custom dispatch queue for stream output handling
@@ -48,8 +71,37 @@ pub enum DispatchQoS {
 ///
 /// let queue = DispatchQueue::new("com.myapp.capture", DispatchQoS::UserInteractive);
 /// ```
+///
+/// # Autorelease Pools and `IOSurface` Lifetime
+///
+/// Sample buffers delivered to a handler are partly managed by Objective-C
+/// autorelease pools. On a queue that inherits its autorelease behavior, those
+/// pools may only drain when the thread goes idle, which never happens during
+/// sustained high frame rate capture. The `IOSurface`s behind the frames then
+/// stay alive longer than the handler holds them, the capture pool runs dry,
+/// and the stream starts dropping frames. Setting
+/// [`DispatchAutoreleaseFrequency::WorkItem`] drains the pool after every
+/// callback so surfaces return to `ScreenCaptureKit` as soon as the handler
+/// is done.
+///
+/// # Sharing a Queue Between Streams
+///
+/// One queue can be passed to
+/// [`add_output_handler_with_queue`](crate::stream::SCStream::add_output_handler_with_queue)
+/// on any number of streams instead of creating a queue per stream; clones
+/// refer to the same underlying queue. Each stream retains the queue for as
+/// long as it has outputs on it, so the wrapper can be dropped once the
+/// handlers are added. [`DispatchQueuePool`] hands out shared queues by label.
+///
+/// Queues created here are serial: handlers of every stream sharing one run
+/// one at a time, never concurrently. That keeps shared handler state simple,
+/// but a slow handler on one stream delays the others.
 pub struct DispatchQueue {
     ptr: *const c_void,
+    label: CString,
+    qos: DispatchQoS,
+    autorelease_frequency: DispatchAutoreleaseFrequency,
+    target: Option<Box<Self>>,
 }
 
 unsafe impl Send for DispatchQueue {}
@@ -79,7 +131,92 @@ impl DispatchQueue {
         let c_label = CString::new(label).expect("Label contains null byte");
         let ptr = unsafe { crate::ffi::dispatch_queue_create(c_label.as_ptr(), qos as i32) };
         assert!(!ptr.is_null(), "Failed to create dispatch queue");
-        Self { ptr }
+        Self {
+            ptr,
+            label: c_label,
+            qos,
+            autorelease_frequency: DispatchAutoreleaseFrequency::default(),
+            target: None,
+        }
+    }
+
+    /// Set how often the queue drains its autorelease pool
+    ///
+    /// Use [`DispatchAutoreleaseFrequency::WorkItem`] for output handlers of
+    /// high frame rate streams; see the [type-level docs](Self) for why.
+    ///
+    /// GCD queue attributes are fixed at creation, so this replaces the
+    /// underlying queue. Call it before handing the queue to a stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if queue creation fails
+    #[must_use]
+    pub fn with_autorelease_frequency(mut self, frequency: DispatchAutoreleaseFrequency) -> Self {
+        self.autorelease_frequency = frequency;
+        self.recreate();
+        self
+    }
+
+    /// Set a target queue that this queue's work is funneled through
+    ///
+    /// Work items inherit the target's priority when it is higher, and several
+    /// queues sharing a serial target never run concurrently. Like
+    /// [`with_autorelease_frequency`](Self::with_autorelease_frequency), this
+    /// replaces the underlying queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::dispatch_queue::{DispatchQoS, DispatchQueue};
+    ///
+    /// let shared = DispatchQueue::new("com.myapp.capture", DispatchQoS::UserInitiated);
+    /// let screen = DispatchQueue::new("com.myapp.screen", DispatchQoS::Default).with_target(&shared);
+    /// let audio = DispatchQueue::new("com.myapp.audio", DispatchQoS::Default).with_target(&shared);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if queue creation fails
+    #[must_use]
+    pub fn with_target(mut self, target: &Self) -> Self {
+        self.target = Some(Box::new(target.clone()));
+        self.recreate();
+        self
+    }
+
+    /// Get the queue label
+    pub fn label(&self) -> &str {
+        self.label.to_str().unwrap_or_default()
+    }
+
+    /// Get the quality of service the queue was created with
+    pub const fn qos(&self) -> DispatchQoS {
+        self.qos
+    }
+
+    /// Get the autorelease frequency
+    pub const fn autorelease_frequency(&self) -> DispatchAutoreleaseFrequency {
+        self.autorelease_frequency
+    }
+
+    /// Replace the underlying queue with one created from the current attributes
+    fn recreate(&mut self) {
+        let target = self
+            .target
+            .as_ref()
+            .map_or(std::ptr::null(), |target| target.as_ptr());
+        let ptr = unsafe {
+            crate::ffi::dispatch_queue_create_with_attributes(
+                self.label.as_ptr(),
+                self.qos as i32,
+                self.autorelease_frequency as i32,
+                target,
+            )
+        };
+        assert!(!ptr.is_null(), "Failed to create dispatch queue");
+        let old = std::mem::replace(&mut self.ptr, ptr);
+        unsafe { crate::ffi::dispatch_queue_release(old) };
     }
 
     /// Returns the raw pointer to the dispatch queue
@@ -95,6 +232,10 @@ impl Clone for DispatchQueue {
         unsafe {
             Self {
                 ptr: crate::ffi::dispatch_queue_retain(self.ptr),
+                label: self.label.clone(),
+                qos: self.qos,
+                autorelease_frequency: self.autorelease_frequency,
+                target: self.target.clone(),
             }
         }
     }
@@ -112,6 +253,124 @@ impl fmt::Debug for DispatchQueue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("DispatchQueue")
             .field("ptr", &self.ptr)
+            .field("label", &self.label())
+            .field("qos", &self.qos)
+            .field("autorelease_frequency", &self.autorelease_frequency)
+            .field("target", &self.target.as_ref().map(|target| target.label()))
+            .finish()
+    }
+}
+
+/// Dispatch queues shared by label
+///
+/// [`get`](Self::get) returns the queue already registered under a label, or
+/// creates one, so streams that ask for the same label share a queue instead
+/// of each tying up a thread. See the
+/// [sharing notes](DispatchQueue#sharing-a-queue-between-streams) for what
+/// sharing a serial queue means for callbacks.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::dispatch_queue::{DispatchQoS, DispatchQueuePool};
+/// use screencapturekit::prelude::*;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let content = SCShareableContent::get()?;
+/// # let config = SCStreamConfiguration::default();
+/// let pool = DispatchQueuePool::new();
+/// let mut streams = Vec::new();
+/// for display in content.displays() {
+///     let filter = SCContentFilter::builder().display(&display).exclude_windows(&[]).build();
+///     let mut stream = SCStream::new(&filter, &config);
+///     let queue = pool.get("com.myapp.capture", DispatchQoS::UserInteractive);
+///     stream.add_output_handler_with_queue(
+///         |_sample, _type| println!("Got frame"),
+///         SCStreamOutputType::Screen,
+///         Some(&queue),
+///     );
+///     streams.push(stream);
+/// }
+/// assert_eq!(pool.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct DispatchQueuePool {
+    queues: Mutex<HashMap<String, DispatchQueue>>,
+}
+
+impl DispatchQueuePool {
+    /// Create an empty pool
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the queue registered under `label`, creating it with `qos` if there
+    /// is none
+    ///
+    /// An existing queue keeps the `QoS` it was created with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the label contains null bytes or if queue creation fails
+    pub fn get(&self, label: &str, qos: DispatchQoS) -> DispatchQueue {
+        self.queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(label.to_string())
+            .or_insert_with(|| DispatchQueue::new(label, qos))
+            .clone()
+    }
+
+    /// Register `queue` under its label, replacing any queue already there
+    ///
+    /// Use this for queues with a custom autorelease frequency or target.
+    pub fn insert(&self, queue: DispatchQueue) -> Option<DispatchQueue> {
+        self.queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(queue.label().to_string(), queue)
+    }
+
+    /// Remove the queue registered under `label`
+    ///
+    /// Streams using the queue keep it alive; later calls to
+    /// [`get`](Self::get) create a new one.
+    pub fn remove(&self, label: &str) -> Option<DispatchQueue> {
+        self.queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(label)
+    }
+
+    /// Number of queues in the pool
+    pub fn len(&self) -> usize {
+        self.queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether the pool has no queues
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for DispatchQueuePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut labels: Vec<String> = self
+            .queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        labels.sort_unstable();
+        f.debug_struct("DispatchQueuePool")
+            .field("queues", &labels)
             .finish()
     }
 }
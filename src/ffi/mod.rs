@@ -57,6 +57,10 @@ extern "C" {
     /// Force CoreGraphics initialization by calling `CGMainDisplayID`
     /// This prevents `CGS_REQUIRE_INIT` crashes on headless systems
     pub fn sc_initialize_core_graphics();
+
+    /// Current login session state: 0 = active, 1 = locked, 2 = login window
+    pub fn sc_session_state() -> i32;
+    pub fn sc_get_os_version(out_major: *mut u32, out_minor: *mut u32, out_patch: *mut u32);
 }
 
 // MARK: - SCShareableContent
@@ -101,6 +105,13 @@ extern "C" {
     pub fn sc_shareable_content_retain(content: *const c_void) -> *const c_void;
     pub fn sc_shareable_content_release(content: *const c_void);
     pub fn sc_shareable_content_get_displays_count(content: *const c_void) -> isize;
+    pub fn sc_shareable_content_get_summary(
+        content: *const c_void,
+        out_displays: *mut isize,
+        out_windows: *mut isize,
+        out_applications: *mut isize,
+        out_on_screen_windows: *mut isize,
+    );
     pub fn sc_shareable_content_get_display_at(
         content: *const c_void,
         index: isize,
@@ -229,6 +240,7 @@ extern "C" {
 extern "C" {
     pub fn sc_stream_configuration_create() -> *const c_void;
     pub fn sc_stream_configuration_retain(config: *const c_void) -> *const c_void;
+    pub fn sc_stream_configuration_copy(config: *const c_void) -> *const c_void;
     pub fn sc_stream_configuration_release(config: *const c_void);
 
     pub fn sc_stream_configuration_set_width(config: *const c_void, width: isize);
@@ -257,6 +269,25 @@ extern "C" {
 
     pub fn sc_stream_configuration_set_pixel_format(config: *const c_void, pixel_format: u32);
     pub fn sc_stream_configuration_get_pixel_format(config: *const c_void) -> u32;
+    pub fn sc_stream_configuration_set_color_range(config: *const c_void, range: i32);
+    pub fn sc_stream_configuration_set_pixel_buffer_attributes(
+        config: *const c_void,
+        metal_compatible: bool,
+        opengl_compatible: bool,
+        extended_left: isize,
+        extended_top: isize,
+        extended_right: isize,
+        extended_bottom: isize,
+    );
+    pub fn sc_stream_configuration_get_pixel_buffer_attributes(
+        config: *const c_void,
+        metal_compatible: *mut bool,
+        opengl_compatible: *mut bool,
+        extended_left: *mut isize,
+        extended_top: *mut isize,
+        extended_right: *mut isize,
+        extended_bottom: *mut isize,
+    );
 
     pub fn sc_stream_configuration_set_minimum_frame_interval(
         config: *const c_void,
@@ -343,6 +374,11 @@ extern "C" {
         b: f32,
     );
     pub fn sc_stream_configuration_set_color_space_name(config: *const c_void, name: *const i8);
+    pub fn sc_stream_configuration_get_color_space_name(
+        config: *const c_void,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
     pub fn sc_stream_configuration_set_color_matrix(config: *const c_void, matrix: *const i8);
     pub fn sc_stream_configuration_get_color_matrix(
         config: *const c_void,
@@ -480,6 +516,25 @@ extern "C" {
         dispatch_queue: *const c_void,
     ) -> bool;
     pub fn sc_stream_remove_stream_output(stream: *const c_void, output_type: i32) -> bool;
+    pub fn sc_replay_copier_create() -> *const c_void;
+    pub fn sc_replay_copier_release(copier: *const c_void);
+    pub fn sc_replay_copier_copy_sample_buffer(
+        copier: *const c_void,
+        sample_buffer: *mut c_void,
+    ) -> *mut c_void;
+    pub fn sc_replay_write_movie(
+        samples: *const *mut c_void,
+        count: usize,
+        path: *const i8,
+        context: *mut c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const i8),
+    );
+    pub fn sc_stream_set_main_thread_delivery(
+        stream: *const c_void,
+        output_type: i32,
+        enabled: bool,
+    );
+    pub fn sc_stream_get_main_thread_delivery(stream: *const c_void, output_type: i32) -> bool;
     pub fn sc_stream_start_capture(
         stream: *const c_void,
         context: *mut c_void,
@@ -502,6 +557,15 @@ extern "C" {
         callback: extern "C" fn(*const c_void, *const i8, *mut c_void),
         user_data: *mut c_void,
     );
+    pub fn sc_stream_capture_sample_buffer_stable(
+        content_filter: *const c_void,
+        config: *const c_void,
+        skip_frames: u32,
+        require_complete: bool,
+        timeout: f64,
+        callback: extern "C" fn(*const c_void, *const i8, *mut c_void),
+        user_data: *mut c_void,
+    );
     pub fn sc_stream_update_configuration(
         stream: *const c_void,
         config: *const c_void,
@@ -520,22 +584,55 @@ extern "C" {
         callback: extern "C" fn(*mut c_void, bool, *const i8),
         context: *mut c_void,
     );
+    pub fn sc_stream_exclude_window_during_recording(
+        stream: *const c_void,
+        window: *const c_void,
+        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        context: *mut c_void,
+    );
     pub fn sc_stream_remove_recording_output(
         stream: *const c_void,
         recording_output: *const c_void,
         callback: extern "C" fn(*mut c_void, bool, *const i8),
         context: *mut c_void,
     );
+    pub fn sc_stream_recording_output_is_fanned_out(
+        stream: *const c_void,
+        recording_output: *const c_void,
+    ) -> bool;
     pub fn sc_stream_retain(stream: *const c_void) -> *const c_void;
     pub fn sc_stream_release(stream: *const c_void);
 
     // macOS 13.0+ - synchronizationClock
     pub fn sc_stream_get_synchronization_clock(stream: *const c_void) -> *const c_void;
+
+    // Copy of the configuration the stream was created or last updated with
+    pub fn sc_stream_copy_configuration(stream: *const c_void) -> *const c_void;
+
+    // Sleep/wake handling (event: 0 = will sleep, 1 = did wake)
+    pub fn sc_stream_set_auto_pause_on_sleep(
+        stream: *const c_void,
+        enabled: bool,
+        callback: extern "C" fn(*const c_void, i32, bool),
+    );
+
+    // Auto-restart after fatal errors (callback receives the error that stopped the stream)
+    pub fn sc_stream_set_auto_restart(
+        stream: *const c_void,
+        enabled: bool,
+        callback: extern "C" fn(*const c_void, i32, *const i8),
+    );
 }
 
 // MARK: - Dispatch Queue
 extern "C" {
     pub fn dispatch_queue_create(label: *const i8, qos: i32) -> *const c_void;
+    pub fn dispatch_queue_create_with_attributes(
+        label: *const i8,
+        qos: i32,
+        autorelease_frequency: i32,
+        target: *const c_void,
+    ) -> *const c_void;
     pub fn dispatch_queue_release(queue: *const c_void);
     pub fn dispatch_queue_retain(queue: *const c_void) -> *const c_void;
 }
@@ -553,6 +650,12 @@ extern "C" {
     pub fn iosurface_unlock(iosurface: *const c_void, options: u32) -> i32;
     pub fn iosurface_is_in_use(iosurface: *const c_void) -> bool;
     pub fn iosurface_release(iosurface: *const c_void);
+    pub fn io_surface_increment_use_count(iosurface: *const c_void);
+    pub fn io_surface_decrement_use_count(iosurface: *const c_void);
+    pub fn io_surface_get_use_count(iosurface: *const c_void) -> i32;
+
+    // Unretained surface behind a sample buffer's image buffer
+    pub fn cm_sample_buffer_peek_iosurface(sample_buffer: *const c_void) -> *const c_void;
 
     // Plane functions (for multi-planar formats like YCbCr 420)
     pub fn iosurface_get_plane_count(iosurface: *const c_void) -> isize;
@@ -656,6 +759,7 @@ extern "C" {
         result: *const c_void,
         index: usize,
     ) -> *const c_void;
+    pub fn sc_picker_result_create_with_filter(filter: *const c_void) -> *const c_void;
     pub fn sc_picker_result_release(result: *const c_void);
 }
 
@@ -683,6 +787,9 @@ extern "C" {
         out_pixel_height: *mut i32,
         out_refresh_rate: *mut f64,
     ) -> bool;
+    pub fn cg_display_rotation(display_id: u32) -> f64;
+    pub fn cg_display_is_in_mirror_set(display_id: u32) -> bool;
+    pub fn cg_display_mirrors_display(display_id: u32) -> u32;
     pub fn cg_display_create_image(display_id: u32) -> *const c_void;
     pub fn cg_display_create_image_rect(
         display_id: u32,
@@ -707,6 +814,7 @@ extern "C" {
         callback: extern "C" fn(*const c_void, *const i8, *mut c_void),
         user_data: *mut c_void,
     );
+    pub fn sc_display_menu_bar_height(display_id: u32) -> f64;
     pub fn sc_screenshot_manager_capture_image_in_rect(
         x: f64,
         y: f64,
@@ -715,14 +823,63 @@ extern "C" {
         callback: extern "C" fn(*const c_void, *const i8, *mut c_void),
         user_data: *mut c_void,
     );
+    pub fn sc_screenshot_manager_capture_and_save(
+        content_filter: *const c_void,
+        config: *const c_void,
+        path: *const i8,
+        format: i32,
+        quality: f32,
+        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        user_data: *mut c_void,
+    );
+    pub fn sc_screenshot_manager_capture_image_in_rect_and_save(
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        path: *const i8,
+        format: i32,
+        quality: f32,
+        callback: extern "C" fn(*mut c_void, bool, *const i8),
+        user_data: *mut c_void,
+    );
     pub fn cgimage_get_width(image: *const c_void) -> usize;
     pub fn cgimage_get_height(image: *const c_void) -> usize;
+    pub fn cgimage_get_color_space_name(
+        image: *const c_void,
+        buffer: *mut i8,
+        buffer_size: isize,
+    ) -> bool;
+    pub fn cgimage_get_size(image: *const c_void, out_width: *mut usize, out_height: *mut usize);
     pub fn cgimage_get_data(
         image: *const c_void,
         out_ptr: *mut *const u8,
         out_length: *mut usize,
     ) -> bool;
     pub fn cgimage_free_data(ptr: *mut u8);
+    pub fn cgimage_create_from_rgba(data: *const u8, width: usize, height: usize) -> *const c_void;
+    pub fn cgimage_draw_annotations(
+        image: *const c_void,
+        kinds: *const i32,
+        geometry: *const f64,
+        colors: *const f64,
+        widths: *const f64,
+        texts: *const *const i8,
+        count: usize,
+    ) -> *const c_void;
+    pub fn cgimage_redact(
+        image: *const c_void,
+        rects: *const f64,
+        count: usize,
+        mode: i32,
+        amount: f64,
+        color: *const f64,
+    ) -> *const c_void;
+    pub fn cgimage_create_thumbnail(image: *const c_void, max_pixel_size: usize) -> *const c_void;
+    pub fn cgimage_create_thumbnail_from_file(
+        path: *const i8,
+        max_pixel_size: usize,
+    ) -> *const c_void;
     pub fn cgimage_release(image: *const c_void);
     pub fn cgimage_save_png(image: *const c_void, path: *const i8) -> bool;
     pub fn cgimage_save_to_file(
@@ -731,6 +888,22 @@ extern "C" {
         format: i32,
         quality: f32,
     ) -> bool;
+    pub fn cgimage_save_hdr(
+        image: *const c_void,
+        path: *const i8,
+        format: i32,
+        quality: f32,
+    ) -> bool;
+    pub fn cgimage_save_to_file_with_metadata(
+        image: *const c_void,
+        path: *const i8,
+        format: i32,
+        quality: f32,
+        capture_time: f64,
+        description: *const i8,
+        dpi: f64,
+    ) -> bool;
+    pub fn cgimage_destination_supports_format(format: i32) -> bool;
 }
 
 // MARK: - SCScreenshotConfiguration (macOS 26.0+)
@@ -937,6 +1110,8 @@ extern "C" {
         timescale: *mut i32,
     );
     pub fn sc_recording_output_get_recorded_file_size(output: *const c_void) -> i64;
+    pub fn sc_recording_output_configuration_set_orientation(config: *const c_void, degrees: i32);
+    pub fn sc_recording_output_configuration_get_orientation(config: *const c_void) -> i32;
 }
 
 // MARK: - Audio Input Devices (AVFoundation)
@@ -962,4 +1137,12 @@ extern "C" {
 
     /// Get the default audio input device name into buffer
     pub fn sc_audio_get_default_input_device_name(buffer: *mut i8, buffer_size: isize) -> bool;
+
+    /// Get the sample rate/channel count pairs of a device (null ID for the default device)
+    pub fn sc_audio_get_input_device_formats(
+        device_id: *const i8,
+        sample_rates: *mut f64,
+        channel_counts: *mut i32,
+        capacity: isize,
+    ) -> isize;
 }
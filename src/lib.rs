@@ -184,8 +184,7 @@
 //! Enable the `async` feature for async/await support:
 //!
 //! ```no_run
-//! use screencapturekit::async_api::{AsyncSCShareableContent, AsyncSCStream};
-//! use screencapturekit::prelude::*;
+//! use screencapturekit::async_prelude::*;
 //!
 //! async fn capture() -> Result<(), Box<dyn std::error::Error>> {
 //!     let content = AsyncSCShareableContent::get().await?;
@@ -224,6 +223,7 @@
 //! | [`dispatch_queue`] | Custom dispatch queues for callbacks |
 //! | [`error`] | Error types and result aliases |
 //! | [`async_api`] | Async wrappers (requires `async` feature) |
+//! | [`async_prelude`] | [`prelude`] plus the async wrappers (requires `async` feature) |
 //! | [`screenshot_manager`] | Single-frame capture (macOS 14.0+) |
 //! | [`recording_output`] | Direct file recording (macOS 15.0+) |
 //!
@@ -232,6 +232,8 @@
 //! | Feature | Description |
 //! |---------|-------------|
 //! | `async` | Runtime-agnostic async API |
+//! | `serde` | `Serialize` for [`stream::StreamDiagnostics`] |
+//! | `testing` | Mock shareable content for tests without a display (`testing` module) |
 //! | `macos_13_0` | macOS 13.0+ APIs (audio capture, synchronization clock) |
 //! | `macos_14_0` | macOS 14.0+ APIs (screenshots, content picker) |
 //! | `macos_14_2` | macOS 14.2+ APIs (menu bar, child windows, presenter overlay) |
@@ -347,10 +349,14 @@ pub mod utils;
 #[cfg(feature = "async")]
 pub mod async_api;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 // Re-export commonly used types
 pub use cm::{
     codec_types, media_types, AudioBuffer, AudioBufferList, CMFormatDescription, CMSampleBuffer,
-    CMSampleTimingInfo, CMTime, CVPixelBuffer, CVPixelBufferPool, IOSurface, SCFrameStatus,
+    CMSampleTimingInfo, CMTime, CVPixelBuffer, CVPixelBufferPool, IOSurface, MediaType,
+    SCFrameStatus,
 };
 pub use utils::four_char_code::FourCharCode;
 
@@ -380,3 +386,35 @@ pub mod prelude {
         ErrorHandler,
     };
 }
+
+/// Prelude for the async API
+///
+/// Everything in [`prelude`] plus the async wrappers:
+/// ```rust
+/// use screencapturekit::async_prelude::*;
+///
+/// async fn first_frame() -> Result<CMSampleBuffer, SCError> {
+///     let content = AsyncSCShareableContent::get().await?;
+///     let filter = SCContentFilter::builder()
+///         .display(&content.displays()[0])
+///         .build();
+///     let config = SCStreamConfiguration::new();
+///     let stream = AsyncSCStream::new(&filter, &config, 8, SCStreamOutputType::Screen);
+///     stream.start_capture()?;
+///     stream
+///         .next()
+///         .await
+///         .ok_or_else(|| SCError::stream_error("Stream ended"))
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub mod async_prelude {
+    #[cfg(feature = "macos_14_0")]
+    pub use crate::async_api::{AsyncSCContentSharingPicker, AsyncSCScreenshotManager};
+    #[cfg(feature = "macos_15_0")]
+    pub use crate::async_api::{AsyncSCRecordingOutput, RecordingEvent};
+    pub use crate::async_api::{
+        AsyncSCShareableContent, AsyncSCShareableContentOptions, AsyncSCStream,
+    };
+    pub use crate::prelude::*;
+}
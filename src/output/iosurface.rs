@@ -5,6 +5,14 @@
 use std::ffi::c_void;
 use std::io;
 
+use crate::stream::configuration::PixelFormat;
+use crate::FourCharCode;
+
+/// `kCVPixelFormatType_OneComponent8`, one 8-bit channel per pixel
+const ONE_COMPONENT_8: FourCharCode = FourCharCode::from_bytes(*b"L008");
+/// `kCVPixelFormatType_TwoComponent8`, two interleaved 8-bit channels per pixel
+const TWO_COMPONENT_8: FourCharCode = FourCharCode::from_bytes(*b"2C08");
+
 /// Lock options for `IOSurface`
 #[repr(u32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -235,6 +243,41 @@ impl IOSurface {
         unsafe { crate::ffi::iosurface_get_pixel_format(self.0) }
     }
 
+    /// Get the pixel format as a [`PixelFormat`]
+    ///
+    /// Returns `None` for formats `ScreenCaptureKit` streams don't produce;
+    /// use [`pixel_format`](Self::pixel_format) for the raw code.
+    pub fn known_pixel_format(&self) -> Option<PixelFormat> {
+        PixelFormat::from_raw(self.pixel_format())
+    }
+
+    /// Get the effective format of one plane as an `OSType`
+    ///
+    /// Single-plane surfaces report their own format for plane 0. For the
+    /// biplanar `YCbCr` formats, the luma plane is
+    /// `kCVPixelFormatType_OneComponent8` (`L008`, an `R8` texture in Metal)
+    /// and the chroma plane is `kCVPixelFormatType_TwoComponent8` (`2C08`,
+    /// `RG8`). Returns `None` for a plane the surface doesn't have or a
+    /// surface format that isn't known.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::output::IOSurface;
+    /// use screencapturekit::FourCharCode;
+    ///
+    /// fn describe(surface: &IOSurface) {
+    ///     for plane in 0..surface.plane_count().max(1) {
+    ///         if let Some(format) = surface.plane_pixel_format(plane) {
+    ///             println!("plane {plane}: {}", FourCharCode::from_u32(format).display());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn plane_pixel_format(&self, plane: usize) -> Option<u32> {
+        plane_format(self.known_pixel_format()?, plane).map(FourCharCode::as_u32)
+    }
+
     /// Get the number of planes in the surface
     ///
     /// Multi-planar formats like YCbCr 420 have multiple planes:
@@ -325,6 +368,42 @@ impl IOSurface {
     pub fn is_in_use(&self) -> bool {
         unsafe { crate::ffi::iosurface_is_in_use(self.0) }
     }
+
+    /// Mark the surface as in use by this process
+    ///
+    /// While the use count is above zero the surface reports
+    /// [`is_in_use`](Self::is_in_use), and the stream's buffer pool won't
+    /// hand it out for another frame. Every call must be balanced by
+    /// [`decrement_use_count`](Self::decrement_use_count).
+    pub fn increment_use_count(&self) {
+        unsafe { crate::ffi::io_surface_increment_use_count(self.0) }
+    }
+
+    /// Release a use taken with [`increment_use_count`](Self::increment_use_count)
+    pub fn decrement_use_count(&self) {
+        unsafe { crate::ffi::io_surface_decrement_use_count(self.0) }
+    }
+
+    /// Number of uses of the surface in this process
+    pub fn use_count(&self) -> i32 {
+        unsafe { crate::ffi::io_surface_get_use_count(self.0) }
+    }
+}
+
+impl Clone for IOSurface {
+    fn clone(&self) -> Self {
+        unsafe { Self(crate::cm::ffi::io_surface_retain(self.0.cast_mut()).cast_const()) }
+    }
+}
+
+/// Per-plane format of a surface in `format`
+fn plane_format(format: PixelFormat, plane: usize) -> Option<FourCharCode> {
+    match (format, plane) {
+        (PixelFormat::BGRA | PixelFormat::l10r, 0) => Some(format.into()),
+        (PixelFormat::YCbCr_420v | PixelFormat::YCbCr_420f, 0) => Some(ONE_COMPONENT_8),
+        (PixelFormat::YCbCr_420v | PixelFormat::YCbCr_420f, 1) => Some(TWO_COMPONENT_8),
+        _ => None,
+    }
 }
 
 impl Drop for IOSurface {
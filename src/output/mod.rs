@@ -11,6 +11,7 @@
 //! - [`PixelBufferLockGuard`] - RAII guard for locked pixel buffer access
 //! - [`IOSurfaceLockGuard`] - RAII guard for locked `IOSurface` access
 //! - [`PixelBufferCursorExt`] - Extension trait for pixel-specific cursor operations
+//! - [`RawFrame`] - `#[repr(C)]` frame description for re-export across another FFI
 //!
 //! ## Metal Integration
 //!
@@ -76,9 +77,11 @@
 pub mod iosurface;
 pub mod metal;
 pub mod pixel_buffer;
+pub mod raw_frame;
 
 pub use crate::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
 pub use iosurface::{CVPixelBufferIOSurface, IOSurface, IOSurfaceLockGuard, IOSurfaceLockOptions};
 pub use pixel_buffer::{
     CVImageBufferLockExt, PixelBufferCursorExt, PixelBufferLockFlags, PixelBufferLockGuard,
 };
+pub use raw_frame::{RawFrame, RawFrameGuard};
@@ -0,0 +1,133 @@
+//! Flat frame description for re-export across another FFI
+//!
+//! [`RawFrame`] is a `#[repr(C)]` plain-old-data view of a captured video
+//! frame: dimensions, pixel format, a pointer to the pixels and the
+//! presentation timestamp. It carries no Rust types, so binding layers (N-API,
+//! a C ABI, ...) can hand it over as is instead of re-deriving the layout.
+//!
+//! The pointer is only valid while the [`RawFrameGuard`] it came from keeps
+//! the pixel buffer locked.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use screencapturekit::cm::CMSampleBuffer;
+//!
+//! # fn example(sample: &CMSampleBuffer) {
+//! if let Some(guard) = sample.to_raw_frame() {
+//!     let frame = guard.frame();
+//!     // Pass `frame` to the foreign side; keep `guard` alive until it's done
+//!     println!("{}x{} at {:p}", frame.width, frame.height, frame.data_ptr);
+//! }
+//! # }
+//! ```
+
+use std::ops::Deref;
+
+use crate::cm::CVPixelBuffer;
+
+use super::PixelBufferLockFlags;
+
+/// Plain-old-data description of a locked video frame
+///
+/// All fields are fixed-size integers or a raw pointer, with C layout.
+/// `data_ptr` points at `data_len` bytes laid out as `height` rows of
+/// `bytes_per_row` bytes each; rows may be padded beyond `width` pixels.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawFrame {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// Stride of one row in bytes
+    pub bytes_per_row: u32,
+    /// Core Video pixel format (`OSType`), e.g. `'BGRA'`
+    pub pixel_format: u32,
+    /// First byte of the pixel data
+    pub data_ptr: *const u8,
+    /// Length of the pixel data in bytes
+    pub data_len: usize,
+    /// Presentation timestamp value
+    pub pts_value: i64,
+    /// Presentation timestamp timescale (units per second)
+    pub pts_timescale: i32,
+}
+
+/// Keeps a frame's pixel buffer locked while its [`RawFrame`] is in use
+///
+/// Created by [`CMSampleBuffer::to_raw_frame`](crate::cm::CMSampleBuffer::to_raw_frame).
+/// The guard owns a reference to the pixel buffer, so it can outlive the
+/// sample buffer it was created from. Dropping it unlocks the buffer and
+/// invalidates [`RawFrame::data_ptr`].
+pub struct RawFrameGuard {
+    buffer: CVPixelBuffer,
+    frame: RawFrame,
+}
+
+impl RawFrameGuard {
+    /// Lock `buffer` read-only and describe its pixels
+    ///
+    /// Returns `None` for planar formats, whose planes don't form a single
+    /// contiguous range, or if locking fails.
+    pub(crate) fn new(buffer: CVPixelBuffer, pts_value: i64, pts_timescale: i32) -> Option<Self> {
+        if buffer.is_planar() {
+            return None;
+        }
+        let flags = PixelBufferLockFlags::ReadOnly.as_u32();
+        buffer.lock_raw(flags).ok()?;
+        let Some(base) = buffer.base_address() else {
+            let _ = buffer.unlock_raw(flags);
+            return None;
+        };
+        let height = buffer.height();
+        let bytes_per_row = buffer.bytes_per_row();
+        // Frame dimensions and strides are far below u32::MAX
+        #[allow(clippy::cast_possible_truncation)]
+        let frame = RawFrame {
+            width: buffer.width() as u32,
+            height: height as u32,
+            bytes_per_row: bytes_per_row as u32,
+            pixel_format: buffer.pixel_format(),
+            data_ptr: base.cast_const(),
+            data_len: height * bytes_per_row,
+            pts_value,
+            pts_timescale,
+        };
+        Some(Self { buffer, frame })
+    }
+
+    /// The frame description, valid while this guard is alive
+    pub const fn frame(&self) -> RawFrame {
+        self.frame
+    }
+
+    /// The pixel data as a slice
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.frame.data_ptr, self.frame.data_len) }
+    }
+}
+
+impl Deref for RawFrameGuard {
+    type Target = RawFrame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl Drop for RawFrameGuard {
+    fn drop(&mut self) {
+        let _ = self
+            .buffer
+            .unlock_raw(PixelBufferLockFlags::ReadOnly.as_u32());
+    }
+}
+
+impl std::fmt::Debug for RawFrameGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawFrameGuard")
+            .field("frame", &self.frame)
+            .finish_non_exhaustive()
+    }
+}
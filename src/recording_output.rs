@@ -32,6 +32,92 @@ pub enum SCRecordingOutputFileType {
     MOV = 1,
 }
 
+/// Rotation applied when a recording is played back
+///
+/// The rotation is written into the video track's transform once recording
+/// finishes; the encoded frames are left as captured.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SCRecordingOrientation {
+    /// Play back as captured
+    #[default]
+    Up = 0,
+    /// Rotate 90° clockwise
+    Right = 90,
+    /// Rotate 180°
+    Down = 180,
+    /// Rotate 90° counterclockwise
+    Left = 270,
+}
+
+impl SCRecordingOrientation {
+    /// The orientation closest to a clockwise rotation in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::recording_output::SCRecordingOrientation;
+    ///
+    /// assert_eq!(SCRecordingOrientation::from_degrees(90.0), SCRecordingOrientation::Right);
+    /// assert_eq!(SCRecordingOrientation::from_degrees(-90.0), SCRecordingOrientation::Left);
+    /// assert_eq!(SCRecordingOrientation::from_degrees(360.0), SCRecordingOrientation::Up);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_degrees(degrees: f64) -> Self {
+        match ((degrees / 90.0).round() as i64).rem_euclid(4) {
+            1 => Self::Right,
+            2 => Self::Down,
+            3 => Self::Left,
+            _ => Self::Up,
+        }
+    }
+
+    /// The orientation of a display, from its rotation in System Settings
+    ///
+    /// Use this with [`SCRecordingOutputConfiguration::with_orientation`] when
+    /// recording a rotated (portrait) display whose frames arrive in the
+    /// panel's native orientation.
+    #[must_use]
+    pub fn of_display(display_id: u32) -> Self {
+        Self::from_degrees(crate::cg_display::CGDisplay::new(display_id).rotation())
+    }
+
+    /// Clockwise rotation in degrees
+    #[must_use]
+    pub const fn degrees(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Video codecs the system can record with
+///
+/// Use this to populate a format picker before anything is configured; it is
+/// the same list as [`SCRecordingOutputConfiguration::available_video_codecs`]
+/// on a fresh configuration.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::recording_output::{self, SCRecordingOutputCodec};
+///
+/// let hevc = recording_output::supported_codecs().contains(&SCRecordingOutputCodec::HEVC);
+/// println!("HEVC recording available: {hevc}");
+/// ```
+#[must_use]
+pub fn supported_codecs() -> Vec<SCRecordingOutputCodec> {
+    SCRecordingOutputConfiguration::new().available_video_codecs()
+}
+
+/// File types the system can record to
+///
+/// The counterpart of [`supported_codecs`] for
+/// [`SCRecordingOutputConfiguration::available_output_file_types`].
+#[must_use]
+pub fn supported_file_types() -> Vec<SCRecordingOutputFileType> {
+    SCRecordingOutputConfiguration::new().available_output_file_types()
+}
+
 /// Configuration for recording output
 pub struct SCRecordingOutputConfiguration {
     ptr: *const c_void,
@@ -102,6 +188,48 @@ impl SCRecordingOutputConfiguration {
         }
     }
 
+    /// Set the playback rotation of the recorded video
+    ///
+    /// The rotation is applied to the finished file just before
+    /// [`recording_did_finish`](SCRecordingOutputDelegate::recording_did_finish)
+    /// is called; if writing it fails,
+    /// [`recording_did_fail`](SCRecordingOutputDelegate::recording_did_fail)
+    /// is called instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::recording_output::{SCRecordingOrientation, SCRecordingOutputConfiguration};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let config = SCRecordingOutputConfiguration::new()
+    ///     .with_output_url(Path::new("/tmp/portrait.mp4"))
+    ///     .with_orientation(SCRecordingOrientation::of_display(display.display_id()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_orientation(self, orientation: SCRecordingOrientation) -> Self {
+        unsafe {
+            crate::ffi::sc_recording_output_configuration_set_orientation(
+                self.ptr,
+                orientation.degrees(),
+            );
+        }
+        self
+    }
+
+    /// Get the playback rotation of the recorded video
+    pub fn orientation(&self) -> SCRecordingOrientation {
+        let degrees =
+            unsafe { crate::ffi::sc_recording_output_configuration_get_orientation(self.ptr) };
+        SCRecordingOrientation::from_degrees(f64::from(degrees))
+    }
+
     /// Get the number of available video codecs
     pub fn available_video_codecs_count(&self) -> usize {
         let count = unsafe {
@@ -211,6 +339,7 @@ impl std::fmt::Debug for SCRecordingOutputConfiguration {
         f.debug_struct("SCRecordingOutputConfiguration")
             .field("video_codec", &self.video_codec())
             .field("file_type", &self.output_file_type())
+            .field("orientation", &self.orientation())
             .finish()
     }
 }
@@ -560,6 +689,46 @@ impl Drop for SCRecordingOutput {
     }
 }
 
+/// How a recording output attached to a stream is being recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordingOutputMode {
+    /// The system encodes the stream into the output's file
+    Native,
+    /// The system refused another recording output on the stream, so frames
+    /// from the stream's own sample handler are encoded into the file instead
+    FannedOut,
+}
+
+/// Handle for a recording output attached to a stream
+///
+/// Returned by [`SCStream::add_recording_output_with_token`](crate::stream::SCStream::add_recording_output_with_token).
+/// Keeps the output alive; pass [`output`](Self::output) to
+/// [`SCStream::remove_recording_output`](crate::stream::SCStream::remove_recording_output)
+/// to stop recording into it.
+#[derive(Debug, Clone)]
+pub struct RecordingOutputToken {
+    output: SCRecordingOutput,
+    mode: RecordingOutputMode,
+}
+
+impl RecordingOutputToken {
+    pub(crate) fn new(output: SCRecordingOutput, mode: RecordingOutputMode) -> Self {
+        Self { output, mode }
+    }
+
+    /// The recording output this token refers to
+    #[must_use]
+    pub fn output(&self) -> &SCRecordingOutput {
+        &self.output
+    }
+
+    /// Whether the output is recorded by the system or fanned out
+    #[must_use]
+    pub fn mode(&self) -> RecordingOutputMode {
+        self.mode
+    }
+}
+
 // Safety: SCRecordingOutput wraps an Objective-C object that is thread-safe
 unsafe impl Send for SCRecordingOutput {}
 unsafe impl Sync for SCRecordingOutput {}
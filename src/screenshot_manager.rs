@@ -3,14 +3,19 @@
 //! Available on macOS 14.0+
 //! Provides high-quality screenshot capture without the overhead of setting up a stream.
 
+use crate::cg_display::CGDisplay;
 use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCRunningApplication, SCShareableContent, SCWindow};
 use crate::stream::configuration::SCStreamConfiguration;
 use crate::stream::content_filter::SCContentFilter;
-use crate::utils::sync_completion::{error_from_cstr, SyncCompletion};
+use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
+use crate::utils::os_version::MacOSVersion;
+use crate::utils::sync_completion::{error_from_cstr, SyncCompletion, UnitCompletion};
+use crate::utils::timer::{spawn_timer_after, Timer};
 use std::ffi::c_void;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[cfg(feature = "macos_15_2")]
-use crate::cg::CGRect;
+use crate::cg::{CGColor, CGPoint, CGRect, CGSize};
 
 /// Image output format for saving screenshots
 ///
@@ -42,9 +47,55 @@ pub enum ImageFormat {
     Bmp,
     /// HEIC format with quality (0.0-1.0) - efficient compression
     Heic(f32),
+    /// WebP format with quality (0.0-1.0)
+    ///
+    /// Not every macOS release can write WebP; check
+    /// [`supported_image_formats`] first.
+    WebP(f32),
+    /// AVIF format with quality (0.0-1.0)
+    ///
+    /// Not every macOS release can write AVIF; check
+    /// [`supported_image_formats`] first.
+    Avif(f32),
 }
 
 impl ImageFormat {
+    /// Create a JPEG format, rejecting a quality outside `0.0..=1.0`
+    ///
+    /// `ImageFormat::Jpeg(q)` silently clamps `q` when the image is encoded.
+    /// Use this instead when the quality comes from user input and an invalid
+    /// value should be reported rather than corrected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::InvalidConfiguration` if `quality` is NaN or outside
+    /// `0.0..=1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::screenshot_manager::ImageFormat;
+    ///
+    /// assert_eq!(ImageFormat::jpeg_checked(0.8).unwrap(), ImageFormat::Jpeg(0.8));
+    /// assert!(ImageFormat::jpeg_checked(80.0).is_err());
+    /// ```
+    pub fn jpeg_checked(quality: f32) -> Result<Self, SCError> {
+        check_quality("JPEG", quality).map(Self::Jpeg)
+    }
+
+    /// Create a HEIC format, rejecting a quality outside `0.0..=1.0`
+    ///
+    /// The checked counterpart of `ImageFormat::Heic(q)`; see
+    /// [`jpeg_checked`](Self::jpeg_checked).
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::InvalidConfiguration` if `quality` is NaN or outside
+    /// `0.0..=1.0`.
+    pub fn heic_checked(quality: f32) -> Result<Self, SCError> {
+        check_quality("HEIC", quality).map(Self::Heic)
+    }
+
     fn to_format_id(self) -> i32 {
         match self {
             Self::Png => 0,
@@ -53,12 +104,14 @@ impl ImageFormat {
             Self::Gif => 3,
             Self::Bmp => 4,
             Self::Heic(_) => 5,
+            Self::WebP(_) => 6,
+            Self::Avif(_) => 7,
         }
     }
 
     fn quality(self) -> f32 {
         match self {
-            Self::Jpeg(q) | Self::Heic(q) => q.clamp(0.0, 1.0),
+            Self::Jpeg(q) | Self::Heic(q) | Self::WebP(q) | Self::Avif(q) => q.clamp(0.0, 1.0),
             _ => 1.0,
         }
     }
@@ -73,6 +126,237 @@ impl ImageFormat {
             Self::Gif => "gif",
             Self::Bmp => "bmp",
             Self::Heic(_) => "heic",
+            Self::WebP(_) => "webp",
+            Self::Avif(_) => "avif",
+        }
+    }
+}
+
+/// List the image formats that can be written on the running system
+///
+/// Asks `ImageIO` which of PNG, JPEG, TIFF, GIF, BMP, HEIC, WebP and AVIF it
+/// can encode, so a UI can offer only formats that [`CGImage::save`] will
+/// accept. Lossy formats are returned with a quality of 1.0.
+///
+/// Unlike `SCScreenshotConfiguration::supported_content_types`, this needs
+/// neither macOS 26.0 nor the `macos_26_0` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::screenshot_manager::{supported_image_formats, ImageFormat};
+///
+/// let formats = supported_image_formats();
+/// let can_heic = formats.iter().any(|f| matches!(f, ImageFormat::Heic(_)));
+/// for format in &formats {
+///     println!("can write .{}", format.extension());
+/// }
+/// ```
+pub fn supported_image_formats() -> Vec<ImageFormat> {
+    [
+        ImageFormat::Png,
+        ImageFormat::Jpeg(1.0),
+        ImageFormat::Tiff,
+        ImageFormat::Gif,
+        ImageFormat::Bmp,
+        ImageFormat::Heic(1.0),
+        ImageFormat::WebP(1.0),
+        ImageFormat::Avif(1.0),
+    ]
+    .into_iter()
+    .filter(|format| unsafe {
+        crate::ffi::cgimage_destination_supports_format(format.to_format_id())
+    })
+    .collect()
+}
+
+fn check_quality(format: &str, quality: f32) -> Result<f32, SCError> {
+    if (0.0..=1.0).contains(&quality) {
+        Ok(quality)
+    } else {
+        Err(SCError::invalid_config(format!(
+            "{format} quality must be between 0.0 and 1.0, got {quality}"
+        )))
+    }
+}
+
+/// Metadata embedded in an image file by [`CGImage::save_with_metadata`]
+///
+/// Every field is optional; unset fields are simply not written. The values are
+/// stored in the standard EXIF/TIFF (and PNG text) properties, so they show up
+/// in Finder's info panel, Preview and `exiftool` without a sidecar file.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::screenshot_manager::ImageMetadata;
+/// use std::time::SystemTime;
+///
+/// let metadata = ImageMetadata::new()
+///     .with_capture_time(SystemTime::now())
+///     .with_description("Display 1 (id 69733378)")
+///     .with_dpi(144.0);
+/// assert_eq!(metadata.dpi(), Some(144.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageMetadata {
+    capture_time: Option<SystemTime>,
+    description: Option<String>,
+    dpi: Option<f64>,
+}
+
+impl ImageMetadata {
+    /// Create empty metadata
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time the image was captured
+    ///
+    /// Written as the EXIF original date/time and TIFF date/time, in local time.
+    #[must_use]
+    pub fn with_capture_time(mut self, time: SystemTime) -> Self {
+        self.capture_time = Some(time);
+        self
+    }
+
+    /// Set a free-form description of the capture source, such as a display or window name
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the resolution in dots per inch
+    ///
+    /// Use `72.0 * scale_factor` for captures of a Retina display so the image
+    /// opens at its on-screen physical size. Non-positive values are ignored.
+    #[must_use]
+    pub fn with_dpi(mut self, dpi: f64) -> Self {
+        self.dpi = Some(dpi);
+        self
+    }
+
+    /// Get the capture time
+    #[must_use]
+    pub const fn capture_time(&self) -> Option<SystemTime> {
+        self.capture_time
+    }
+
+    /// Get the source description
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the resolution in dots per inch
+    #[must_use]
+    pub const fn dpi(&self) -> Option<f64> {
+        self.dpi
+    }
+
+    /// Seconds since the Unix epoch, or NaN when no time is set
+    fn capture_time_secs(&self) -> f64 {
+        self.capture_time
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(f64::NAN, |elapsed| elapsed.as_secs_f64())
+    }
+}
+
+/// A drawing operation for [`CGImage::draw`]
+///
+/// Coordinates are in image pixels with the origin at the top-left corner, the
+/// same space as [`CGImage::rgba_data`]. Colors are sRGB.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawOp {
+    /// Rectangle outline; the stroke is centered on the rectangle's edges
+    Rect {
+        rect: CGRect,
+        color: CGColor,
+        width: f64,
+    },
+    /// Filled rectangle, e.g. a translucent highlight
+    FilledRect { rect: CGRect, color: CGColor },
+    /// Straight line with round caps
+    Line {
+        from: CGPoint,
+        to: CGPoint,
+        color: CGColor,
+        width: f64,
+    },
+    /// Single line of text in the system font
+    ///
+    /// `origin` is the top-left corner of the text; `size` is the font size in pixels.
+    Text {
+        text: String,
+        origin: CGPoint,
+        color: CGColor,
+        size: f64,
+    },
+}
+
+impl DrawOp {
+    /// Operation kind as understood by the Swift bridge
+    const fn kind(&self) -> i32 {
+        match self {
+            Self::Rect { .. } => 0,
+            Self::FilledRect { .. } => 1,
+            Self::Line { .. } => 2,
+            Self::Text { .. } => 3,
+        }
+    }
+
+    /// Geometry as four values: a rectangle, two line end points, or the text origin
+    const fn geometry(&self) -> [f64; 4] {
+        match self {
+            Self::Rect { rect, .. } | Self::FilledRect { rect, .. } => {
+                [rect.x, rect.y, rect.width, rect.height]
+            }
+            Self::Line { from, to, .. } => [from.x, from.y, to.x, to.y],
+            Self::Text { origin, .. } => [origin.x, origin.y, 0.0, 0.0],
+        }
+    }
+
+    const fn color(&self) -> CGColor {
+        match self {
+            Self::Rect { color, .. }
+            | Self::FilledRect { color, .. }
+            | Self::Line { color, .. }
+            | Self::Text { color, .. } => *color,
+        }
+    }
+
+    /// Stroke width, or font size for text
+    const fn width(&self) -> f64 {
+        match self {
+            Self::Rect { width, .. } | Self::Line { width, .. } => *width,
+            Self::FilledRect { .. } => 0.0,
+            Self::Text { size, .. } => *size,
+        }
+    }
+}
+
+/// How [`CGImage::redact`] hides a region
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RedactMode {
+    /// Gaussian blur with the given radius in pixels
+    ///
+    /// Small radii can leave large text readable; 10 or more is a safer choice.
+    Blur(f64),
+    /// Mosaic of square blocks with the given edge length in pixels
+    Pixelate(u32),
+    /// Cover the region with a solid color
+    Solid(CGColor),
+}
+
+impl RedactMode {
+    /// Mode, amount and color as passed to the Swift bridge
+    const fn raw(self) -> (i32, f64, CGColor) {
+        match self {
+            Self::Blur(radius) => (0, radius, CGColor::BLACK),
+            Self::Pixelate(block) => (1, block as f64, CGColor::BLACK),
+            Self::Solid(color) => (2, 0.0, color),
         }
     }
 }
@@ -201,6 +485,143 @@ pub fn capture_sample_buffer_with_stream(
     completion.wait().map_err(SCError::internal_error)
 }
 
+/// Frame selection for [`capture_sample_buffer_with_stream_stable`]
+///
+/// The first frames of a freshly started stream are often blank or still
+/// being composited, which is the usual cause of a black single-frame capture.
+/// These options discard such warm-up frames before one is returned.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::screenshot_manager::StableCaptureOptions;
+///
+/// use std::time::Duration;
+///
+/// let options = StableCaptureOptions {
+///     skip_frames: 2,
+///     require_complete: true,
+///     timeout: Duration::from_secs(2),
+/// };
+/// assert_eq!(StableCaptureOptions::default().skip_frames, 0);
+/// assert_eq!(StableCaptureOptions::default().timeout, Duration::from_secs(5));
+/// # let _ = options;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableCaptureOptions {
+    /// Number of frames to discard before returning one
+    ///
+    /// When `require_complete` is set, only complete frames are counted.
+    pub skip_frames: u32,
+    /// Only return a frame with status [`SCFrameStatus::Complete`](crate::cm::SCFrameStatus::Complete)
+    /// that carries an image buffer
+    pub require_complete: bool,
+    /// How long to wait for a matching frame (5 seconds by default)
+    pub timeout: Duration,
+}
+
+impl Default for StableCaptureOptions {
+    fn default() -> Self {
+        Self {
+            skip_frames: 0,
+            require_complete: false,
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl StableCaptureOptions {
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Options that wait for the first complete frame
+    #[must_use]
+    pub const fn complete() -> Self {
+        Self {
+            skip_frames: 0,
+            require_complete: true,
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the number of frames to discard
+    #[must_use]
+    pub const fn with_skip_frames(mut self, skip_frames: u32) -> Self {
+        self.skip_frames = skip_frames;
+        self
+    }
+
+    /// Set whether only complete frames are accepted
+    #[must_use]
+    pub const fn with_require_complete(mut self, require_complete: bool) -> Self {
+        self.require_complete = require_complete;
+        self
+    }
+
+    /// Set how long to wait for a matching frame
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Single-frame capture that skips warm-up frames
+///
+/// Behaves like [`capture_sample_buffer_with_stream`], but keeps the stream
+/// running until a frame matching `options` arrives. With
+/// `require_complete` set, a display that is not changing can take a moment
+/// to produce its first complete frame.
+///
+/// # Errors
+///
+/// Returns an error if the stream cannot be started or capture fails, and
+/// [`SCError::Timeout`] if no matching frame arrives within `options.timeout`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::prelude::*;
+/// use screencapturekit::screenshot_manager::{
+///     capture_sample_buffer_with_stream_stable, StableCaptureOptions,
+/// };
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let content = SCShareableContent::get()?;
+/// # let display = &content.displays()[0];
+/// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+/// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+/// let options = StableCaptureOptions::complete().with_skip_frames(1);
+/// let sample_buffer = capture_sample_buffer_with_stream_stable(&filter, &config, options)?;
+/// assert!(sample_buffer.image_buffer().is_some());
+/// # Ok(())
+/// # }
+/// ```
+pub fn capture_sample_buffer_with_stream_stable(
+    content_filter: &SCContentFilter,
+    configuration: &SCStreamConfiguration,
+    options: StableCaptureOptions,
+) -> Result<crate::cm::CMSampleBuffer, SCError> {
+    let (completion, context) = SyncCompletion::<crate::cm::CMSampleBuffer>::new();
+
+    unsafe {
+        crate::ffi::sc_stream_capture_sample_buffer_stable(
+            content_filter.as_ptr(),
+            configuration.as_ptr(),
+            options.skip_frames,
+            options.require_complete,
+            options.timeout.as_secs_f64(),
+            sample_buffer_callback,
+            context,
+        );
+    }
+
+    let timeout = options.timeout;
+    completion
+        .wait_timeout(timeout)
+        .ok_or_else(|| SCError::Timeout(format!("No matching frame within {timeout:?}")))?
+        .map_err(SCError::internal_error)
+}
+
 
 #[cfg(feature = "macos_26_0")]
 extern "C" fn screenshot_output_callback(
@@ -225,6 +646,98 @@ extern "C" fn screenshot_output_callback(
     }
 }
 
+/// Resolution of a capture of `filter`, derived from its backing scale
+///
+/// With the content rectangle available, the ratio of image pixels to content
+/// points accounts for any scaling applied by the stream configuration.
+#[cfg(feature = "macos_14_0")]
+fn capture_dpi(filter: &SCContentFilter, image: &CGImage) -> Option<f64> {
+    #[cfg(feature = "macos_14_2")]
+    {
+        let points = filter.content_rect().width;
+        if points > 0.0 {
+            // Image widths are far below 2^52, so the conversion is exact
+            #[allow(clippy::cast_precision_loss)]
+            return Some(72.0 * image.width() as f64 / points);
+        }
+    }
+    #[cfg(not(feature = "macos_14_2"))]
+    let _ = image;
+
+    let scale = f64::from(filter.point_pixel_scale());
+    (scale > 0.0).then_some(72.0 * scale)
+}
+
+#[cfg(not(feature = "macos_14_0"))]
+fn capture_dpi(_filter: &SCContentFilter, _image: &CGImage) -> Option<f64> {
+    None
+}
+
+/// The frontmost normal window in `content` whose frame contains `point`
+///
+/// Only layer 0 windows are considered, so menus, the Dock and floating
+/// overlays are never hit. The system lists on-screen windows front to back,
+/// so the first hit in `content` is the frontmost one.
+fn frontmost_window_at(content: &SCShareableContent, point: CGPoint) -> Option<SCWindow> {
+    content.windows().into_iter().find(|window| {
+        window.window_layer() == 0 && window.is_on_screen() && window.frame().contains_point(point)
+    })
+}
+
+/// Pixels per point of the display that holds the center of `window`
+///
+/// Falls back to 1.0 when no display in `content` contains it.
+#[cfg(not(feature = "macos_14_0"))]
+fn window_pixel_scale(content: &SCShareableContent, window: &SCWindow) -> f64 {
+    let center = window.frame().center();
+    content
+        .displays()
+        .iter()
+        .find(|display| display.frame().contains_point(center))
+        .map_or(1.0, display_pixel_scale)
+}
+
+/// Pixels per point of `display`, from its current mode
+fn display_pixel_scale(display: &SCDisplay) -> f64 {
+    CGDisplay::new(display.display_id())
+        .display_mode()
+        .filter(|mode| mode.logical_width() > 0)
+        .map_or(1.0, |mode| {
+            f64::from(mode.pixel_width()) / f64::from(mode.logical_width())
+        })
+}
+
+/// Whether `app` has a window in `content` that is on screen and overlaps `display`
+fn has_window_on_display(
+    content: &SCShareableContent,
+    app: &SCRunningApplication,
+    display: &SCDisplay,
+) -> bool {
+    let bounds = display.frame();
+    let overlaps = |frame: CGRect| {
+        frame.x < bounds.x + bounds.width
+            && bounds.x < frame.x + frame.width
+            && frame.y < bounds.y + bounds.height
+            && bounds.y < frame.y + frame.height
+    };
+    content.windows().iter().any(|window| {
+        window.is_on_screen()
+            && window
+                .owning_application()
+                .is_some_and(|owner| owner.process_id() == app.process_id())
+            && overlaps(window.frame())
+    })
+}
+
+fn check_thumbnail_size(max_pixel_size: usize) -> Result<(), SCError> {
+    if max_pixel_size == 0 {
+        return Err(SCError::invalid_config(
+            "Thumbnail size must be at least 1 pixel",
+        ));
+    }
+    Ok(())
+}
+
 /// `CGImage` wrapper for screenshots
 ///
 /// Represents a Core Graphics image returned from screenshot capture.
@@ -250,11 +763,135 @@ extern "C" fn screenshot_output_callback(
 /// ```
 pub struct CGImage {
     ptr: *const c_void,
+    dpi: Option<f64>,
 }
 
 impl CGImage {
     pub(crate) fn from_ptr(ptr: *const c_void) -> Self {
-        Self { ptr }
+        Self { ptr, dpi: None }
+    }
+
+    /// Create an image from raw RGBA pixel data
+    ///
+    /// `data` uses the same layout as [`rgba_data`](Self::rgba_data): 4 bytes
+    /// per pixel with premultiplied alpha, row-major, no row padding. The image
+    /// is tagged as sRGB. This lets pixels processed in Rust be saved with
+    /// [`save`](Self::save) again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dimension is zero, `data` is not exactly
+    /// `width * height * 4` bytes, or the image cannot be created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::screenshot_manager::{CGImage, ImageFormat};
+    ///
+    /// # fn example(image: &CGImage) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut pixels = image.rgba_data()?;
+    /// // Invert the colors, leaving alpha alone
+    /// for pixel in pixels.chunks_exact_mut(4) {
+    ///     for channel in &mut pixel[..3] {
+    ///         *channel = pixel[3] - *channel;
+    ///     }
+    /// }
+    /// let inverted = CGImage::from_rgba(image.width(), image.height(), &pixels)?;
+    /// inverted.save("/tmp/inverted.png", ImageFormat::Png)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_rgba(width: usize, height: usize, data: &[u8]) -> Result<Self, SCError> {
+        if width == 0 {
+            return Err(SCError::invalid_dimension("width", width));
+        }
+        if height == 0 {
+            return Err(SCError::invalid_dimension("height", height));
+        }
+        let expected = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(4));
+        if expected != Some(data.len()) {
+            return Err(SCError::InvalidBuffer(format!(
+                "expected {width}x{height}x4 bytes of RGBA data, got {}",
+                data.len()
+            )));
+        }
+
+        let ptr = unsafe { crate::ffi::cgimage_create_from_rgba(data.as_ptr(), width, height) };
+        if ptr.is_null() {
+            return Err(SCError::internal_error(
+                "Failed to create CGImage from RGBA data",
+            ));
+        }
+        Ok(Self::from_ptr(ptr))
+    }
+
+    /// Set the resolution written into files saved from this image
+    ///
+    /// Without a DPI, encoders assume 72 DPI, so a Retina capture opens at
+    /// twice its physical size in print and publishing tools. Use
+    /// `72.0 * backing_scale` for display captures. Images returned by
+    /// [`SCScreenshotManager::capture_image`] are tagged automatically.
+    /// Non-positive values clear the setting.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::SCScreenshotManager;
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let mut image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// image.set_dpi(144.0);
+    /// image.save_png("/tmp/screenshot.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_dpi(&mut self, dpi: f64) -> &mut Self {
+        self.dpi = (dpi > 0.0).then_some(dpi);
+        self
+    }
+
+    /// Get the resolution written into saved files, if one is set
+    #[must_use]
+    pub const fn dpi(&self) -> Option<f64> {
+        self.dpi
+    }
+
+    /// Get the number of image pixels per point
+    ///
+    /// Divide pixel coordinates by this to map them back to screen points.
+    /// Images returned by [`SCScreenshotManager::capture_image`] carry the
+    /// scale of the captured content, including any scaling applied by the
+    /// stream configuration, so a full-size capture of a Retina display
+    /// reports `2.0`. Derived from [`dpi`](Self::dpi); returns `1.0` if no
+    /// resolution is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::SCScreenshotManager;
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(3840).with_height(2160);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// let scale = image.scale_factor();
+    /// println!("{} x {} points", image.width() as f64 / scale, image.height() as f64 / scale);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn scale_factor(&self) -> f64 {
+        self.dpi.map_or(1.0, |dpi| dpi / 72.0)
     }
 
     /// Get image width in pixels
@@ -287,6 +924,45 @@ impl CGImage {
         unsafe { crate::ffi::cgimage_get_height(self.ptr) }
     }
 
+    /// Get image width and height in pixels
+    ///
+    /// Reads both dimensions in one call, which is cheaper than calling
+    /// [`width`](Self::width) and [`height`](Self::height) when laying out
+    /// many images. No pixel data is copied.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn size(&self) -> CGSize {
+        let mut width = 0;
+        let mut height = 0;
+        unsafe { crate::ffi::cgimage_get_size(self.ptr, &mut width, &mut height) };
+        CGSize::new(width as f64, height as f64)
+    }
+
+    /// Name of the image's color space, such as `"kCGColorSpaceSRGB"` or
+    /// `"kCGColorSpaceDisplayP3"`
+    ///
+    /// Check this before converting or displaying an image to avoid color
+    /// shifts; HDR captures report an extended-range space such as
+    /// `"kCGColorSpaceExtendedLinearDisplayP3"`. Returns `None` for
+    /// device-dependent and unnamed color spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::screenshot_manager::CGImage;
+    ///
+    /// let image = CGImage::from_rgba(1, 1, &[255, 0, 0, 255]).unwrap();
+    /// assert_eq!(image.color_space_name().as_deref(), Some("kCGColorSpaceSRGB"));
+    /// ```
+    #[must_use]
+    pub fn color_space_name(&self) -> Option<String> {
+        unsafe {
+            ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                crate::ffi::cgimage_get_color_space_name(self.ptr, buf, len)
+            })
+        }
+    }
+
     #[must_use]
     pub fn as_ptr(&self) -> *const c_void {
         self.ptr
@@ -328,6 +1004,230 @@ impl CGImage {
         Ok(data)
     }
 
+    /// Compute a hash of the image's dimensions and RGBA pixel data
+    ///
+    /// Two images with identical pixels produce the same hash, which makes this
+    /// useful for skipping unchanged frames. The hash is not stable across
+    /// Rust versions and should not be persisted.
+    ///
+    /// # Errors
+    /// Returns an error if the pixel data cannot be extracted
+    pub fn content_hash(&self) -> Result<u64, SCError> {
+        use std::hash::{Hash, Hasher};
+
+        let data = self.rgba_data()?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.width().hash(&mut hasher);
+        self.height().hash(&mut hasher);
+        hasher.write(&data);
+        Ok(hasher.finish())
+    }
+
+    /// Draw annotations onto a copy of the image
+    ///
+    /// Operations are applied in order, so later ones paint over earlier ones.
+    /// The original image is left unchanged; the returned image keeps its DPI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a text operation contains null bytes or rendering fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cg::{CGColor, CGPoint, CGRect};
+    /// use screencapturekit::screenshot_manager::{CGImage, DrawOp, ImageFormat};
+    ///
+    /// # fn example(image: &CGImage) -> Result<(), Box<dyn std::error::Error>> {
+    /// let annotated = image.draw(&[
+    ///     DrawOp::FilledRect {
+    ///         rect: CGRect::new(100.0, 100.0, 300.0, 80.0),
+    ///         color: CGColor::rgba(1.0, 1.0, 0.0, 0.3),
+    ///     },
+    ///     DrawOp::Rect {
+    ///         rect: CGRect::new(100.0, 100.0, 300.0, 80.0),
+    ///         color: CGColor::RED,
+    ///         width: 4.0,
+    ///     },
+    ///     DrawOp::Line {
+    ///         from: CGPoint::new(500.0, 400.0),
+    ///         to: CGPoint::new(400.0, 180.0),
+    ///         color: CGColor::RED,
+    ///         width: 4.0,
+    ///     },
+    ///     DrawOp::Text {
+    ///         text: "Click here".to_string(),
+    ///         origin: CGPoint::new(420.0, 410.0),
+    ///         color: CGColor::RED,
+    ///         size: 28.0,
+    ///     },
+    /// ])?;
+    /// annotated.save("/tmp/annotated.png", ImageFormat::Png)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw(&self, ops: &[DrawOp]) -> Result<Self, SCError> {
+        let texts = ops
+            .iter()
+            .map(|op| match op {
+                DrawOp::Text { text, .. } => std::ffi::CString::new(text.as_str())
+                    .map(Some)
+                    .map_err(|_| SCError::internal_error("Text contains null bytes")),
+                _ => Ok(None),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let text_ptrs: Vec<*const i8> = texts
+            .iter()
+            .map(|text| text.as_ref().map_or(std::ptr::null(), |text| text.as_ptr()))
+            .collect();
+        let kinds: Vec<i32> = ops.iter().map(DrawOp::kind).collect();
+        let geometry: Vec<f64> = ops.iter().flat_map(DrawOp::geometry).collect();
+        let colors: Vec<f64> = ops
+            .iter()
+            .flat_map(|op| {
+                let color = op.color();
+                [color.red, color.green, color.blue, color.alpha]
+            })
+            .collect();
+        let widths: Vec<f64> = ops.iter().map(DrawOp::width).collect();
+
+        let ptr = unsafe {
+            crate::ffi::cgimage_draw_annotations(
+                self.ptr,
+                kinds.as_ptr(),
+                geometry.as_ptr(),
+                colors.as_ptr(),
+                widths.as_ptr(),
+                text_ptrs.as_ptr(),
+                ops.len(),
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error("Failed to draw onto CGImage"));
+        }
+        Ok(Self { ptr, dpi: self.dpi })
+    }
+
+    /// Hide regions of a copy of the image
+    ///
+    /// `rects` are in image pixels with the origin at the top-left corner. The
+    /// blur and pixelate effects sample only the image itself, so redacted
+    /// regions at the edges don't fade to transparent. The original image is
+    /// left unchanged; the returned image keeps its DPI.
+    ///
+    /// Blurring and pixelation are not always irreversible for text; use
+    /// [`RedactMode::Solid`] for secrets such as passwords.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rendering fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cg::{CGColor, CGRect};
+    /// use screencapturekit::screenshot_manager::{CGImage, RedactMode};
+    ///
+    /// # fn example(image: &CGImage) -> Result<(), Box<dyn std::error::Error>> {
+    /// let names = [CGRect::new(40.0, 120.0, 300.0, 24.0)];
+    /// let blurred = image.redact(&names, RedactMode::Blur(12.0))?;
+    ///
+    /// let password = [CGRect::new(40.0, 200.0, 200.0, 24.0)];
+    /// let safe = blurred.redact(&password, RedactMode::Solid(CGColor::BLACK))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn redact(&self, rects: &[CGRect], mode: RedactMode) -> Result<Self, SCError> {
+        let geometry: Vec<f64> = rects
+            .iter()
+            .flat_map(|rect| [rect.x, rect.y, rect.width, rect.height])
+            .collect();
+        let (kind, amount, color) = mode.raw();
+        let color = [color.red, color.green, color.blue, color.alpha];
+
+        let ptr = unsafe {
+            crate::ffi::cgimage_redact(
+                self.ptr,
+                geometry.as_ptr(),
+                rects.len(),
+                kind,
+                amount,
+                color.as_ptr(),
+            )
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error("Failed to redact CGImage"));
+        }
+        Ok(Self { ptr, dpi: self.dpi })
+    }
+
+    /// Create a downscaled copy for previews
+    ///
+    /// Draws the image into a smaller bitmap with high-quality interpolation,
+    /// without encoding it first. The longer side of the result is at most
+    /// `max_pixel_size`; the aspect ratio is kept and images that already fit
+    /// are not enlarged. The DPI is scaled with the image.
+    ///
+    /// For screenshots already saved to disk,
+    /// [`thumbnail_from_file`](Self::thumbnail_from_file) skips decoding the
+    /// full image altogether.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_pixel_size` is 0 or the thumbnail cannot be
+    /// created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::screenshot_manager::CGImage;
+    ///
+    /// # fn example(screenshot: &CGImage) -> Result<(), Box<dyn std::error::Error>> {
+    /// let preview = screenshot.thumbnail(256)?;
+    /// assert!(preview.width().max(preview.height()) <= 256);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn thumbnail(&self, max_pixel_size: usize) -> Result<Self, SCError> {
+        check_thumbnail_size(max_pixel_size)?;
+        let ptr = unsafe { crate::ffi::cgimage_create_thumbnail(self.ptr, max_pixel_size) };
+        if ptr.is_null() {
+            return Err(SCError::internal_error("Failed to create thumbnail"));
+        }
+        let mut thumbnail = Self { ptr, dpi: None };
+        if let Some(dpi) = self.dpi {
+            // Widths are far below 2^52, so the conversion is exact
+            #[allow(clippy::cast_precision_loss)]
+            thumbnail.set_dpi(dpi * thumbnail.width() as f64 / self.width() as f64);
+        }
+        Ok(thumbnail)
+    }
+
+    /// Load a downscaled copy of an image file
+    ///
+    /// Only the downsampled image is decoded, so this is the cheapest way to
+    /// preview a large screenshot on disk. Any format `ImageIO` can read is
+    /// accepted. Sizing works as in [`thumbnail`](Self::thumbnail).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_pixel_size` is 0, or the file can't be read
+    /// as an image.
+    pub fn thumbnail_from_file(path: &str, max_pixel_size: usize) -> Result<Self, SCError> {
+        check_thumbnail_size(max_pixel_size)?;
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+        let ptr = unsafe {
+            crate::ffi::cgimage_create_thumbnail_from_file(c_path.as_ptr(), max_pixel_size)
+        };
+        if ptr.is_null() {
+            return Err(SCError::internal_error(format!(
+                "Failed to create thumbnail from {path}"
+            )));
+        }
+        Ok(Self { ptr, dpi: None })
+    }
+
     /// Save the image to a PNG file
     ///
     /// # Arguments
@@ -390,6 +1290,10 @@ impl CGImage {
     /// # }
     /// ```
     pub fn save(&self, path: &str, format: ImageFormat) -> Result<(), SCError> {
+        if self.dpi.is_some() {
+            return self.save_with_metadata(path, format, &ImageMetadata::new());
+        }
+
         let c_path = std::ffi::CString::new(path)
             .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
 
@@ -411,6 +1315,137 @@ impl CGImage {
             )))
         }
     }
+
+    /// Save an HDR image without clipping its highlights
+    ///
+    /// [`save`](Self::save) hands the pixels to the encoder as they are, and
+    /// most formats can't hold values above SDR white, so an image from
+    /// `SCScreenshotOutput::hdr_image` loses its highlights. This keeps the
+    /// extra range where the format allows it:
+    ///
+    /// | Format | Result |
+    /// |--------|--------|
+    /// | `Heic`, `Avif`, `Jpeg` | SDR image plus an ISO HDR gain map on macOS 15+; SDR before that |
+    /// | `Tiff` | Extended-range pixels and color space written unchanged |
+    /// | `Png` | Extended-range images re-encoded as 16-bit Rec. 2100 PQ |
+    /// | `Gif`, `Bmp`, `WebP` | SDR, highlights clipped |
+    ///
+    /// Viewers without gain map support show the SDR base image. SDR images
+    /// can be passed too and are saved as [`save`](Self::save) would, without
+    /// a gain map or PQ conversion.
+    ///
+    /// # Errors
+    /// Returns an error if the path contains null bytes or the image cannot
+    /// be saved
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{SCScreenshotManager, ImageFormat};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    /// image.save_hdr("/tmp/screenshot.heic", ImageFormat::Heic(0.9))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_hdr(&self, path: &str, format: ImageFormat) -> Result<(), SCError> {
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+
+        let success = unsafe {
+            crate::ffi::cgimage_save_hdr(
+                self.ptr,
+                c_path.as_ptr(),
+                format.to_format_id(),
+                format.quality(),
+            )
+        };
+
+        if success {
+            Ok(())
+        } else {
+            Err(SCError::internal_error(format!(
+                "Failed to save HDR image as {}",
+                format.extension().to_uppercase()
+            )))
+        }
+    }
+
+    /// Save the image to a file with embedded metadata
+    ///
+    /// Behaves like [`save`](Self::save), additionally writing the capture time,
+    /// source description and DPI from `metadata` into the file's EXIF/TIFF
+    /// properties. When `metadata` has no DPI, the image's own
+    /// [`dpi`](Self::dpi) is used. Formats without metadata support (BMP, GIF) keep only what
+    /// the encoder accepts.
+    ///
+    /// # Errors
+    /// Returns an error if the path or description contains null bytes, or the
+    /// image cannot be saved
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use screencapturekit::screenshot_manager::{SCScreenshotManager, ImageFormat, ImageMetadata};
+    /// # use screencapturekit::stream::{content_filter::SCContentFilter, configuration::SCStreamConfiguration};
+    /// # use screencapturekit::shareable_content::SCShareableContent;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let image = SCScreenshotManager::capture_image(&filter, &config)?;
+    ///
+    /// let metadata = ImageMetadata::new()
+    ///     .with_capture_time(std::time::SystemTime::now())
+    ///     .with_description(format!("display {}", display.display_id()));
+    /// image.save_with_metadata("/tmp/archive/frame.jpg", ImageFormat::Jpeg(0.9), &metadata)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_with_metadata(
+        &self,
+        path: &str,
+        format: ImageFormat,
+        metadata: &ImageMetadata,
+    ) -> Result<(), SCError> {
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+        let c_description = metadata
+            .description()
+            .map(std::ffi::CString::new)
+            .transpose()
+            .map_err(|_| SCError::internal_error("Description contains null bytes"))?;
+
+        let success = unsafe {
+            crate::ffi::cgimage_save_to_file_with_metadata(
+                self.ptr,
+                c_path.as_ptr(),
+                format.to_format_id(),
+                format.quality(),
+                metadata.capture_time_secs(),
+                c_description
+                    .as_ref()
+                    .map_or(std::ptr::null(), |description| description.as_ptr()),
+                metadata.dpi().or(self.dpi).unwrap_or(0.0),
+            )
+        };
+
+        if success {
+            Ok(())
+        } else {
+            Err(SCError::internal_error(format!(
+                "Failed to save image as {}",
+                format.extension().to_uppercase()
+            )))
+        }
+    }
 }
 
 impl Drop for CGImage {
@@ -428,7 +1463,8 @@ impl std::fmt::Debug for CGImage {
         f.debug_struct("CGImage")
             .field("width", &self.width())
             .field("height", &self.height())
-            .finish()
+            .field("dpi", &self.dpi)
+            .finish_non_exhaustive()
     }
 }
 
@@ -487,7 +1523,11 @@ impl SCScreenshotManager {
             );
         }
 
-        completion.wait().map_err(SCError::ScreenshotError)
+        let mut image = completion.wait().map_err(SCError::ScreenshotError)?;
+        if let Some(dpi) = capture_dpi(content_filter, &image) {
+            image.set_dpi(dpi);
+        }
+        Ok(image)
     }
 
     /// Capture a single screenshot as a `CMSampleBuffer`
@@ -520,6 +1560,422 @@ impl SCScreenshotManager {
         completion.wait().map_err(SCError::ScreenshotError)
     }
 
+    /// Capture a screenshot straight to a file
+    ///
+    /// The image is captured and encoded on the system side and never crosses
+    /// into Rust, which suits command-line tools that only need the file. The
+    /// result matches [`capture_image`](Self::capture_image) followed by
+    /// [`CGImage::save`], including the DPI metadata.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The system is not macOS 14.0+
+    /// - Screen recording permission is not granted
+    /// - The capture fails, or the file can't be written
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::{ImageFormat, SCScreenshotManager};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let filter = SCContentFilter::builder().display(&content.displays()[0]).build();
+    /// let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// SCScreenshotManager::capture_and_save(&filter, &config, "/tmp/screen.jpg", ImageFormat::Jpeg(0.9))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_and_save(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        path: &str,
+        format: ImageFormat,
+    ) -> Result<(), SCError> {
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+        let (completion, context) = UnitCompletion::new();
+
+        unsafe {
+            crate::ffi::sc_screenshot_manager_capture_and_save(
+                content_filter.as_ptr(),
+                configuration.as_ptr(),
+                c_path.as_ptr(),
+                format.to_format_id(),
+                format.quality(),
+                UnitCompletion::callback,
+                context,
+            );
+        }
+
+        completion.wait().map_err(SCError::ScreenshotError)
+    }
+
+    /// Capture a screenshot with the best API the running system offers
+    ///
+    /// Picks the capture path at runtime, so callers don't have to handle the
+    /// version matrix themselves:
+    ///
+    /// | macOS | API used |
+    /// |-------|----------|
+    /// | 26.0+ | [`capture_screenshot`](Self::capture_screenshot) (SDR image; needs the `macos_26_0` feature) |
+    /// | 14.0+ | [`capture_image`](Self::capture_image) |
+    /// | 12.3–13 | [`capture_image_with_stream`] |
+    ///
+    /// The width, height and cursor visibility of `configuration` are applied
+    /// on every path. Other settings, such as the source rectangle, are
+    /// ignored on the 26.0 path.
+    ///
+    /// # Errors
+    /// Returns an error if screen recording permission is not granted or the
+    /// capture fails.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let filter = SCContentFilter::builder().display(display).build();
+    /// let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    ///
+    /// let image = SCScreenshotManager::capture_best(&filter, &config)?;
+    /// image.save_png("/tmp/screenshot.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_best(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+    ) -> Result<CGImage, SCError> {
+        let version = MacOSVersion::current();
+
+        #[cfg(feature = "macos_26_0")]
+        if version.is_at_least(26, 0) {
+            let screenshot_config = SCScreenshotConfiguration::new()
+                .with_width(configuration.width() as usize)
+                .with_height(configuration.height() as usize)
+                .with_shows_cursor(configuration.shows_cursor());
+            let output = Self::capture_screenshot(content_filter, &screenshot_config)?;
+            if let Some(mut image) = output.sdr_image() {
+                if let Some(dpi) = capture_dpi(content_filter, &image) {
+                    image.set_dpi(dpi);
+                }
+                return Ok(image);
+            }
+        }
+
+        if version.is_at_least(14, 0) {
+            Self::capture_image(content_filter, configuration)
+        } else {
+            capture_image_with_stream(content_filter, configuration)
+        }
+    }
+
+    /// Capture only the menu bar of a display
+    ///
+    /// The height of the strip is queried from the system rather than
+    /// assumed, so the taller menu bar of displays with a camera notch is
+    /// captured in full. If the menu bar is set to hide automatically, the
+    /// standard menu bar height is used. The image has the display's native
+    /// pixel density.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The system is not macOS 14.0+
+    /// - Screen recording permission is not granted
+    /// - The capture fails for any reason
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let menu_bar = SCScreenshotManager::capture_menu_bar(display)?;
+    /// menu_bar.save_png("/tmp/menu_bar.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_menu_bar(display: &SCDisplay) -> Result<CGImage, SCError> {
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(display.as_ptr(), "capture_menu_bar")?;
+        let height = unsafe { crate::ffi::sc_display_menu_bar_height(display.display_id()) };
+        let frame = display.frame();
+        if height <= 0.0 || frame.width <= 0.0 {
+            return Err(SCError::ScreenshotError(
+                "Could not determine the menu bar size".to_string(),
+            ));
+        }
+
+        let scale = display_pixel_scale(display);
+
+        let filter = SCContentFilter::builder().display(display).build();
+        #[cfg(feature = "macos_14_2")]
+        let filter = {
+            let mut filter = filter;
+            filter.set_include_menu_bar(true);
+            filter
+        };
+
+        let config = SCStreamConfiguration::new()
+            .with_source_rect_in_display(display, CGRect::new(0.0, 0.0, frame.width, height))
+            .with_size(CGSize::new(frame.width * scale, height * scale));
+        Self::capture_image(&filter, &config)
+    }
+
+    /// Capture the window under a point on the screen
+    ///
+    /// Finds the frontmost on-screen window whose frame contains `point`,
+    /// captures it alone with a window filter at its native pixel density, and
+    /// returns the window together with the image. This is the primitive
+    /// behind "capture the window I clicked on".
+    ///
+    /// `point` is in global display coordinates (points, origin at the top
+    /// left of the main display), the same space as [`SCWindow::frame`].
+    /// Only normal windows ([`window_layer`](SCWindow::window_layer) 0) are
+    /// hit, so desktop windows, menus, the Dock and floating overlays are
+    /// skipped; to hit-test other layers, walk
+    /// [`SCShareableContent::windows`] yourself. Windows are hit-tested by
+    /// frame, so a transparent window covering the point wins over the window
+    /// visible through it.
+    ///
+    /// The pixel density comes from the filter on macOS 14.0+. Without the
+    /// `macos_14_0` feature it is read from the mode of the display holding
+    /// the window's center.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No window contains the point
+    /// - The system is not macOS 14.0+
+    /// - Screen recording permission is not granted
+    /// - The capture fails for any reason
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::cg::CGPoint;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (window, image) = SCScreenshotManager::capture_window_at_point(CGPoint::new(400.0, 300.0))?;
+    /// println!("Captured {:?}: {}x{}", window.title(), image.width(), image.height());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_window_at_point(point: CGPoint) -> Result<(SCWindow, CGImage), SCError> {
+        let content = SCShareableContent::with_options()
+            .on_screen_windows_only(true)
+            .exclude_desktop_windows(true)
+            .get()?;
+        let window = frontmost_window_at(&content, point).ok_or_else(|| {
+            SCError::ScreenshotError(format!("No window at ({}, {})", point.x, point.y))
+        })?;
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(window.as_ptr(), "capture_window_at_point")?;
+
+        let filter = SCContentFilter::builder().window(&window).build();
+        #[cfg(feature = "macos_14_0")]
+        let scale = match f64::from(filter.point_pixel_scale()) {
+            scale if scale > 0.0 => scale,
+            _ => 1.0,
+        };
+        #[cfg(not(feature = "macos_14_0"))]
+        let scale = window_pixel_scale(&content, &window);
+
+        let frame = window.frame();
+        let config = SCStreamConfiguration::new()
+            .with_size(CGSize::new(frame.width * scale, frame.height * scale));
+        let image = Self::capture_image(&filter, &config)?;
+        Ok((window, image))
+    }
+
+    /// Capture all windows of an application on one display as a single image
+    ///
+    /// Builds a filter that includes only `app` on `display`, so its windows
+    /// are composited in their on-screen stacking order with everything else
+    /// left out. The image covers the whole display at its native pixel
+    /// density; areas without a window of the app are left blank.
+    ///
+    /// Only `display` is captured. For an app with windows on several
+    /// displays, call this once per display; windows spanning two displays
+    /// are clipped to each. This looks up the on-screen windows first; use
+    /// [`capture_application_in`](Self::capture_application_in) to reuse
+    /// content the caller already has.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The app has no on-screen window on `display`
+    /// - The system is not macOS 14.0+
+    /// - Screen recording permission is not granted
+    /// - The capture fails for any reason
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let app = content
+    ///     .applications()
+    ///     .into_iter()
+    ///     .find(|app| app.bundle_identifier() == "com.apple.finder")
+    ///     .ok_or("Finder is not running")?;
+    /// let image = SCScreenshotManager::capture_application(&app, &content.displays()[0])?;
+    /// image.save_png("/tmp/finder.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_application(
+        app: &SCRunningApplication,
+        display: &SCDisplay,
+    ) -> Result<CGImage, SCError> {
+        let content = SCShareableContent::with_options()
+            .on_screen_windows_only(true)
+            .exclude_desktop_windows(true)
+            .get()?;
+        Self::capture_application_in(&content, app, display)
+    }
+
+    /// Capture all windows of an application on one display, checking
+    /// against `content` the caller already fetched
+    ///
+    /// Behaves like [`capture_application`](Self::capture_application), but
+    /// decides whether `app` has a window on `display` from the windows in
+    /// `content` instead of enumerating them again. Only windows that
+    /// `content` reports as on screen count, so it may be fetched with or
+    /// without [`on_screen_windows_only`](crate::shareable_content::SCShareableContentOptions::on_screen_windows_only).
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as
+    /// [`capture_application`](Self::capture_application), including when
+    /// `content` has no on-screen window of `app` on `display`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let app = content
+    ///     .applications()
+    ///     .into_iter()
+    ///     .find(|app| app.bundle_identifier() == "com.apple.finder")
+    ///     .ok_or("Finder is not running")?;
+    /// for display in content.displays() {
+    ///     if let Ok(image) = SCScreenshotManager::capture_application_in(&content, &app, &display) {
+    ///         image.save_png(&format!("/tmp/finder-{}.png", display.display_id()))?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_application_in(
+        content: &SCShareableContent,
+        app: &SCRunningApplication,
+        display: &SCDisplay,
+    ) -> Result<CGImage, SCError> {
+        #[cfg(feature = "testing")]
+        for ptr in [app.as_ptr(), display.as_ptr()] {
+            crate::testing::reject_mock(ptr, "capture_application_in")?;
+        }
+        if !has_window_on_display(content, app, display) {
+            return Err(SCError::ScreenshotError(format!(
+                "{} has no windows on display {}",
+                app.application_name(),
+                display.display_id()
+            )));
+        }
+
+        let filter = SCContentFilter::builder()
+            .display(display)
+            .include_applications(&[app], &[])
+            .build();
+        let scale = display_pixel_scale(display);
+        let frame = display.frame();
+        let config = SCStreamConfiguration::new()
+            .with_size(CGSize::new(frame.width * scale, frame.height * scale));
+        Self::capture_image(&filter, &config)
+    }
+
+    /// Capture `count` screenshots spaced `interval` apart
+    ///
+    /// Useful for grabbing a quick burst and keeping the sharpest frame.
+    /// Captures run one after another on the calling thread, so they never
+    /// overlap or contend with each other on the window server. Each capture
+    /// is scheduled relative to the first one; if a capture takes longer than
+    /// `interval`, the next one starts as soon as it finishes. The images are
+    /// returned in capture order.
+    ///
+    /// Use [`capture_burst_partial`](Self::capture_burst_partial) to keep the
+    /// frames captured before a failure.
+    ///
+    /// # Errors
+    /// Returns an error naming the failed frame if any capture fails, for the
+    /// same reasons as [`capture_image`](Self::capture_image).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::screenshot_manager::SCScreenshotManager;
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let filter = SCContentFilter::builder().display(&content.displays()[0]).build();
+    /// let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    ///
+    /// let frames =
+    ///     SCScreenshotManager::capture_burst(&filter, &config, 5, Duration::from_millis(100))?;
+    /// println!("Captured {} frames", frames.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture_burst(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        count: usize,
+        interval: std::time::Duration,
+    ) -> Result<Vec<CGImage>, SCError> {
+        match Self::capture_burst_partial(content_filter, configuration, count, interval) {
+            (frames, None) => Ok(frames),
+            (_, Some((index, error))) => Err(SCError::ScreenshotError(format!(
+                "Burst frame {index} of {count} failed: {error}"
+            ))),
+        }
+    }
+
+    /// Capture a burst like [`capture_burst`](Self::capture_burst), keeping
+    /// the frames captured before a failure
+    ///
+    /// Returns the frames in capture order, and the index and error of the
+    /// capture that failed, if any. The burst stops at the first failure, so
+    /// the index equals the number of frames returned.
+    pub fn capture_burst_partial(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        count: usize,
+        interval: std::time::Duration,
+    ) -> (Vec<CGImage>, Option<(usize, SCError)>) {
+        let start = std::time::Instant::now();
+        let mut frames = Vec::with_capacity(count);
+        let mut due = start;
+        for index in 0..count {
+            std::thread::sleep(due.saturating_duration_since(std::time::Instant::now()));
+            match Self::capture_image(content_filter, configuration) {
+                Ok(image) => frames.push(image),
+                Err(error) => return (frames, Some((index, error))),
+            }
+            due += interval;
+        }
+        (frames, None)
+    }
+
     /// Capture a screenshot of a specific screen region (macOS 15.2+)
     ///
     /// This method captures the content within the specified rectangle,
@@ -563,6 +2019,44 @@ impl SCScreenshotManager {
         completion.wait().map_err(SCError::ScreenshotError)
     }
 
+    /// Capture a screen region straight to a file (macOS 15.2+)
+    ///
+    /// The file-writing counterpart of
+    /// [`capture_image_in_rect`](Self::capture_image_in_rect); see
+    /// [`capture_and_save`](Self::capture_and_save).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The system is not macOS 15.2+
+    /// - Screen recording permission is not granted
+    /// - The capture fails, or the file can't be written
+    #[cfg(feature = "macos_15_2")]
+    pub fn capture_image_in_rect_and_save(
+        rect: CGRect,
+        path: &str,
+        format: ImageFormat,
+    ) -> Result<(), SCError> {
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+        let (completion, context) = UnitCompletion::new();
+
+        unsafe {
+            crate::ffi::sc_screenshot_manager_capture_image_in_rect_and_save(
+                rect.x,
+                rect.y,
+                rect.width,
+                rect.height,
+                c_path.as_ptr(),
+                format.to_format_id(),
+                format.quality(),
+                UnitCompletion::callback,
+                context,
+            );
+        }
+
+        completion.wait().map_err(SCError::ScreenshotError)
+    }
+
     /// Capture a screenshot with advanced configuration (macOS 26.0+)
     ///
     /// This method uses the new `SCScreenshotConfiguration` for more control
@@ -648,6 +2142,240 @@ impl SCScreenshotManager {
     }
 }
 
+// ============================================================================
+// TimeLapse
+// ============================================================================
+
+/// Periodic screenshot capture to a directory
+///
+/// Captures a screenshot every `interval` on a background thread and writes
+/// each frame to `output_dir`, named with a sequence number and the capture
+/// timestamp in milliseconds since the Unix epoch (e.g.
+/// `frame_000042_1700000000000.jpg`).
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::prelude::*;
+/// use screencapturekit::screenshot_manager::{ImageFormat, TimeLapse};
+/// use std::time::Duration;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let content = SCShareableContent::get()?;
+/// let display = &content.displays()[0];
+/// let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+/// let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+///
+/// let timelapse = TimeLapse::new(&filter, &config, Duration::from_secs(5))
+///     .output_dir("/tmp/timelapse")
+///     .format(ImageFormat::Jpeg(0.8))
+///     .skip_unchanged(true)
+///     .start()?;
+///
+/// std::thread::sleep(Duration::from_secs(60));
+/// let frames = timelapse.stop();
+/// println!("Captured {frames} frames");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TimeLapse {
+    filter: SCContentFilter,
+    configuration: SCStreamConfiguration,
+    interval: std::time::Duration,
+    output_dir: std::path::PathBuf,
+    format: ImageFormat,
+    skip_unchanged: bool,
+}
+
+impl TimeLapse {
+    /// Create a time-lapse for the given filter, configuration and capture interval
+    ///
+    /// Frames are written to the current directory as PNG unless
+    /// [`output_dir`](Self::output_dir) or [`format`](Self::format) is set.
+    /// `interval` must not be zero; [`start`](Self::start) rejects it.
+    #[must_use]
+    pub fn new(
+        content_filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        interval: std::time::Duration,
+    ) -> Self {
+        Self {
+            filter: content_filter.clone(),
+            configuration: configuration.clone(),
+            interval,
+            output_dir: std::path::PathBuf::from("."),
+            format: ImageFormat::Png,
+            skip_unchanged: false,
+        }
+    }
+
+    /// Set the directory frames are written to (created on start if missing)
+    #[must_use]
+    pub fn output_dir(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.output_dir = path.into();
+        self
+    }
+
+    /// Set the image format frames are saved in
+    #[must_use]
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Skip saving frames whose pixels are identical to the previous frame
+    ///
+    /// Uses [`CGImage::content_hash`] to compare consecutive captures.
+    #[must_use]
+    pub fn skip_unchanged(mut self, skip: bool) -> Self {
+        self.skip_unchanged = skip;
+        self
+    }
+
+    /// Start capturing on a background thread
+    ///
+    /// The first frame is captured immediately. Capture or save failures do not
+    /// stop the time-lapse; the most recent one is available from
+    /// [`TimeLapseHandle::last_error`].
+    ///
+    /// Frames are named `frame_{sequence}_{milliseconds}`, where the sequence
+    /// number counts up from zero for this time-lapse, so files sort in
+    /// capture order and never overwrite each other, even if the system
+    /// clock steps backwards.
+    ///
+    /// # Errors
+    /// Returns an error if the interval is zero, the output directory cannot
+    /// be created or the background thread cannot be spawned.
+    pub fn start(self) -> Result<TimeLapseHandle, SCError> {
+        if self.interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "Time-lapse interval must be greater than zero",
+            ));
+        }
+        std::fs::create_dir_all(&self.output_dir).map_err(|e| {
+            SCError::internal_error(format!(
+                "Failed to create output directory {}: {e}",
+                self.output_dir.display()
+            ))
+        })?;
+
+        let state = std::sync::Arc::new(TimeLapseState {
+            frame_count: std::sync::atomic::AtomicUsize::new(0),
+            last_error: std::sync::Mutex::new(None),
+        });
+
+        let thread_state = std::sync::Arc::clone(&state);
+        let mut last_hash = None;
+        let mut sequence = 0_u64;
+        let timer = spawn_timer_after(
+            "screencapturekit-timelapse",
+            std::time::Duration::ZERO,
+            self.interval,
+            move |_| {
+                if let Err(e) = self.capture_frame(&thread_state, &mut last_hash, &mut sequence) {
+                    *thread_state
+                        .last_error
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(e);
+                }
+                true
+            },
+        )
+        .map_err(|e| SCError::internal_error(format!("Failed to spawn time-lapse thread: {e}")))?;
+
+        Ok(TimeLapseHandle {
+            state,
+            timer: Some(timer),
+        })
+    }
+
+    fn capture_frame(
+        &self,
+        state: &TimeLapseState,
+        last_hash: &mut Option<u64>,
+        sequence: &mut u64,
+    ) -> Result<(), SCError> {
+        let image = SCScreenshotManager::capture_image(&self.filter, &self.configuration)?;
+
+        if self.skip_unchanged {
+            let hash = image.content_hash()?;
+            if *last_hash == Some(hash) {
+                return Ok(());
+            }
+            *last_hash = Some(hash);
+        }
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        let path = self.output_dir.join(format!(
+            "frame_{sequence:06}_{timestamp}.{}",
+            self.format.extension()
+        ));
+        let path = path
+            .to_str()
+            .ok_or_else(|| SCError::internal_error("Output path is not valid UTF-8"))?;
+
+        image.save(path, self.format)?;
+        *sequence += 1;
+        state
+            .frame_count
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+struct TimeLapseState {
+    frame_count: std::sync::atomic::AtomicUsize,
+    last_error: std::sync::Mutex<Option<SCError>>,
+}
+
+/// Handle to a running [`TimeLapse`]
+///
+/// Dropping the handle stops the capture thread.
+pub struct TimeLapseHandle {
+    state: std::sync::Arc<TimeLapseState>,
+    timer: Option<Timer>,
+}
+
+impl TimeLapseHandle {
+    /// Number of frames written to disk so far
+    pub fn frame_count(&self) -> usize {
+        self.state
+            .frame_count
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// The most recent capture or save error, if any
+    pub fn last_error(&self) -> Option<SCError> {
+        self.state
+            .last_error
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Stop capturing and wait for the background thread to finish
+    ///
+    /// Returns the total number of frames written.
+    pub fn stop(mut self) -> usize {
+        if let Some(timer) = self.timer.take() {
+            timer.stop();
+        }
+        self.frame_count()
+    }
+}
+
+impl std::fmt::Debug for TimeLapseHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeLapseHandle")
+            .field("frame_count", &self.frame_count())
+            .field("running", &self.timer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 // ============================================================================
 // SCScreenshotConfiguration (macOS 26.0+)
 // ============================================================================
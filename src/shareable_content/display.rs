@@ -6,6 +6,19 @@ use std::ffi::c_void;
 ///
 /// Represents a physical or virtual display that can be captured.
 ///
+/// # Thread Safety
+///
+/// `SCDisplay` is `Send` and `Sync`. It is an immutable snapshot taken when the
+/// shareable content was fetched, so it can be moved into worker threads to
+/// build filters there.
+///
+/// # Mirrored Displays
+///
+/// When displays are mirrored, capturing the mirror target yields the
+/// content of its source display. Use
+/// [`CGDisplay::mirror_source`](crate::cg_display::CGDisplay::mirror_source)
+/// to detect this before starting a capture.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -72,11 +85,19 @@ impl SCDisplay {
     /// # }
     /// ```
     pub fn display_id(&self) -> u32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::display(self.0) {
+            return mock.display_id;
+        }
         unsafe { crate::ffi::sc_display_get_display_id(self.0) }
     }
 
     /// Get display frame (position and size)
     pub fn frame(&self) -> CGRect {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::display(self.0) {
+            return mock.frame;
+        }
         let mut x = 0.0;
         let mut y = 0.0;
         let mut width = 0.0;
@@ -108,6 +129,10 @@ impl SCDisplay {
     /// # }
     /// ```
     pub fn height(&self) -> u32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::display(self.0) {
+            return mock.height;
+        }
         // FFI returns isize but display dimensions are always positive and fit in u32
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         unsafe {
@@ -117,6 +142,10 @@ impl SCDisplay {
 
     /// Get display width in pixels
     pub fn width(&self) -> u32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::display(self.0) {
+            return mock.width;
+        }
         // FFI returns isize but display dimensions are always positive and fit in u32
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         unsafe {
@@ -127,6 +156,10 @@ impl SCDisplay {
 
 impl Drop for SCDisplay {
     fn drop(&mut self) {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return;
+        }
         if !self.0.is_null() {
             unsafe {
                 crate::ffi::sc_display_release(self.0);
@@ -137,10 +170,16 @@ impl Drop for SCDisplay {
 
 impl Clone for SCDisplay {
     fn clone(&self) -> Self {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return Self(self.0);
+        }
         unsafe { Self(crate::ffi::sc_display_retain(self.0)) }
     }
 }
 
+// SAFETY: SCDisplay is an immutable snapshot object; all accessors are read-only
+// and retain/release are atomic.
 unsafe impl Send for SCDisplay {}
 unsafe impl Sync for SCDisplay {}
 
@@ -35,18 +35,133 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Headless Systems and CI
+//!
+//! On machines without an attached display (CI runners, SSH sessions into a
+//! headless Mac), [`SCShareableContent::get`] returns `Ok` with no displays
+//! rather than failing, and [`SCShareableContent::is_empty`] returns `true`.
+//! Tests that need a display should check this and skip:
+//!
+//! ```no_run
+//! use screencapturekit::shareable_content::SCShareableContent;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = SCShareableContent::get()?;
+//! if content.is_empty() {
+//!     eprintln!("No displays available, skipping capture test");
+//!     return Ok(());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Screen recording permission is still required; grant it to the terminal or
+//! CI agent in System Settings, or use a runner image that pre-approves it.
 
 pub mod display;
 pub mod running_application;
+pub mod selection;
+pub mod watch;
 pub mod window;
 pub use display::SCDisplay;
 pub use running_application::SCRunningApplication;
+pub use selection::{SavedWindow, WindowSelection};
+pub use watch::{ContentDelta, ContentWatcher};
 pub use window::SCWindow;
 
 use crate::error::SCError;
 use crate::utils::sync_completion::{error_from_cstr, SyncCompletion};
 use core::fmt;
 use std::ffi::c_void;
+use std::sync::Once;
+
+/// Force `CoreGraphics` initialization before the first content query
+///
+/// Without this, `ScreenCaptureKit` can abort with `CGS_REQUIRE_INIT` on
+/// machines that have no window server connection yet.
+pub(crate) fn ensure_core_graphics_initialized() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| unsafe { crate::ffi::sc_initialize_core_graphics() });
+}
+
+/// Map a failed content query to an empty result on systems with no displays
+pub(crate) fn content_or_empty(
+    result: Result<SCShareableContent, String>,
+) -> Result<SCShareableContent, SCError> {
+    result.or_else(|e| {
+        let headless =
+            crate::cg_display::CGDisplay::active_displays().is_ok_and(|ids| ids.is_empty());
+        if headless {
+            Ok(SCShareableContent::empty())
+        } else {
+            Err(SCError::NoShareableContent(e))
+        }
+    })
+}
+
+/// State of the current user's login session
+///
+/// `ScreenCaptureKit` cannot see the lock screen or the login window: while
+/// the session is not [`Active`](Self::Active), running streams keep
+/// delivering frames but they are blank, and new content queries may fail.
+/// Check [`SCShareableContent::session_state`] to pause capture or explain
+/// the gap to the user instead of recording black video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionState {
+    /// The user is logged in and the screen is unlocked
+    Active,
+    /// The screen is locked or the screen saver requires a password
+    Locked,
+    /// The login window is showing, e.g. during fast user switching
+    LoginWindow,
+}
+
+impl SessionState {
+    /// Returns `true` if screen content can be captured in this state
+    #[must_use]
+    pub const fn is_capturable(self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "Active"),
+            Self::Locked => write!(f, "Locked"),
+            Self::LoginWindow => write!(f, "Login Window"),
+        }
+    }
+}
+
+/// Counts of the content available for capture
+///
+/// Returned by [`SCShareableContent::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ContentSummary {
+    /// Number of displays
+    pub display_count: usize,
+    /// Number of windows, including off-screen ones
+    pub window_count: usize,
+    /// Number of running applications
+    pub application_count: usize,
+    /// Number of windows currently on screen
+    pub on_screen_window_count: usize,
+}
+
+impl fmt::Display for ContentSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} displays, {} windows ({} on screen), {} applications",
+            self.display_count,
+            self.window_count,
+            self.on_screen_window_count,
+            self.application_count
+        )
+    }
+}
 
 #[repr(transparent)]
 pub struct SCShareableContent(*const c_void);
@@ -92,6 +207,13 @@ impl std::hash::Hash for SCShareableContent {
 
 impl Clone for SCShareableContent {
     fn clone(&self) -> Self {
+        if self.0.is_null() {
+            return Self::empty();
+        }
+        #[cfg(feature = "testing")]
+        if crate::testing::content(self.0).is_some() {
+            return Self(self.0);
+        }
         unsafe { Self(crate::ffi::sc_shareable_content_retain(self.0)) }
     }
 }
@@ -105,6 +227,40 @@ impl SCShareableContent {
         Self(ptr)
     }
 
+    /// Content with no displays, windows or applications (headless systems)
+    const fn empty() -> Self {
+        Self(std::ptr::null())
+    }
+
+    /// Returns `true` if there are no displays to capture
+    ///
+    /// This is the case on headless machines such as CI runners. Windows and
+    /// applications cannot be captured without a display either.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// if content.is_empty() {
+    ///     println!("Nothing to capture");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        if self.0.is_null() {
+            return true;
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return mock.displays.is_empty();
+        }
+        unsafe { crate::ffi::sc_shareable_content_get_displays_count(self.0) <= 0 }
+    }
+
     /// Get shareable content (displays, windows, and applications)
     ///
     /// # Examples
@@ -121,6 +277,9 @@ impl SCShareableContent {
     /// # }
     /// ```
     ///
+    /// On headless systems this returns `Ok` with no displays; see
+    /// [`is_empty`](Self::is_empty).
+    ///
     /// # Errors
     ///
     /// Returns an error if screen recording permission is not granted.
@@ -128,6 +287,68 @@ impl SCShareableContent {
         Self::with_options().get()
     }
 
+    /// Get shareable content using the given options
+    ///
+    /// Picker UIs usually want only on-screen windows, which avoids listing
+    /// hundreds of hidden helper and background windows. [`get`](Self::get)
+    /// is equivalent to passing `SCShareableContentOptions::default()`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::{SCShareableContent, SCShareableContentOptions};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = SCShareableContentOptions::default()
+    ///     .on_screen_windows_only(true)
+    ///     .exclude_desktop_windows(true);
+    /// let content = SCShareableContent::get_with_options(options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if screen recording permission is not granted.
+    pub fn get_with_options(options: SCShareableContentOptions) -> Result<Self, SCError> {
+        options.get()
+    }
+
+    /// Get the state of the current login session
+    ///
+    /// Reads the `CoreGraphics` session dictionary, so it needs no screen
+    /// recording permission and is cheap enough to poll. A process that is not
+    /// attached to the console session (for example one started over SSH
+    /// while another user is logged in) reports
+    /// [`LoginWindow`](SessionState::LoginWindow).
+    ///
+    /// With the `testing` feature,
+    /// [`set_mock_session_state`](crate::testing::set_mock_session_state)
+    /// overrides the result.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::{SCShareableContent, SessionState};
+    ///
+    /// match SCShareableContent::session_state() {
+    ///     SessionState::Active => println!("Ready to capture"),
+    ///     SessionState::Locked => println!("Screen is locked, capture paused"),
+    ///     SessionState::LoginWindow => println!("No user session on screen"),
+    /// }
+    /// ```
+    pub fn session_state() -> SessionState {
+        #[cfg(feature = "testing")]
+        if let Some(state) = crate::testing::session_state() {
+            return state;
+        }
+        match unsafe { crate::ffi::sc_session_state() } {
+            1 => SessionState::Locked,
+            2 => SessionState::LoginWindow,
+            _ => SessionState::Active,
+        }
+    }
+
     /// Create options builder for customizing shareable content retrieval
     ///
     /// # Examples
@@ -163,6 +384,16 @@ impl SCShareableContent {
     /// # }
     /// ```
     pub fn displays(&self) -> Vec<SCDisplay> {
+        if self.0.is_null() {
+            return Vec::new();
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return crate::testing::display_ptrs(mock)
+                .into_iter()
+                .map(|ptr| unsafe { SCDisplay::from_ptr(ptr) })
+                .collect();
+        }
         unsafe {
             let count = crate::ffi::sc_shareable_content_get_displays_count(self.0);
             // FFI returns isize but count is always positive
@@ -198,6 +429,16 @@ impl SCShareableContent {
     /// # }
     /// ```
     pub fn windows(&self) -> Vec<SCWindow> {
+        if self.0.is_null() {
+            return Vec::new();
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return crate::testing::window_ptrs(mock)
+                .into_iter()
+                .map(|ptr| unsafe { SCWindow::from_ptr(ptr) })
+                .collect();
+        }
         unsafe {
             let count = crate::ffi::sc_shareable_content_get_windows_count(self.0);
             // FFI returns isize but count is always positive
@@ -215,6 +456,50 @@ impl SCShareableContent {
         }
     }
 
+    /// Find a window by its window ID
+    ///
+    /// Useful for window IDs persisted from an earlier session. The returned
+    /// window is a new handle rather than a reference, since the content does
+    /// not keep wrappers around; windows that don't match are released as the
+    /// list is scanned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let saved_id = 1234;
+    /// let content = SCShareableContent::get()?;
+    /// match content.window_with_id(saved_id) {
+    ///     Some(window) => println!("Found: {:?}", window.title()),
+    ///     None => println!("Window {saved_id} is gone"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn window_with_id(&self, id: u32) -> Option<SCWindow> {
+        if self.0.is_null() {
+            return None;
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return crate::testing::window_ptrs(mock)
+                .into_iter()
+                .map(|ptr| unsafe { SCWindow::from_ptr(ptr) })
+                .find(|window| window.window_id() == id);
+        }
+        unsafe {
+            let count = crate::ffi::sc_shareable_content_get_windows_count(self.0);
+            (0..count)
+                .map(|i| crate::ffi::sc_shareable_content_get_window_at(self.0, i))
+                .filter(|window_ptr| !window_ptr.is_null())
+                .map(|window_ptr| SCWindow::from_ptr(window_ptr))
+                .find(|window| window.window_id() == id)
+        }
+    }
+
     /// Get all available running applications
     ///
     /// # Examples
@@ -231,6 +516,16 @@ impl SCShareableContent {
     /// # }
     /// ```
     pub fn applications(&self) -> Vec<SCRunningApplication> {
+        if self.0.is_null() {
+            return Vec::new();
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return crate::testing::application_ptrs(mock)
+                .into_iter()
+                .map(|ptr| unsafe { SCRunningApplication::from_ptr(ptr) })
+                .collect();
+        }
         unsafe {
             let count = crate::ffi::sc_shareable_content_get_applications_count(self.0);
             // FFI returns isize but count is always positive
@@ -248,6 +543,58 @@ impl SCShareableContent {
         }
     }
 
+    /// Count the displays, windows and applications
+    ///
+    /// Everything is counted in one call without creating a wrapper for each
+    /// item, which makes this cheaper than taking the length of
+    /// [`displays`](Self::displays), [`windows`](Self::windows) and
+    /// [`applications`](Self::applications).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let summary = SCShareableContent::get()?.summary();
+    /// println!("Can capture {summary}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> ContentSummary {
+        if self.0.is_null() {
+            return ContentSummary::default();
+        }
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::content(self.0) {
+            return ContentSummary {
+                display_count: mock.displays.len(),
+                window_count: mock.windows.len(),
+                application_count: mock.applications.len(),
+                on_screen_window_count: mock.windows.iter().filter(|w| w.is_on_screen).count(),
+            };
+        }
+        let (mut displays, mut windows, mut applications, mut on_screen) = (0, 0, 0, 0);
+        unsafe {
+            crate::ffi::sc_shareable_content_get_summary(
+                self.0,
+                &mut displays,
+                &mut windows,
+                &mut applications,
+                &mut on_screen,
+            );
+        }
+        // FFI returns isize but counts are never negative
+        #[allow(clippy::cast_sign_loss)]
+        ContentSummary {
+            display_count: displays as usize,
+            window_count: windows as usize,
+            application_count: applications as usize,
+            on_screen_window_count: on_screen as usize,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn as_ptr(&self) -> *const c_void {
         self.0
@@ -256,6 +603,10 @@ impl SCShareableContent {
 
 impl Drop for SCShareableContent {
     fn drop(&mut self) {
+        #[cfg(feature = "testing")]
+        if crate::testing::content(self.0).is_some() {
+            return;
+        }
         if !self.0.is_null() {
             unsafe {
                 crate::ffi::sc_shareable_content_release(self.0);
@@ -296,7 +647,8 @@ impl SCShareableContentOptions {
     /// Exclude desktop windows from the shareable content.
     ///
     /// When set to `true`, desktop-level windows (like the desktop background)
-    /// are excluded from the returned window list.
+    /// are excluded from the returned window list. See
+    /// [`ZOrderQuery::exclude_desktop_windows`] for what counts as a desktop window.
     #[must_use]
     pub fn exclude_desktop_windows(mut self, exclude: bool) -> Self {
         self.exclude_desktop_windows = exclude;
@@ -321,6 +673,11 @@ impl SCShareableContentOptions {
     ///
     /// Returns an error if screen recording permission is not granted or retrieval fails.
     pub fn get(self) -> Result<SCShareableContent, SCError> {
+        #[cfg(feature = "testing")]
+        if let Some(ptr) = crate::testing::active_content() {
+            return Ok(unsafe { SCShareableContent::from_ptr(ptr) });
+        }
+        ensure_core_graphics_initialized();
         let (completion, context) = SyncCompletion::<SCShareableContent>::new();
 
         unsafe {
@@ -332,7 +689,7 @@ impl SCShareableContentOptions {
             );
         }
 
-        completion.wait().map_err(SCError::NoShareableContent)
+        content_or_empty(completion.wait())
     }
 
     /// Get shareable content with only windows below a reference window
@@ -348,6 +705,9 @@ impl SCShareableContentOptions {
     ///
     /// Returns an error if screen recording permission is not granted or retrieval fails.
     pub fn below_window(self, reference_window: &SCWindow) -> Result<SCShareableContent, SCError> {
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(reference_window.as_ptr(), "below_window")?;
+        ensure_core_graphics_initialized();
         let (completion, context) = SyncCompletion::<SCShareableContent>::new();
 
         unsafe {
@@ -359,7 +719,7 @@ impl SCShareableContentOptions {
             );
         }
 
-        completion.wait().map_err(SCError::NoShareableContent)
+        content_or_empty(completion.wait())
     }
 
     /// Get shareable content with only windows above a reference window
@@ -375,6 +735,9 @@ impl SCShareableContentOptions {
     ///
     /// Returns an error if screen recording permission is not granted or retrieval fails.
     pub fn above_window(self, reference_window: &SCWindow) -> Result<SCShareableContent, SCError> {
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(reference_window.as_ptr(), "above_window")?;
+        ensure_core_graphics_initialized();
         let (completion, context) = SyncCompletion::<SCShareableContent>::new();
 
         unsafe {
@@ -386,7 +749,101 @@ impl SCShareableContentOptions {
             );
         }
 
-        completion.wait().map_err(SCError::NoShareableContent)
+        content_or_empty(completion.wait())
+    }
+}
+
+// MARK: - Z-order queries
+
+/// Which side of the reference window a [`ZOrderQuery`] returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stacking {
+    Above,
+    Below,
+}
+
+/// Builder for retrieving windows relative to another window's stacking order
+///
+/// Created with [`SCShareableContent::query`]. Without a reference window,
+/// [`fetch`](Self::fetch) returns all shareable content.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::shareable_content::SCShareableContent;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let content = SCShareableContent::get()?;
+/// let windows = content.windows();
+/// let my_window = &windows[0];
+///
+/// // Everything stacked on top of `my_window`, ignoring wallpaper and desktop icons
+/// let covering = SCShareableContent::query()
+///     .above(my_window)
+///     .exclude_desktop_windows(true)
+///     .fetch()?;
+/// println!("{} windows above", covering.windows().len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ZOrderQuery<'a> {
+    reference: Option<(&'a SCWindow, Stacking)>,
+    exclude_desktop_windows: bool,
+}
+
+impl<'a> ZOrderQuery<'a> {
+    /// Only return on-screen windows stacked above `window`
+    #[must_use]
+    pub fn above(mut self, window: &'a SCWindow) -> Self {
+        self.reference = Some((window, Stacking::Above));
+        self
+    }
+
+    /// Only return on-screen windows stacked below `window`
+    #[must_use]
+    pub fn below(mut self, window: &'a SCWindow) -> Self {
+        self.reference = Some((window, Stacking::Below));
+        self
+    }
+
+    /// Exclude desktop windows from the results
+    ///
+    /// Desktop windows are the windows the system draws at desktop level rather
+    /// than windows belonging to a regular application: the wallpaper, the
+    /// desktop icons drawn by Finder, and similar backdrop layers. They sit at
+    /// the bottom of the stacking order, so they show up in almost every
+    /// [`below`](Self::below) query unless excluded.
+    #[must_use]
+    pub fn exclude_desktop_windows(mut self, exclude: bool) -> Self {
+        self.exclude_desktop_windows = exclude;
+        self
+    }
+
+    /// Run the query
+    ///
+    /// This blocks until the content is retrieved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if screen recording permission is not granted or retrieval fails.
+    pub fn fetch(self) -> Result<SCShareableContent, SCError> {
+        let options = SCShareableContent::with_options()
+            .exclude_desktop_windows(self.exclude_desktop_windows);
+        match self.reference {
+            Some((window, Stacking::Above)) => options.above_window(window),
+            Some((window, Stacking::Below)) => options.below_window(window),
+            None => options.get(),
+        }
+    }
+}
+
+impl SCShareableContent {
+    /// Create a builder for querying windows by stacking order
+    ///
+    /// See [`ZOrderQuery`].
+    pub fn query<'a>() -> ZOrderQuery<'a> {
+        ZOrderQuery::default()
     }
 }
 
@@ -401,6 +858,7 @@ impl SCShareableContent {
     /// Returns an error if retrieval fails.
     #[cfg(feature = "macos_14_4")]
     pub fn current_process() -> Result<Self, SCError> {
+        ensure_core_graphics_initialized();
         let (completion, context) = SyncCompletion::<Self>::new();
 
         unsafe {
@@ -410,7 +868,7 @@ impl SCShareableContent {
             );
         }
 
-        completion.wait().map_err(SCError::NoShareableContent)
+        content_or_empty(completion.wait())
     }
 }
 
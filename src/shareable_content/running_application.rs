@@ -59,11 +59,19 @@ impl SCRunningApplication {
 
     /// Get process ID
     pub fn process_id(&self) -> i32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::application(self.0) {
+            return mock.process_id;
+        }
         unsafe { crate::ffi::sc_running_application_get_process_id(self.0) }
     }
 
     /// Get application name
     pub fn application_name(&self) -> String {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::application(self.0) {
+            return mock.application_name.clone();
+        }
         unsafe {
             ffi_string_owned_or_empty(|| {
                 crate::ffi::sc_running_application_get_application_name_owned(self.0)
@@ -73,6 +81,10 @@ impl SCRunningApplication {
 
     /// Get bundle identifier
     pub fn bundle_identifier(&self) -> String {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::application(self.0) {
+            return mock.bundle_identifier.clone();
+        }
         unsafe {
             ffi_string_owned_or_empty(|| {
                 crate::ffi::sc_running_application_get_bundle_identifier_owned(self.0)
@@ -83,6 +95,10 @@ impl SCRunningApplication {
 
 impl Drop for SCRunningApplication {
     fn drop(&mut self) {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return;
+        }
         if !self.0.is_null() {
             unsafe {
                 crate::ffi::sc_running_application_release(self.0);
@@ -93,6 +109,10 @@ impl Drop for SCRunningApplication {
 
 impl Clone for SCRunningApplication {
     fn clone(&self) -> Self {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return Self(self.0);
+        }
         unsafe { Self(crate::ffi::sc_running_application_retain(self.0)) }
     }
 }
@@ -0,0 +1,168 @@
+//! Saving a set of windows and finding them again later
+//!
+//! Window IDs only last as long as the window, so a selection restored in a
+//! new session usually has to be matched by the owning application and the
+//! window title instead. [`WindowSelection`] records both and, with the
+//! `serde` feature, can be written to disk between launches.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use screencapturekit::shareable_content::{SCShareableContent, WindowSelection};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let content = SCShareableContent::get()?;
+//! let selection = WindowSelection::from_windows(&content.windows()[..2]);
+//!
+//! // ... later, possibly after a relaunch
+//! let content = SCShareableContent::get()?;
+//! let (windows, missing) = selection.resolve_with_missing(&content);
+//! println!("Restored {} windows, {} gone", windows.len(), missing.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+
+use super::{SCShareableContent, SCWindow};
+
+/// A window as recorded in a [`WindowSelection`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedWindow {
+    /// Window ID at the time the selection was made
+    pub window_id: u32,
+    /// Bundle identifier of the owning application, if it had one
+    pub bundle_id: Option<String>,
+    /// Window title at the time the selection was made
+    pub title: Option<String>,
+}
+
+impl SavedWindow {
+    /// Record a window's ID, owning application and title
+    #[must_use]
+    pub fn from_window(window: &SCWindow) -> Self {
+        Self {
+            window_id: window.window_id(),
+            bundle_id: bundle_id_of(window),
+            title: window.title(),
+        }
+    }
+}
+
+/// A set of windows that can be re-found in later shareable content
+///
+/// Windows are matched by ID first. An ID only counts as a match if the
+/// window still belongs to the same application, since the window server
+/// reuses IDs. Windows whose ID is gone are matched by bundle identifier and
+/// title, which is what finds them again after the application relaunches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowSelection {
+    /// Saved windows, in selection order
+    pub windows: Vec<SavedWindow>,
+}
+
+impl WindowSelection {
+    /// An empty selection
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the given windows
+    #[must_use]
+    pub fn from_windows(windows: &[SCWindow]) -> Self {
+        Self {
+            windows: windows.iter().map(SavedWindow::from_window).collect(),
+        }
+    }
+
+    /// Add a window to the selection
+    pub fn push(&mut self, window: &SCWindow) {
+        self.windows.push(SavedWindow::from_window(window));
+    }
+
+    /// Number of saved windows
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` if no windows are saved
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Windows in `content` that match the saved entries, in selection order
+    ///
+    /// Saved windows that cannot be found are left out; use
+    /// [`resolve_with_missing`](Self::resolve_with_missing) to find out which.
+    #[must_use]
+    pub fn resolve(&self, content: &SCShareableContent) -> Vec<SCWindow> {
+        self.resolve_with_missing(content).0
+    }
+
+    /// Like [`resolve`](Self::resolve), also returning the saved windows that
+    /// could not be found
+    ///
+    /// Each current window matches at most one saved entry.
+    #[must_use]
+    pub fn resolve_with_missing(
+        &self,
+        content: &SCShareableContent,
+    ) -> (Vec<SCWindow>, Vec<SavedWindow>) {
+        let current = content.windows();
+        let mut taken = HashSet::new();
+
+        // ID matches first, so a title match can't claim a window whose ID
+        // another entry still holds
+        let mut matches: Vec<Option<usize>> = self
+            .windows
+            .iter()
+            .map(|saved| {
+                let index = current.iter().position(|window| {
+                    window.window_id() == saved.window_id
+                        && (saved.bundle_id.is_none() || bundle_id_of(window) == saved.bundle_id)
+                })?;
+                taken.insert(index).then_some(index)
+            })
+            .collect();
+
+        for (saved, slot) in self.windows.iter().zip(&mut matches) {
+            if slot.is_some() || saved.bundle_id.is_none() {
+                continue;
+            }
+            *slot = current
+                .iter()
+                .enumerate()
+                .find(|(index, window)| {
+                    !taken.contains(index)
+                        && window.title() == saved.title
+                        && bundle_id_of(window) == saved.bundle_id
+                })
+                .map(|(index, _)| index);
+            if let Some(index) = *slot {
+                taken.insert(index);
+            }
+        }
+
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+        for (saved, slot) in self.windows.iter().zip(matches) {
+            match slot {
+                Some(index) => found.push(current[index].clone()),
+                None => missing.push(saved.clone()),
+            }
+        }
+        (found, missing)
+    }
+}
+
+fn bundle_id_of(window: &SCWindow) -> Option<String> {
+    window
+        .owning_application()
+        .map(|app| app.bundle_identifier())
+        .filter(|id| !id.is_empty())
+}
@@ -0,0 +1,170 @@
+//! Polling for windows and applications that appear or go away
+//!
+//! `ScreenCaptureKit` has no notification for changes to the shareable
+//! content, so [`SCShareableContent::watch`] re-queries it on a background
+//! thread and reports the difference between successive enumerations.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use super::{SCRunningApplication, SCShareableContent, SCWindow};
+use crate::error::SCError;
+use crate::utils::timer::{spawn_timer, Timer};
+
+/// Windows and applications that changed between two polls
+///
+/// Window IDs and process IDs are sorted in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentDelta {
+    /// IDs of windows that appeared
+    pub added_windows: Vec<u32>,
+    /// IDs of windows that closed
+    pub removed_windows: Vec<u32>,
+    /// Process IDs of applications that launched
+    pub added_applications: Vec<i32>,
+    /// Process IDs of applications that quit
+    pub removed_applications: Vec<i32>,
+}
+
+impl ContentDelta {
+    /// Returns `true` if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added_windows.is_empty()
+            && self.removed_windows.is_empty()
+            && self.added_applications.is_empty()
+            && self.removed_applications.is_empty()
+    }
+}
+
+/// The window and process IDs of one enumeration
+struct Snapshot {
+    windows: BTreeSet<u32>,
+    applications: BTreeSet<i32>,
+}
+
+impl Snapshot {
+    fn of(content: &SCShareableContent) -> Self {
+        Self {
+            windows: content.windows().iter().map(SCWindow::window_id).collect(),
+            applications: content
+                .applications()
+                .iter()
+                .map(SCRunningApplication::process_id)
+                .collect(),
+        }
+    }
+
+    fn delta_since(&self, previous: &Self) -> ContentDelta {
+        ContentDelta {
+            added_windows: self
+                .windows
+                .difference(&previous.windows)
+                .copied()
+                .collect(),
+            removed_windows: previous
+                .windows
+                .difference(&self.windows)
+                .copied()
+                .collect(),
+            added_applications: self
+                .applications
+                .difference(&previous.applications)
+                .copied()
+                .collect(),
+            removed_applications: previous
+                .applications
+                .difference(&self.applications)
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// Handle to a running [`SCShareableContent::watch`]
+///
+/// Polling stops when the handle is dropped or [`stop`](Self::stop) is called.
+/// A poll already in progress finishes first, but its result is not reported.
+#[must_use = "the watch stops when the handle is dropped"]
+pub struct ContentWatcher {
+    _timer: Timer,
+}
+
+impl ContentWatcher {
+    /// Stop polling
+    pub fn stop(self) {}
+}
+
+impl std::fmt::Debug for ContentWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentWatcher").finish_non_exhaustive()
+    }
+}
+
+impl SCShareableContent {
+    /// Report windows and applications that appear or go away
+    ///
+    /// The content is enumerated once immediately to establish a baseline,
+    /// then again every `interval` on a background thread. `callback` runs on
+    /// that thread whenever an enumeration differs from the previous one; polls
+    /// with no changes, and polls that fail (for example while the screen is
+    /// locked), are not reported.
+    ///
+    /// Every window is considered, including off-screen ones, so minimizing a
+    /// window does not report it as removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interval` is zero, if the baseline enumeration
+    /// fails, or if the polling thread cannot be started.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use screencapturekit::shareable_content::SCShareableContent;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let watcher = SCShareableContent::watch(Duration::from_secs(1), |delta| {
+    ///     for id in &delta.added_windows {
+    ///         println!("Window {id} opened");
+    ///     }
+    ///     for id in &delta.removed_windows {
+    ///         println!("Window {id} closed");
+    ///     }
+    /// })?;
+    /// std::thread::sleep(Duration::from_secs(30));
+    /// watcher.stop();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch<F>(interval: Duration, callback: F) -> Result<ContentWatcher, SCError>
+    where
+        F: Fn(ContentDelta) + Send + 'static,
+    {
+        if interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "Watch interval must be greater than zero",
+            ));
+        }
+        let mut previous = Snapshot::of(&Self::get()?);
+        let timer = spawn_timer(
+            "screencapturekit-content-watch",
+            interval,
+            move |is_stopped| {
+                let Ok(content) = Self::get() else {
+                    return true;
+                };
+                let current = Snapshot::of(&content);
+                drop(content);
+                let delta = current.delta_since(&previous);
+                previous = current;
+                if !delta.is_empty() && !is_stopped() {
+                    callback(delta);
+                }
+                true
+            },
+        )
+        .map_err(|e| SCError::internal_error(format!("Failed to spawn watch thread: {e}")))?;
+        Ok(ContentWatcher { _timer: timer })
+    }
+}
@@ -5,10 +5,52 @@ use std::ffi::c_void;
 
 use super::SCRunningApplication;
 
+/// Apps whose windows play DRM-protected video
+const PROTECTED_PLAYBACK_BUNDLE_IDS: &[&str] = &[
+    "com.apple.TV",
+    "com.apple.Music",
+    "com.netflix.Netflix",
+    "com.disney.disneyplus",
+    "com.amazon.aiv.AIVApp",
+    "com.hbo.hbonow",
+];
+
+/// Browsers that use `FairPlay` or Widevine for streaming sites
+const BROWSER_BUNDLE_IDS: &[&str] = &[
+    "com.apple.Safari",
+    "com.google.Chrome",
+    "org.mozilla.firefox",
+    "com.microsoft.edgemac",
+    "company.thebrowser.Browser",
+    "com.brave.Browser",
+];
+
+/// Streaming services whose name usually appears in the tab title
+const PROTECTED_STREAMING_TITLES: &[&str] = &[
+    "Netflix",
+    "Disney+",
+    "Prime Video",
+    "Apple TV",
+    "Max",
+    "Hulu",
+];
+
 /// Wrapper around `SCWindow` from `ScreenCaptureKit`
 ///
 /// Represents a window that can be captured.
 ///
+/// # Protected Content
+///
+/// Video protected by DRM (`FairPlay` in Apple TV or Safari, Widevine in other
+/// browsers) is blanked by the window server before it reaches the capture
+/// pipeline. Frames still arrive with [`SCFrameStatus::Complete`], but the
+/// video area is black. There is no API that reports this, so apps should use
+/// [`is_likely_protected`](Self::is_likely_protected) together with
+/// [`CVPixelBuffer::is_black`] to tell users why a window captures black.
+///
+/// [`SCFrameStatus::Complete`]: crate::cm::SCFrameStatus::Complete
+/// [`CVPixelBuffer::is_black`]: crate::cm::CVPixelBuffer::is_black
+///
 /// # Examples
 ///
 /// ```no_run
@@ -60,6 +102,11 @@ impl SCWindow {
 
     /// Get the owning application
     pub fn owning_application(&self) -> Option<SCRunningApplication> {
+        #[cfg(feature = "testing")]
+        if crate::testing::window(self.0).is_some() {
+            return crate::testing::owning_application(self.0)
+                .map(|ptr| unsafe { SCRunningApplication::from_ptr(ptr) });
+        }
         unsafe {
             let app_ptr = crate::ffi::sc_window_get_owning_application(self.0);
             if app_ptr.is_null() {
@@ -72,11 +119,19 @@ impl SCWindow {
 
     /// Get the window ID
     pub fn window_id(&self) -> u32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.window_id;
+        }
         unsafe { crate::ffi::sc_window_get_window_id(self.0) }
     }
 
     /// Get the window frame (position and size)
     pub fn frame(&self) -> CGRect {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.frame;
+        }
         let mut x = 0.0;
         let mut y = 0.0;
         let mut width = 0.0;
@@ -89,11 +144,19 @@ impl SCWindow {
 
     /// Get the window title (if available)
     pub fn title(&self) -> Option<String> {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.title.clone();
+        }
         unsafe { ffi_string_owned(|| crate::ffi::sc_window_get_title_owned(self.0)) }
     }
 
     /// Get window layer
     pub fn window_layer(&self) -> i32 {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.window_layer;
+        }
         // FFI returns isize but window layer fits in i32
         #[allow(clippy::cast_possible_truncation)]
         unsafe {
@@ -103,18 +166,73 @@ impl SCWindow {
 
     /// Check if window is on screen
     pub fn is_on_screen(&self) -> bool {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.is_on_screen;
+        }
         unsafe { crate::ffi::sc_window_is_on_screen(self.0) }
     }
 
+    /// Guess whether the window plays DRM-protected video
+    ///
+    /// Returns `true` for windows of dedicated streaming apps, and for browser
+    /// windows whose title names a known streaming service. This is a
+    /// heuristic: it can miss protected players it doesn't know about and flag
+    /// a browser tab that is not playing anything. Combine it with a check on
+    /// the captured frames before warning the user:
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// fn warn_if_blanked(window: &SCWindow, sample: &CMSampleBuffer) {
+    ///     let black = sample.image_buffer().is_some_and(|buffer| buffer.is_black());
+    ///     if black && window.is_likely_protected() {
+    ///         eprintln!("{} shows protected content, which macOS hides from capture", window);
+    ///     }
+    /// }
+    /// ```
+    pub fn is_likely_protected(&self) -> bool {
+        let Some(app) = self.owning_application() else {
+            return false;
+        };
+        let bundle_id = app.bundle_identifier();
+        if PROTECTED_PLAYBACK_BUNDLE_IDS.contains(&bundle_id.as_str()) {
+            return true;
+        }
+        BROWSER_BUNDLE_IDS.contains(&bundle_id.as_str())
+            && self.title().is_some_and(|title| {
+                PROTECTED_STREAMING_TITLES
+                    .iter()
+                    .any(|service| title_names_service(&title, service))
+            })
+    }
+
     /// Check if window is active (macOS 14.0+)
     #[cfg(feature = "macos_14_0")]
     pub fn is_active(&self) -> bool {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = crate::testing::window(self.0) {
+            return mock.is_active;
+        }
         unsafe { crate::ffi::sc_window_is_active(self.0) }
     }
 }
 
+/// Whether `service` appears in `title` as a whole word, so "Max" does not match "Maximum"
+fn title_names_service(title: &str, service: &str) -> bool {
+    title.match_indices(service).any(|(start, _)| {
+        let before = title[..start].chars().next_back();
+        let after = title[start + service.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
 impl Drop for SCWindow {
     fn drop(&mut self) {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return;
+        }
         if !self.0.is_null() {
             unsafe {
                 crate::ffi::sc_window_release(self.0);
@@ -125,6 +243,10 @@ impl Drop for SCWindow {
 
 impl Clone for SCWindow {
     fn clone(&self) -> Self {
+        #[cfg(feature = "testing")]
+        if crate::testing::is_mock(self.0) {
+            return Self(self.0);
+        }
         unsafe { Self(crate::ffi::sc_window_retain(self.0)) }
     }
 }
@@ -0,0 +1,95 @@
+//! Batched live reconfiguration for [`SCStream::update`](super::SCStream::update)
+
+use crate::cg::CGRect;
+use crate::stream::configuration::{PixelFormat, SCStreamConfiguration};
+
+/// Changes collected by [`SCStream::update`](super::SCStream::update)
+///
+/// Each method records a change on a copy of the stream's current
+/// configuration; nothing reaches the system until the closure passed to
+/// `update` returns, at which point everything is applied in one call. Use
+/// [`configuration`](Self::configuration) for settings without a shortcut
+/// here.
+#[derive(Debug)]
+pub struct ConfigChanges {
+    configuration: SCStreamConfiguration,
+    changed: bool,
+}
+
+impl ConfigChanges {
+    pub(crate) fn new(configuration: SCStreamConfiguration) -> Self {
+        Self {
+            configuration,
+            changed: false,
+        }
+    }
+
+    /// Returns `true` if no change was recorded
+    pub fn is_empty(&self) -> bool {
+        !self.changed
+    }
+
+    /// The configuration the changes are applied to, for any other setting
+    pub fn configuration(&mut self) -> &mut SCStreamConfiguration {
+        self.changed = true;
+        &mut self.configuration
+    }
+
+    /// Change the output width in pixels
+    pub fn width(&mut self, width: u32) -> &mut Self {
+        self.configuration().set_width(width);
+        self
+    }
+
+    /// Change the output height in pixels
+    pub fn height(&mut self, height: u32) -> &mut Self {
+        self.configuration().set_height(height);
+        self
+    }
+
+    /// Change the maximum frame rate
+    pub fn fps(&mut self, fps: u32) -> &mut Self {
+        self.configuration().set_fps(fps);
+        self
+    }
+
+    /// Change the pixel format
+    pub fn pixel_format(&mut self, pixel_format: PixelFormat) -> &mut Self {
+        self.configuration().set_pixel_format(pixel_format);
+        self
+    }
+
+    /// Show or hide the cursor
+    pub fn shows_cursor(&mut self, shows_cursor: bool) -> &mut Self {
+        self.configuration().set_shows_cursor(shows_cursor);
+        self
+    }
+
+    /// Change the number of frames buffered by the system
+    pub fn queue_depth(&mut self, queue_depth: u32) -> &mut Self {
+        self.configuration().set_queue_depth(queue_depth);
+        self
+    }
+
+    /// Change the captured region
+    pub fn source_rect(&mut self, source_rect: CGRect) -> &mut Self {
+        self.configuration().set_source_rect(source_rect);
+        self
+    }
+
+    /// Change where the captured content is placed in the output frame
+    pub fn destination_rect(&mut self, destination_rect: CGRect) -> &mut Self {
+        self.configuration().set_destination_rect(destination_rect);
+        self
+    }
+
+    /// Enable or disable scaling the content to fit the output size
+    pub fn scales_to_fit(&mut self, scales_to_fit: bool) -> &mut Self {
+        self.configuration().set_scales_to_fit(scales_to_fit);
+        self
+    }
+
+    pub(crate) fn into_configuration(self) -> Option<SCStreamConfiguration> {
+        self.changed.then_some(self.configuration)
+    }
+}
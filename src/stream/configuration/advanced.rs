@@ -42,6 +42,7 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Check if shadows are ignored for single window capture (macOS 14.0+)
     #[cfg(feature = "macos_14_0")]
     pub fn ignores_shadows_single_window(&self) -> bool {
         unsafe {
@@ -157,6 +158,10 @@ impl SCStreamConfiguration {
 
     /// Sets whether to ignore shadow display configuration.
     ///
+    /// Alias of [`set_ignores_shadows_display`](Self::set_ignores_shadows_display):
+    /// both map to `ignoreShadowsDisplay`, so window shadows are left out of
+    /// display captures.
+    ///
     /// Available on macOS 14.0+
     ///
     /// Requires the `macos_14_0` feature flag to be enabled.
@@ -179,6 +184,7 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Check if shadows are ignored for display capture (macOS 14.0+)
     #[cfg(feature = "macos_14_0")]
     pub fn ignores_shadow_display_configuration(&self) -> bool {
         unsafe {
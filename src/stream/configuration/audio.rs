@@ -2,13 +2,86 @@
 //!
 //! Methods for configuring audio capture, sample rate, and channel count.
 
+use crate::audio_devices::{AudioDeviceFormat, AudioInputDevice};
+use crate::error::SCError;
 use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
 
 use super::internal::SCStreamConfiguration;
 
+/// Complete audio settings for [`SCStreamConfiguration::with_audio`]
+///
+/// The default is 48 kHz stereo without the microphone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioConfig {
+    /// Sample rate in Hz
+    pub sample_rate: i32,
+    /// Number of channels (1 = mono, 2 = stereo)
+    pub channels: i32,
+    /// Also capture the microphone (macOS 15.0+)
+    pub captures_microphone: bool,
+}
+
+impl AudioConfig {
+    /// Sample rates `ScreenCaptureKit` accepts for system audio, in Hz
+    pub const SUPPORTED_SAMPLE_RATES: [i32; 4] = [8000, 16000, 24000, 48000];
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 48000,
+            channels: 2,
+            captures_microphone: false,
+        }
+    }
+}
+
 impl SCStreamConfiguration {
+    /// Create a configuration for audio-only capture
+    ///
+    /// `ScreenCaptureKit` has no audio-only mode: a stream always needs a
+    /// content filter and always produces video, even if no screen output
+    /// handler is registered. This preset keeps the video side as cheap as
+    /// possible so it can be ignored:
+    ///
+    /// - 2x2 pixel frames (the smallest size the system accepts)
+    /// - 1 frame per second
+    /// - cursor hidden
+    ///
+    /// and enables audio at 48 kHz stereo. Pair it with any display filter and
+    /// register only an [`Audio`](crate::stream::output_type::SCStreamOutputType::Audio)
+    /// handler; video frames without a handler are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let config = SCStreamConfiguration::audio_only();
+    /// assert!(config.captures_audio());
+    /// assert_eq!(config.sample_rate(), 48000);
+    /// assert_eq!(config.channel_count(), 2);
+    /// ```
+    #[must_use]
+    pub fn audio_only() -> Self {
+        Self::new()
+            .with_width(2)
+            .with_height(2)
+            .with_fps(1)
+            .with_shows_cursor(false)
+            .with_captures_audio(true)
+            .with_sample_rate(48000)
+            .with_channel_count(2)
+    }
+
     /// Enable or disable audio capture
     ///
+    /// Enabling audio also fills in a sample rate of 48 kHz and 2 channels if
+    /// the configuration has none yet, so audio is never delivered silent or
+    /// mono by accident. A rate or channel count set earlier is kept, and
+    /// setting either afterwards overrides the default. Use
+    /// [`with_audio`](Self::with_audio) to configure everything at once.
+    ///
     /// # Examples
     ///
     /// ```
@@ -17,11 +90,54 @@ impl SCStreamConfiguration {
     /// let mut config = SCStreamConfiguration::default();
     /// config.set_captures_audio(true);
     /// assert!(config.captures_audio());
+    /// assert!(config.sample_rate() > 0);
+    /// assert!(config.channel_count() > 0);
     /// ```
     pub fn set_captures_audio(&mut self, captures_audio: bool) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_captures_audio(self.as_ptr(), captures_audio);
         }
+        if captures_audio {
+            let defaults = AudioConfig::default();
+            if self.sample_rate() <= 0 {
+                self.set_sample_rate(defaults.sample_rate);
+            }
+            if self.channel_count() <= 0 {
+                self.set_channel_count(defaults.channels);
+            }
+        }
+        self
+    }
+
+    /// Enable audio capture with the given sample rate, channels and microphone setting
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::configuration::AudioConfig;
+    ///
+    /// let mut config = SCStreamConfiguration::new();
+    /// config.set_audio(AudioConfig {
+    ///     sample_rate: 24000,
+    ///     channels: 1,
+    ///     ..AudioConfig::default()
+    /// });
+    /// assert!(config.captures_audio());
+    /// assert_eq!(config.sample_rate(), 24000);
+    /// assert_eq!(config.channel_count(), 1);
+    /// ```
+    pub fn set_audio(&mut self, audio: AudioConfig) -> &mut Self {
+        self.set_sample_rate(audio.sample_rate)
+            .set_channel_count(audio.channels)
+            .set_captures_microphone(audio.captures_microphone)
+            .set_captures_audio(true)
+    }
+
+    /// Enable audio capture with the given settings (builder pattern)
+    #[must_use]
+    pub fn with_audio(mut self, audio: AudioConfig) -> Self {
+        self.set_audio(audio);
         self
     }
 
@@ -252,4 +368,71 @@ impl SCStreamConfiguration {
             })
         }
     }
+
+    /// Check the system audio format against what `ScreenCaptureKit` accepts
+    ///
+    /// Only applies with [`captures_audio`](Self::captures_audio) enabled:
+    /// the sample rate must be one of [`AudioConfig::SUPPORTED_SAMPLE_RATES`]
+    /// and the channel count 1 or 2.
+    pub(crate) fn validate_audio_format(&self) -> Result<(), SCError> {
+        if !self.captures_audio() {
+            return Ok(());
+        }
+        let sample_rate = self.sample_rate();
+        if !AudioConfig::SUPPORTED_SAMPLE_RATES.contains(&sample_rate) {
+            return Err(SCError::invalid_config(format!(
+                "Sample rate {sample_rate} Hz is not supported (supported: {:?})",
+                AudioConfig::SUPPORTED_SAMPLE_RATES
+            )));
+        }
+        let channels = self.channel_count();
+        if !(1..=2).contains(&channels) {
+            return Err(SCError::invalid_config(format!(
+                "Channel count {channels} is not supported (use 1 or 2)"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that the selected microphone supports the configured format
+    ///
+    /// With microphone capture enabled, the selected microphone (or the
+    /// default one) must support the configured sample rate and channel
+    /// count. Otherwise the system silently delivers microphone audio in a
+    /// format of its choosing. Devices that don't report their formats are
+    /// accepted as is.
+    pub(crate) fn validate_microphone(&self) -> Result<(), SCError> {
+        if !self.captures_microphone() {
+            return Ok(());
+        }
+
+        let device = match self.microphone_capture_device_id() {
+            Some(id) if !id.is_empty() => AudioInputDevice::with_id(&id).ok_or_else(|| {
+                SCError::invalid_config(format!("No audio input device with ID {id}"))
+            })?,
+            _ => match AudioInputDevice::default_device() {
+                Some(device) => device,
+                None => return Ok(()),
+            },
+        };
+
+        let formats = device.supported_formats();
+        let wanted = AudioDeviceFormat {
+            sample_rate: self.sample_rate(),
+            channels: self.channel_count(),
+        };
+        if formats.is_empty() || formats.contains(&wanted) {
+            return Ok(());
+        }
+
+        let supported = formats
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(SCError::invalid_config(format!(
+            "Microphone \"{}\" does not support {wanted} (supported: {supported})",
+            device.name
+        )))
+    }
 }
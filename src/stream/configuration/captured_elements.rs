@@ -123,6 +123,10 @@ impl SCStreamConfiguration {
     /// Ignore shadows for display capture (macOS 14.0+)
     ///
     /// When set to `true`, window shadows are excluded from display capture.
+    /// This is the same underlying property as
+    /// [`set_ignores_shadow_display_configuration`](Self::set_ignores_shadow_display_configuration);
+    /// use [`set_ignores_shadows_single_window`](Self::set_ignores_shadows_single_window)
+    /// for filters that target a single window.
     ///
     /// # Availability
     /// macOS 14.0+. On earlier versions, this setting has no effect.
@@ -153,7 +157,10 @@ impl SCStreamConfiguration {
 
     /// Ignore global clip for display capture (macOS 14.0+)
     ///
-    /// When set to `true`, the global clip region is ignored for display capture.
+    /// The global clip is the region the window server clips window content to
+    /// when compositing the desktop, such as the parts of a window that extend
+    /// past the edge of the display. When set to `true`, windows included in a
+    /// display capture are not cut off by that clip.
     ///
     /// # Availability
     /// macOS 14.0+. On earlier versions, this setting has no effect.
@@ -184,7 +191,11 @@ impl SCStreamConfiguration {
 
     /// Ignore global clip for single window capture (macOS 14.0+)
     ///
-    /// When set to `true`, the global clip region is ignored for single window capture.
+    /// When set to `true`, a window captured through a single-window
+    /// [`SCContentFilter`](crate::stream::content_filter::SCContentFilter)
+    /// is captured in full, including any part that is dragged off-screen or
+    /// otherwise clipped by the global clip (see
+    /// [`set_ignore_global_clip_display`](Self::set_ignore_global_clip_display)).
     ///
     /// # Availability
     /// macOS 14.0+. On earlier versions, this setting has no effect.
@@ -214,4 +225,53 @@ impl SCStreamConfiguration {
             crate::ffi::sc_stream_configuration_get_ignore_global_clip_single_window(self.as_ptr())
         }
     }
+
+    /// Configure a clean capture with no window shadows and no global clipping (macOS 14.0+)
+    ///
+    /// Sets all four shadow and clip flags at once:
+    /// [`set_ignores_shadows_single_window`](Self::set_ignores_shadows_single_window),
+    /// [`set_ignores_shadows_display`](Self::set_ignores_shadows_display),
+    /// [`set_ignore_global_clip_single_window`](Self::set_ignore_global_clip_single_window) and
+    /// [`set_ignore_global_clip_display`](Self::set_ignore_global_clip_display).
+    /// The result is a tight, unclipped image of the captured windows whether
+    /// the filter targets a single window or a display.
+    ///
+    /// # Availability
+    /// macOS 14.0+. On earlier versions, this setting has no effect.
+    ///
+    /// # Examples
+    /// ```
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_shows_cursor(false)
+    ///     .with_clean_window_capture(true);
+    /// ```
+    #[cfg(feature = "macos_14_0")]
+    pub fn set_clean_window_capture(&mut self, clean: bool) -> &mut Self {
+        self.set_ignores_shadows_single_window(clean)
+            .set_ignores_shadows_display(clean)
+            .set_ignore_global_clip_single_window(clean)
+            .set_ignore_global_clip_display(clean)
+    }
+
+    /// Configure a clean capture with no shadows or clipping (builder pattern)
+    #[cfg(feature = "macos_14_0")]
+    #[must_use]
+    pub fn with_clean_window_capture(mut self, clean: bool) -> Self {
+        self.set_clean_window_capture(clean);
+        self
+    }
+
+    /// Check if all shadow and global clip flags are set (macOS 14.0+)
+    ///
+    /// Returns `true` only when every flag touched by
+    /// [`set_clean_window_capture`](Self::set_clean_window_capture) is enabled.
+    #[cfg(feature = "macos_14_0")]
+    pub fn clean_window_capture(&self) -> bool {
+        self.ignores_shadows_single_window()
+            && self.ignores_shadows_display()
+            && self.ignore_global_clip_single_window()
+            && self.ignore_global_clip_display()
+    }
 }
@@ -1,9 +1,89 @@
 use super::internal::SCStreamConfiguration;
+use crate::cg_display::CGDisplay;
 use crate::cm::CMTime;
+use crate::shareable_content::SCDisplay;
 
 #[cfg(feature = "macos_14_0")]
 use super::SCCaptureResolutionType;
 
+/// How often the stream delivers frames
+///
+/// Used with [`SCStreamConfiguration::set_frame_pacing`], which translates the
+/// choice into a [`minimum_frame_interval`](SCStreamConfiguration::minimum_frame_interval).
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::stream::configuration::{FramePacing, SCStreamConfiguration};
+///
+/// let config = SCStreamConfiguration::new().with_frame_pacing(FramePacing::DisplayLink);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FramePacing {
+    /// Match the refresh rate of the main display
+    ///
+    /// The rate is read from the display's current mode when the pacing is
+    /// set, so 59.94 Hz and 120 Hz panels get an exact interval instead of a
+    /// rounded fps. Displays that report no fixed rate, such as some
+    /// variable-refresh panels, fall back to 60 Hz.
+    #[default]
+    DisplayLink,
+    /// Match the refresh rate of the display with this ID
+    ///
+    /// Use this when capturing a display other than the main one, or a window
+    /// that lives on it, so frames follow that panel's refresh instead. See
+    /// [`for_display`](Self::for_display). An ID that matches no display falls
+    /// back to 60 Hz.
+    Display(u32),
+    /// A fixed number of frames per second, as with [`SCStreamConfiguration::set_fps`]
+    ///
+    /// The rate is clamped to `1..=i32::MAX`, so `Fixed(0)` delivers one
+    /// frame per second instead of producing an invalid interval.
+    Fixed(u32),
+}
+
+impl FramePacing {
+    /// Pace frames to the refresh rate of `display`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::configuration::FramePacing;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// // Record the last display at its own refresh rate
+    /// let display = content.displays().last().ok_or("no display")?.clone();
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_frame_pacing(FramePacing::for_display(&display));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn for_display(display: &SCDisplay) -> Self {
+        Self::Display(display.display_id())
+    }
+
+    /// The frame interval this pacing resolves to right now
+    #[must_use]
+    pub fn frame_interval(self) -> CMTime {
+        match self {
+            Self::DisplayLink => CGDisplay::active_displays()
+                .ok()
+                .and_then(|ids| ids.first().copied())
+                .and_then(|id| CGDisplay::new(id).display_mode())
+                .unwrap_or_default()
+                .frame_interval(),
+            Self::Display(id) => CGDisplay::new(id)
+                .display_mode()
+                .unwrap_or_default()
+                .frame_interval(),
+            Self::Fixed(fps) => CMTime::new(1, i32::try_from(fps.max(1)).unwrap_or(i32::MAX)),
+        }
+    }
+}
+
 impl SCStreamConfiguration {
     /// Set the queue depth for frame buffering
     pub fn set_queue_depth(&mut self, queue_depth: u32) -> &mut Self {
@@ -34,6 +114,14 @@ impl SCStreamConfiguration {
     }
 
     /// Set the minimum frame interval
+    ///
+    /// This caps the frame rate; it does not make the system send frames
+    /// while the captured content is unchanged. Use
+    /// [`SCStream::add_output_handler_with_heartbeat`](crate::stream::SCStream::add_output_handler_with_heartbeat)
+    /// when frames must arrive on a fixed schedule.
+    ///
+    /// To lock capture to a display's refresh rate, pass the interval from
+    /// [`DisplayMode::frame_interval`](crate::cg_display::DisplayMode::frame_interval).
     pub fn set_minimum_frame_interval(&mut self, cm_time: &CMTime) -> &mut Self {
         unsafe {
             crate::ffi::sc_stream_configuration_set_minimum_frame_interval(
@@ -54,6 +142,25 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Get the minimum frame interval
+    ///
+    /// Returns the `CMTime` most recently set with
+    /// [`set_minimum_frame_interval`](Self::set_minimum_frame_interval) or
+    /// [`set_fps`](Self::set_fps), or the system default if neither was called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::cm::CMTime;
+    /// use screencapturekit::stream::configuration::SCStreamConfiguration;
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_minimum_frame_interval(&CMTime::new(1, 30));
+    ///
+    /// let interval = config.minimum_frame_interval();
+    /// assert_eq!(interval.value, 1);
+    /// assert_eq!(interval.timescale, 30);
+    /// ```
     pub fn minimum_frame_interval(&self) -> CMTime {
         unsafe {
             let mut value: i64 = 0;
@@ -129,6 +236,43 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Set how frames are paced
+    ///
+    /// [`FramePacing::DisplayLink`] ties delivery to the main display's
+    /// refresh so recordings on 59.94, 120 or 144 Hz displays don't judder
+    /// the way a rounded fixed fps does. When the filter captures another
+    /// display, pass it with [`FramePacing::for_display`] so its refresh rate
+    /// is used instead. The result is stored as the minimum
+    /// frame interval: a later call to
+    /// [`set_minimum_frame_interval`](Self::set_minimum_frame_interval) or
+    /// [`set_fps`](Self::set_fps) overrides it, and the refresh rate is not
+    /// re-read if the display mode changes afterwards.
+    ///
+    /// A [`FramePacing::Fixed`] rate of 0 is clamped to 1 fps, and rates above
+    /// `i32::MAX` to `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::stream::configuration::{FramePacing, SCStreamConfiguration};
+    ///
+    /// let mut config = SCStreamConfiguration::new();
+    /// config.set_frame_pacing(FramePacing::Fixed(30));
+    /// assert_eq!(config.fps(), 30);
+    /// ```
+    pub fn set_frame_pacing(&mut self, pacing: FramePacing) -> &mut Self {
+        self.set_minimum_frame_interval(&pacing.frame_interval())
+    }
+
+    /// Set how frames are paced (builder pattern)
+    ///
+    /// See [`set_frame_pacing`](Self::set_frame_pacing) for details.
+    #[must_use]
+    pub fn with_frame_pacing(mut self, pacing: FramePacing) -> Self {
+        self.set_frame_pacing(pacing);
+        self
+    }
+
     /// Set the capture resolution type (macOS 14.0+)
     ///
     /// Controls how the capture resolution is determined.
@@ -2,13 +2,20 @@
 //!
 //! Methods for configuring color space, pixel format, and background color.
 
+use crate::utils::ffi_string::{ffi_string_from_buffer, SMALL_BUFFER_SIZE};
 use crate::utils::four_char_code::FourCharCode;
 
-use super::{internal::SCStreamConfiguration, pixel_format::PixelFormat};
+use super::{
+    internal::SCStreamConfiguration,
+    pixel_format::{ColorRange, ColorSpace, PixelFormat},
+};
 
 impl SCStreamConfiguration {
     /// Set the pixel format for captured frames
     ///
+    /// The configured width and height are rounded up to the new format's
+    /// alignment, e.g. to even values for the `YCbCr` formats.
+    ///
     /// # Examples
     ///
     /// ```
@@ -25,6 +32,7 @@ impl SCStreamConfiguration {
                 four_char_code.as_u32(),
             );
         }
+        self.align_dimensions();
         self
     }
 
@@ -43,6 +51,53 @@ impl SCStreamConfiguration {
         }
     }
 
+    /// Set the value range of `YCbCr` frames
+    ///
+    /// `ScreenCaptureKit` has no separate range setting: the range is part of
+    /// the pixel format (`420v` is video range, `420f` full range). This
+    /// setting picks the matching variant of whichever `YCbCr` format is
+    /// configured, now or later, so the format family and the range can be
+    /// chosen independently. [`PixelFormat::YCbCr_420v`] and
+    /// [`PixelFormat::YCbCr_420f`] then behave the same; the range set here
+    /// decides. RGB formats are unaffected and always full range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::{ColorRange, PixelFormat, SCStreamConfiguration};
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_color_range(ColorRange::Full)
+    ///     .with_pixel_format(PixelFormat::YCbCr_420v);
+    /// assert_eq!(config.pixel_format(), PixelFormat::YCbCr_420f);
+    /// assert_eq!(config.color_range(), ColorRange::Full);
+    /// ```
+    pub fn set_color_range(&mut self, range: ColorRange) -> &mut Self {
+        let value = match range {
+            ColorRange::Video => 0,
+            ColorRange::Full => 1,
+        };
+        unsafe {
+            crate::ffi::sc_stream_configuration_set_color_range(self.as_ptr(), value);
+        }
+        self
+    }
+
+    /// Set the value range of `YCbCr` frames (builder pattern)
+    #[must_use]
+    pub fn with_color_range(mut self, range: ColorRange) -> Self {
+        self.set_color_range(range);
+        self
+    }
+
+    /// Get the effective value range of captured frames
+    ///
+    /// Derived from the configured pixel format, so it reflects both
+    /// [`set_color_range`](Self::set_color_range) and the format itself.
+    pub fn color_range(&self) -> ColorRange {
+        self.pixel_format().color_range()
+    }
+
     /// Set the background color for captured content
     ///
     /// Available on macOS 13.0+
@@ -88,6 +143,60 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Get the color space name for captured content
+    ///
+    /// Returns the name passed to [`set_color_space_name`](Self::set_color_space_name)
+    /// or [`set_color_space`](Self::set_color_space). A configuration that never
+    /// set one reports the framework's default, or `None` if that is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::stream::configuration::SCStreamConfiguration;
+    ///
+    /// let config = SCStreamConfiguration::new().with_color_space_name("kCGColorSpaceDisplayP3");
+    /// assert_eq!(config.color_space_name().as_deref(), Some("kCGColorSpaceDisplayP3"));
+    /// ```
+    pub fn color_space_name(&self) -> Option<String> {
+        unsafe {
+            ffi_string_from_buffer(SMALL_BUFFER_SIZE, |buf, len| {
+                crate::ffi::sc_stream_configuration_get_color_space_name(self.as_ptr(), buf, len)
+            })
+        }
+    }
+
+    /// Set the color space for captured content
+    ///
+    /// Typed counterpart of [`set_color_space_name`](Self::set_color_space_name)
+    /// that passes the matching `CGColorSpace` name for you.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::{ColorSpace, SCStreamConfiguration};
+    ///
+    /// let config = SCStreamConfiguration::new().with_color_space(ColorSpace::DisplayP3);
+    /// ```
+    pub fn set_color_space(&mut self, color_space: ColorSpace) -> &mut Self {
+        self.set_color_space_name(color_space.name())
+    }
+
+    /// Set the color space (builder pattern)
+    #[must_use]
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.set_color_space(color_space);
+        self
+    }
+
+    /// Get the color space for captured content
+    ///
+    /// Typed counterpart of [`color_space_name`](Self::color_space_name).
+    /// Returns `None` when no name is set or the name isn't one of the
+    /// [`ColorSpace`] variants.
+    pub fn color_space(&self) -> Option<ColorSpace> {
+        ColorSpace::from_name(&self.color_space_name()?)
+    }
+
     /// Set the color matrix for captured content
     ///
     /// Available on macOS 13.0+. The matrix should be a 3x3 array in row-major order.
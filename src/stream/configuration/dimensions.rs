@@ -3,14 +3,46 @@
 //! This module provides methods to configure the output dimensions, scaling behavior,
 //! and source/destination rectangles for captured streams.
 
-use crate::cg::CGRect;
+use crate::cg::{CGRect, CGSize};
+use crate::cg_display::CGDisplay;
+use crate::error::SCError;
+use crate::shareable_content::{SCDisplay, SCWindow};
 
 use super::internal::SCStreamConfiguration;
+use super::pixel_format::align_up;
+
+/// Largest output width or height [`with_scale`](SCStreamConfiguration::with_scale)
+/// will produce, matching the largest surface the GPU can allocate
+pub const MAX_OUTPUT_DIMENSION: u32 = 16384;
+
+/// How source content is fitted into the output frame
+///
+/// Combines [`scales_to_fit`](SCStreamConfiguration::scales_to_fit),
+/// [`preserves_aspect_ratio`](SCStreamConfiguration::preserves_aspect_ratio)
+/// and the source/destination rectangles into one intent. Apply it with
+/// [`SCStreamConfiguration::set_content_fit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContentFitMode {
+    /// Scale to fill the output exactly, distorting the content if the
+    /// aspect ratios differ
+    Stretch,
+    /// Scale to fit inside the output, keeping the aspect ratio and
+    /// letterboxing or pillarboxing the rest
+    #[default]
+    Contain,
+    /// Scale to fill the output, keeping the aspect ratio and cropping the
+    /// edges of the source that don't fit
+    Cover,
+}
 
 impl SCStreamConfiguration {
     /// Set the output width in pixels
     ///
-    /// The width determines the width of captured frames.
+    /// The width determines the width of captured frames. It is rounded up to
+    /// the [`width_alignment`](crate::stream::configuration::PixelFormat::width_alignment)
+    /// of the current pixel format, so an odd width becomes even for `YCbCr`
+    /// formats, and [`width`](Self::width) returns the rounded value. See
+    /// [`effective_dimensions`](Self::effective_dimensions).
     ///
     /// # Examples
     ///
@@ -22,6 +54,7 @@ impl SCStreamConfiguration {
     /// assert_eq!(config.width(), 1920);
     /// ```
     pub fn set_width(&mut self, width: u32) -> &mut Self {
+        let width = align_up(width, self.pixel_format().width_alignment());
         // FFI expects isize; u32 may wrap on 32-bit platforms (acceptable)
         #[allow(clippy::cast_possible_wrap)]
         unsafe {
@@ -68,7 +101,9 @@ impl SCStreamConfiguration {
 
     /// Set the output height in pixels
     ///
-    /// The height determines the height of captured frames.
+    /// The height determines the height of captured frames. Like the width, it
+    /// is rounded up to the alignment of the current pixel format, and
+    /// [`height`](Self::height) returns the rounded value.
     ///
     /// # Examples
     ///
@@ -80,6 +115,7 @@ impl SCStreamConfiguration {
     /// assert_eq!(config.height(), 1080);
     /// ```
     pub fn set_height(&mut self, height: u32) -> &mut Self {
+        let height = align_up(height, self.pixel_format().height_alignment());
         // FFI expects isize; u32 may wrap on 32-bit platforms (acceptable)
         #[allow(clippy::cast_possible_wrap)]
         unsafe {
@@ -124,6 +160,168 @@ impl SCStreamConfiguration {
         }
     }
 
+    /// Get the output size after alignment to the pixel format
+    ///
+    /// The stored width and height are aligned to the current
+    /// [`PixelFormat`](crate::stream::configuration::PixelFormat), so this is
+    /// the size of the frames the stream will deliver rather than the values
+    /// originally requested. [`set_width`](Self::set_width),
+    /// [`set_height`](Self::set_height) and
+    /// [`set_pixel_format`](Self::set_pixel_format) already round the stored
+    /// values; this also covers configurations whose size was set elsewhere,
+    /// such as a picker's recommended configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::configuration::PixelFormat;
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_width(1921)
+    ///     .with_height(1081)
+    ///     .with_pixel_format(PixelFormat::YCbCr_420v);
+    /// assert_eq!(config.effective_dimensions(), (1922, 1082));
+    /// ```
+    pub fn effective_dimensions(&self) -> (u32, u32) {
+        self.pixel_format()
+            .align_dimensions(self.width(), self.height())
+    }
+
+    /// Set the output width and height in pixels from a size
+    ///
+    /// Each dimension is rounded to the nearest pixel (negative values become
+    /// zero) and then aligned like [`set_width`](Self::set_width) and
+    /// [`set_height`](Self::set_height).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let mut config = SCStreamConfiguration::default();
+    /// config.set_size(CGSize::new(1280.0, 720.0));
+    /// assert_eq!((config.width(), config.height()), (1280, 720));
+    /// ```
+    pub fn set_size(&mut self, size: CGSize) -> &mut Self {
+        // Saturating float-to-int casts clamp out-of-range values
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (width, height) = (size.width.round() as u32, size.height.round() as u32);
+        self.set_width(width).set_height(height)
+    }
+
+    /// Set the output width and height from a size (builder pattern)
+    ///
+    /// Pairs with sizes reported elsewhere in the crate, such as a display's
+    /// frame.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let config = SCStreamConfiguration::new().with_size(display.frame().size());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_size(mut self, size: CGSize) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Get the configured output width and height in pixels
+    pub fn size(&self) -> CGSize {
+        CGSize::new(f64::from(self.width()), f64::from(self.height()))
+    }
+
+    /// Check that the output size is non-zero and within [`MAX_OUTPUT_DIMENSION`]
+    pub(crate) fn validate_dimensions(&self) -> Result<(), SCError> {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return Err(SCError::invalid_config(format!(
+                "Output size {width}x{height} has a zero dimension"
+            )));
+        }
+        if width > MAX_OUTPUT_DIMENSION || height > MAX_OUTPUT_DIMENSION {
+            return Err(SCError::invalid_config(format!(
+                "Output size {width}x{height} exceeds the maximum of {MAX_OUTPUT_DIMENSION}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Set the output size to the display's native resolution times `scale`
+    ///
+    /// `1.0` captures every backing pixel of a Retina display and `0.5`
+    /// captures at half that in each direction, so fractional sizes like
+    /// `0.75` don't need computing by hand. If the result would exceed
+    /// [`MAX_OUTPUT_DIMENSION`] on either side, both sides shrink by the same
+    /// factor to keep the aspect ratio. A scale that is not a positive number
+    /// leaves the size unchanged.
+    ///
+    /// Only the output size changes; combine with
+    /// [`scales_to_fit`](Self::scales_to_fit) when downscaling a source rect
+    /// or window.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let mut config = SCStreamConfiguration::new();
+    /// config.set_scale(display, 1.0 / 1.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_scale(&mut self, display: &SCDisplay, scale: f64) -> &mut Self {
+        if !(scale.is_finite() && scale > 0.0) {
+            return self;
+        }
+        let size = scaled_size(native_size(display), scale);
+        self.set_size(size)
+    }
+
+    /// Set the output size to the display's native resolution times `scale`
+    /// (builder pattern)
+    ///
+    /// See [`set_scale`](Self::set_scale).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_scale(display, 0.75)
+    ///     .with_scales_to_fit(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_scale(mut self, display: &SCDisplay, scale: f64) -> Self {
+        self.set_scale(display, scale);
+        self
+    }
+
+    /// Re-apply the pixel format's alignment to the stored width and height
+    pub(crate) fn align_dimensions(&mut self) {
+        let (width, height) = (self.width(), self.height());
+        if self.pixel_format().align_dimensions(width, height) != (width, height) {
+            self.set_width(width);
+            self.set_height(height);
+        }
+    }
+
     /// Enable or disable scaling to fit the output dimensions
     ///
     /// When enabled, the source content will be scaled to fit within the
@@ -159,8 +357,14 @@ impl SCStreamConfiguration {
 
     /// Set the source rectangle to capture
     ///
-    /// Defines which portion of the source content to capture. Coordinates are
-    /// relative to the source content's coordinate system.
+    /// Defines which portion of the source content to capture. The rectangle is
+    /// passed to the system unchanged, so it must already be in the raw
+    /// OS coordinate space: points (not pixels), relative to the top-left corner
+    /// of the captured display. Global desktop coordinates such as
+    /// [`SCDisplay::frame`] or [`SCWindow::frame`](crate::shareable_content::SCWindow::frame)
+    /// are not accepted directly; use
+    /// [`set_source_rect_in_display`](Self::set_source_rect_in_display) to have
+    /// the rectangle converted and clamped for a specific display.
     ///
     /// # Examples
     ///
@@ -193,6 +397,112 @@ impl SCStreamConfiguration {
         self
     }
 
+    /// Set the source rectangle from a display-local rectangle
+    ///
+    /// `local_rect` is measured in points from the top-left corner of `display`,
+    /// with y increasing downwards. It is converted into the space
+    /// the system expects and clipped to the display's bounds, so a
+    /// rectangle that hangs off the edge of the display captures only the
+    /// visible part instead of producing blank frames.
+    ///
+    /// To capture a region given in global desktop coordinates (for example a
+    /// window's frame), subtract the display's [`frame`](SCDisplay::frame)
+    /// origin first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::cg::CGRect;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    ///
+    /// // Capture the top-left 800x600 points of the display
+    /// let mut config = SCStreamConfiguration::new();
+    /// config.set_source_rect_in_display(display, CGRect::new(0.0, 0.0, 800.0, 600.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_source_rect_in_display(
+        &mut self,
+        display: &SCDisplay,
+        local_rect: CGRect,
+    ) -> &mut Self {
+        let bounds = display.frame();
+        self.set_source_rect(local_to_source_rect(
+            bounds.width,
+            bounds.height,
+            local_rect,
+        ))
+    }
+
+    /// Set the source rectangle from a display-local rectangle (builder pattern)
+    ///
+    /// See [`set_source_rect_in_display`](Self::set_source_rect_in_display).
+    #[must_use]
+    pub fn with_source_rect_in_display(mut self, display: &SCDisplay, local_rect: CGRect) -> Self {
+        self.set_source_rect_in_display(display, local_rect);
+        self
+    }
+
+    /// Set the source rectangle from a rectangle within a window
+    ///
+    /// For a filter that captures a single window
+    /// ([`SCContentFilter::builder().window(..)`](crate::stream::content_filter::SCContentFilterBuilder::window)),
+    /// the system measures the source rectangle from the window's own top-left
+    /// corner rather than the display's. `rect_in_window` is given in those
+    /// window-local points and clipped to the window's current
+    /// [`frame`](SCWindow::frame) size, so capturing just the video area of a
+    /// media player is a matter of passing that area's offset and size.
+    ///
+    /// To cut the same region out of a display capture instead, offset
+    /// `rect_in_window` by the window's frame origin minus the display's and
+    /// use [`set_source_rect_in_display`](Self::set_source_rect_in_display).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::cg::CGRect;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let window = &content.windows()[0];
+    /// let filter = SCContentFilter::builder().window(window).build();
+    ///
+    /// // Skip a 28 point title bar
+    /// let frame = window.frame();
+    /// let config = SCStreamConfiguration::new().with_window_source_rect(
+    ///     window,
+    ///     CGRect::new(0.0, 28.0, frame.width, frame.height - 28.0),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_window_source_rect(
+        &mut self,
+        window: &SCWindow,
+        rect_in_window: CGRect,
+    ) -> &mut Self {
+        let bounds = window.frame();
+        self.set_source_rect(local_to_source_rect(
+            bounds.width,
+            bounds.height,
+            rect_in_window,
+        ))
+    }
+
+    /// Set the source rectangle from a rectangle within a window (builder pattern)
+    ///
+    /// See [`set_window_source_rect`](Self::set_window_source_rect).
+    #[must_use]
+    pub fn with_window_source_rect(mut self, window: &SCWindow, rect_in_window: CGRect) -> Self {
+        self.set_window_source_rect(window, rect_in_window);
+        self
+    }
+
     /// Get the configured source rectangle
     pub fn source_rect(&self) -> CGRect {
         unsafe {
@@ -304,4 +614,128 @@ impl SCStreamConfiguration {
     pub fn preserves_aspect_ratio(&self) -> bool {
         unsafe { crate::ffi::sc_stream_configuration_get_preserves_aspect_ratio(self.as_ptr()) }
     }
+
+    /// Fit the source content into the output frame
+    ///
+    /// Sets `scales_to_fit`, `preserves_aspect_ratio` and the source and
+    /// destination rectangles for the chosen [`ContentFitMode`], replacing any
+    /// values set individually. `source_size` is the size of the captured
+    /// content in points (e.g. a display's or window's frame size) and is only
+    /// used by [`Cover`](ContentFitMode::Cover), which sets the source
+    /// rectangle to a centered crop matching the output's aspect ratio.
+    /// `Stretch` and `Contain` clear the source rectangle so the whole content
+    /// is captured again, including after an earlier `Cover`. Set the output
+    /// width and height first.
+    ///
+    /// `preserves_aspect_ratio` requires macOS 14.0+; on older systems
+    /// `Contain` behaves like `Stretch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::cg::CGSize;
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::configuration::ContentFitMode;
+    ///
+    /// // Fill a square thumbnail from a 16:9 display, cropping the sides
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_width(512)
+    ///     .with_height(512)
+    ///     .with_content_fit(ContentFitMode::Cover, CGSize::new(1920.0, 1080.0));
+    /// assert_eq!(config.source_rect().width, 1080.0);
+    /// ```
+    pub fn set_content_fit(&mut self, mode: ContentFitMode, source_size: CGSize) -> &mut Self {
+        self.set_scales_to_fit(true);
+        self.set_preserves_aspect_ratio(mode != ContentFitMode::Stretch);
+        self.set_destination_rect(CGRect::zero());
+        let source_rect = if mode == ContentFitMode::Cover {
+            cover_source_rect(source_size, self.size())
+        } else {
+            CGRect::zero()
+        };
+        self.set_source_rect(source_rect)
+    }
+
+    /// Fit the source content into the output frame (builder pattern)
+    ///
+    /// See [`set_content_fit`](Self::set_content_fit).
+    #[must_use]
+    pub fn with_content_fit(mut self, mode: ContentFitMode, source_size: CGSize) -> Self {
+        self.set_content_fit(mode, source_size);
+        self
+    }
+}
+
+/// Backing-pixel size of a display, falling back to its size in points
+fn native_size(display: &SCDisplay) -> CGSize {
+    CGDisplay::new(display.display_id())
+        .display_mode()
+        .filter(|mode| mode.pixel_width() > 0 && mode.pixel_height() > 0)
+        .map_or_else(
+            || display.frame().size(),
+            |mode| {
+                CGSize::new(
+                    f64::from(mode.pixel_width()),
+                    f64::from(mode.pixel_height()),
+                )
+            },
+        )
+}
+
+/// `native` times `scale`, shrunk uniformly to fit [`MAX_OUTPUT_DIMENSION`]
+fn scaled_size(native: CGSize, scale: f64) -> CGSize {
+    let width = native.width * scale;
+    let height = native.height * scale;
+    let limit = f64::from(MAX_OUTPUT_DIMENSION);
+    let largest = width.max(height);
+    if largest > limit {
+        let fit = limit / largest;
+        CGSize::new((width * fit).min(limit), (height * fit).min(limit))
+    } else {
+        CGSize::new(width, height)
+    }
+}
+
+/// Largest centered rectangle of `source` with the aspect ratio of `output`
+///
+/// Returns an empty rectangle (capture everything) when either size is empty.
+fn cover_source_rect(source: CGSize, output: CGSize) -> CGRect {
+    if source.is_empty() || output.is_empty() {
+        return CGRect::zero();
+    }
+    let output_aspect = output.width / output.height;
+    if source.width / source.height > output_aspect {
+        let width = source.height * output_aspect;
+        CGRect::new((source.width - width) / 2.0, 0.0, width, source.height)
+    } else {
+        let height = source.width / output_aspect;
+        CGRect::new(0.0, (source.height - height) / 2.0, source.width, height)
+    }
+}
+
+/// Convert a local rectangle into a `sourceRect` for content of the given size
+///
+/// The system measures `sourceRect` in points from the top-left corner of the
+/// captured display or window, which is the same orientation as the local
+/// rectangle, so only the origin needs normalizing (negative sizes flip the
+/// rectangle) and the result is clipped to the content. A rectangle entirely
+/// outside yields an empty rectangle at the nearest edge.
+fn local_to_source_rect(content_width: f64, content_height: f64, local: CGRect) -> CGRect {
+    let content_width = content_width.max(0.0);
+    let content_height = content_height.max(0.0);
+    let (x0, x1) = if local.width < 0.0 {
+        (local.x + local.width, local.x)
+    } else {
+        (local.x, local.x + local.width)
+    };
+    let (y0, y1) = if local.height < 0.0 {
+        (local.y + local.height, local.y)
+    } else {
+        (local.y, local.y + local.height)
+    };
+    let min_x = x0.clamp(0.0, content_width);
+    let max_x = x1.clamp(0.0, content_width);
+    let min_y = y0.clamp(0.0, content_height);
+    let max_y = y1.clamp(0.0, content_height);
+    CGRect::new(min_x, min_y, max_x - min_x, max_y - min_y)
 }
@@ -6,6 +6,15 @@ use std::fmt;
 /// Configuration for a screen capture stream, including dimensions,
 /// pixel format, audio settings, and other capture parameters.
 ///
+/// # Thread Safety
+///
+/// `SCStreamConfiguration` is `Send` and `Sync`. Setters take `&mut self`, so a
+/// single value cannot be read and written at the same time. Note that
+/// [`Clone`] retains the same underlying object rather than copying it: clones
+/// moved to different threads must not be mutated concurrently. Use
+/// [`deep_copy`](Self::deep_copy) for a configuration that can be changed
+/// independently.
+///
 /// # Examples
 ///
 /// ```
@@ -43,6 +52,70 @@ impl SCStreamConfiguration {
     pub(crate) fn as_ptr(&self) -> *const c_void {
         self.0
     }
+
+    /// Check the configuration for values that produce a broken stream
+    ///
+    /// The setters pass values to the framework unchecked, and a stream
+    /// started with an invalid configuration may simply never deliver a
+    /// frame. Run this before [`SCStream::new`](crate::stream::SCStream::new)
+    /// to get an error instead. It checks that:
+    ///
+    /// - width and height are set and at most [`MAX_OUTPUT_DIMENSION`](super::MAX_OUTPUT_DIMENSION)
+    /// - with audio capture on, the sample rate is one of
+    ///   [`AudioConfig::SUPPORTED_SAMPLE_RATES`](super::AudioConfig::SUPPORTED_SAMPLE_RATES)
+    ///   and the channel count is 1 or 2
+    /// - with microphone capture on, the selected microphone ID matches a
+    ///   connected device, and that device (or the default one) supports the
+    ///   configured sample rate and channel count; devices that don't report
+    ///   their formats are accepted as is
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::InvalidConfiguration` describing the first problem
+    /// found. For the microphone the message lists the formats it does
+    /// support.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// let config = SCStreamConfiguration::new()
+    ///     .with_width(1920)
+    ///     .with_height(0)
+    ///     .with_captures_audio(true)
+    ///     .with_sample_rate(96000)
+    ///     .with_channel_count(8);
+    /// if let Err(e) = config.validate() {
+    ///     eprintln!("{e}");
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), crate::error::SCError> {
+        self.validate_dimensions()?;
+        self.validate_audio_format()?;
+        self.validate_microphone()
+    }
+
+    /// Copy every property into a new, independent configuration
+    ///
+    /// Unlike [`Clone`], which shares the underlying object, changes to the
+    /// copy do not affect `self`. The copy is a different object, so it never
+    /// compares equal to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::SCStreamConfiguration;
+    ///
+    /// let base = SCStreamConfiguration::new().with_width(1920);
+    /// let mut thumbnail = base.deep_copy();
+    /// thumbnail.set_width(320);
+    /// assert_eq!(base.width(), 1920);
+    /// ```
+    #[must_use]
+    pub fn deep_copy(&self) -> Self {
+        unsafe { Self(crate::ffi::sc_stream_configuration_copy(self.0)) }
+    }
 }
 
 impl Drop for SCStreamConfiguration {
@@ -61,6 +134,11 @@ impl Clone for SCStreamConfiguration {
     }
 }
 
+// SAFETY: Retain/release are atomic and the getters are property loads, so a
+// handle can be moved, shared and dropped on any thread. Setters take
+// `&mut self`, but `Clone` shares the object, so clones held by different
+// threads can still write it at once. The type docs rule that out;
+// `deep_copy` gives each thread its own object.
 unsafe impl Send for SCStreamConfiguration {}
 unsafe impl Sync for SCStreamConfiguration {}
 
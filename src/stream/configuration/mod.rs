@@ -6,12 +6,17 @@ pub mod captured_elements;
 pub mod captured_frames;
 pub mod colors;
 pub mod dimensions;
+pub mod pixel_buffer_attributes;
 pub mod pixel_format;
 pub mod stream_properties;
 
 pub use advanced::SCPresenterOverlayAlertSetting;
+pub use audio::AudioConfig;
+pub use captured_frames::FramePacing;
+pub use dimensions::{ContentFitMode, MAX_OUTPUT_DIMENSION};
 pub use internal::SCStreamConfiguration;
-pub use pixel_format::PixelFormat;
+pub use pixel_buffer_attributes::PixelBufferAttributes;
+pub use pixel_format::{ColorRange, ColorSpace, PixelFormat};
 pub use stream_properties::SCCaptureDynamicRange;
 
 /// Capture resolution type for stream configuration (macOS 14.0+)
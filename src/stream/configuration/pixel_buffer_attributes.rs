@@ -0,0 +1,163 @@
+//! Pixel buffer attributes for delivered frames
+//!
+//! Controls the `CoreVideo` properties of the pixel buffers handed to output
+//! handlers, for callers that pass frames straight to Metal or OpenGL.
+
+use super::internal::SCStreamConfiguration;
+
+/// Requested `CoreVideo` attributes for captured pixel buffers
+///
+/// Maps to `kCVPixelBufferMetalCompatibilityKey`,
+/// `kCVPixelBufferOpenGLCompatibilityKey` and the
+/// `kCVPixelBufferExtendedPixels*Key` family.
+///
+/// `ScreenCaptureKit` delivers IOSurface-backed buffers, which already satisfy
+/// Metal and OpenGL compatibility, so those frames are passed through untouched.
+/// A frame that does not meet the request (for example when extended pixels
+/// are required) is copied once into a buffer from a pool created with these
+/// attributes before it reaches the handler.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::stream::configuration::{PixelBufferAttributes, SCStreamConfiguration};
+///
+/// let config = SCStreamConfiguration::new().with_pixel_buffer_attributes(
+///     PixelBufferAttributes::new()
+///         .with_metal_compatibility(true)
+///         .with_extended_pixels(0, 0, 16, 0),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PixelBufferAttributes {
+    metal_compatible: bool,
+    opengl_compatible: bool,
+    extended_pixels: [usize; 4],
+}
+
+impl PixelBufferAttributes {
+    /// Create attributes with no requirements
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            metal_compatible: false,
+            opengl_compatible: false,
+            extended_pixels: [0; 4],
+        }
+    }
+
+    /// Require buffers that can be wrapped as Metal textures
+    #[must_use]
+    pub const fn with_metal_compatibility(mut self, compatible: bool) -> Self {
+        self.metal_compatible = compatible;
+        self
+    }
+
+    /// Require buffers that can be bound as OpenGL textures
+    #[must_use]
+    pub const fn with_opengl_compatibility(mut self, compatible: bool) -> Self {
+        self.opengl_compatible = compatible;
+        self
+    }
+
+    /// Require padding pixels around the image, in pixels per edge
+    #[must_use]
+    pub const fn with_extended_pixels(
+        mut self,
+        left: usize,
+        top: usize,
+        right: usize,
+        bottom: usize,
+    ) -> Self {
+        self.extended_pixels = [left, top, right, bottom];
+        self
+    }
+
+    /// Whether Metal compatibility is requested
+    #[must_use]
+    pub const fn metal_compatible(&self) -> bool {
+        self.metal_compatible
+    }
+
+    /// Whether OpenGL compatibility is requested
+    #[must_use]
+    pub const fn opengl_compatible(&self) -> bool {
+        self.opengl_compatible
+    }
+
+    /// Requested extended pixels as `(left, top, right, bottom)`
+    #[must_use]
+    pub const fn extended_pixels(&self) -> (usize, usize, usize, usize) {
+        let [left, top, right, bottom] = self.extended_pixels;
+        (left, top, right, bottom)
+    }
+
+    /// Whether no attributes are requested
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !self.metal_compatible
+            && !self.opengl_compatible
+            && self.extended_pixels[0] == 0
+            && self.extended_pixels[1] == 0
+            && self.extended_pixels[2] == 0
+            && self.extended_pixels[3] == 0
+    }
+}
+
+impl SCStreamConfiguration {
+    /// Set the attributes of delivered pixel buffers
+    ///
+    /// Passing empty attributes clears any previous request.
+    /// See [`PixelBufferAttributes`] for when frames are copied.
+    pub fn set_pixel_buffer_attributes(&mut self, attributes: PixelBufferAttributes) -> &mut Self {
+        let [left, top, right, bottom] = attributes.extended_pixels;
+        // FFI expects isize; extended pixel counts are small and never wrap in practice
+        #[allow(clippy::cast_possible_wrap)]
+        unsafe {
+            crate::ffi::sc_stream_configuration_set_pixel_buffer_attributes(
+                self.as_ptr(),
+                attributes.metal_compatible,
+                attributes.opengl_compatible,
+                left as isize,
+                top as isize,
+                right as isize,
+                bottom as isize,
+            );
+        }
+        self
+    }
+
+    /// Set the attributes of delivered pixel buffers (builder pattern)
+    #[must_use]
+    pub fn with_pixel_buffer_attributes(mut self, attributes: PixelBufferAttributes) -> Self {
+        self.set_pixel_buffer_attributes(attributes);
+        self
+    }
+
+    /// Get the requested pixel buffer attributes
+    pub fn pixel_buffer_attributes(&self) -> PixelBufferAttributes {
+        let mut metal_compatible = false;
+        let mut opengl_compatible = false;
+        let mut extended: [isize; 4] = [0; 4];
+        unsafe {
+            let [left, top, right, bottom] = &mut extended;
+            crate::ffi::sc_stream_configuration_get_pixel_buffer_attributes(
+                self.as_ptr(),
+                &mut metal_compatible,
+                &mut opengl_compatible,
+                left,
+                top,
+                right,
+                bottom,
+            );
+        }
+        // Extended pixel counts are never negative
+        #[allow(clippy::cast_sign_loss)]
+        let extended_pixels = extended.map(|value| value.max(0) as usize);
+        PixelBufferAttributes {
+            metal_compatible,
+            opengl_compatible,
+            extended_pixels,
+        }
+    }
+}
@@ -67,3 +67,211 @@ impl From<FourCharCode> for PixelFormat {
         }
     }
 }
+
+/// Luma/chroma value range of `YCbCr` pixel formats
+///
+/// Video range maps black and white to 16 and 235, the convention most video
+/// encoders and players expect. Full range uses 0 to 255. RGB formats are
+/// always full range.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::stream::configuration::{ColorRange, PixelFormat};
+///
+/// assert_eq!(PixelFormat::YCbCr_420v.color_range(), ColorRange::Video);
+/// assert_eq!(PixelFormat::BGRA.color_range(), ColorRange::Full);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorRange {
+    /// Limited "video" range (16-235 for 8-bit luma)
+    Video,
+    /// Full range (0-255)
+    #[default]
+    Full,
+}
+
+impl Display for ColorRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Video => write!(f, "video"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+/// Color space of captured frames
+///
+/// Each variant maps to a `CGColorSpace` name constant, so a typo in a color
+/// space can no longer slip through as an unrecognized string.
+///
+/// # Examples
+///
+/// ```
+/// use screencapturekit::stream::configuration::ColorSpace;
+///
+/// assert_eq!(ColorSpace::DisplayP3.name(), "kCGColorSpaceDisplayP3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorSpace {
+    /// sRGB, the default for most displays and web content
+    #[default]
+    SRGB,
+    /// Display P3, the wide gamut of Apple displays
+    DisplayP3,
+    /// sRGB with components allowed outside 0.0-1.0 for EDR content
+    ExtendedSRGB,
+    /// Linear-light extended sRGB, suited to compositing and HDR processing
+    ExtendedLinearSRGB,
+    /// ITU-R BT.2020, the gamut used by HDR video
+    Rec2020,
+}
+
+impl ColorSpace {
+    /// The `CGColorSpace` name constant for this color space
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::SRGB => "kCGColorSpaceSRGB",
+            Self::DisplayP3 => "kCGColorSpaceDisplayP3",
+            Self::ExtendedSRGB => "kCGColorSpaceExtendedSRGB",
+            Self::ExtendedLinearSRGB => "kCGColorSpaceExtendedLinearSRGB",
+            Self::Rec2020 => "kCGColorSpaceITUR_2020",
+        }
+    }
+
+    /// Look up the color space for a `CGColorSpace` name, returning `None`
+    /// for names this enum doesn't cover
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::ColorSpace;
+    ///
+    /// assert_eq!(ColorSpace::from_name("kCGColorSpaceITUR_2020"), Some(ColorSpace::Rec2020));
+    /// assert_eq!(ColorSpace::from_name("kCGColorSpaceGenericGray"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::SRGB,
+            Self::DisplayP3,
+            Self::ExtendedSRGB,
+            Self::ExtendedLinearSRGB,
+            Self::Rec2020,
+        ]
+        .into_iter()
+        .find(|color_space| color_space.name() == name)
+    }
+}
+
+impl Display for ColorSpace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl PixelFormat {
+    /// Look up the format for a raw `OSType`, returning `None` for codes this
+    /// enum doesn't cover
+    ///
+    /// Unlike `From<u32>`, which falls back to [`BGRA`](Self::BGRA), this
+    /// lets callers tell an unknown format apart from a real BGRA one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::PixelFormat;
+    /// use screencapturekit::FourCharCode;
+    ///
+    /// let nv12 = FourCharCode::from_bytes(*b"420v").as_u32();
+    /// assert_eq!(PixelFormat::from_raw(nv12), Some(PixelFormat::YCbCr_420v));
+    /// assert_eq!(PixelFormat::from_raw(0), None);
+    /// ```
+    #[must_use]
+    pub fn from_raw(value: u32) -> Option<Self> {
+        match &FourCharCode::from_u32(value).as_bytes() {
+            b"BGRA" => Some(Self::BGRA),
+            b"l10r" => Some(Self::l10r),
+            b"420v" => Some(Self::YCbCr_420v),
+            b"420f" => Some(Self::YCbCr_420f),
+            _ => None,
+        }
+    }
+
+    /// The value range of frames in this format
+    #[must_use]
+    pub const fn color_range(self) -> ColorRange {
+        match self {
+            Self::YCbCr_420v => ColorRange::Video,
+            Self::BGRA | Self::l10r | Self::YCbCr_420f => ColorRange::Full,
+        }
+    }
+
+    /// Width multiple required by this format
+    ///
+    /// Packed RGB formats (`BGRA`, `l10r`) accept any width and return 1. The
+    /// 4:2:0 `YCbCr` formats share one chroma sample between two columns, so
+    /// their width must be even and this returns 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::PixelFormat;
+    ///
+    /// assert_eq!(PixelFormat::BGRA.width_alignment(), 1);
+    /// assert_eq!(PixelFormat::YCbCr_420v.width_alignment(), 2);
+    /// ```
+    #[must_use]
+    pub const fn width_alignment(self) -> u32 {
+        match self {
+            Self::BGRA | Self::l10r => 1,
+            Self::YCbCr_420v | Self::YCbCr_420f => 2,
+        }
+    }
+
+    /// Height multiple required by this format
+    ///
+    /// 4:2:0 subsampling halves the chroma plane vertically as well, so the
+    /// `YCbCr` formats need an even height; RGB formats return 1.
+    #[must_use]
+    pub const fn height_alignment(self) -> u32 {
+        self.width_alignment()
+    }
+
+    /// Round `width` and `height` up to the alignment this format requires
+    ///
+    /// Values too close to `u32::MAX` to round up are rounded down instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::stream::configuration::PixelFormat;
+    ///
+    /// assert_eq!(PixelFormat::YCbCr_420f.align_dimensions(1921, 1081), (1922, 1082));
+    /// assert_eq!(PixelFormat::BGRA.align_dimensions(1921, 1081), (1921, 1081));
+    /// assert_eq!(
+    ///     PixelFormat::YCbCr_420v.align_dimensions(u32::MAX, 1),
+    ///     (u32::MAX - 1, 2)
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn align_dimensions(self, width: u32, height: u32) -> (u32, u32) {
+        (
+            align_up(width, self.width_alignment()),
+            align_up(height, self.height_alignment()),
+        )
+    }
+}
+
+/// Round `value` up to a multiple of `alignment`, or down to the largest
+/// multiple if rounding up would overflow
+pub(super) const fn align_up(value: u32, alignment: u32) -> u32 {
+    match value % alignment {
+        0 => value,
+        rem => match value.checked_add(alignment - rem) {
+            Some(aligned) => aligned,
+            None => value - rem,
+        },
+    }
+}
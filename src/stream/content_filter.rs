@@ -27,14 +27,24 @@ use std::fmt;
 #[cfg(feature = "macos_14_2")]
 use crate::cg::CGRect;
 use crate::{
+    error::SCError,
     ffi,
-    shareable_content::{SCDisplay, SCRunningApplication, SCWindow},
+    shareable_content::{SCDisplay, SCRunningApplication, SCShareableContent, SCWindow},
 };
 
 /// Content filter for `ScreenCaptureKit` streams
 ///
 /// Defines what content to capture (displays, windows, or applications).
 ///
+/// # Thread Safety
+///
+/// `SCContentFilter` is `Send` and `Sync`. A built filter is only read by streams,
+/// so it can be created on one thread and handed to streams on others, as in the
+/// `f_multithread` example. [`Clone`] retains the same underlying object, so
+/// clones held by different threads must not be changed with
+/// [`set_include_menu_bar`](Self::set_include_menu_bar) at the same time. Build
+/// a [`SendFilter`] to hand a filter to another thread without sharing it.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -103,6 +113,38 @@ impl SCContentFilter {
         SCContentFilterBuilder::new()
     }
 
+    /// Create a filter that captures a single window by ID
+    ///
+    /// Looks the window up in freshly fetched shareable content, so a window ID
+    /// saved from a previous session can be captured without enumerating
+    /// windows yourself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::WindowNotFound`] if no window with `window_id` exists
+    /// anymore, or an error if the shareable content cannot be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let saved_id = 1234;
+    /// let filter = SCContentFilter::for_window_id(saved_id)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_window_id(window_id: u32) -> Result<Self, SCError> {
+        let content = SCShareableContent::get()?;
+        let window = content
+            .window_with_id(window_id)
+            .ok_or_else(|| SCError::WindowNotFound(format!("no window with ID {window_id}")))?;
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(window.as_ptr(), "SCContentFilter::for_window_id")?;
+        Ok(Self::builder().window(&window).build())
+    }
+
     /// Creates a content filter from a picker-returned pointer
     ///
     /// This is used internally when the content sharing picker returns a filter.
@@ -162,10 +204,27 @@ impl SCContentFilter {
         SCShareableContentStyle::from(value)
     }
 
-    /// Get the stream type (macOS 14.0+)
+    /// Get the stream type
     ///
-    /// Returns whether this filter captures a window or a display.
-    #[cfg(feature = "macos_14_0")]
+    /// Returns whether this filter captures a window or a display. This is
+    /// how to tell what a filter from the content picker represents.
+    /// `ScreenCaptureKit` reports the type on macOS 14.0+; on earlier systems
+    /// this returns [`SCStreamType::Unknown`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::SCStreamType;
+    ///
+    /// fn describe(filter: &SCContentFilter) -> &'static str {
+    ///     match filter.stream_type() {
+    ///         SCStreamType::Window => "a single window",
+    ///         SCStreamType::Display => "a whole display",
+    ///         SCStreamType::Unknown => "something",
+    ///     }
+    /// }
+    /// ```
     pub fn stream_type(&self) -> SCStreamType {
         let value = unsafe { ffi::sc_content_filter_get_stream_type(self.0) };
         SCStreamType::from(value)
@@ -311,34 +370,36 @@ impl std::fmt::Display for SCShareableContentStyle {
     }
 }
 
-/// Stream type for filters (macOS 14.0+)
+/// Stream type for filters
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-#[cfg(feature = "macos_14_0")]
 pub enum SCStreamType {
     /// Window-based stream
     #[default]
     Window = 0,
     /// Display-based stream
     Display = 1,
+    /// The system did not report a type (before macOS 14.0, or a type this
+    /// crate does not know)
+    Unknown = -1,
 }
 
-#[cfg(feature = "macos_14_0")]
 impl From<i32> for SCStreamType {
     fn from(value: i32) -> Self {
         match value {
+            0 => Self::Window,
             1 => Self::Display,
-            _ => Self::Window,
+            _ => Self::Unknown,
         }
     }
 }
 
-#[cfg(feature = "macos_14_0")]
 impl std::fmt::Display for SCStreamType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Window => write!(f, "Window"),
             Self::Display => write!(f, "Display"),
+            Self::Unknown => write!(f, "Unknown"),
         }
     }
 }
@@ -373,11 +434,54 @@ impl fmt::Display for SCContentFilter {
     }
 }
 
-// Safety: SCContentFilter wraps an Objective-C object that is thread-safe
-// The underlying SCContentFilter object can be safely sent between threads
+// SAFETY: Retain/release are atomic and the getters are property loads, so a
+// handle can be moved, shared and dropped on any thread. The borrow checker
+// does not serialize writes, though: `Clone` shares the object, so
+// `set_include_menu_bar` on clones held by different threads can race. The type
+// docs rule that out; `SendFilter` hands a filter over without sharing it.
 unsafe impl Send for SCContentFilter {}
 unsafe impl Sync for SCContentFilter {}
 
+/// A content filter that no other handle shares, for moving to another thread
+///
+/// [`SCContentFilter::clone`] shares the underlying object, so a filter sent
+/// to a worker thread may still be changed through a clone left behind. A
+/// `SendFilter` is made by [`SCContentFilterBuilder::build_send`] from a
+/// freshly built filter and can't be cloned or read until it is unwrapped with
+/// [`into_inner`](Self::into_inner), so the receiving thread starts with the
+/// only handle.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::prelude::*;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let content = SCShareableContent::get()?;
+/// let filter = SCContentFilter::builder()
+///     .display(&content.displays()[0])
+///     .exclude_windows(&[])
+///     .build_send();
+///
+/// std::thread::spawn(move || {
+///     let mut filter = filter.into_inner();
+///     filter.set_include_menu_bar(true);
+///     // ... start a stream with `filter`
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SendFilter(SCContentFilter);
+
+impl SendFilter {
+    /// Take the filter out on the thread that uses it
+    #[must_use]
+    pub fn into_inner(self) -> SCContentFilter {
+        self.0
+    }
+}
+
 /// Builder for creating `SCContentFilter` instances
 ///
 /// # Examples
@@ -421,6 +525,8 @@ enum FilterType {
     DisplayExcluding {
         display: SCDisplay,
         windows: Vec<SCWindow>,
+        /// Windows resolved from IDs, kept apart so `exclude_windows` does not replace them
+        resolved_windows: Vec<SCWindow>,
     },
     DisplayIncluding {
         display: SCDisplay,
@@ -434,10 +540,96 @@ enum FilterType {
     DisplayExcludingApplications {
         display: SCDisplay,
         applications: Vec<SCRunningApplication>,
+        /// Applications resolved from bundle identifiers, kept apart so
+        /// `exclude_applications` does not replace them
+        resolved_applications: Vec<SCRunningApplication>,
         excepting_windows: Vec<SCWindow>,
     },
 }
 
+impl FilterType {
+    /// Pointers of every display, window and application the filter refers to
+    #[cfg(feature = "testing")]
+    fn object_ptrs(&self) -> Vec<*const c_void> {
+        let mut ptrs = Vec::new();
+        match self {
+            Self::None => {}
+            Self::Window(window) => ptrs.push(window.as_ptr()),
+            Self::DisplayExcluding {
+                display,
+                windows,
+                resolved_windows,
+            } => {
+                ptrs.push(display.as_ptr());
+                ptrs.extend(windows.iter().chain(resolved_windows).map(SCWindow::as_ptr));
+            }
+            Self::DisplayIncluding { display, windows } => {
+                ptrs.push(display.as_ptr());
+                ptrs.extend(windows.iter().map(SCWindow::as_ptr));
+            }
+            Self::DisplayIncludingApplications {
+                display,
+                applications,
+                excepting_windows,
+            } => {
+                ptrs.push(display.as_ptr());
+                ptrs.extend(applications.iter().map(SCRunningApplication::as_ptr));
+                ptrs.extend(excepting_windows.iter().map(SCWindow::as_ptr));
+            }
+            Self::DisplayExcludingApplications {
+                display,
+                applications,
+                resolved_applications,
+                excepting_windows,
+            } => {
+                ptrs.push(display.as_ptr());
+                ptrs.extend(
+                    applications
+                        .iter()
+                        .chain(resolved_applications)
+                        .map(SCRunningApplication::as_ptr),
+                );
+                ptrs.extend(excepting_windows.iter().map(SCWindow::as_ptr));
+            }
+        }
+        ptrs
+    }
+}
+
+/// Look up the `SCWindow`s in `content` for the given window IDs
+fn resolve_window_ids(content: &SCShareableContent, ids: &[u32]) -> Vec<SCWindow> {
+    content
+        .windows()
+        .into_iter()
+        .filter(|window| ids.contains(&window.window_id()))
+        .collect()
+}
+
+/// Look up the running applications in `content` with the given bundle identifiers
+fn resolve_bundle_ids(
+    content: &SCShareableContent,
+    bundle_ids: &[&str],
+) -> Vec<SCRunningApplication> {
+    content
+        .applications()
+        .into_iter()
+        .filter(|app| bundle_ids.contains(&app.bundle_identifier().as_str()))
+        .collect()
+}
+
+/// Look up the windows in `content` owned by applications with the given bundle identifiers
+fn resolve_bundle_id_windows(content: &SCShareableContent, bundle_ids: &[&str]) -> Vec<SCWindow> {
+    content
+        .windows()
+        .into_iter()
+        .filter(|window| {
+            window
+                .owning_application()
+                .is_some_and(|app| bundle_ids.contains(&app.bundle_identifier().as_str()))
+        })
+        .collect()
+}
+
 impl SCContentFilterBuilder {
     fn new() -> Self {
         Self {
@@ -453,6 +645,7 @@ impl SCContentFilterBuilder {
         self.filter_type = FilterType::DisplayExcluding {
             display: display.clone(),
             windows: Vec::new(),
+            resolved_windows: Vec::new(),
         };
         self
     }
@@ -477,6 +670,55 @@ impl SCContentFilterBuilder {
         self
     }
 
+    /// Exclude windows from the display capture by window ID
+    ///
+    /// `SCWindow` values from an older [`SCShareableContent`] snapshot are not
+    /// the same objects as the ones in a refreshed snapshot, so an exclusion
+    /// list built from references goes stale. Window IDs stay the same for the
+    /// lifetime of a window, so storing IDs and excluding by ID keeps the list
+    /// valid across refreshes.
+    ///
+    /// The IDs are resolved against `content`, which should be a current
+    /// snapshot; IDs of windows it does not contain, such as windows that have
+    /// since closed, are skipped. Repeated calls add to the exclusions, and
+    /// they can be combined with [`exclude_windows`](Self::exclude_windows).
+    /// Has no effect unless `.display()` was called first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let hidden: Vec<u32> = content
+    ///     .windows()
+    ///     .iter()
+    ///     .filter(|w| w.title().is_some_and(|t| t.contains("Secret")))
+    ///     .map(SCWindow::window_id)
+    ///     .collect();
+    ///
+    /// // Later, against refreshed content:
+    /// let content = SCShareableContent::get()?;
+    /// let filter = SCContentFilter::builder()
+    ///     .display(&content.displays()[0])
+    ///     .exclude_window_ids(&content, &hidden)
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn exclude_window_ids(mut self, content: &SCShareableContent, ids: &[u32]) -> Self {
+        if let FilterType::DisplayExcluding {
+            ref mut resolved_windows,
+            ..
+        } = self.filter_type
+        {
+            resolved_windows.extend(resolve_window_ids(content, ids));
+        }
+        self
+    }
+
     /// Include only specific windows in the display capture
     #[must_use]
     pub fn include_windows(mut self, windows: &[&SCWindow]) -> Self {
@@ -489,6 +731,60 @@ impl SCContentFilterBuilder {
         self
     }
 
+    /// Capture only the desktop picture (wallpaper) of the display
+    ///
+    /// When `true`, the display filter is built with an empty included-windows
+    /// list, so no application windows, Dock or menu bar are composited into
+    /// the frame. This differs from `.exclude_windows(&all_windows)`, which
+    /// still renders the menu bar and any windows created after the filter
+    /// was built.
+    ///
+    /// Passing `false` undoes an earlier `.desktop_only(true)`, turning the
+    /// filter back into a display capture with no exclusions; any other
+    /// filter, such as one from `.include_windows()`, is left unchanged.
+    /// Has no effect unless `.display()` was called first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    ///
+    /// let wallpaper = SCContentFilter::builder()
+    ///     .display(display)
+    ///     .desktop_only(true)
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn desktop_only(mut self, desktop_only: bool) -> Self {
+        self.filter_type = match self.filter_type {
+            FilterType::DisplayExcluding { display, .. }
+            | FilterType::DisplayIncluding { display, .. }
+                if desktop_only =>
+            {
+                FilterType::DisplayIncluding {
+                    display,
+                    windows: Vec::new(),
+                }
+            }
+            // Only an empty included-windows list is a desktop-only capture
+            FilterType::DisplayIncluding { display, windows } if windows.is_empty() => {
+                FilterType::DisplayExcluding {
+                    display,
+                    windows: Vec::new(),
+                    resolved_windows: Vec::new(),
+                }
+            }
+            other => other,
+        };
+        self
+    }
+
     /// Include specific applications and optionally except certain windows
     #[must_use]
     pub fn include_applications(
@@ -512,25 +808,119 @@ impl SCContentFilterBuilder {
     ///
     /// Captures everything on the display except the specified applications.
     /// Windows in `excepting_windows` will still be captured even if their
-    /// owning application is excluded.
+    /// owning application is excluded. Can be combined with
+    /// [`exclude_app_bundle_ids`](Self::exclude_app_bundle_ids).
     #[must_use]
     pub fn exclude_applications(
         mut self,
         applications: &[&SCRunningApplication],
         excepting_windows: &[&SCWindow],
     ) -> Self {
-        if let FilterType::DisplayExcluding { display, .. }
-        | FilterType::DisplayIncluding { display, .. } = self.filter_type
-        {
-            self.filter_type = FilterType::DisplayExcludingApplications {
-                display,
-                applications: applications.iter().map(|a| (*a).clone()).collect(),
-                excepting_windows: excepting_windows.iter().map(|w| (*w).clone()).collect(),
-            };
+        let applications = applications.iter().map(|a| (*a).clone()).collect();
+        let excepting_windows = excepting_windows.iter().map(|w| (*w).clone()).collect();
+        match self.filter_type {
+            FilterType::DisplayExcluding { display, .. }
+            | FilterType::DisplayIncluding { display, .. } => {
+                self.filter_type = FilterType::DisplayExcludingApplications {
+                    display,
+                    applications,
+                    resolved_applications: Vec::new(),
+                    excepting_windows,
+                };
+            }
+            FilterType::DisplayExcludingApplications {
+                applications: ref mut excluded,
+                excepting_windows: ref mut excepted,
+                ..
+            } => {
+                *excluded = applications;
+                *excepted = excepting_windows;
+            }
+            _ => {}
         }
         self
     }
 
+    /// Exclude applications from the display capture by bundle identifier
+    ///
+    /// Unlike [`exclude_applications`](Self::exclude_applications), this needs
+    /// no [`SCRunningApplication`] values, and bundle identifiers stay valid
+    /// across content refreshes and application relaunches. The identifiers
+    /// are resolved against `content`, which should be a current snapshot;
+    /// identifiers of applications that are not running are skipped. Repeated
+    /// calls add to the exclusions.
+    ///
+    /// A filter cannot exclude applications and individual windows at the
+    /// same time. If windows were already excluded, the current windows of
+    /// the applications are excluded along with them instead, so windows the
+    /// applications open later are captured.
+    ///
+    /// Has no effect unless `.display()` was called first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let display = &content.displays()[0];
+    ///
+    /// // Hide the Dock and notifications
+    /// let filter = SCContentFilter::builder()
+    ///     .display(display)
+    ///     .exclude_app_bundle_ids(&content, &["com.apple.dock", "com.apple.notificationcenterui"])
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn exclude_app_bundle_ids(
+        mut self,
+        content: &SCShareableContent,
+        bundle_ids: &[&str],
+    ) -> Self {
+        self.filter_type = match self.filter_type {
+            FilterType::DisplayExcluding {
+                display,
+                windows,
+                mut resolved_windows,
+            } if !windows.is_empty() || !resolved_windows.is_empty() => {
+                resolved_windows.extend(resolve_bundle_id_windows(content, bundle_ids));
+                FilterType::DisplayExcluding {
+                    display,
+                    windows,
+                    resolved_windows,
+                }
+            }
+            FilterType::DisplayExcluding { display, .. }
+            | FilterType::DisplayIncluding { display, .. } => {
+                FilterType::DisplayExcludingApplications {
+                    display,
+                    applications: Vec::new(),
+                    resolved_applications: resolve_bundle_ids(content, bundle_ids),
+                    excepting_windows: Vec::new(),
+                }
+            }
+            FilterType::DisplayExcludingApplications {
+                display,
+                applications,
+                mut resolved_applications,
+                excepting_windows,
+            } => {
+                resolved_applications.extend(resolve_bundle_ids(content, bundle_ids));
+                FilterType::DisplayExcludingApplications {
+                    display,
+                    applications,
+                    resolved_applications,
+                    excepting_windows,
+                }
+            }
+            other => other,
+        };
+        self
+    }
+
     /// Set the content rectangle (macOS 14.2+)
     #[cfg(feature = "macos_14_2")]
     #[must_use]
@@ -539,21 +929,47 @@ impl SCContentFilterBuilder {
         self
     }
 
+    /// Build the content filter for use on another thread
+    ///
+    /// See [`SendFilter`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`build`](Self::build).
+    #[must_use]
+    pub fn build_send(self) -> SendFilter {
+        SendFilter(self.build())
+    }
+
     /// Build the content filter
     ///
     /// # Panics
     ///
     /// Panics if no filter type was set. Call `.display()` or `.window()` before `.build()`.
+    ///
+    /// With the `testing` feature, also panics if any display, window or
+    /// application comes from mock content, which cannot be captured.
     #[must_use]
     #[allow(clippy::too_many_lines)]
     pub fn build(self) -> SCContentFilter {
+        #[cfg(feature = "testing")]
+        for ptr in self.filter_type.object_ptrs() {
+            if let Err(err) = crate::testing::reject_mock(ptr, "SCContentFilterBuilder::build") {
+                panic!("{err}");
+            }
+        }
         let filter = match self.filter_type {
             FilterType::Window(window) => unsafe {
                 let ptr =
                     ffi::sc_content_filter_create_with_desktop_independent_window(window.as_ptr());
                 SCContentFilter(ptr)
             },
-            FilterType::DisplayExcluding { display, windows } => {
+            FilterType::DisplayExcluding {
+                display,
+                mut windows,
+                resolved_windows,
+            } => {
+                windows.extend(resolved_windows);
                 let window_refs: Vec<&SCWindow> = windows.iter().collect();
                 unsafe {
                     let window_ptrs: Vec<*const c_void> =
@@ -626,9 +1042,11 @@ impl SCContentFilterBuilder {
             }
             FilterType::DisplayExcludingApplications {
                 display,
-                applications,
+                mut applications,
+                resolved_applications,
                 excepting_windows,
             } => {
+                applications.extend(resolved_applications);
                 let app_refs: Vec<&SCRunningApplication> = applications.iter().collect();
                 let window_refs: Vec<&SCWindow> = excepting_windows.iter().collect();
                 unsafe {
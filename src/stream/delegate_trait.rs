@@ -6,6 +6,7 @@
 //! to create a stream with a delegate that receives error callbacks.
 
 use crate::error::SCError;
+use crate::stream::configuration::PixelFormat;
 
 /// Trait for handling stream lifecycle events
 ///
@@ -88,6 +89,44 @@ pub trait SCStreamDelegateTrait: Send {
     /// This callback occurs for all content filter types.
     fn stream_did_become_inactive(&self) {}
 
+    /// Called before the system sleeps when auto-pause is enabled
+    ///
+    /// `paused` is `true` if capture was stopped successfully.
+    /// See [`SCStream::set_auto_pause_on_sleep`](crate::stream::SCStream::set_auto_pause_on_sleep).
+    fn stream_will_sleep(&self, _paused: bool) {}
+
+    /// Called after the system wakes when auto-pause is enabled
+    ///
+    /// Only called if capture was paused for sleep. `resumed` is `false` if it
+    /// could not be restarted, for example because the captured display or
+    /// window is gone.
+    fn stream_did_wake(&self, _resumed: bool) {}
+
+    /// Called after a stream with auto-restart enabled recovered from a fatal error
+    ///
+    /// `error` is the error that stopped the previous capture session. If the
+    /// restart fails, [`did_stop_with_error`](Self::did_stop_with_error) and
+    /// [`stream_did_stop`](Self::stream_did_stop) are called instead.
+    /// See [`SCStream::set_auto_restart`](crate::stream::SCStream::set_auto_restart).
+    fn did_restart(&self, _error: SCError) {}
+
+    /// Called when an output handler on this stream panics
+    ///
+    /// `message` is the panic payload when it is a string. What happens to the
+    /// handler afterwards depends on the stream's
+    /// [`PanicPolicy`](crate::stream::PanicPolicy).
+    fn output_handler_did_panic(&self, _message: String) {}
+
+    /// Called when the first frame uses a different pixel format than the
+    /// configuration asked for
+    ///
+    /// The system substitutes a format it can produce instead of failing, for
+    /// example BGRA when 10-bit [`l10r`](PixelFormat::l10r) is requested on an
+    /// SDR display. The check runs once per configuration, on the first frame
+    /// with pixels, and is not called when the formats match or the delivered
+    /// format has no [`PixelFormat`] variant.
+    fn did_negotiate_format(&self, _requested: PixelFormat, _actual: PixelFormat) {}
+
     /// Called when stream stops with an error
     fn did_stop_with_error(&self, _error: SCError) {}
 
@@ -191,6 +230,11 @@ pub struct StreamCallbacks {
     on_inactive: Option<Box<dyn Fn() + Send + 'static>>,
     on_video_effect_start: Option<Box<dyn Fn() + Send + 'static>>,
     on_video_effect_stop: Option<Box<dyn Fn() + Send + 'static>>,
+    on_sleep: Option<Box<dyn Fn(bool) + Send + 'static>>,
+    on_wake: Option<Box<dyn Fn(bool) + Send + 'static>>,
+    on_restart: Option<Box<dyn Fn(SCError) + Send + 'static>>,
+    on_handler_panic: Option<Box<dyn Fn(String) + Send + 'static>>,
+    on_format_negotiated: Option<Box<dyn Fn(PixelFormat, PixelFormat) + Send + 'static>>,
 }
 
 impl StreamCallbacks {
@@ -204,6 +248,11 @@ impl StreamCallbacks {
             on_inactive: None,
             on_video_effect_start: None,
             on_video_effect_stop: None,
+            on_sleep: None,
+            on_wake: None,
+            on_restart: None,
+            on_handler_panic: None,
+            on_format_negotiated: None,
         }
     }
 
@@ -266,6 +315,66 @@ impl StreamCallbacks {
         self.on_video_effect_stop = Some(Box::new(f));
         self
     }
+
+    /// Set the callback for when the system is about to sleep
+    ///
+    /// Only fires when auto-pause on sleep is enabled on the stream.
+    #[must_use]
+    pub fn on_sleep<F>(mut self, f: F) -> Self
+    where
+        F: Fn(bool) + Send + 'static,
+    {
+        self.on_sleep = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when the system wakes from sleep
+    ///
+    /// Only fires when auto-pause on sleep is enabled on the stream and
+    /// capture was paused for sleep.
+    #[must_use]
+    pub fn on_wake<F>(mut self, f: F) -> Self
+    where
+        F: Fn(bool) + Send + 'static,
+    {
+        self.on_wake = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when the stream restarts after a fatal error
+    ///
+    /// Only fires when auto-restart is enabled on the stream.
+    #[must_use]
+    pub fn on_restart<F>(mut self, f: F) -> Self
+    where
+        F: Fn(SCError) + Send + 'static,
+    {
+        self.on_restart = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when an output handler panics
+    #[must_use]
+    pub fn on_handler_panic<F>(mut self, f: F) -> Self
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        self.on_handler_panic = Some(Box::new(f));
+        self
+    }
+
+    /// Set the callback for when the delivered pixel format differs from the
+    /// requested one
+    ///
+    /// Receives the requested and the actual format.
+    #[must_use]
+    pub fn on_format_negotiated<F>(mut self, f: F) -> Self
+    where
+        F: Fn(PixelFormat, PixelFormat) + Send + 'static,
+    {
+        self.on_format_negotiated = Some(Box::new(f));
+        self
+    }
 }
 
 impl Default for StreamCallbacks {
@@ -310,4 +419,34 @@ impl SCStreamDelegateTrait for StreamCallbacks {
             f();
         }
     }
+
+    fn stream_will_sleep(&self, paused: bool) {
+        if let Some(ref f) = self.on_sleep {
+            f(paused);
+        }
+    }
+
+    fn stream_did_wake(&self, resumed: bool) {
+        if let Some(ref f) = self.on_wake {
+            f(resumed);
+        }
+    }
+
+    fn did_restart(&self, error: SCError) {
+        if let Some(ref f) = self.on_restart {
+            f(error);
+        }
+    }
+
+    fn output_handler_did_panic(&self, message: String) {
+        if let Some(ref f) = self.on_handler_panic {
+            f(message);
+        }
+    }
+
+    fn did_negotiate_format(&self, requested: PixelFormat, actual: PixelFormat) {
+        if let Some(ref f) = self.on_format_negotiated {
+            f(requested, actual);
+        }
+    }
 }
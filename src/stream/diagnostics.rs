@@ -0,0 +1,404 @@
+//! Stream diagnostics for bug reports
+//!
+//! [`SCStream::diagnostics`](crate::stream::SCStream::diagnostics) returns a
+//! [`StreamDiagnostics`] snapshot with everything needed to triage a
+//! "capture not working" report: how the stream is configured, what it is
+//! capturing, and whether frames are actually arriving.
+//!
+//! With the `serde` feature enabled, [`StreamDiagnostics`] implements
+//! `Serialize` so it can be attached to a report as JSON.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+//! # let config = SCStreamConfiguration::default();
+//! let mut stream = SCStream::new(&filter, &config);
+//! stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+//! stream.start_capture()?;
+//! std::thread::sleep(std::time::Duration::from_secs(2));
+//!
+//! println!("{}", stream.diagnostics());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::cm::{AudioFormat, SCFrameStatus};
+use crate::error::SCError;
+use crate::stream::configuration::{PixelFormat, SCStreamConfiguration};
+use crate::stream::content_filter::SCContentFilter;
+use crate::utils::timer::{spawn_timer, Timer};
+
+/// Number of recent frame arrivals used to estimate the frame rate
+const FPS_WINDOW: usize = 60;
+
+/// Refresh rate assumed when the configuration does not limit the frame rate
+const ASSUMED_REFRESH_RATE: u32 = 60;
+
+/// Snapshot of a stream's configuration and runtime state
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StreamDiagnostics {
+    /// Summary of the stream configuration (size, pixel format, frame rate, ...)
+    pub configuration: String,
+    /// Content filter style, e.g. `"Display"` or `"Window"` (macOS 14.0+)
+    pub filter_style: Option<String>,
+    /// Screen frames handed to at least one output handler
+    pub frames_delivered: u64,
+    /// Screen frames discarded because no output handler was registered
+    pub frames_dropped: u64,
+    /// Estimated screen frames the system skipped because output handlers
+    /// fell behind; see [`SCStream::dropped_due_to_backpressure`](crate::stream::SCStream::dropped_due_to_backpressure)
+    pub dropped_due_to_backpressure: u64,
+    /// Frame rate measured over the most recent delivered frames
+    pub measured_fps: f64,
+    /// Status attached to the most recent screen frame
+    pub last_frame_status: Option<SCFrameStatus>,
+    /// Whether `start_capture` succeeded and `stop_capture` has not been called since
+    pub is_capturing: bool,
+}
+
+impl fmt::Display for StreamDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "configuration: {}", self.configuration)?;
+        writeln!(
+            f,
+            "filter style: {}",
+            self.filter_style.as_deref().unwrap_or("unknown")
+        )?;
+        writeln!(f, "capturing: {}", self.is_capturing)?;
+        writeln!(
+            f,
+            "frames: {} delivered, {} dropped, {} lost to backpressure",
+            self.frames_delivered, self.frames_dropped, self.dropped_due_to_backpressure
+        )?;
+        writeln!(f, "measured fps: {:.1}", self.measured_fps)?;
+        match self.last_frame_status {
+            Some(status) => write!(f, "last frame status: {status}"),
+            None => write!(f, "last frame status: none"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct StatsState {
+    configuration: String,
+    filter_style: Option<String>,
+    frames_delivered: u64,
+    frames_dropped: u64,
+    recent_frames: VecDeque<Instant>,
+    last_frame_status: Option<SCFrameStatus>,
+    is_capturing: bool,
+    output_dimensions: Option<(u32, u32)>,
+    dropped_due_to_backpressure: u64,
+    /// Expected seconds between frames, from the configured frame rate
+    frame_interval: f64,
+    /// Presentation time of the last complete frame, in seconds
+    last_complete_pts: Option<f64>,
+    /// Whether the handlers for the last frame took longer than a frame interval
+    handlers_overran: bool,
+    /// Whether a complete frame has arrived since capture started
+    first_frame_seen: bool,
+    /// Pixel format the configuration asks for, until a frame's format was
+    /// compared against it
+    unchecked_pixel_format: Option<PixelFormat>,
+    /// Format of the first system audio buffer
+    audio_format: Option<AudioFormat>,
+}
+
+/// Per-stream counters shared between the stream handle and the sample callback
+#[derive(Default)]
+pub(crate) struct StreamStats {
+    state: Mutex<StatsState>,
+    /// Signalled when the first complete frame arrives or capture stops
+    first_frame: Condvar,
+    /// Stats callback thread; not joined on drop, since the last stream
+    /// handle may be dropped from inside the callback
+    timer: Mutex<Option<Timer>>,
+}
+
+impl StreamStats {
+    pub(crate) fn new(filter: &SCContentFilter, configuration: &SCStreamConfiguration) -> Self {
+        let stats = Self::default();
+        stats.set_configuration(configuration);
+        stats.set_filter(filter);
+        stats
+    }
+
+    pub(crate) fn set_configuration(&self, configuration: &SCStreamConfiguration) {
+        let description = format!(
+            "{}x{} {} @ {} fps, queue depth {}, cursor {}, audio {}",
+            configuration.width(),
+            configuration.height(),
+            configuration.pixel_format(),
+            configuration.fps(),
+            configuration.queue_depth(),
+            configuration.shows_cursor(),
+            configuration.captures_audio(),
+        );
+        // A zero interval means "as fast as the display refreshes"
+        let frame_interval = match configuration.fps() {
+            0 => 1.0 / f64::from(ASSUMED_REFRESH_RATE),
+            fps => 1.0 / f64::from(fps),
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.configuration = description;
+            state.frame_interval = frame_interval;
+            state.last_complete_pts = None;
+            // The next frame reports the size produced by the new configuration
+            state.output_dimensions = None;
+            state.unchecked_pixel_format = Some(configuration.pixel_format());
+            state.audio_format = None;
+        }
+    }
+
+    pub(crate) fn set_filter(&self, filter: &SCContentFilter) {
+        #[cfg(feature = "macos_14_0")]
+        let style = Some(filter.style().to_string());
+        // Filter style queries require macOS 14.0
+        #[cfg(not(feature = "macos_14_0"))]
+        let style = {
+            let _ = filter;
+            None
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.filter_style = style;
+        }
+    }
+
+    pub(crate) fn set_capturing(&self, capturing: bool) {
+        if let Ok(mut state) = self.state.lock() {
+            state.is_capturing = capturing;
+            // The next start waits for a fresh frame; a stopped stream wakes waiters
+            if !capturing {
+                state.first_frame_seen = false;
+                self.first_frame.notify_all();
+            }
+        }
+    }
+
+    pub(crate) fn is_capturing(&self) -> bool {
+        self.state.lock().is_ok_and(|state| state.is_capturing)
+    }
+
+    /// Block until a complete frame has arrived since capture started
+    pub(crate) fn wait_for_first_frame(&self, timeout: Duration) -> Result<(), SCError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if state.first_frame_seen {
+                return Ok(());
+            }
+            if !state.is_capturing {
+                return Err(SCError::stream_error("Stream is not capturing"));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(SCError::Timeout(format!(
+                    "No complete frame within {timeout:?}"
+                )));
+            }
+            state = self
+                .first_frame
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    fn note_frame(&self, state: &mut StatsState, status: Option<SCFrameStatus>) {
+        // Frames still in flight after a stop must not satisfy the next start
+        if !state.is_capturing {
+            return;
+        }
+        if status == Some(SCFrameStatus::Complete) && !state.first_frame_seen {
+            state.first_frame_seen = true;
+            self.first_frame.notify_all();
+        }
+    }
+
+    /// Count a delivered frame; `pts` is its presentation time in seconds
+    ///
+    /// A complete frame that arrives more than one interval after the previous
+    /// one, right after the handlers overran, means the system ran out of
+    /// buffers and skipped the frames in between. Gaps without an overrun are
+    /// left alone, since the system also stops sending frames while the screen
+    /// is static.
+    pub(crate) fn record_delivered(&self, status: Option<SCFrameStatus>, pts: Option<f64>) {
+        if let Ok(mut state) = self.state.lock() {
+            if status == Some(SCFrameStatus::Complete) {
+                if let (Some(pts), Some(previous)) = (pts, state.last_complete_pts) {
+                    if state.handlers_overran && state.frame_interval > 0.0 {
+                        let slots = ((pts - previous) / state.frame_interval).round();
+                        if slots > 1.0 {
+                            // Bounded by the gap between two frames, so the cast is exact
+                            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                            let skipped = (slots - 1.0) as u64;
+                            state.dropped_due_to_backpressure += skipped;
+                        }
+                    }
+                }
+                if pts.is_some() {
+                    state.last_complete_pts = pts;
+                }
+            }
+            self.note_frame(&mut state, status);
+            state.frames_delivered += 1;
+            state.last_frame_status = status;
+            if state.recent_frames.len() == FPS_WINDOW {
+                state.recent_frames.pop_front();
+            }
+            state.recent_frames.push_back(Instant::now());
+        }
+    }
+
+    /// Record how long the output handlers took for the last frame
+    pub(crate) fn record_handler_time(&self, elapsed: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            state.handlers_overran = elapsed.as_secs_f64() > state.frame_interval;
+        }
+    }
+
+    pub(crate) fn dropped_due_to_backpressure(&self) -> u64 {
+        self.state
+            .lock()
+            .map_or(0, |state| state.dropped_due_to_backpressure)
+    }
+
+    pub(crate) fn output_dimensions(&self) -> Option<(u32, u32)> {
+        self.state.lock().ok()?.output_dimensions
+    }
+
+    pub(crate) fn set_output_dimensions(&self, dimensions: (u32, u32)) {
+        if let Ok(mut state) = self.state.lock() {
+            state.output_dimensions = Some(dimensions);
+        }
+    }
+
+    pub(crate) fn audio_format(&self) -> Option<AudioFormat> {
+        self.state.lock().ok()?.audio_format
+    }
+
+    pub(crate) fn set_audio_format(&self, format: AudioFormat) {
+        if let Ok(mut state) = self.state.lock() {
+            state.audio_format = Some(format);
+        }
+    }
+
+    pub(crate) fn pixel_format_checked(&self) -> bool {
+        self.state
+            .lock()
+            .map_or(true, |state| state.unchecked_pixel_format.is_none())
+    }
+
+    /// Compare the first frame's pixel format with the requested one
+    ///
+    /// Returns the requested and actual format the first time they differ
+    /// after a configuration change. Formats without a [`PixelFormat`]
+    /// variant are not reported.
+    pub(crate) fn check_pixel_format(&self, actual: u32) -> Option<(PixelFormat, PixelFormat)> {
+        let requested = self.state.lock().ok()?.unchecked_pixel_format.take()?;
+        PixelFormat::from_raw(actual)
+            .filter(|actual| *actual != requested)
+            .map(|actual| (requested, actual))
+    }
+
+    pub(crate) fn record_dropped(&self, status: Option<SCFrameStatus>) {
+        if let Ok(mut state) = self.state.lock() {
+            self.note_frame(&mut state, status);
+            state.frames_dropped += 1;
+            state.last_frame_status = status;
+        }
+    }
+
+    /// Report a snapshot to `callback` every `interval` on a background thread,
+    /// replacing any previous callback
+    pub(crate) fn start_timer<F>(
+        self: &Arc<Self>,
+        interval: Duration,
+        callback: F,
+    ) -> Result<(), SCError>
+    where
+        F: Fn(StreamDiagnostics) + Send + 'static,
+    {
+        if interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "Stats interval must be greater than zero",
+            ));
+        }
+        let stats = Arc::downgrade(self);
+        let handle = spawn_timer("screencapturekit-stats", interval, move |_| {
+            // Stop once every stream handle is gone
+            let Some(snapshot) = stats.upgrade().map(|stats| stats.snapshot()) else {
+                return false;
+            };
+            callback(snapshot);
+            true
+        })
+        .map_err(|e| SCError::internal_error(format!("Failed to spawn stats thread: {e}")))?;
+
+        let previous = self
+            .timer
+            .lock()
+            .ok()
+            .and_then(|mut timer| timer.replace(handle));
+        drop(previous);
+        Ok(())
+    }
+
+    pub(crate) fn stop_timer(&self) {
+        let previous = self.timer.lock().ok().and_then(|mut timer| timer.take());
+        drop(previous);
+    }
+
+    pub(crate) fn snapshot(&self) -> StreamDiagnostics {
+        let Ok(state) = self.state.lock() else {
+            return StreamDiagnostics {
+                configuration: String::new(),
+                filter_style: None,
+                frames_delivered: 0,
+                frames_dropped: 0,
+                dropped_due_to_backpressure: 0,
+                measured_fps: 0.0,
+                last_frame_status: None,
+                is_capturing: false,
+            };
+        };
+
+        let measured_fps = match (state.recent_frames.front(), state.recent_frames.back()) {
+            (Some(first), Some(last)) if state.recent_frames.len() > 1 => {
+                let elapsed = last.duration_since(*first).as_secs_f64();
+                // Window length is bounded by FPS_WINDOW, so the cast is exact
+                #[allow(clippy::cast_precision_loss)]
+                let intervals = (state.recent_frames.len() - 1) as f64;
+                if elapsed > 0.0 {
+                    intervals / elapsed
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        };
+
+        StreamDiagnostics {
+            configuration: state.configuration.clone(),
+            filter_style: state.filter_style.clone(),
+            frames_delivered: state.frames_delivered,
+            frames_dropped: state.frames_dropped,
+            dropped_due_to_backpressure: state.dropped_due_to_backpressure,
+            measured_fps,
+            last_frame_status: state.last_frame_status,
+            is_capturing: state.is_capturing,
+        }
+    }
+}
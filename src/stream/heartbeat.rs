@@ -0,0 +1,167 @@
+//! Repeating the last frame while the screen is idle
+//!
+//! `ScreenCaptureKit` only sends a new frame when something on screen
+//! changes, and there is no configuration option to make it send frames on a
+//! fixed schedule; the minimum frame interval only caps the rate. Recorders
+//! that expect one frame per interval see gaps instead. A heartbeat output
+//! re-delivers the last complete frame with an advanced timestamp whenever
+//! the system stays quiet for longer than the interval. See
+//! [`SCStream::add_output_handler_with_heartbeat`](crate::stream::SCStream::add_output_handler_with_heartbeat).
+
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use crate::cm::{CMSampleBuffer, CMSampleTimingInfo, CMTime, SCFrameStatus};
+use crate::stream::{
+    diagnostics::StreamStats,
+    output_trait::{deliver, SCStreamOutputTrait},
+    output_type::SCStreamOutputType,
+    sc_stream::deliver_outside_callback,
+};
+use crate::utils::timer::{spawn_timer_after, Timer};
+
+struct LastFrame {
+    sample: CMSampleBuffer,
+    arrived: Instant,
+    /// Repeats delivered since the frame arrived
+    repeats: u32,
+}
+
+struct HeartbeatState<H> {
+    handler: H,
+    last: Option<LastFrame>,
+}
+
+/// Output handler that remembers the last complete frame for the heartbeat
+/// thread
+///
+/// Repeats are delivered under the handler registry lock, like real frames,
+/// so a repeat is never delivered after, or at the same time as, a newer
+/// real frame.
+pub struct HeartbeatOutput<H> {
+    state: Arc<Mutex<HeartbeatState<H>>>,
+}
+
+impl<H: SCStreamOutputTrait + 'static> HeartbeatOutput<H> {
+    /// Wrap `handler`; no frames are repeated until a [`Repeater`] is spawned
+    pub fn new(handler: H) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(HeartbeatState {
+                handler,
+                last: None,
+            })),
+        }
+    }
+
+    /// Handle for starting the repeat thread once the output is registered
+    pub fn repeater(&self) -> Repeater<H> {
+        Repeater {
+            state: Arc::downgrade(&self.state),
+        }
+    }
+}
+
+/// Starts the thread that repeats the last frame of a [`HeartbeatOutput`]
+pub struct Repeater<H> {
+    state: Weak<Mutex<HeartbeatState<H>>>,
+}
+
+impl<H: SCStreamOutputTrait + 'static> Repeater<H> {
+    /// Repeat frames every `interval` for the output registered as handler `id`
+    ///
+    /// The timer checks for a due repeat [`POLLS_PER_INTERVAL`] times per
+    /// interval. It ends once the handler is removed, or disabled after a
+    /// panic, or `stats` goes away, and only repeats frames while the stream
+    /// is capturing. `interval` must not be zero.
+    pub fn spawn(
+        self,
+        id: usize,
+        interval: Duration,
+        stats: &Arc<StreamStats>,
+    ) -> std::io::Result<Timer> {
+        let stream_stats = Arc::downgrade(stats);
+        let poll = (interval / POLLS_PER_INTERVAL).max(Duration::from_millis(1));
+        spawn_timer_after("screencapturekit-heartbeat", poll, poll, move |_| {
+            repeat_if_due(&self.state, &stream_stats, id, interval)
+        })
+    }
+}
+
+/// How often per interval the heartbeat timer looks for a due repeat, which
+/// bounds how late a repeat is delivered to a fraction of the interval
+pub const POLLS_PER_INTERVAL: u32 = 4;
+
+impl<H: SCStreamOutputTrait> SCStreamOutputTrait for HeartbeatOutput<H> {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if of_type == SCStreamOutputType::Screen
+            && sample_buffer.frame_status() == Some(SCFrameStatus::Complete)
+        {
+            state.last = Some(LastFrame {
+                sample: sample_buffer.clone(),
+                arrived: Instant::now(),
+                repeats: 0,
+            });
+        }
+        deliver(&state.handler, sample_buffer, of_type);
+    }
+}
+
+/// Deliver the last frame again if a repeat is due; returns `false` once the
+/// handler or the stream is gone
+fn repeat_if_due<H: SCStreamOutputTrait>(
+    handler_state: &Weak<Mutex<HeartbeatState<H>>>,
+    stream_stats: &Weak<StreamStats>,
+    id: usize,
+    interval: Duration,
+) -> bool {
+    // Hold the strong references only for this tick
+    let (Some(shared), Some(stream)) = (handler_state.upgrade(), stream_stats.upgrade()) else {
+        return false;
+    };
+    // Repeats go through the same panic policy and statistics as real frames
+    deliver_outside_callback(id, &stream, || {
+        let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+        // A restarted stream waits for a fresh frame instead of repeating
+        // one from before the stop
+        if !stream.is_capturing() {
+            state.last = None;
+        }
+        let sample = state.last.as_mut().and_then(|last| {
+            let due = last.arrived + interval * (last.repeats + 1);
+            if Instant::now() < due {
+                return None;
+            }
+            last.repeats += 1;
+            retimed(&last.sample, interval, last.repeats)
+        })?;
+        let timing = (
+            sample.frame_status(),
+            sample.presentation_timestamp().as_seconds(),
+        );
+        deliver(&state.handler, sample, SCStreamOutputType::Screen);
+        drop(state);
+        Some(timing)
+    })
+}
+
+/// A copy of `sample` presented `repeats` intervals after the original
+fn retimed(sample: &CMSampleBuffer, interval: Duration, repeats: u32) -> Option<CMSampleBuffer> {
+    let original = sample.presentation_timestamp();
+    if !original.is_valid() || original.timescale <= 0 {
+        return None;
+    }
+    // Interval in the timescale of the original timestamp; rounding error
+    // stays below one tick per repeat
+    #[allow(clippy::cast_possible_truncation)]
+    let step = (interval.as_secs_f64() * f64::from(original.timescale)).round() as i64;
+    let timing = CMSampleTimingInfo {
+        duration: CMTime::new(step, original.timescale),
+        presentation_time_stamp: CMTime {
+            value: original.value + step * i64::from(repeats),
+            ..original
+        },
+        decode_time_stamp: CMTime::INVALID,
+    };
+    sample.create_copy_with_new_timing(&[timing]).ok()
+}
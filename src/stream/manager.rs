@@ -0,0 +1,278 @@
+//! Coordinated lifecycle for multiple `SCStream`s
+//!
+//! Apps that capture several windows or displays at once end up creating one
+//! [`SCStream`] per source, each with its own handler and start/stop calls.
+//! [`StreamManager`] keeps those streams together with the
+//! [`SCShareableContent`] they were built from, starts and stops them as a
+//! group, and stops them in reverse creation order when dropped.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::StreamManager;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut manager = StreamManager::new()?;
+//! let windows: Vec<SCWindow> = manager.content().windows().into_iter().take(2).collect();
+//!
+//! for window in &windows {
+//!     let filter = SCContentFilter::builder().window(window).build();
+//!     let config = SCStreamConfiguration::new().with_width(1280).with_height(720);
+//!     let id = window.window_id();
+//!     manager.add_stream(&filter, &config, move |_sample, _type| {
+//!         println!("Frame from window {id}");
+//!     })?;
+//! }
+//!
+//! manager.start_all()?;
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! manager.stop_all()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::error::SCError;
+use crate::shareable_content::SCShareableContent;
+use crate::stream::{
+    configuration::SCStreamConfiguration, content_filter::SCContentFilter,
+    output_trait::SCStreamOutputTrait, output_type::SCStreamOutputType, sc_stream::SCStream,
+};
+
+/// Identifier for a stream owned by a [`StreamManager`]
+///
+/// IDs are assigned in increasing order and are never reused by the same manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StreamId(usize);
+
+impl StreamId {
+    /// Get the raw numeric value of this ID
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StreamId({})", self.0)
+    }
+}
+
+struct ManagedStream {
+    id: StreamId,
+    stream: SCStream,
+    running: bool,
+}
+
+/// Owns a group of streams and manages their capture lifecycle together
+///
+/// Streams are started in the order they were added and stopped in reverse
+/// order, so a later stream that depends on an earlier one is always torn down
+/// first. Dropping the manager stops any streams that are still running.
+pub struct StreamManager {
+    content: SCShareableContent,
+    streams: Vec<ManagedStream>,
+    next_id: usize,
+}
+
+impl StreamManager {
+    /// Create a manager using the current shareable content
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shareable content cannot be retrieved.
+    pub fn new() -> Result<Self, SCError> {
+        SCShareableContent::get().map(Self::with_content)
+    }
+
+    /// Create a manager from shareable content that was already fetched
+    #[must_use]
+    pub const fn with_content(content: SCShareableContent) -> Self {
+        Self {
+            content,
+            streams: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Get the shareable content this manager was created with
+    #[must_use]
+    pub const fn content(&self) -> &SCShareableContent {
+        &self.content
+    }
+
+    /// Create a stream and register a screen output handler for it
+    ///
+    /// The handler only receives frames from this stream. The stream is not
+    /// started until [`start_all`](Self::start_all) is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output handler cannot be registered; the stream
+    /// is not added in that case.
+    pub fn add_stream(
+        &mut self,
+        filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        handler: impl SCStreamOutputTrait + 'static,
+    ) -> Result<StreamId, SCError> {
+        let mut stream = SCStream::new(filter, configuration);
+        if stream
+            .add_output_handler(handler, SCStreamOutputType::Screen)
+            .is_none()
+        {
+            return Err(SCError::stream_error("Failed to add Screen output"));
+        }
+        Ok(self.insert(stream))
+    }
+
+    /// Take ownership of an already configured stream
+    ///
+    /// Use this when the stream needs a delegate or additional output types.
+    pub fn add_existing_stream(&mut self, stream: SCStream) -> StreamId {
+        self.insert(stream)
+    }
+
+    fn insert(&mut self, stream: SCStream) -> StreamId {
+        let id = StreamId(self.next_id);
+        self.next_id += 1;
+        self.streams.push(ManagedStream {
+            id,
+            stream,
+            running: false,
+        });
+        id
+    }
+
+    /// Get a stream by ID
+    #[must_use]
+    pub fn stream(&self, id: StreamId) -> Option<&SCStream> {
+        self.streams
+            .iter()
+            .find(|managed| managed.id == id)
+            .map(|managed| &managed.stream)
+    }
+
+    /// Get a mutable reference to a stream by ID
+    pub fn stream_mut(&mut self, id: StreamId) -> Option<&mut SCStream> {
+        self.streams
+            .iter_mut()
+            .find(|managed| managed.id == id)
+            .map(|managed| &mut managed.stream)
+    }
+
+    /// Remove a stream from the manager, stopping it first if it is running
+    ///
+    /// Returns the stream so it can continue to be used on its own.
+    pub fn remove_stream(&mut self, id: StreamId) -> Option<SCStream> {
+        let pos = self.streams.iter().position(|managed| managed.id == id)?;
+        let mut managed = self.streams.remove(pos);
+        if managed.running {
+            let _ = managed.stream.stop_capture();
+            managed.running = false;
+        }
+        Some(managed.stream)
+    }
+
+    /// IDs of all managed streams, in the order they were added
+    #[must_use]
+    pub fn ids(&self) -> Vec<StreamId> {
+        self.streams.iter().map(|managed| managed.id).collect()
+    }
+
+    /// Whether the stream with the given ID has been started by this manager
+    #[must_use]
+    pub fn is_running(&self, id: StreamId) -> bool {
+        self.streams
+            .iter()
+            .any(|managed| managed.id == id && managed.running)
+    }
+
+    /// Number of managed streams
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// Whether the manager has no streams
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+
+    /// Start every stream that is not already running, in the order they were added
+    ///
+    /// If a stream fails to start, the streams started by this call are stopped
+    /// again so the group is never left partially running.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the first stream that failed to start.
+    pub fn start_all(&mut self) -> Result<(), SCError> {
+        let mut started = Vec::new();
+        for index in 0..self.streams.len() {
+            let managed = &mut self.streams[index];
+            if managed.running {
+                continue;
+            }
+            if let Err(error) = managed.stream.start_capture() {
+                for &rollback in started.iter().rev() {
+                    let managed: &mut ManagedStream = &mut self.streams[rollback];
+                    let _ = managed.stream.stop_capture();
+                    managed.running = false;
+                }
+                return Err(error);
+            }
+            managed.running = true;
+            started.push(index);
+        }
+        Ok(())
+    }
+
+    /// Stop every running stream, in reverse order of addition
+    ///
+    /// All streams are asked to stop even if one of them fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while stopping.
+    pub fn stop_all(&mut self) -> Result<(), SCError> {
+        let mut first_error = None;
+        for managed in self.streams.iter_mut().rev() {
+            if !managed.running {
+                continue;
+            }
+            match managed.stream.stop_capture() {
+                Ok(()) => managed.running = false,
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+impl Drop for StreamManager {
+    fn drop(&mut self) {
+        let _ = self.stop_all();
+        // Release streams in reverse order as well
+        while self.streams.pop().is_some() {}
+    }
+}
+
+impl fmt::Debug for StreamManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamManager")
+            .field("content", &self.content)
+            .field("streams", &self.ids())
+            .field(
+                "running",
+                &self.streams.iter().filter(|m| m.running).count(),
+            )
+            .finish_non_exhaustive()
+    }
+}
@@ -9,6 +9,9 @@
 //! - [`content_filter::SCContentFilter`] - Filter for selecting what to capture
 //! - [`output_trait::SCStreamOutputTrait`] - Trait for receiving captured frames
 //! - [`output_type::SCStreamOutputType`] - Type of output (screen, audio)
+//! - [`StreamManager`] - Start and stop several streams as a group
+//! - [`ReplayBuffer`] - Keep the last few seconds of frames and save them on demand
+//! - [`SyncedCapture`] - Receive each screen frame together with its audio
 //!
 //! ## Example
 //!
@@ -30,18 +33,30 @@
 //! # Ok::<(), screencapturekit::error::SCError>(())
 //! ```
 
+pub mod config_changes;
 pub mod configuration;
 pub mod content_filter;
 pub mod delegate_trait;
+pub mod diagnostics;
+mod heartbeat;
+pub mod manager;
 pub mod output_trait;
 pub mod output_type;
+pub mod replay;
 pub mod sc_stream;
+pub mod synced;
 
+pub use config_changes::ConfigChanges;
+pub use content_filter::SCStreamType;
 pub use delegate_trait::ErrorHandler;
 pub use delegate_trait::SCStreamDelegateTrait as SCStreamDelegate;
 pub use delegate_trait::StreamCallbacks;
+pub use diagnostics::StreamDiagnostics;
+pub use manager::{StreamId, StreamManager};
 pub use output_trait::SCStreamOutputTrait as SCStreamOutput;
-pub use sc_stream::SCStream;
+pub use replay::ReplayBuffer;
+pub use sc_stream::{PanicPolicy, SCStream};
+pub use synced::SyncedCapture;
 
 #[cfg(feature = "macos_14_0")]
-pub use content_filter::{SCShareableContentStyle, SCStreamType};
+pub use content_filter::SCShareableContentStyle;
@@ -3,6 +3,7 @@
 //! Defines the interface for receiving captured frames and audio buffers.
 
 use crate::cm::CMSampleBuffer;
+use crate::output::{CVPixelBufferIOSurface, IOSurface};
 
 use super::output_type::SCStreamOutputType;
 
@@ -10,6 +11,17 @@ use super::output_type::SCStreamOutputType;
 ///
 /// Implement this trait to receive callbacks when the stream captures frames or audio.
 ///
+/// # Ordering
+///
+/// Samples of one output type are delivered one at a time, in the order the
+/// system produced them, on that output's queue (or on the main thread with
+/// [`deliver_on_main_thread`](crate::stream::SCStream::deliver_on_main_thread)).
+/// Different output types use different queues, so there is no ordering
+/// between, say, a video frame and an audio buffer; use their presentation
+/// timestamps to line them up. Each sample carries a
+/// [`sequence_number`](CMSampleBuffer::sequence_number) to check the order
+/// after handing samples to other threads.
+///
 /// # Examples
 ///
 /// ## Using a struct
@@ -70,6 +82,89 @@ pub trait SCStreamOutputTrait: Send {
     /// - `sample_buffer`: The captured sample (video frame or audio buffer)
     /// - `of_type`: Type of output (Screen, Audio, or Microphone)
     fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType);
+
+    /// Whether samples should arrive through
+    /// [`did_output_surface`](Self::did_output_surface)
+    ///
+    /// Looking up the surface costs two Core Video calls per frame, so the
+    /// stream only does it for handlers that return `true` here. The default
+    /// is `false`.
+    fn wants_surface(&self) -> bool {
+        false
+    }
+
+    /// Called instead of [`did_output_sample_buffer`](Self::did_output_sample_buffer)
+    /// when the sample is backed by an `IOSurface`
+    ///
+    /// The stream extracts the surface up front, so zero-copy GPU consumers
+    /// don't have to go through `sample.image_buffer()?.io_surface()?` in
+    /// every handler. Only called for handlers whose
+    /// [`wants_surface`](Self::wants_surface) returns `true`. Samples without a
+    /// surface, such as audio and idle frames, still arrive through
+    /// `did_output_sample_buffer`.
+    ///
+    /// The default implementation drops the surface and forwards the sample
+    /// to `did_output_sample_buffer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use screencapturekit::cm::CMSampleBuffer;
+    /// use screencapturekit::output::IOSurface;
+    /// use screencapturekit::stream::{
+    ///     output_trait::SCStreamOutputTrait,
+    ///     output_type::SCStreamOutputType,
+    /// };
+    ///
+    /// struct GpuHandler;
+    ///
+    /// impl SCStreamOutputTrait for GpuHandler {
+    ///     fn did_output_sample_buffer(&self, _sample: CMSampleBuffer, _of_type: SCStreamOutputType) {}
+    ///
+    ///     fn wants_surface(&self) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn did_output_surface(
+    ///         &self,
+    ///         surface: IOSurface,
+    ///         _sample: CMSampleBuffer,
+    ///         _of_type: SCStreamOutputType,
+    ///     ) {
+    ///         println!("Frame surface: {}x{}", surface.width(), surface.height());
+    ///     }
+    /// }
+    /// ```
+    fn did_output_surface(
+        &self,
+        surface: IOSurface,
+        sample_buffer: CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        drop(surface);
+        self.did_output_sample_buffer(sample_buffer, of_type);
+    }
+}
+
+/// Hand `sample_buffer` to `handler`, through
+/// [`did_output_surface`](SCStreamOutputTrait::did_output_surface) if the
+/// handler wants surfaces and the sample is backed by an `IOSurface`
+pub(crate) fn deliver<H: SCStreamOutputTrait + ?Sized>(
+    handler: &H,
+    sample_buffer: CMSampleBuffer,
+    of_type: SCStreamOutputType,
+) {
+    let surface = if handler.wants_surface() {
+        sample_buffer
+            .image_buffer()
+            .and_then(|pixel_buffer| pixel_buffer.iosurface())
+    } else {
+        None
+    };
+    match surface {
+        Some(surface) => handler.did_output_surface(surface, sample_buffer, of_type),
+        None => handler.did_output_sample_buffer(sample_buffer, of_type),
+    }
 }
 
 /// Blanket implementation for closures
@@ -0,0 +1,282 @@
+//! In-memory replay buffer
+//!
+//! Game-capture style apps record continuously but only keep the last few
+//! seconds, writing them to disk when the user asks for it. [`ReplayBuffer`]
+//! attaches to a stream as a screen output handler, keeps frames covering a
+//! fixed duration in a ring buffer, and encodes the most recent part to an
+//! H.264 movie with [`save_last`](ReplayBuffer::save_last).
+//!
+//! Frames are copied out of the capture pool as they arrive, so buffering does
+//! not hold on to the stream's `IOSurface`s and capture keeps running at full
+//! rate. The copies are uncompressed: a 1920x1080 BGRA frame is about 8 MB, so
+//! 10 seconds at 30 fps needs roughly 2.4 GB. Lower the resolution, frame rate
+//! or duration accordingly, or capture in a YUV pixel format.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::ReplayBuffer;
+//! use std::time::Duration;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(1280)
+//!     .with_height(720)
+//!     .with_pixel_format(PixelFormat::YCbCr_420v);
+//! let mut stream = SCStream::new(&filter, &config);
+//!
+//! let replay = ReplayBuffer::new(Duration::from_secs(30));
+//! replay.attach(&mut stream);
+//! stream.start_capture()?;
+//!
+//! // ... later, when the user presses "save replay"
+//! replay.save_last(Duration::from_secs(10), "/tmp/replay.mp4")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::cm::CMSampleBuffer;
+use crate::error::SCError;
+use crate::stream::{
+    output_trait::SCStreamOutputTrait, output_type::SCStreamOutputType, sc_stream::SCStream,
+};
+use crate::utils::sync_completion::UnitCompletion;
+
+/// Swift-side helper that copies frames into a private pixel buffer pool
+struct FrameCopier(*const c_void);
+
+impl FrameCopier {
+    fn new() -> Self {
+        Self(unsafe { crate::ffi::sc_replay_copier_create() })
+    }
+
+    fn copy(&self, sample: &CMSampleBuffer) -> Option<CMSampleBuffer> {
+        let ptr =
+            unsafe { crate::ffi::sc_replay_copier_copy_sample_buffer(self.0, sample.as_ptr()) };
+        CMSampleBuffer::from_raw(ptr)
+    }
+}
+
+impl Drop for FrameCopier {
+    fn drop(&mut self) {
+        unsafe { crate::ffi::sc_replay_copier_release(self.0) };
+    }
+}
+
+// SAFETY: the Swift copier serializes access to its pool with a lock
+unsafe impl Send for FrameCopier {}
+unsafe impl Sync for FrameCopier {}
+
+struct BufferedFrame {
+    sample: CMSampleBuffer,
+    /// Presentation time in seconds
+    time: f64,
+    size: (usize, usize),
+}
+
+struct ReplayState {
+    duration: Duration,
+    frames: Mutex<VecDeque<BufferedFrame>>,
+    copier: FrameCopier,
+}
+
+/// Ring buffer holding the most recent frames of a stream
+///
+/// Cloning a `ReplayBuffer` is cheap and yields a handle to the same buffer,
+/// which is how the output handler and the rest of the app share it.
+#[derive(Clone)]
+pub struct ReplayBuffer {
+    state: Arc<ReplayState>,
+}
+
+impl ReplayBuffer {
+    /// Create a buffer that retains frames covering `duration`
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            state: Arc::new(ReplayState {
+                duration,
+                frames: Mutex::new(VecDeque::new()),
+                copier: FrameCopier::new(),
+            }),
+        }
+    }
+
+    /// Register this buffer as a screen output handler on `stream`
+    ///
+    /// Returns the handler ID, which can be passed to
+    /// [`SCStream::remove_output_handler`] to detach the buffer again.
+    pub fn attach(&self, stream: &mut SCStream) -> Option<usize> {
+        stream.add_output_handler(self.clone(), SCStreamOutputType::Screen)
+    }
+
+    /// The duration of frames this buffer retains
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.state.duration
+    }
+
+    /// Number of buffered frames
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.state
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no frames are buffered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.state
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    /// Time span between the oldest and newest buffered frame
+    #[must_use]
+    pub fn buffered_duration(&self) -> Duration {
+        let frames = self
+            .state
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match (frames.front(), frames.back()) {
+            (Some(first), Some(last)) => Duration::from_secs_f64((last.time - first.time).max(0.0)),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Drop all buffered frames
+    pub fn clear(&self) {
+        let drained: Vec<BufferedFrame> = self
+            .state
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect();
+        // Release the frames outside the lock
+        drop(drained);
+    }
+
+    /// Encode the most recent `duration` of buffered frames to a movie file
+    ///
+    /// The movie is H.264 in an MP4 container when `path` ends in `.mp4` and a
+    /// `.mov` movie otherwise. An existing file at `path` is replaced.
+    /// Capture continues while the file is written; frames arriving during the
+    /// save are buffered as usual but not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no frames are buffered, the path contains null
+    /// bytes, or encoding fails.
+    pub fn save_last(&self, duration: Duration, path: &str) -> Result<(), SCError> {
+        let c_path = std::ffi::CString::new(path)
+            .map_err(|_| SCError::internal_error("Path contains null bytes"))?;
+
+        let samples: Vec<CMSampleBuffer> = {
+            let frames = self
+                .state
+                .frames
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let Some(newest) = frames.back().map(|frame| frame.time) else {
+                return Err(SCError::stream_error("Replay buffer is empty"));
+            };
+            let cutoff = newest - duration.as_secs_f64();
+            frames
+                .iter()
+                .filter(|frame| frame.time >= cutoff)
+                .map(|frame| frame.sample.clone())
+                .collect()
+        };
+        let ptrs: Vec<*mut c_void> = samples.iter().map(CMSampleBuffer::as_ptr).collect();
+
+        let (completion, context) = UnitCompletion::new();
+        unsafe {
+            crate::ffi::sc_replay_write_movie(
+                ptrs.as_ptr(),
+                ptrs.len(),
+                c_path.as_ptr(),
+                context,
+                UnitCompletion::callback,
+            );
+        }
+        // The Swift side keeps its own references until the write finishes
+        drop(samples);
+        completion.wait().map_err(SCError::stream_error)
+    }
+
+    fn push(&self, sample: &CMSampleBuffer) {
+        // Frames without an image (idle or blank frames) are skipped
+        let Some(copy) = self.state.copier.copy(sample) else {
+            return;
+        };
+        let Some(time) = copy.presentation_timestamp().as_seconds() else {
+            return;
+        };
+        let size = copy
+            .image_buffer()
+            .map_or((0, 0), |image| (image.width(), image.height()));
+        let frame = BufferedFrame {
+            sample: copy,
+            time,
+            size,
+        };
+
+        let window = self.state.duration.as_secs_f64();
+        let mut evicted = Vec::new();
+        let mut frames = self
+            .state
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // The encoder needs a constant frame size, so a resize starts a new history
+        if frames.back().is_some_and(|last| last.size != frame.size) {
+            evicted.extend(frames.drain(..));
+        }
+        frames.push_back(frame);
+        while frames
+            .front()
+            .is_some_and(|first| time - first.time > window)
+        {
+            evicted.extend(frames.pop_front());
+        }
+        drop(frames);
+        // Release evicted frames outside the lock
+        drop(evicted);
+    }
+}
+
+impl SCStreamOutputTrait for ReplayBuffer {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        if of_type == SCStreamOutputType::Screen {
+            self.push(&sample_buffer);
+        }
+        // `sample_buffer` is dropped here, returning the surface to the capture pool
+    }
+}
+
+impl fmt::Debug for ReplayBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayBuffer")
+            .field("duration", &self.state.duration)
+            .field("frames", &self.len())
+            .field("buffered_duration", &self.buffered_duration())
+            .finish()
+    }
+}
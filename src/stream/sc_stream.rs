@@ -6,24 +6,84 @@
 use std::collections::HashMap;
 use std::ffi::{c_void, CStr};
 use std::fmt;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::{Duration, Instant};
 
 use crate::error::SCError;
+use crate::stream::config_changes::ConfigChanges;
 use crate::stream::delegate_trait::SCStreamDelegateTrait;
+use crate::stream::diagnostics::{StreamDiagnostics, StreamStats};
+use crate::stream::heartbeat::HeartbeatOutput;
 use crate::utils::sync_completion::UnitCompletion;
+use crate::utils::timer::Timer;
 use crate::{
+    cm::CMSampleBuffer,
     dispatch_queue::DispatchQueue,
     ffi,
+    output::IOSurface,
     stream::{
-        configuration::SCStreamConfiguration, content_filter::SCContentFilter,
-        output_trait::SCStreamOutputTrait, output_type::SCStreamOutputType,
+        configuration::{PixelFormat, SCStreamConfiguration},
+        content_filter::SCContentFilter,
+        output_trait::{deliver, SCStreamOutputTrait},
+        output_type::SCStreamOutputType,
     },
 };
 
+/// What to do when an output handler panics
+///
+/// Panics are always caught before they can unwind into the Swift bridge and
+/// logged to stderr. The policy decides what happens afterwards. The stream's
+/// delegate, if any, is notified through
+/// [`output_handler_did_panic`](crate::stream::delegate_trait::SCStreamDelegateTrait::output_handler_did_panic)
+/// unless the policy is [`Abort`](Self::Abort).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PanicPolicy {
+    /// Abort the process immediately
+    Abort,
+    /// Stop delivering samples to the handler that panicked (default)
+    #[default]
+    RemoveHandler,
+    /// Keep delivering samples to the handler
+    Ignore,
+}
+
 // Handler entry with reference count
 struct HandlerEntry {
     handler: Box<dyn SCStreamOutputTrait>,
+    /// Stream pointer and output type the handler was registered for
+    stream: usize,
+    of_type: SCStreamOutputType,
     ref_count: usize,
+    panic_policy: PanicPolicy,
+    /// Set once the handler has panicked under `PanicPolicy::RemoveHandler`
+    disabled: bool,
+    /// Timer repeating frames for a heartbeat handler
+    heartbeat: Option<Timer>,
+}
+
+impl Drop for HandlerEntry {
+    // Entries are only dropped outside the registry lock, which the
+    // heartbeat takes on every tick
+    fn drop(&mut self) {
+        if let Some(timer) = self.heartbeat.take() {
+            timer.stop();
+        }
+    }
+}
+
+/// Whether `stream` has a handler for `of_type` other than those in `except`
+///
+/// All handlers of one type share a single native output, so it is only added
+/// for the first handler and removed with the last.
+fn has_handlers_for(
+    handlers: &HashMap<usize, HandlerEntry>,
+    stream: usize,
+    of_type: SCStreamOutputType,
+    except: &[usize],
+) -> bool {
+    handlers.iter().any(|(id, entry)| {
+        entry.stream == stream && entry.of_type == of_type && !except.contains(id)
+    })
 }
 
 // Global registry for output handlers with reference counting
@@ -37,8 +97,76 @@ struct DelegateEntry {
 }
 static DELEGATE_REGISTRY: Mutex<Option<HashMap<usize, DelegateEntry>>> = Mutex::new(None);
 
-// C callback for stream errors that dispatches to registered delegate
-extern "C" fn delegate_error_callback(stream: *const c_void, error_code: i32, msg: *const i8) {
+// Global registry of per-stream statistics (keyed by stream pointer). The stream
+// handles own the stats; the registry only holds weak references for the callback.
+static STATS_REGISTRY: Mutex<Option<HashMap<usize, Weak<StreamStats>>>> = Mutex::new(None);
+
+fn stats_for_stream(stream: *const c_void) -> Option<Arc<StreamStats>> {
+    STATS_REGISTRY
+        .lock()
+        .ok()?
+        .as_ref()?
+        .get(&(stream as usize))?
+        .upgrade()
+}
+
+// A stream that stopped with an error is no longer capturing; this also wakes
+// callers blocked in `wait_for_first_frame`
+fn mark_stopped(stream: *const c_void) {
+    if let Some(stats) = stats_for_stream(stream) {
+        stats.set_capturing(false);
+    }
+}
+
+// Read the frame status without taking ownership of the sample buffer
+fn peek_frame_status(sample_buffer: *const c_void) -> Option<crate::cm::SCFrameStatus> {
+    let buffer = std::mem::ManuallyDrop::new(unsafe {
+        crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut())
+    });
+    buffer.frame_status()
+}
+
+// Read the presentation time in seconds without taking ownership of the sample buffer
+fn peek_presentation_seconds(sample_buffer: *const c_void) -> Option<f64> {
+    let buffer = std::mem::ManuallyDrop::new(unsafe {
+        crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut())
+    });
+    buffer.presentation_timestamp().as_seconds()
+}
+
+// Read the pixel buffer size without taking ownership of the sample buffer
+fn peek_frame_dimensions(sample_buffer: *const c_void) -> Option<(u32, u32)> {
+    let buffer = std::mem::ManuallyDrop::new(unsafe {
+        crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut())
+    });
+    let pixel_buffer = buffer.image_buffer()?;
+    Some((
+        u32::try_from(pixel_buffer.width()).ok()?,
+        u32::try_from(pixel_buffer.height()).ok()?,
+    ))
+}
+
+// Read the pixel format without taking ownership of the sample buffer
+fn peek_pixel_format(sample_buffer: *const c_void) -> Option<u32> {
+    let buffer = std::mem::ManuallyDrop::new(unsafe {
+        crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut())
+    });
+    Some(buffer.image_buffer()?.pixel_format())
+}
+
+// Read the audio format without taking ownership of the sample buffer
+fn peek_audio_format(sample_buffer: *const c_void) -> Option<crate::cm::AudioFormat> {
+    let buffer = std::mem::ManuallyDrop::new(unsafe {
+        crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut())
+    });
+    let asbd = buffer
+        .format_description()?
+        .audio_stream_basic_description()?;
+    Some(asbd.into())
+}
+
+// Build the error reported by the Swift bridge for a stopped stream
+fn stream_error(error_code: i32, msg: *const i8) -> (SCError, String) {
     let message = if msg.is_null() {
         "Unknown error".to_string()
     } else {
@@ -59,6 +187,13 @@ extern "C" fn delegate_error_callback(stream: *const c_void, error_code: i32, ms
     } else {
         SCError::StreamError(message.clone())
     };
+    (error, message)
+}
+
+// C callback for stream errors that dispatches to registered delegate
+extern "C" fn delegate_error_callback(stream: *const c_void, error_code: i32, msg: *const i8) {
+    let (error, message) = stream_error(error_code, msg);
+    mark_stopped(stream);
 
     // Look up delegate in registry and call it
     let stream_key = stream as usize;
@@ -76,53 +211,288 @@ extern "C" fn delegate_error_callback(stream: *const c_void, error_code: i32, ms
     eprintln!("SCStream error: {error}");
 }
 
+// C callback for automatic restarts that dispatches to registered delegate
+extern "C" fn restart_callback(stream: *const c_void, error_code: i32, msg: *const i8) {
+    let (error, _) = stream_error(error_code, msg);
+    let stream_key = stream as usize;
+    if let Ok(registry) = DELEGATE_REGISTRY.lock() {
+        if let Some(entry) = registry.as_ref().and_then(|d| d.get(&stream_key)) {
+            // A panicking delegate must not unwind into Swift
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                entry.delegate.did_restart(error);
+            }));
+        }
+    }
+}
+
+// C callback for sleep/wake events that dispatches to registered delegate
+extern "C" fn sleep_wake_callback(stream: *const c_void, event: i32, success: bool) {
+    let stream_key = stream as usize;
+    if let Ok(registry) = DELEGATE_REGISTRY.lock() {
+        if let Some(entry) = registry.as_ref().and_then(|d| d.get(&stream_key)) {
+            // A panicking delegate must not unwind into Swift
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match event {
+                0 => entry.delegate.stream_will_sleep(success),
+                1 => entry.delegate.stream_did_wake(success),
+                _ => {}
+            }));
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+// Notify the stream's delegate (if any) that the system picked another pixel format
+fn notify_format_negotiated(stream: *const c_void, requested: PixelFormat, actual: PixelFormat) {
+    let stream_key = stream as usize;
+    if let Ok(registry) = DELEGATE_REGISTRY.lock() {
+        if let Some(entry) = registry.as_ref().and_then(|d| d.get(&stream_key)) {
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                entry.delegate.did_negotiate_format(requested, actual);
+            }));
+        }
+    }
+}
+
+// Notify the stream's delegate (if any) that one of its handlers panicked
+fn notify_handler_panic(stream: *const c_void, message: &str) {
+    let stream_key = stream as usize;
+    if let Ok(registry) = DELEGATE_REGISTRY.lock() {
+        if let Some(entry) = registry.as_ref().and_then(|d| d.get(&stream_key)) {
+            // A panicking delegate must not unwind into Swift either
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                entry.delegate.output_handler_did_panic(message.to_string());
+            }));
+        }
+    }
+}
+
 // C callback that retrieves handler from registry
 extern "C" fn sample_handler(
-    _stream: *const c_void,
+    stream: *const c_void,
     sample_buffer: *const c_void,
     output_type: i32,
 ) {
-    // Mutex poisoning is unrecoverable in C callback context; unwrap is appropriate
-    let registry = HANDLER_REGISTRY.lock().unwrap();
-    if let Some(handlers) = registry.as_ref() {
-        if handlers.is_empty() {
-            // No handlers registered - release the buffer that Swift passed us
+    let output_type_enum = match output_type {
+        0 => SCStreamOutputType::Screen,
+        1 => SCStreamOutputType::Audio,
+        2 => SCStreamOutputType::Microphone,
+        _ => {
+            eprintln!("Unknown output type: {output_type}");
+            // Unknown type - release the buffer
             unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
             return;
         }
+    };
 
-        let output_type_enum = match output_type {
-            0 => SCStreamOutputType::Screen,
-            1 => SCStreamOutputType::Audio,
-            2 => SCStreamOutputType::Microphone,
-            _ => {
-                eprintln!("Unknown output type: {output_type}");
-                // Unknown type - release the buffer
-                unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
-                return;
+    // Only screen frames are counted in the stream diagnostics
+    let stats = if output_type_enum == SCStreamOutputType::Screen {
+        stats_for_stream(stream).map(|stats| {
+            (
+                stats,
+                peek_frame_status(sample_buffer),
+                peek_presentation_seconds(sample_buffer),
+            )
+        })
+    } else {
+        None
+    };
+    if let Some((stats, _, _)) = &stats {
+        // Idle frames carry no pixel buffer; keep looking until one does
+        if stats.output_dimensions().is_none() {
+            if let Some(dimensions) = peek_frame_dimensions(sample_buffer) {
+                stats.set_output_dimensions(dimensions);
             }
-        };
-
-        let handler_count = handlers.len();
+        }
+    }
+    let negotiated = stats.as_ref().and_then(|(stats, _, _)| {
+        if stats.pixel_format_checked() {
+            return None;
+        }
+        peek_pixel_format(sample_buffer).and_then(|actual| stats.check_pixel_format(actual))
+    });
+    // The audio format is fixed for a configuration; read it from the first buffer only
+    if output_type_enum == SCStreamOutputType::Audio {
+        if let Some(stats) = stats_for_stream(stream) {
+            if stats.audio_format().is_none() {
+                if let Some(format) = peek_audio_format(sample_buffer) {
+                    stats.set_audio_format(format);
+                }
+            }
+        }
+    }
 
-        // Call all registered handlers
-        for (idx, (_id, entry)) in handlers.iter().enumerate() {
-            // Convert raw pointer to CMSampleBuffer
-            let buffer = unsafe { crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut()) };
+    // Delegate notifications go out once the handler registry is unlocked, so
+    // a delegate that adds or removes handlers can't deadlock
+    let panics = dispatch_sample(stream, sample_buffer, output_type_enum, stats);
+    if let Some((requested, actual)) = negotiated {
+        notify_format_negotiated(stream, requested, actual);
+    }
+    for message in panics {
+        notify_handler_panic(stream, &message);
+    }
+}
 
-            // For all handlers except the last, we need to retain the buffer
-            if idx < handler_count - 1 {
-                // Retain the buffer so it's not released when this handler's buffer is dropped
-                unsafe { crate::cm::ffi::cm_sample_buffer_retain(sample_buffer.cast_mut()) };
-            }
-            // The last handler will release the original retained reference from Swift
+// A screen frame's stream statistics, frame status and presentation time
+type SampleStats = (
+    Arc<StreamStats>,
+    Option<crate::cm::SCFrameStatus>,
+    Option<f64>,
+);
 
-            entry.handler.did_output_sample_buffer(buffer, output_type_enum);
+// Hand a sample to the handlers registered on `stream` for `output_type`,
+// releasing it if there are none; returns the messages of handlers that panicked
+fn dispatch_sample(
+    stream: *const c_void,
+    sample_buffer: *const c_void,
+    output_type: SCStreamOutputType,
+    stats: Option<SampleStats>,
+) -> Vec<String> {
+    let mut panics = Vec::new();
+    // Mutex poisoning is unrecoverable in C callback context; unwrap is appropriate
+    let mut registry = HANDLER_REGISTRY.lock().unwrap();
+    let Some(handlers) = registry.as_mut() else {
+        drop(registry);
+        if let Some((stats, status, _)) = stats {
+            stats.record_dropped(status);
         }
-    } else {
         // No registry - release the buffer
         unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
+        return panics;
+    };
+    // Every stream shares this callback, so only hand the sample to the
+    // handlers registered on this stream
+    let mut active: Vec<(&usize, &mut HandlerEntry)> = handlers
+        .iter_mut()
+        .filter(|(_, entry)| !entry.disabled && entry.stream == stream as usize)
+        .collect();
+    if active.is_empty() {
+        if let Some((stats, status, _)) = stats {
+            stats.record_dropped(status);
+        }
+        // No handlers registered - release the buffer that Swift passed us
+        unsafe { crate::cm::ffi::cm_sample_buffer_release(sample_buffer.cast_mut()) };
+        return panics;
+    }
+
+    if let Some((stats, status, pts)) = &stats {
+        stats.record_delivered(*status, *pts);
+    }
+    let handlers_started = Instant::now();
+
+    let handler_count = active.len();
+
+    // Call all registered handlers
+    for (idx, (id, entry)) in active.iter_mut().enumerate() {
+        // Convert raw pointer to CMSampleBuffer
+        let buffer = unsafe { crate::cm::CMSampleBuffer::from_ptr(sample_buffer.cast_mut()) };
+
+        // For all handlers except the last, we need to retain the buffer
+        if idx < handler_count - 1 {
+            // Retain the buffer so it's not released when this handler's buffer is dropped
+            unsafe { crate::cm::ffi::cm_sample_buffer_retain(sample_buffer.cast_mut()) };
+        }
+        // The last handler will release the original retained reference from Swift
+
+        // Unwinding across the FFI boundary is undefined behavior, so catch it here.
+        // The buffer is moved into the closure and released during unwinding.
+        let handler = &entry.handler;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            deliver(handler.as_ref(), buffer, output_type);
+        }));
+
+        if let Err(payload) = result {
+            panics.push(apply_panic_policy(**id, entry, payload.as_ref()));
+        }
+    }
+
+    drop(registry);
+    if let Some((stats, _, _)) = stats {
+        stats.record_handler_time(handlers_started.elapsed());
+    }
+    panics
+}
+
+// Log a handler panic and apply the handler's policy; returns the panic message
+fn apply_panic_policy(
+    id: usize,
+    entry: &mut HandlerEntry,
+    payload: &(dyn std::any::Any + Send),
+) -> String {
+    let message = panic_message(payload);
+    eprintln!("SCStream output handler {id} panicked: {message}");
+    match entry.panic_policy {
+        PanicPolicy::Abort => std::process::abort(),
+        PanicPolicy::RemoveHandler => entry.disabled = true,
+        PanicPolicy::Ignore => {}
+    }
+    message
+}
+
+/// Run `deliver` on behalf of handler `id` for a sample that did not come
+/// from the system, such as a heartbeat repeat
+///
+/// `deliver` runs under the handler registry lock, like the handlers in
+/// `sample_handler`, so it never overlaps a real sample, and under the same
+/// panic guard and policy. It returns the frame status and presentation time
+/// of the sample it delivered, if any, for the stream statistics.
+///
+/// Returns `false` without calling `deliver` once the handler has been
+/// removed or disabled.
+pub(crate) fn deliver_outside_callback(
+    id: usize,
+    stats: &StreamStats,
+    deliver: impl FnOnce() -> Option<(Option<crate::cm::SCFrameStatus>, Option<f64>)>,
+) -> bool {
+    let mut registry = HANDLER_REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let Some(entry) = registry.as_mut().and_then(|handlers| handlers.get_mut(&id)) else {
+        return false;
+    };
+    if entry.disabled {
+        return false;
+    }
+
+    let started = Instant::now();
+    let panicked = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(deliver)) {
+        Ok(Some((frame_status, presentation))) => {
+            stats.record_delivered(frame_status, presentation);
+            stats.record_handler_time(started.elapsed());
+            None
+        }
+        Ok(None) => None,
+        Err(payload) => Some(apply_panic_policy(id, entry, payload.as_ref())),
+    };
+    let stream = entry.stream as *const c_void;
+    drop(registry);
+
+    if let Some(message) = panicked {
+        notify_handler_panic(stream, &message);
+    }
+    true
+}
+
+fn register_stats(
+    ptr: *const c_void,
+    filter: &SCContentFilter,
+    configuration: &SCStreamConfiguration,
+) -> Arc<StreamStats> {
+    let stats = Arc::new(StreamStats::new(filter, configuration));
+    if !ptr.is_null() {
+        if let Ok(mut registry) = STATS_REGISTRY.lock() {
+            registry
+                .get_or_insert_with(HashMap::new)
+                .insert(ptr as usize, Arc::downgrade(&stats));
+        }
     }
+    stats
 }
 
 /// `SCStream` is a lightweight wrapper around the Swift `SCStream` instance.
@@ -131,6 +501,14 @@ extern "C" fn sample_handler(
 /// This is the primary and only implementation of `SCStream` in v1.0+.
 /// All `ScreenCaptureKit` operations go through Swift FFI bindings.
 ///
+/// # Teardown Order
+///
+/// When the last handle to a stream is dropped, its native outputs are
+/// removed first, so the system stops delivering samples. Only then are the
+/// output handlers dropped, followed by the delegate and the stream itself.
+/// A handler's `Drop` therefore never races with one of its own callbacks,
+/// and handlers may safely own data that the stream also references.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -164,6 +542,10 @@ pub struct SCStream {
     ptr: *const c_void,
     /// Handler IDs registered by this stream instance, keyed by output type
     handler_ids: Vec<(usize, SCStreamOutputType)>,
+    /// Panic policy applied to handlers added to this stream
+    panic_policy: PanicPolicy,
+    /// Counters reported by `diagnostics()`, shared between clones
+    stats: Arc<StreamStats>,
 }
 
 unsafe impl Send for SCStream {}
@@ -193,7 +575,8 @@ impl SCStream {
     /// # }
     /// ```
     pub fn new(filter: &SCContentFilter, configuration: &SCStreamConfiguration) -> Self {
-        extern "C" fn error_callback(_stream: *const c_void, error_code: i32, msg: *const i8) {
+        extern "C" fn error_callback(stream: *const c_void, error_code: i32, msg: *const i8) {
+            mark_stopped(stream);
             let message = if msg.is_null() {
                 "Unknown error"
             } else {
@@ -221,6 +604,8 @@ impl SCStream {
         Self {
             ptr,
             handler_ids: Vec::new(),
+            panic_policy: PanicPolicy::default(),
+            stats: register_stats(ptr, filter, configuration),
         }
     }
 
@@ -292,6 +677,8 @@ impl SCStream {
         Self {
             ptr,
             handler_ids: Vec::new(),
+            panic_policy: PanicPolicy::default(),
+            stats: register_stats(ptr, filter, configuration),
         }
     }
 
@@ -304,6 +691,10 @@ impl SCStream {
     ///   - A closure `|CMSampleBuffer, SCStreamOutputType| { ... }`
     /// * `of_type` - The type of output to receive (Screen, Audio, or Microphone)
     ///
+    /// The handler only receives samples from this stream (and its clones),
+    /// but every handler on the stream is passed the samples of every output
+    /// type that has been added, so check the output type in the callback.
+    ///
     /// # Returns
     ///
     /// Returns `Some(handler_id)` on success, `None` on failure.
@@ -368,6 +759,14 @@ impl SCStream {
     /// * `of_type` - The type of output to receive
     /// * `queue` - Optional custom dispatch queue for callbacks
     ///
+    /// Handlers of one type share a single native output, which calls them in
+    /// turn for every sample. Its queue is the one given with the first
+    /// handler of that type; `queue` is ignored for later ones.
+    ///
+    /// The stream retains `queue`, so it may be dropped after this call. The
+    /// same queue can serve several streams; see
+    /// [`DispatchQueue`](crate::dispatch_queue::DispatchQueue#sharing-a-queue-between-streams).
+    ///
     /// # Panics
     ///
     /// Panics if the internal handler registry mutex is poisoned.
@@ -410,21 +809,26 @@ impl SCStream {
         };
 
         // Store handler in registry
-        {
+        let shares_output = {
             // Mutex poisoning is unrecoverable; unwrap is appropriate
             let mut registry = HANDLER_REGISTRY.lock().unwrap();
-            if registry.is_none() {
-                *registry = Some(HashMap::new());
-            }
-            // We just ensured registry is Some above
-            registry.as_mut().unwrap().insert(
+            let handlers = registry.get_or_insert_with(HashMap::new);
+            let shares_output = has_handlers_for(handlers, self.ptr as usize, of_type, &[]);
+            handlers.insert(
                 handler_id,
                 HandlerEntry {
                     handler: Box::new(handler),
+                    stream: self.ptr as usize,
+                    of_type,
                     ref_count: 1,
+                    panic_policy: self.panic_policy,
+                    disabled: false,
+                    heartbeat: None,
                 },
             );
-        }
+            drop(registry);
+            shares_output
+        };
 
         // Convert output type to int for Swift
         let output_type_int = match of_type {
@@ -433,7 +837,9 @@ impl SCStream {
             SCStreamOutputType::Microphone => 2,
         };
 
-        let ok = if let Some(q) = queue {
+        let ok = if shares_output {
+            true
+        } else if let Some(q) = queue {
             unsafe {
                 ffi::sc_stream_add_stream_output_with_queue(
                     self.ptr,
@@ -460,6 +866,142 @@ impl SCStream {
         }
     }
 
+    /// Add a screen output handler that keeps receiving frames while the
+    /// screen is idle
+    ///
+    /// The system only delivers a frame when the captured content changes, so
+    /// a static screen produces no frames at all, however low the minimum
+    /// frame interval. Here, whenever `interval` passes without a new complete
+    /// frame, the last one is delivered again as a copy whose presentation
+    /// timestamp is advanced by one interval per repeat. Recorders that
+    /// expect a continuous timeline get one frame per interval.
+    ///
+    /// Repeats come from a background thread, never at the same time as a
+    /// real frame, and stop while the stream is stopped. The thread ends when
+    /// the handler is removed or the stream is dropped. They are subject to
+    /// the stream's [`PanicPolicy`] and counted in its diagnostics like real
+    /// frames. The last frame is kept between repeats, which holds one
+    /// surface of the capture pool, so consider raising the
+    /// [`queue_depth`](SCStreamConfiguration::queue_depth) by one.
+    ///
+    /// Returns the handler ID on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interval` is zero, the output cannot be added to
+    /// the stream, or the background thread cannot be started; the handler is
+    /// not registered in any of these cases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// let config = SCStreamConfiguration::new().with_fps(30);
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler_with_heartbeat(
+    ///     |sample: CMSampleBuffer, _| println!("frame at {:?}", sample.presentation_timestamp()),
+    ///     Duration::from_secs(1) / 30,
+    /// )?;
+    /// stream.start_capture()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_output_handler_with_heartbeat(
+        &mut self,
+        handler: impl SCStreamOutputTrait + 'static,
+        interval: Duration,
+    ) -> Result<usize, SCError> {
+        if interval.is_zero() {
+            return Err(SCError::invalid_config(
+                "Heartbeat interval must be greater than zero",
+            ));
+        }
+        let output = HeartbeatOutput::new(handler);
+        let repeater = output.repeater();
+        let id = self
+            .add_output_handler(output, SCStreamOutputType::Screen)
+            .ok_or_else(|| SCError::stream_error("Failed to add Screen output"))?;
+        match repeater.spawn(id, interval, &self.stats) {
+            Ok(timer) => {
+                // Stopped along with the handler, so no repeat outlives it
+                let mut registry = HANDLER_REGISTRY
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                if let Some(entry) = registry.as_mut().and_then(|handlers| handlers.get_mut(&id)) {
+                    entry.heartbeat = Some(timer);
+                }
+                drop(registry);
+                Ok(id)
+            }
+            Err(err) => {
+                self.remove_output_handler(id, SCStreamOutputType::Screen);
+                Err(SCError::internal_error(format!(
+                    "Failed to start the heartbeat thread: {err}"
+                )))
+            }
+        }
+    }
+
+    /// Receive each frame's `IOSurface` directly, for low-latency preview
+    ///
+    /// `f` is handed the surface backing the frame without retaining it or
+    /// wrapping it in a `CVPixelBuffer`, so it can go straight to the GPU
+    /// (for example with [`metal_textures`](crate::output::IOSurface::metal_textures))
+    /// at high frame rates. Frames without an image, such as idle frames,
+    /// are skipped; only [`SCStreamOutputType::Screen`] outputs carry surfaces.
+    ///
+    /// The surface reference is only valid for the duration of the call. As
+    /// soon as `f` returns, the stream may reuse the surface for a later
+    /// frame. To keep it longer, [`clone`](Clone::clone) it and call
+    /// [`increment_use_count`](crate::output::IOSurface::increment_use_count)
+    /// inside the callback, then call
+    /// [`decrement_use_count`](crate::output::IOSurface::decrement_use_count)
+    /// when done. Holding on to surfaces starves the stream's buffer pool, so
+    /// release them within a frame or two.
+    ///
+    /// Returns the handler ID, which can be passed to
+    /// [`remove_output_handler`](Self::remove_output_handler).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example(filter: &SCContentFilter, config: &SCStreamConfiguration) {
+    /// let mut stream = SCStream::new(filter, config);
+    /// let mut frames = 0u64;
+    /// stream.on_surface(SCStreamOutputType::Screen, move |surface| {
+    ///     frames += 1;
+    ///     println!("frame {frames}: {}x{}", surface.width(), surface.height());
+    /// });
+    /// # }
+    /// ```
+    pub fn on_surface<F>(&mut self, of_type: SCStreamOutputType, f: F) -> Option<usize>
+    where
+        F: FnMut(&IOSurface) + Send + 'static,
+    {
+        let f = Mutex::new(f);
+        self.add_output_handler(
+            move |sample: CMSampleBuffer, _of_type| {
+                let ptr = unsafe { ffi::cm_sample_buffer_peek_iosurface(sample.as_ptr()) };
+                // Not retained, so it must not be released either
+                let Some(surface) = (unsafe { IOSurface::from_ptr(ptr) }) else {
+                    return;
+                };
+                let surface = std::mem::ManuallyDrop::new(surface);
+                let mut f = f.lock().unwrap_or_else(PoisonError::into_inner);
+                f(&surface);
+            },
+            of_type,
+        )
+    }
+
     /// Remove an output handler
     ///
     /// # Arguments
@@ -479,99 +1021,389 @@ impl SCStream {
         let Some(pos) = self.handler_ids.iter().position(|(hid, _)| *hid == id) else {
             return false;
         };
-        self.handler_ids.remove(pos);
 
-        // Decrement ref count in global registry, remove if zero
-        #[allow(clippy::option_if_let_else)] // Can't use map_or due to borrow conflicts
-        let should_remove_from_swift = {
-            let mut registry = HANDLER_REGISTRY.lock().unwrap();
-            if let Some(handlers) = registry.as_mut() {
-                if let Some(entry) = handlers.get_mut(&id) {
-                    entry.ref_count = entry.ref_count.saturating_sub(1);
-                    if entry.ref_count == 0 {
-                        handlers.remove(&id);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        };
+        let removes_output = HANDLER_REGISTRY
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|handlers| {
+                handlers.get(&id).is_some_and(|entry| entry.ref_count <= 1)
+                    && !has_handlers_for(handlers, self.ptr as usize, of_type, &[id])
+            });
 
-        // Only tell Swift to remove the output if this was the last reference
-        if should_remove_from_swift {
+        // Only tell Swift to remove the output once the last reference to the
+        // last handler of this type goes. This happens before the handler is
+        // freed so no callback can reach it afterwards.
+        if removes_output {
             let output_type_int = match of_type {
                 SCStreamOutputType::Screen => 0,
                 SCStreamOutputType::Audio => 1,
                 SCStreamOutputType::Microphone => 2,
             };
-            unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) }
-        } else {
-            true
+            if !unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) } {
+                return false;
+            }
         }
+        self.handler_ids.remove(pos);
+
+        // Decrement ref count in global registry, remove if zero
+        let removed = {
+            let mut registry = HANDLER_REGISTRY.lock().unwrap();
+            registry.as_mut().and_then(|handlers| {
+                let entry = handlers.get_mut(&id)?;
+                entry.ref_count = entry.ref_count.saturating_sub(1);
+                if entry.ref_count == 0 {
+                    handlers.remove(&id)
+                } else {
+                    None
+                }
+            })
+        };
+        // Drop the handler outside the registry lock
+        drop(removed);
+        true
     }
 
-    /// Start capturing screen content
+    /// Remove every output handler of the given type registered through this stream
     ///
-    /// This method blocks until the capture operation completes or fails.
+    /// The native stream output is detached first; the Rust handlers are only
+    /// freed once that succeeds, so a sample that is already in flight can never
+    /// be delivered to a dropped closure. Handlers of the same type are shared by
+    /// all clones of this stream and are removed for them too.
     ///
-    /// # Errors
+    /// # Panics
     ///
-    /// Returns `SCError::CaptureStartFailed` if the capture fails to start.
-    pub fn start_capture(&self) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
-        unsafe { ffi::sc_stream_start_capture(self.ptr, context, UnitCompletion::callback) };
-        completion.wait().map_err(SCError::CaptureStartFailed)
-    }
-
-    /// Stop capturing screen content
+    /// Panics if the internal handler registry mutex is poisoned.
     ///
-    /// This method blocks until the capture operation completes or fails.
+    /// # Returns
     ///
-    /// # Errors
+    /// Returns `true` if at least one handler was removed, `false` if none were
+    /// registered for `of_type` or the native removal failed.
     ///
-    /// Returns `SCError::CaptureStopFailed` if the capture fails to stop.
-    pub fn stop_capture(&self) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
-        unsafe { ffi::sc_stream_stop_capture(self.ptr, context, UnitCompletion::callback) };
-        completion.wait().map_err(SCError::CaptureStopFailed)
-    }
-
-    /// Update the stream configuration
+    /// # Examples
     ///
-    /// This method blocks until the configuration update completes or fails.
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
     ///
-    /// # Errors
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default().with_captures_audio(true);
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler(|_, _| println!("Audio"), SCStreamOutputType::Audio);
+    /// stream.start_capture()?;
     ///
-    /// Returns `SCError::StreamError` if the configuration update fails.
-    pub fn update_configuration(
-        &self,
-        configuration: &SCStreamConfiguration,
-    ) -> Result<(), SCError> {
-        let (completion, context) = UnitCompletion::new();
-        unsafe {
-            ffi::sc_stream_update_configuration(
-                self.ptr,
-                configuration.as_ptr(),
-                context,
-                UnitCompletion::callback,
-            );
+    /// // Stop receiving audio without stopping the stream
+    /// stream.remove_output_handler_for_type(SCStreamOutputType::Audio);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_output_handler_for_type(&mut self, of_type: SCStreamOutputType) -> bool {
+        let ids: Vec<usize> = self
+            .handler_ids
+            .iter()
+            .filter(|(_, ty)| *ty == of_type)
+            .map(|(id, _)| *id)
+            .collect();
+        if ids.is_empty() {
+            return false;
         }
-        completion.wait().map_err(SCError::StreamError)
-    }
 
-    /// Update the content filter
-    ///
-    /// This method blocks until the filter update completes or fails.
-    ///
-    /// # Errors
-    ///
-    /// Returns `SCError::StreamError` if the filter update fails.
-    pub fn update_content_filter(&self, filter: &SCContentFilter) -> Result<(), SCError> {
+        let output_type_int = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        // Handlers registered through a clone of this stream keep the output
+        let others_remain = HANDLER_REGISTRY
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|handlers| has_handlers_for(handlers, self.ptr as usize, of_type, &ids));
+        if !others_remain
+            && !unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) }
+        {
+            return false;
+        }
+        self.handler_ids.retain(|(_, ty)| *ty != of_type);
+
+        let removed: Vec<HandlerEntry> = {
+            let mut registry = HANDLER_REGISTRY.lock().unwrap();
+            registry.as_mut().map_or_else(Vec::new, |handlers| {
+                ids.iter().filter_map(|id| handlers.remove(id)).collect()
+            })
+        };
+        // Drop the handlers outside the registry lock
+        drop(removed);
+        true
+    }
+
+    /// Deliver callbacks for `of_type` on the main thread
+    ///
+    /// Samples are still produced on the output queue, then forwarded to the
+    /// main dispatch queue before the handlers run. This is meant for handlers
+    /// that update UI (`AppKit`, egui, ...) directly from a frame.
+    ///
+    /// The hop to the main queue adds latency and frames queue up behind any
+    /// other main-thread work, so don't use this for high frame rate processing;
+    /// do the heavy work on the output queue and hand only the result to the UI.
+    /// Callbacks only run while the main run loop is running (for example inside
+    /// `NSApplication::run`); a `main` that just sleeps never receives them.
+    ///
+    /// The setting applies to handlers added before or after this call and is
+    /// shared by all clones of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.deliver_on_main_thread(SCStreamOutputType::Screen);
+    /// stream.add_output_handler(
+    ///     |_sample, _type| { /* safe to touch UI here */ },
+    ///     SCStreamOutputType::Screen,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deliver_on_main_thread(&mut self, of_type: SCStreamOutputType) -> &mut Self {
+        self.set_main_thread_delivery(of_type, true)
+    }
+
+    /// Enable or disable main-thread delivery for `of_type`
+    ///
+    /// See [`deliver_on_main_thread`](Self::deliver_on_main_thread).
+    pub fn set_main_thread_delivery(
+        &mut self,
+        of_type: SCStreamOutputType,
+        enabled: bool,
+    ) -> &mut Self {
+        let output_type_int = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        unsafe { ffi::sc_stream_set_main_thread_delivery(self.ptr, output_type_int, enabled) };
+        self
+    }
+
+    /// Whether callbacks for `of_type` are delivered on the main thread
+    #[must_use]
+    pub fn delivers_on_main_thread(&self, of_type: SCStreamOutputType) -> bool {
+        let output_type_int = match of_type {
+            SCStreamOutputType::Screen => 0,
+            SCStreamOutputType::Audio => 1,
+            SCStreamOutputType::Microphone => 2,
+        };
+        unsafe { ffi::sc_stream_get_main_thread_delivery(self.ptr, output_type_int) }
+    }
+
+    /// Start capturing screen content
+    ///
+    /// This method blocks until the capture operation completes or fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::InvalidConfiguration` if an `Audio` or `Microphone`
+    /// output handler was added but the configuration does not enable
+    /// [`captures_audio`](SCStreamConfiguration::set_captures_audio) or
+    /// [`captures_microphone`](SCStreamConfiguration::set_captures_microphone),
+    /// since that handler would never be called.
+    ///
+    /// Returns `SCError::CaptureStartFailed` if the capture fails to start.
+    pub fn start_capture(&self) -> Result<(), SCError> {
+        self.check_audio_outputs()?;
+        // Set first: frames can arrive before the start completes
+        self.stats.set_capturing(true);
+        let (completion, context) = UnitCompletion::new();
+        unsafe { ffi::sc_stream_start_capture(self.ptr, context, UnitCompletion::callback) };
+        completion.wait().map_err(|error| {
+            self.stats.set_capturing(false);
+            SCError::CaptureStartFailed(error)
+        })?;
+        Ok(())
+    }
+
+    /// Block until the first complete frame arrives after starting capture
+    ///
+    /// Capture warms up for a moment after [`start_capture`](Self::start_capture)
+    /// returns, and the first frames may be idle or missing. Screenshot and
+    /// recording tools call this before relying on the content, so the
+    /// warm-up isn't captured. Returns immediately if a complete frame has
+    /// already arrived; stopping the stream resets this for the next start.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SCError::Timeout`] if no complete frame arrives within
+    /// `timeout`, and an error if the stream is not capturing or stops while
+    /// waiting, whether through [`stop_capture`](Self::stop_capture) or an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    /// stream.start_capture()?;
+    /// stream.wait_for_first_frame(Duration::from_secs(2))?;
+    /// // Frames from here on show the captured content
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_first_frame(&self, timeout: Duration) -> Result<(), SCError> {
+        self.stats.wait_for_first_frame(timeout)
+    }
+
+    /// Reject audio handlers that the configuration will never feed
+    fn check_audio_outputs(&self) -> Result<(), SCError> {
+        let has_output = |of_type| self.handler_ids.iter().any(|(_, t)| *t == of_type);
+        let wants_audio = has_output(SCStreamOutputType::Audio);
+        let wants_microphone = has_output(SCStreamOutputType::Microphone);
+        if !wants_audio && !wants_microphone {
+            return Ok(());
+        }
+        let Some(configuration) = self.configuration() else {
+            return Ok(());
+        };
+        if wants_audio && !configuration.captures_audio() {
+            return Err(SCError::invalid_config(
+                "An Audio output handler was added but captures_audio is false; \
+                 call with_captures_audio(true) to receive audio samples",
+            ));
+        }
+        if wants_microphone && !configuration.captures_microphone() {
+            return Err(SCError::invalid_config(
+                "A Microphone output handler was added but captures_microphone is false; \
+                 call with_captures_microphone(true) to receive microphone samples",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stop capturing screen content
+    ///
+    /// This method blocks until the capture operation completes or fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::CaptureStopFailed` if the capture fails to stop.
+    pub fn stop_capture(&self) -> Result<(), SCError> {
+        let (completion, context) = UnitCompletion::new();
+        unsafe { ffi::sc_stream_stop_capture(self.ptr, context, UnitCompletion::callback) };
+        completion.wait().map_err(SCError::CaptureStopFailed)?;
+        self.stats.set_capturing(false);
+        Ok(())
+    }
+
+    /// Update the stream configuration
+    ///
+    /// This method blocks until the configuration update completes or fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::StreamError` if the configuration update fails.
+    pub fn update_configuration(
+        &self,
+        configuration: &SCStreamConfiguration,
+    ) -> Result<(), SCError> {
+        let (completion, context) = UnitCompletion::new();
+        unsafe {
+            ffi::sc_stream_update_configuration(
+                self.ptr,
+                configuration.as_ptr(),
+                context,
+                UnitCompletion::callback,
+            );
+        }
+        completion.wait().map_err(SCError::StreamError)?;
+        self.stats.set_configuration(configuration);
+        Ok(())
+    }
+
+    /// Apply several configuration changes in a single update
+    ///
+    /// Every [`update_configuration`](Self::update_configuration) call is a
+    /// round-trip to the system, and applying changes one at a time can show
+    /// intermediate states (a new width with the old height, for example).
+    /// `update` hands `changes` a [`ConfigChanges`] over a copy of the
+    /// configuration the stream is currently using, then applies the result
+    /// with one `update_configuration` call. If the closure changes nothing,
+    /// the system is not contacted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::StreamError` if the stream has no configuration to
+    /// start from or the update fails (live updates need macOS 14.0+).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::new().with_width(1920).with_height(1080);
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.start_capture()?;
+    ///
+    /// stream.update(|changes| {
+    ///     changes.width(1280).height(720).fps(30);
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<F>(&mut self, changes: F) -> Result<(), SCError>
+    where
+        F: FnOnce(&mut ConfigChanges),
+    {
+        let current = self
+            .configuration()
+            .ok_or_else(|| SCError::stream_error("Stream has no configuration to update"))?;
+        let mut batch = ConfigChanges::new(current);
+        changes(&mut batch);
+        batch.into_configuration().map_or(Ok(()), |configuration| {
+            self.update_configuration(&configuration)
+        })
+    }
+
+    /// Get a copy of the configuration the stream is using
+    ///
+    /// This is the configuration passed to [`new`](Self::new) or the last
+    /// successful [`update_configuration`](Self::update_configuration), as it
+    /// was at that moment. Changing the copy does not affect the stream.
+    pub fn configuration(&self) -> Option<SCStreamConfiguration> {
+        if self.ptr.is_null() {
+            return None;
+        }
+        let ptr = unsafe { ffi::sc_stream_copy_configuration(self.ptr) };
+        (!ptr.is_null()).then(|| SCStreamConfiguration(ptr))
+    }
+
+    /// Update the content filter
+    ///
+    /// This method blocks until the filter update completes or fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::StreamError` if the filter update fails.
+    pub fn update_content_filter(&self, filter: &SCContentFilter) -> Result<(), SCError> {
         let (completion, context) = UnitCompletion::new();
         unsafe {
             ffi::sc_stream_update_content_filter(
@@ -581,7 +1413,9 @@ impl SCStream {
                 UnitCompletion::callback,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)?;
+        self.stats.set_filter(filter);
+        Ok(())
     }
 
     /// Get the synchronization clock for this stream (macOS 13.0+)
@@ -608,6 +1442,10 @@ impl SCStream {
     /// will start when capture begins. The recording is written to the file URL
     /// specified in the `SCRecordingOutputConfiguration`.
     ///
+    /// A stream can carry several recording outputs; see
+    /// [`add_recording_output_with_token`](Self::add_recording_output_with_token),
+    /// which also reports how the output is recorded.
+    ///
     /// # Errors
     ///
     /// Returns `SCError::StreamError` if adding the recording output fails.
@@ -616,6 +1454,60 @@ impl SCStream {
         &self,
         recording_output: &crate::recording_output::SCRecordingOutput,
     ) -> Result<(), SCError> {
+        self.add_recording_output_with_token(recording_output)
+            .map(drop)
+    }
+
+    /// Add a recording output and report how it is recorded (macOS 15.0+)
+    ///
+    /// Behaves like [`add_recording_output`](Self::add_recording_output).
+    /// Several outputs can record the same stream at once, e.g. a `ProRes`
+    /// master next to an H.264 proxy. Each output is first handed to the
+    /// system. If the system refuses it because the stream already has a
+    /// recording output, the output is fanned out instead: the stream's
+    /// frames (and audio, when captured) are encoded into its file with
+    /// `AVAssetWriter`, using the output's codec and file type. The returned
+    /// token reports which path was taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SCError::StreamError` if adding the recording output fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::recording_output::{
+    ///     SCRecordingOutput, SCRecordingOutputCodec, SCRecordingOutputConfiguration,
+    /// };
+    ///
+    /// # fn example(stream: &SCStream) -> Result<(), Box<dyn std::error::Error>> {
+    /// let master = SCRecordingOutputConfiguration::new()
+    ///     .with_output_url(Path::new("/tmp/master.mov"))
+    ///     .with_video_codec(SCRecordingOutputCodec::HEVC);
+    /// let proxy = SCRecordingOutputConfiguration::new()
+    ///     .with_output_url(Path::new("/tmp/proxy.mp4"))
+    ///     .with_video_codec(SCRecordingOutputCodec::H264);
+    ///
+    /// let master = stream
+    ///     .add_recording_output_with_token(&SCRecordingOutput::new(&master).ok_or("unavailable")?)?;
+    /// let proxy = stream
+    ///     .add_recording_output_with_token(&SCRecordingOutput::new(&proxy).ok_or("unavailable")?)?;
+    /// println!("master: {:?}, proxy: {:?}", master.mode(), proxy.mode());
+    ///
+    /// stream.remove_recording_output(proxy.output())?;
+    /// stream.remove_recording_output(master.output())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "macos_15_0")]
+    pub fn add_recording_output_with_token(
+        &self,
+        recording_output: &crate::recording_output::SCRecordingOutput,
+    ) -> Result<crate::recording_output::RecordingOutputToken, SCError> {
+        use crate::recording_output::{RecordingOutputMode, RecordingOutputToken};
+
         let (completion, context) = UnitCompletion::new();
         unsafe {
             ffi::sc_stream_add_recording_output(
@@ -625,7 +1517,16 @@ impl SCStream {
                 context,
             );
         }
-        completion.wait().map_err(SCError::StreamError)
+        completion.wait().map_err(SCError::StreamError)?;
+        let fanned_out = unsafe {
+            ffi::sc_stream_recording_output_is_fanned_out(self.ptr, recording_output.as_ptr())
+        };
+        let mode = if fanned_out {
+            RecordingOutputMode::FannedOut
+        } else {
+            RecordingOutputMode::Native
+        };
+        Ok(RecordingOutputToken::new(recording_output.clone(), mode))
     }
 
     /// Remove a recording output from the stream (macOS 15.0+)
@@ -652,6 +1553,386 @@ impl SCStream {
         completion.wait().map_err(SCError::StreamError)
     }
 
+    /// Hide a window from the stream only while it is recording (macOS 15.0+)
+    ///
+    /// Useful for a recording-controls panel that should show up in the live
+    /// preview but not in the recorded file. When the first recording output
+    /// is added, the content filter is rebuilt to leave out the window before
+    /// recording starts; once the last recording output is removed, the
+    /// previous filter is restored. Filters passed to
+    /// [`update_content_filter`](Self::update_content_filter) during a
+    /// recording have the window left out as well.
+    ///
+    /// Call this before [`add_recording_output`](Self::add_recording_output).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no window has `window_id`, or if the stream's
+    /// filter cannot leave out the window: filters from the content sharing
+    /// picker, a filter that captures only this window, and filters that
+    /// exclude applications while the window's application is still captured.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::recording_output::{SCRecordingOutput, SCRecordingOutputConfiguration};
+    ///
+    /// # fn example(controls_window_id: u32) -> Result<(), Box<dyn std::error::Error>> {
+    /// let content = SCShareableContent::get()?;
+    /// let filter = SCContentFilter::builder().display(&content.displays()[0]).build();
+    /// let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+    /// stream.exclude_window_during_recording(controls_window_id)?;
+    /// stream.start_capture()?;
+    ///
+    /// let config = SCRecordingOutputConfiguration::new()
+    ///     .with_output_url(Path::new("/tmp/recording.mp4"));
+    /// let recording = SCRecordingOutput::new(&config).ok_or("recording unavailable")?;
+    /// stream.add_recording_output(&recording)?; // controls hidden from here
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "macos_15_0")]
+    pub fn exclude_window_during_recording(&self, window_id: u32) -> Result<(), SCError> {
+        let window = crate::shareable_content::SCShareableContent::get()?
+            .window_with_id(window_id)
+            .ok_or_else(|| SCError::invalid_config(format!("No window with ID {window_id}")))?;
+        #[cfg(feature = "testing")]
+        crate::testing::reject_mock(window.as_ptr(), "exclude_window_during_recording")?;
+        let (completion, context) = UnitCompletion::new();
+        unsafe {
+            ffi::sc_stream_exclude_window_during_recording(
+                self.ptr,
+                window.as_ptr(),
+                UnitCompletion::callback,
+                context,
+            );
+        }
+        completion.wait().map_err(SCError::StreamError)
+    }
+
+    /// Automatically pause capture while the system sleeps
+    ///
+    /// When enabled, capture is stopped when the system is about to sleep and
+    /// restarted after it wakes, once the display or window the filter
+    /// captures is found in the shareable content again. If it disappeared
+    /// during sleep, the restart fails and the stream stays stopped; rebuild the filter and call
+    /// [`update_content_filter`](Self::update_content_filter) and
+    /// [`start_capture`](Self::start_capture) to recover.
+    ///
+    /// A delegate passed to [`new_with_delegate`](Self::new_with_delegate)
+    /// receives [`stream_will_sleep`](SCStreamDelegateTrait::stream_will_sleep)
+    /// and [`stream_did_wake`](SCStreamDelegateTrait::stream_did_wake).
+    /// The observer is removed once the last clone of the stream is dropped.
+    ///
+    /// Sleep notifications are posted on the main thread, so the process needs
+    /// a running main run loop (as any `AppKit` app has) for this to take effect.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let delegate = StreamCallbacks::new()
+    ///     .on_sleep(|paused| println!("Sleeping, capture paused: {paused}"))
+    ///     .on_wake(|resumed| println!("Awake, capture resumed: {resumed}"));
+    ///
+    /// let stream = SCStream::new_with_delegate(&filter, &config, delegate);
+    /// stream.set_auto_pause_on_sleep(true);
+    /// stream.start_capture()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_auto_pause_on_sleep(&self, enabled: bool) {
+        if self.ptr.is_null() {
+            return;
+        }
+        unsafe { ffi::sc_stream_set_auto_pause_on_sleep(self.ptr, enabled, sleep_wake_callback) };
+    }
+
+    /// Automatically restart capture after the stream stops with a fatal error
+    ///
+    /// When enabled, a stream that stops because of an error (other than the
+    /// user stopping it from the system UI) is torn down and recreated from
+    /// the filter and configuration it was last given, its output handlers are
+    /// added back and capture is started again. The `SCStream` value stays the
+    /// same throughout; handler IDs, diagnostics and main-thread delivery
+    /// settings carry over. Recording outputs are not restored.
+    ///
+    /// A delegate passed to [`new_with_delegate`](Self::new_with_delegate)
+    /// receives [`did_restart`](SCStreamDelegateTrait::did_restart) with the
+    /// error that triggered the restart. If the restart itself fails, the error
+    /// is reported through [`did_stop_with_error`](SCStreamDelegateTrait::did_stop_with_error)
+    /// as if auto-restart were disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let delegate = StreamCallbacks::new()
+    ///     .on_restart(|error| eprintln!("Capture restarted after: {error}"))
+    ///     .on_error(|error| eprintln!("Capture failed: {error}"));
+    ///
+    /// let mut stream = SCStream::new_with_delegate(&filter, &config, delegate);
+    /// stream.set_auto_restart(true);
+    /// stream.start_capture()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_auto_restart(&mut self, enabled: bool) {
+        if self.ptr.is_null() {
+            return;
+        }
+        unsafe { ffi::sc_stream_set_auto_restart(self.ptr, enabled, restart_callback) };
+    }
+
+    /// Set what happens when one of this stream's output handlers panics
+    ///
+    /// A panic inside [`did_output_sample_buffer`](SCStreamOutputTrait::did_output_sample_buffer)
+    /// would otherwise unwind into Swift, which is undefined behavior. Panics are
+    /// always caught and logged; the policy applies to handlers already added to
+    /// this stream and to any added later. Defaults to [`PanicPolicy::RemoveHandler`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal handler registry mutex is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    /// use screencapturekit::stream::{PanicPolicy, StreamCallbacks};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let delegate = StreamCallbacks::new()
+    ///     .on_handler_panic(|msg| eprintln!("Handler panicked: {msg}"));
+    ///
+    /// let mut stream = SCStream::new_with_delegate(&filter, &config, delegate);
+    /// stream.set_panic_policy(PanicPolicy::Ignore);
+    /// stream.add_output_handler(|_sample, _type| { /* ... */ }, SCStreamOutputType::Screen);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_panic_policy(&mut self, policy: PanicPolicy) -> &mut Self {
+        self.panic_policy = policy;
+        if let Some(handlers) = HANDLER_REGISTRY.lock().unwrap().as_mut() {
+            for (id, _) in &self.handler_ids {
+                if let Some(entry) = handlers.get_mut(id) {
+                    entry.panic_policy = policy;
+                }
+            }
+        }
+        self
+    }
+
+    /// Get the panic policy for this stream's output handlers
+    pub const fn panic_policy(&self) -> PanicPolicy {
+        self.panic_policy
+    }
+
+    /// Collect a diagnostics snapshot for bug reports
+    ///
+    /// Includes a summary of the configuration, the filter style, how many
+    /// screen frames were delivered or dropped, the measured frame rate, the
+    /// status of the last frame, and whether capture is running. Clones of a
+    /// stream share the same counters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let stream = SCStream::new(&filter, &config);
+    /// let diagnostics = stream.diagnostics();
+    /// if !diagnostics.is_capturing {
+    ///     eprintln!("Stream not running:\n{diagnostics}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diagnostics(&self) -> StreamDiagnostics {
+        self.stats.snapshot()
+    }
+
+    /// Get the size of the frames the stream actually delivers
+    ///
+    /// The system may adjust the requested
+    /// [`width`](SCStreamConfiguration::width) and
+    /// [`height`](SCStreamConfiguration::height), for example to satisfy
+    /// alignment requirements or a source rectangle. This returns the pixel
+    /// buffer size of the first screen frame received, so consumers that
+    /// allocate their own buffers can size them correctly.
+    ///
+    /// Returns `None` until a frame with a pixel buffer has arrived. The
+    /// cached size is cleared by
+    /// [`update_configuration`](Self::update_configuration) and picked up
+    /// again from the next frame.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    /// stream.start_capture()?;
+    /// std::thread::sleep(std::time::Duration::from_millis(500));
+    ///
+    /// if let Some((width, height)) = stream.output_dimensions() {
+    ///     println!("Frames are {width}x{height}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn output_dimensions(&self) -> Option<(u32, u32)> {
+        self.stats.output_dimensions()
+    }
+
+    /// Format of the system audio this stream delivers
+    ///
+    /// Read once from the first [`Audio`](SCStreamOutputType::Audio) buffer
+    /// and cached, so encoders can be set up from it without inspecting
+    /// the format description of every buffer. Returns `None` until that
+    /// buffer arrives, and again after
+    /// [`update_configuration`](Self::update_configuration) until the next
+    /// one does. Microphone buffers are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// let config = SCStreamConfiguration::new().with_captures_audio(true);
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler(|_, _| {}, SCStreamOutputType::Audio);
+    /// stream.start_capture()?;
+    /// std::thread::sleep(std::time::Duration::from_millis(500));
+    ///
+    /// if let Some(format) = stream.audio_format() {
+    ///     println!("{} Hz, {} channels", format.sample_rate, format.channels);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn audio_format(&self) -> Option<crate::cm::AudioFormat> {
+        self.stats.audio_format()
+    }
+
+    /// Estimated number of screen frames skipped because handlers fell behind
+    ///
+    /// The system renders into a fixed pool of
+    /// [`queue_depth`](SCStreamConfiguration::queue_depth) surfaces. When output
+    /// handlers take longer than a frame interval, or hold on to sample buffers,
+    /// the pool runs dry and frames are skipped without any callback. The
+    /// count is inferred from the presentation-time gap before the next
+    /// complete frame, and only when the handlers for the preceding frame
+    /// overran, so gaps from a static screen are not counted.
+    ///
+    /// A growing count means the handlers should do less work per frame, hand
+    /// frames off to another thread, or the stream needs a larger queue depth.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    /// stream.start_capture()?;
+    /// std::thread::sleep(std::time::Duration::from_secs(5));
+    ///
+    /// let skipped = stream.dropped_due_to_backpressure();
+    /// if skipped > 0 {
+    ///     eprintln!("{skipped} frames lost; consider raising the queue depth");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dropped_due_to_backpressure(&self) -> u64 {
+        self.stats.dropped_due_to_backpressure()
+    }
+
+    /// Receive a diagnostics snapshot every `interval`
+    ///
+    /// A push alternative to polling [`diagnostics`](Self::diagnostics) for
+    /// dashboards and health checks. The callback runs on a background thread
+    /// and sees the same frame rate and delivered/dropped counts; the crate
+    /// does not track the depth of the sample queue, so queue fullness is not
+    /// reported. Setting a new callback replaces the previous one, and the
+    /// thread exits after [`clear_stats_callback`](Self::clear_stats_callback)
+    /// or once every clone of the stream has been dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interval` is zero or the thread cannot be spawned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use screencapturekit::prelude::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let content = SCShareableContent::get()?;
+    /// # let display = &content.displays()[0];
+    /// # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+    /// # let config = SCStreamConfiguration::default();
+    /// let mut stream = SCStream::new(&filter, &config);
+    /// stream.set_stats_callback(Duration::from_secs(1), |stats| {
+    ///     println!("{:.1} fps, {} dropped", stats.measured_fps, stats.frames_dropped);
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_stats_callback<F>(&mut self, interval: Duration, callback: F) -> Result<(), SCError>
+    where
+        F: Fn(StreamDiagnostics) + Send + 'static,
+    {
+        self.stats.start_timer(interval, callback)
+    }
+
+    /// Stop the callback installed by [`set_stats_callback`](Self::set_stats_callback)
+    pub fn clear_stats_callback(&mut self) {
+        self.stats.stop_timer();
+    }
+
     /// Returns the raw pointer to the underlying Swift `SCStream` instance.
     #[allow(dead_code)]
     pub(crate) fn as_ptr(&self) -> *const c_void {
@@ -660,37 +1941,61 @@ impl SCStream {
 }
 
 impl Drop for SCStream {
-    #[allow(clippy::option_if_let_else)] // Can't use map_or due to borrow conflicts
     fn drop(&mut self) {
-        // Clean up all registered handlers (decrement ref counts)
-        for (id, of_type) in std::mem::take(&mut self.handler_ids) {
-            let should_remove_from_swift = {
-                let mut registry = HANDLER_REGISTRY.lock().unwrap();
-                if let Some(handlers) = registry.as_mut() {
-                    if let Some(entry) = handlers.get_mut(&id) {
-                        entry.ref_count = entry.ref_count.saturating_sub(1);
-                        if entry.ref_count == 0 {
-                            handlers.remove(&id);
-                            true
-                        } else {
-                            false
+        // Take the handlers this handle held the last reference to out of the
+        // registry. `sample_handler` can no longer reach them, but they stay
+        // alive until the native outputs are gone.
+        let mut released: Vec<HandlerEntry> = Vec::new();
+        let mut native_types: Vec<SCStreamOutputType> = Vec::new();
+        {
+            let mut registry = HANDLER_REGISTRY.lock().unwrap();
+            if let Some(handlers) = registry.as_mut() {
+                for (id, of_type) in std::mem::take(&mut self.handler_ids) {
+                    let Some(entry) = handlers.get_mut(&id) else {
+                        continue;
+                    };
+                    entry.ref_count = entry.ref_count.saturating_sub(1);
+                    if entry.ref_count == 0 {
+                        released.extend(handlers.remove(&id));
+                        if !native_types.contains(&of_type) {
+                            native_types.push(of_type);
                         }
-                    } else {
-                        false
                     }
-                } else {
-                    false
                 }
+                // The output stays while another handle still has handlers of its type
+                native_types.retain(|of_type| {
+                    !has_handlers_for(handlers, self.ptr as usize, *of_type, &[])
+                });
+            }
+        }
+
+        // 1. Remove the native outputs so no further callbacks are scheduled
+        for of_type in native_types {
+            let output_type_int = match of_type {
+                SCStreamOutputType::Screen => 0,
+                SCStreamOutputType::Audio => 1,
+                SCStreamOutputType::Microphone => 2,
             };
+            unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) };
+        }
+
+        // 2. Drop the handler boxes, outside the registry lock
+        drop(released);
 
-            // Only tell Swift to remove the output if this was the last reference
-            if should_remove_from_swift {
-                let output_type_int = match of_type {
-                    SCStreamOutputType::Screen => 0,
-                    SCStreamOutputType::Audio => 1,
-                    SCStreamOutputType::Microphone => 2,
+        // 3. Release the stats entry and sleep/wake observer (last handle only),
+        //    the delegate and the stream pointer
+        if Arc::strong_count(&self.stats) == 1 {
+            // The observer is keyed by pointer, so a stale entry would fire for
+            // whatever stream is later allocated at the same address
+            if !self.ptr.is_null() {
+                unsafe {
+                    ffi::sc_stream_set_auto_pause_on_sleep(self.ptr, false, sleep_wake_callback);
                 };
-                unsafe { ffi::sc_stream_remove_stream_output(self.ptr, output_type_int) };
+            }
+            if let Ok(mut registry) = STATS_REGISTRY.lock() {
+                if let Some(stats) = registry.as_mut() {
+                    stats.remove(&(self.ptr as usize));
+                }
             }
         }
 
@@ -776,6 +2081,8 @@ impl Clone for SCStream {
             Self {
                 ptr: crate::ffi::sc_stream_retain(self.ptr),
                 handler_ids: self.handler_ids.clone(),
+                panic_policy: self.panic_policy,
+                stats: Arc::clone(&self.stats),
             }
         }
     }
@@ -786,7 +2093,8 @@ impl fmt::Debug for SCStream {
         f.debug_struct("SCStream")
             .field("ptr", &self.ptr)
             .field("handler_ids", &self.handler_ids)
-            .finish()
+            .field("panic_policy", &self.panic_policy)
+            .finish_non_exhaustive()
     }
 }
 
@@ -795,3 +2103,233 @@ impl fmt::Display for SCStream {
         write!(f, "SCStream")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
+    use crate::shareable_content::SCShareableContent;
+    use crate::stream::delegate_trait::StreamCallbacks;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    // Tests that create streams share the global registries, so they must
+    // not overlap
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    /// What a [`TrackingHandler`] observed, shared with the test body
+    #[derive(Default)]
+    struct Teardown {
+        dropped: AtomicBool,
+        late_callbacks: AtomicUsize,
+    }
+
+    struct TrackingHandler(Arc<Teardown>);
+
+    impl SCStreamOutputTrait for TrackingHandler {
+        fn did_output_sample_buffer(&self, _sample: CMSampleBuffer, _of_type: SCStreamOutputType) {
+            if self.0.dropped.load(Ordering::SeqCst) {
+                self.0.late_callbacks.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    impl Drop for TrackingHandler {
+        fn drop(&mut self) {
+            self.0.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_drop_stops_callbacks_before_dropping_handlers() {
+        let _serial = SERIAL
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Ok(content) = SCShareableContent::get() else {
+            return;
+        };
+        let Some(display) = content.displays().into_iter().next() else {
+            return;
+        };
+        let filter = SCContentFilter::builder()
+            .display(&display)
+            .exclude_windows(&[])
+            .build();
+        let config = SCStreamConfiguration::new()
+            .with_width(320)
+            .with_height(240);
+
+        let teardown = Arc::new(Teardown::default());
+        let mut stream = SCStream::new(&filter, &config);
+        stream.add_output_handler(
+            TrackingHandler(Arc::clone(&teardown)),
+            SCStreamOutputType::Screen,
+        );
+        // Tear down while frames are still arriving, if capture is permitted
+        if stream.start_capture().is_ok() {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+
+        drop(stream);
+        assert!(
+            teardown.dropped.load(Ordering::SeqCst),
+            "dropping the last handle must drop its handlers"
+        );
+
+        // Give any frame the native output had already scheduled time to land
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert_eq!(teardown.late_callbacks.load(Ordering::SeqCst), 0);
+    }
+
+    /// Hand `sample_handler` a synthetic frame as if the bridge delivered it
+    fn deliver_synthetic_frame(stream: &SCStream) {
+        let pixel_buffer =
+            CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+        let sample = CMSampleBuffer::create_for_image_buffer(
+            &pixel_buffer,
+            CMTime::new(0, 30),
+            CMTime::new(1, 30),
+        )
+        .expect("Failed to create sample buffer");
+        // The callback takes over the reference the bridge would have retained
+        let ptr = sample.as_ptr();
+        std::mem::forget(sample);
+        sample_handler(stream.ptr, ptr, 0);
+    }
+
+    #[test]
+    fn test_panic_policy_disables_handler_and_notifies_delegate() {
+        let _serial = SERIAL
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Ok(content) = SCShareableContent::get() else {
+            return;
+        };
+        let Some(display) = content.displays().into_iter().next() else {
+            return;
+        };
+        let filter = SCContentFilter::builder()
+            .display(&display)
+            .exclude_windows(&[])
+            .build();
+        let config = SCStreamConfiguration::new()
+            .with_width(320)
+            .with_height(240);
+
+        // A removed handler sees only the first of two frames
+        let cases = [(PanicPolicy::RemoveHandler, 1), (PanicPolicy::Ignore, 2)];
+        for (policy, expected_calls) in cases {
+            let (tx, rx) = mpsc::channel();
+            let delegate = StreamCallbacks::new().on_handler_panic(move |message| {
+                let _ = tx.send(message);
+            });
+            let mut stream = SCStream::new_with_delegate(&filter, &config, delegate);
+            stream.set_panic_policy(policy);
+
+            let calls = Arc::new(AtomicUsize::new(0));
+            let counter = Arc::clone(&calls);
+            stream.add_output_handler(
+                move |_: CMSampleBuffer, _| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    panic!("handler failed");
+                },
+                SCStreamOutputType::Screen,
+            );
+
+            deliver_synthetic_frame(&stream);
+            deliver_synthetic_frame(&stream);
+
+            assert_eq!(calls.load(Ordering::SeqCst), expected_calls, "{policy:?}");
+            let messages: Vec<String> = rx.try_iter().collect();
+            assert_eq!(messages.len(), expected_calls, "{policy:?}");
+            assert!(messages.iter().all(|m| m.contains("handler failed")));
+        }
+    }
+
+    #[test]
+    fn test_panic_notification_runs_outside_handler_registry_lock() {
+        let _serial = SERIAL
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Ok(content) = SCShareableContent::get() else {
+            return;
+        };
+        let Some(display) = content.displays().into_iter().next() else {
+            return;
+        };
+        let filter = SCContentFilter::builder()
+            .display(&display)
+            .exclude_windows(&[])
+            .build();
+        let config = SCStreamConfiguration::new()
+            .with_width(320)
+            .with_height(240);
+
+        // A delegate that adds or removes handlers needs the registry lock
+        let (tx, rx) = mpsc::channel();
+        let delegate = StreamCallbacks::new().on_handler_panic(move |_| {
+            let _ = tx.send(HANDLER_REGISTRY.try_lock().is_ok());
+        });
+        let mut stream = SCStream::new_with_delegate(&filter, &config, delegate);
+        stream.add_output_handler(
+            |_: CMSampleBuffer, _| panic!("handler failed"),
+            SCStreamOutputType::Screen,
+        );
+
+        deliver_synthetic_frame(&stream);
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [true]);
+    }
+
+    #[test]
+    fn test_first_frame_follows_stop_with_error() {
+        use crate::cm::SCFrameStatus;
+        use std::time::Duration;
+
+        let _serial = SERIAL
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Ok(content) = SCShareableContent::get() else {
+            return;
+        };
+        let Some(display) = content.displays().into_iter().next() else {
+            return;
+        };
+        let filter = SCContentFilter::builder()
+            .display(&display)
+            .exclude_windows(&[])
+            .build();
+        let config = SCStreamConfiguration::new()
+            .with_width(320)
+            .with_height(240);
+        let stream = SCStream::new_with_delegate(&filter, &config, StreamCallbacks::new());
+
+        // A waiter is woken as soon as the stream stops with an error
+        stream.stats.set_capturing(true);
+        let stats = Arc::clone(&stream.stats);
+        let waiter =
+            std::thread::spawn(move || stats.wait_for_first_frame(Duration::from_secs(30)));
+        std::thread::sleep(Duration::from_millis(50));
+        delegate_error_callback(stream.ptr, 0, std::ptr::null());
+        let result = waiter.join().expect("waiter panicked");
+        assert!(matches!(result, Err(SCError::StreamError(_))), "{result:?}");
+        assert!(!stream.stats.is_capturing());
+
+        // A frame still in flight after the stop does not count for the next start
+        stream
+            .stats
+            .record_delivered(Some(SCFrameStatus::Complete), Some(0.0));
+        stream.stats.set_capturing(true);
+        assert!(matches!(
+            stream.stats.wait_for_first_frame(Duration::from_millis(10)),
+            Err(SCError::Timeout(_))
+        ));
+        stream
+            .stats
+            .record_delivered(Some(SCFrameStatus::Complete), Some(1.0));
+        assert!(stream
+            .stats
+            .wait_for_first_frame(Duration::from_millis(10))
+            .is_ok());
+    }
+}
@@ -0,0 +1,342 @@
+//! Screen frames paired with their audio
+//!
+//! `ScreenCaptureKit` delivers screen frames and audio buffers to separate
+//! outputs, on their own schedules, both stamped with the stream's
+//! [`synchronization_clock`](SCStream::synchronization_clock). Recording and
+//! streaming apps usually want them back together. [`SyncedCapture`] owns a
+//! stream with audio enabled and hands each complete frame to a callback
+//! together with the audio buffers that start while that frame is on screen.
+//!
+//! A frame is delivered once audio has arrived up to the next frame's
+//! timestamp, or once newer samples are more than the alignment window ahead
+//! of it, whichever comes first. Audio that starts before the oldest frame
+//! still waiting for delivery has nothing to attach to and is dropped, so the
+//! window should cover the audio latency of the capture.
+//!
+//! Waiting frames keep their capture surfaces, so raise the stream's
+//! [`queue_depth`](SCStreamConfiguration::queue_depth) by roughly the number
+//! of frames that fit in the window.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use screencapturekit::prelude::*;
+//! use screencapturekit::stream::SyncedCapture;
+//! use std::time::Duration;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let content = SCShareableContent::get()?;
+//! # let display = &content.displays()[0];
+//! # let filter = SCContentFilter::builder().display(display).exclude_windows(&[]).build();
+//! let config = SCStreamConfiguration::new()
+//!     .with_width(1280)
+//!     .with_height(720)
+//!     .with_fps(30)
+//!     .with_queue_depth(8)
+//!     .with_captures_audio(true);
+//!
+//! let capture = SyncedCapture::new(&filter, &config, Duration::from_millis(100), |video, audio| {
+//!     let time = video.presentation_timestamp();
+//!     println!("frame at {time:?} with {} audio buffers", audio.len());
+//! })?;
+//! capture.start_capture()?;
+//! std::thread::sleep(Duration::from_secs(5));
+//! capture.stop_capture()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::cm::{CMSampleBuffer, SCFrameStatus};
+use crate::error::SCError;
+use crate::stream::{
+    configuration::SCStreamConfiguration, content_filter::SCContentFilter,
+    output_trait::SCStreamOutputTrait, output_type::SCStreamOutputType, sc_stream::SCStream,
+};
+
+type SyncedCallback = Box<dyn FnMut(CMSampleBuffer, Vec<CMSampleBuffer>) + Send>;
+
+struct PendingFrame<T> {
+    video: T,
+    /// Presentation time in seconds
+    time: f64,
+    audio: Vec<T>,
+}
+
+/// Groups audio with the frame that was on screen when it started
+///
+/// Generic over the sample type so the timing rules don't depend on real
+/// sample buffers.
+struct Aligner<T> {
+    /// Seconds a frame may wait for its audio
+    window: f64,
+    pending: VecDeque<PendingFrame<T>>,
+    /// End time of the latest audio received, in seconds
+    audio_until: f64,
+}
+
+impl<T> Aligner<T> {
+    fn new(window: Duration) -> Self {
+        Self {
+            window: window.as_secs_f64(),
+            pending: VecDeque::new(),
+            audio_until: f64::NEG_INFINITY,
+        }
+    }
+
+    fn push_video(&mut self, video: T, time: f64) -> Vec<(T, Vec<T>)> {
+        self.pending.push_back(PendingFrame {
+            video,
+            time,
+            audio: Vec::new(),
+        });
+        self.take_ready()
+    }
+
+    fn push_audio(&mut self, audio: T, start: f64, end: f64) -> Vec<(T, Vec<T>)> {
+        // Audio older than every waiting frame belongs to one already delivered
+        if let Some(frame) = self
+            .pending
+            .iter_mut()
+            .rev()
+            .find(|frame| frame.time <= start)
+        {
+            frame.audio.push(audio);
+        }
+        self.audio_until = self.audio_until.max(end);
+        self.take_ready()
+    }
+
+    fn take_ready(&mut self) -> Vec<(T, Vec<T>)> {
+        let mut ready = Vec::new();
+        while let Some(front) = self.pending.front() {
+            // Audio arrives in order, so audio reaching the next frame means
+            // everything that starts before it is already here
+            let covered = self
+                .pending
+                .get(1)
+                .is_some_and(|next| self.audio_until >= next.time);
+            let newest = self
+                .pending
+                .back()
+                .map_or(front.time, |frame| frame.time)
+                .max(self.audio_until);
+            if !covered && newest - front.time <= self.window {
+                break;
+            }
+            if let Some(frame) = self.pending.pop_front() {
+                ready.push((frame.video, frame.audio));
+            }
+        }
+        ready
+    }
+
+    fn flush(&mut self) -> Vec<(T, Vec<T>)> {
+        self.pending
+            .drain(..)
+            .map(|frame| (frame.video, frame.audio))
+            .collect()
+    }
+}
+
+struct SyncedState {
+    aligner: Aligner<CMSampleBuffer>,
+    callback: SyncedCallback,
+}
+
+impl SyncedState {
+    fn deliver(&mut self, ready: Vec<(CMSampleBuffer, Vec<CMSampleBuffer>)>) {
+        for (video, audio) in ready {
+            (self.callback)(video, audio);
+        }
+    }
+}
+
+/// Output handler shared by the screen and audio outputs
+///
+/// Both outputs go through one lock, which also keeps the callback from
+/// running on two capture queues at once.
+struct SyncedOutput(Arc<Mutex<SyncedState>>);
+
+impl SCStreamOutputTrait for SyncedOutput {
+    fn did_output_sample_buffer(&self, sample_buffer: CMSampleBuffer, of_type: SCStreamOutputType) {
+        let Some(start) = sample_buffer.presentation_timestamp().as_seconds() else {
+            return;
+        };
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let ready = match of_type {
+            // Idle and blank frames carry no image to pair audio with
+            SCStreamOutputType::Screen
+                if sample_buffer.frame_status() == Some(SCFrameStatus::Complete) =>
+            {
+                state.aligner.push_video(sample_buffer, start)
+            }
+            SCStreamOutputType::Audio => {
+                let end = start + sample_buffer.duration().as_seconds().unwrap_or(0.0);
+                state.aligner.push_audio(sample_buffer, start, end)
+            }
+            _ => return,
+        };
+        state.deliver(ready);
+    }
+}
+
+/// A stream that delivers each screen frame with its audio
+///
+/// The callback receives a complete frame and the audio buffers, in order,
+/// whose presentation time falls between that frame and the next. It runs on
+/// a capture queue and holds up both outputs while it does, so it should
+/// hand heavy work off to another thread.
+pub struct SyncedCapture {
+    stream: SCStream,
+    state: Arc<Mutex<SyncedState>>,
+    window: Duration,
+}
+
+impl SyncedCapture {
+    /// Create a stream for `filter` that pairs frames with audio
+    ///
+    /// `window` is how long a frame may wait for its audio before it is
+    /// delivered with whatever has arrived.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `configuration` does not capture audio, or if the
+    /// outputs cannot be added to the stream.
+    pub fn new<F>(
+        filter: &SCContentFilter,
+        configuration: &SCStreamConfiguration,
+        window: Duration,
+        callback: F,
+    ) -> Result<Self, SCError>
+    where
+        F: FnMut(CMSampleBuffer, Vec<CMSampleBuffer>) + Send + 'static,
+    {
+        if !configuration.captures_audio() {
+            return Err(SCError::invalid_config(
+                "SyncedCapture requires a configuration that captures audio",
+            ));
+        }
+
+        let state = Arc::new(Mutex::new(SyncedState {
+            aligner: Aligner::new(window),
+            callback: Box::new(callback),
+        }));
+        let mut stream = SCStream::new(filter, configuration);
+        for of_type in [SCStreamOutputType::Screen, SCStreamOutputType::Audio] {
+            if stream
+                .add_output_handler(SyncedOutput(Arc::clone(&state)), of_type)
+                .is_none()
+            {
+                return Err(SCError::stream_error(format!(
+                    "Failed to add {of_type:?} output"
+                )));
+            }
+        }
+
+        Ok(Self {
+            stream,
+            state,
+            window,
+        })
+    }
+
+    /// Start capturing
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails to start.
+    pub fn start_capture(&self) -> Result<(), SCError> {
+        self.stream.start_capture()
+    }
+
+    /// Stop capturing and deliver the frames still waiting for audio
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails to stop. Waiting frames are
+    /// delivered either way.
+    pub fn stop_capture(&self) -> Result<(), SCError> {
+        let result = self.stream.stop_capture();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let ready = state.aligner.flush();
+        state.deliver(ready);
+        result
+    }
+
+    /// The underlying stream, e.g. for diagnostics or configuration updates
+    #[must_use]
+    pub fn stream(&self) -> &SCStream {
+        &self.stream
+    }
+
+    /// How long a frame may wait for its audio
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+impl fmt::Debug for SyncedCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncedCapture")
+            .field("stream", &self.stream)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aligner() -> Aligner<&'static str> {
+        Aligner::new(Duration::from_millis(100))
+    }
+
+    #[test]
+    fn test_frame_waits_for_audio_up_to_next_frame() {
+        let mut aligner = aligner();
+        assert!(aligner.push_video("v0", 0.0).is_empty());
+        assert!(aligner.push_audio("a0", 0.0, 0.02).is_empty());
+        assert!(aligner.push_video("v1", 0.033).is_empty());
+
+        let ready = aligner.push_audio("a1", 0.02, 0.04);
+        assert_eq!(ready, vec![("v0", vec!["a0", "a1"])]);
+        assert!(aligner.push_audio("a2", 0.04, 0.07).is_empty());
+        assert_eq!(aligner.flush(), vec![("v1", vec!["a2"])]);
+    }
+
+    #[test]
+    fn test_audio_goes_to_frame_on_screen() {
+        let mut aligner = aligner();
+        aligner.push_video("v0", 0.0);
+        aligner.push_video("v1", 0.033);
+        // Late audio for the first frame still finds it while it waits
+        aligner.push_audio("a0", 0.01, 0.02);
+        let ready = aligner.push_audio("a1", 0.04, 0.05);
+        assert_eq!(ready, vec![("v0", vec!["a0"])]);
+        assert_eq!(aligner.flush(), vec![("v1", vec!["a1"])]);
+    }
+
+    #[test]
+    fn test_frame_delivered_after_window_without_next_frame() {
+        let mut aligner = aligner();
+        aligner.push_video("v0", 0.0);
+        assert!(aligner.push_audio("a0", 0.05, 0.09).is_empty());
+        let ready = aligner.push_audio("a1", 0.09, 0.12);
+        assert_eq!(ready, vec![("v0", vec!["a0", "a1"])]);
+    }
+
+    #[test]
+    fn test_audio_before_first_frame_is_dropped() {
+        let mut aligner = aligner();
+        aligner.push_audio("a0", 0.0, 0.02);
+        aligner.push_video("v0", 0.03);
+        assert_eq!(aligner.flush(), vec![("v0", vec![])]);
+    }
+}
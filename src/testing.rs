@@ -0,0 +1,373 @@
+//! Mock shareable content for tests (requires the `testing` feature)
+//!
+//! CI machines usually have no display and no screen recording permission, so
+//! [`SCShareableContent::get`](crate::shareable_content::SCShareableContent::get)
+//! has nothing useful to return there. Installing a [`MockContent`] makes the
+//! synchronous content queries (`get`, `get_with_options` and
+//! `with_options().get()`) return the injected displays, windows and
+//! applications instead, wrapped in ordinary [`SCDisplay`], [`SCWindow`] and
+//! [`SCRunningApplication`] values whose accessors never call into the system.
+//! Query options are ignored; the mock is returned as given.
+//!
+//! Mock wrappers only describe content and never reach the system. APIs that
+//! would hand them to `ScreenCaptureKit` refuse them instead: the z-order
+//! queries and the screenshot helpers that take a window, display or
+//! application return [`SCError::InvalidConfiguration`], and
+//! [`SCContentFilterBuilder::build`] panics, so no filter, and therefore no
+//! `SCStream`, is ever created from mock content.
+//!
+//! # Examples
+//!
+//! ```
+//! use screencapturekit::shareable_content::SCShareableContent;
+//! use screencapturekit::testing::{self, MockApplication, MockContent, MockDisplay, MockWindow};
+//!
+//! testing::set_mock_content(MockContent {
+//!     displays: vec![MockDisplay::new(1, 1920, 1080)],
+//!     windows: vec![MockWindow::new(42, "Inbox").with_owner(501)],
+//!     applications: vec![MockApplication::new(501, "Mail", "com.apple.mail")],
+//! });
+//!
+//! let content = SCShareableContent::get().unwrap();
+//! assert_eq!(content.displays()[0].width(), 1920);
+//! let window = content.window_with_id(42).unwrap();
+//! assert_eq!(window.owning_application().unwrap().application_name(), "Mail");
+//!
+//! testing::clear_mock_content();
+//! ```
+//!
+//! [`SCDisplay`]: crate::shareable_content::SCDisplay
+//! [`SCWindow`]: crate::shareable_content::SCWindow
+//! [`SCRunningApplication`]: crate::shareable_content::SCRunningApplication
+//! [`SCError::InvalidConfiguration`]: crate::error::SCError::InvalidConfiguration
+//! [`SCContentFilterBuilder::build`]: crate::stream::content_filter::SCContentFilterBuilder::build
+
+use std::ffi::c_void;
+use std::sync::Mutex;
+
+use crate::cg::CGRect;
+use crate::error::SCError;
+use crate::shareable_content::SessionState;
+
+/// A fake display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockDisplay {
+    /// Returned by [`SCDisplay::display_id`](crate::shareable_content::SCDisplay::display_id)
+    pub display_id: u32,
+    /// Width in points
+    pub width: u32,
+    /// Height in points
+    pub height: u32,
+    /// Position and size in global desktop coordinates
+    pub frame: CGRect,
+}
+
+impl MockDisplay {
+    /// A display of the given size at the desktop origin
+    #[must_use]
+    pub fn new(display_id: u32, width: u32, height: u32) -> Self {
+        Self {
+            display_id,
+            width,
+            height,
+            frame: CGRect::new(0.0, 0.0, f64::from(width), f64::from(height)),
+        }
+    }
+
+    /// Move the display within the desktop
+    #[must_use]
+    pub fn with_frame(mut self, frame: CGRect) -> Self {
+        self.frame = frame;
+        self
+    }
+}
+
+/// A fake window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockWindow {
+    /// Returned by [`SCWindow::window_id`](crate::shareable_content::SCWindow::window_id)
+    pub window_id: u32,
+    /// Window title, or `None` for an untitled window
+    pub title: Option<String>,
+    /// Position and size in global desktop coordinates
+    pub frame: CGRect,
+    /// Window server layer; `0` for regular application windows
+    pub window_layer: i32,
+    /// Whether the window is currently visible on screen
+    pub is_on_screen: bool,
+    /// Whether the window is active (reported on macOS 14.0+)
+    pub is_active: bool,
+    /// Process ID of the owning entry in [`MockContent::applications`]
+    pub owner_process_id: Option<i32>,
+}
+
+impl MockWindow {
+    /// An on-screen, inactive window with no owner and an 800x600 frame
+    #[must_use]
+    pub fn new(window_id: u32, title: impl Into<String>) -> Self {
+        Self {
+            window_id,
+            title: Some(title.into()),
+            frame: CGRect::new(0.0, 0.0, 800.0, 600.0),
+            window_layer: 0,
+            is_on_screen: true,
+            is_active: false,
+            owner_process_id: None,
+        }
+    }
+
+    /// Set the window frame
+    #[must_use]
+    pub fn with_frame(mut self, frame: CGRect) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    /// Attach the window to the application with this process ID
+    #[must_use]
+    pub fn with_owner(mut self, process_id: i32) -> Self {
+        self.owner_process_id = Some(process_id);
+        self
+    }
+
+    /// Mark the window as on or off screen
+    #[must_use]
+    pub fn with_on_screen(mut self, is_on_screen: bool) -> Self {
+        self.is_on_screen = is_on_screen;
+        self
+    }
+}
+
+/// A fake running application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockApplication {
+    /// Process ID, matched against [`MockWindow::owner_process_id`]
+    pub process_id: i32,
+    /// Localized name, such as `"Mail"`
+    pub application_name: String,
+    /// Bundle identifier, such as `"com.apple.mail"`
+    pub bundle_identifier: String,
+}
+
+impl MockApplication {
+    /// An application with the given process ID, name and bundle identifier
+    #[must_use]
+    pub fn new(
+        process_id: i32,
+        application_name: impl Into<String>,
+        bundle_identifier: impl Into<String>,
+    ) -> Self {
+        Self {
+            process_id,
+            application_name: application_name.into(),
+            bundle_identifier: bundle_identifier.into(),
+        }
+    }
+}
+
+/// The content returned by queries while a mock is installed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockContent {
+    /// Returned by [`SCShareableContent::displays`](crate::shareable_content::SCShareableContent::displays)
+    pub displays: Vec<MockDisplay>,
+    /// Returned by [`SCShareableContent::windows`](crate::shareable_content::SCShareableContent::windows)
+    pub windows: Vec<MockWindow>,
+    /// Returned by [`SCShareableContent::applications`](crate::shareable_content::SCShareableContent::applications)
+    pub applications: Vec<MockApplication>,
+}
+
+/// Every mock installed so far, plus the index of the active one
+///
+/// Installed content is leaked so wrappers handed out earlier stay valid after
+/// the mock is replaced or cleared. Installing content equal to an earlier
+/// mock reuses that allocation, so only distinct contents are leaked.
+struct Registry {
+    installed: Vec<&'static MockContent>,
+    active: Option<&'static MockContent>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    installed: Vec::new(),
+    active: None,
+});
+
+fn with_registry<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    let mut registry = REGISTRY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    f(&mut registry)
+}
+
+/// Make content queries return `content` until it is cleared or replaced
+///
+/// The mock is process-wide. Tests that install different content should not
+/// run concurrently; use a shared lock or `--test-threads=1`.
+///
+/// Wrappers returned by earlier queries point into the content they came
+/// from, so installed content is never freed. Each distinct `content` is
+/// leaked once for the life of the process; installing the same content
+/// again reuses it.
+pub fn set_mock_content(content: MockContent) {
+    with_registry(|registry| {
+        let known = registry
+            .installed
+            .iter()
+            .copied()
+            .find(|known| **known == content);
+        let content = known.unwrap_or_else(|| {
+            let leaked: &'static MockContent = Box::leak(Box::new(content));
+            registry.installed.push(leaked);
+            leaked
+        });
+        registry.active = Some(content);
+    });
+}
+
+/// Restore real content queries
+pub fn clear_mock_content() {
+    with_registry(|registry| registry.active = None);
+}
+
+/// Returns `true` if a mock is currently installed
+pub fn is_mock_active() -> bool {
+    with_registry(|registry| registry.active.is_some())
+}
+
+static SESSION_STATE: Mutex<Option<SessionState>> = Mutex::new(None);
+
+/// Make [`SCShareableContent::session_state`] report `state` until it is
+/// cleared or replaced
+///
+/// Lets tests drive a lock or user switch without touching the real session.
+/// Like the content mock it is process-wide.
+///
+/// [`SCShareableContent::session_state`]: crate::shareable_content::SCShareableContent::session_state
+pub fn set_mock_session_state(state: SessionState) {
+    *SESSION_STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(state);
+}
+
+/// Restore the real session state
+pub fn clear_mock_session_state() {
+    *SESSION_STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+}
+
+pub(crate) fn session_state() -> Option<SessionState> {
+    *SESSION_STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Pointer standing in for the active mock's `SCShareableContent`
+pub(crate) fn active_content() -> Option<*const c_void> {
+    with_registry(|registry| registry.active.map(addr))
+}
+
+fn addr<T>(value: &T) -> *const c_void {
+    std::ptr::addr_of!(*value).cast()
+}
+
+/// Look up the mock object a wrapper pointer refers to
+fn find<T>(
+    ptr: *const c_void,
+    items: impl Fn(&'static MockContent) -> &'static [T],
+) -> Option<(&'static MockContent, &'static T)> {
+    if ptr.is_null() {
+        return None;
+    }
+    with_registry(|registry| {
+        registry.installed.iter().find_map(|content| {
+            items(content)
+                .iter()
+                .find(|item| addr(*item) == ptr)
+                .map(|item| (*content, item))
+        })
+    })
+}
+
+pub(crate) fn content(ptr: *const c_void) -> Option<&'static MockContent> {
+    if ptr.is_null() {
+        return None;
+    }
+    with_registry(|registry| {
+        registry
+            .installed
+            .iter()
+            .copied()
+            .find(|content| addr(*content) == ptr)
+    })
+}
+
+pub(crate) fn display(ptr: *const c_void) -> Option<&'static MockDisplay> {
+    find(ptr, |content| &content.displays).map(|(_, display)| display)
+}
+
+pub(crate) fn window(ptr: *const c_void) -> Option<&'static MockWindow> {
+    find(ptr, |content| &content.windows).map(|(_, window)| window)
+}
+
+pub(crate) fn application(ptr: *const c_void) -> Option<&'static MockApplication> {
+    find(ptr, |content| &content.applications).map(|(_, app)| app)
+}
+
+/// Pointers for each display of a mock content
+pub(crate) fn display_ptrs(content: &'static MockContent) -> Vec<*const c_void> {
+    content.displays.iter().map(addr).collect()
+}
+
+pub(crate) fn window_ptrs(content: &'static MockContent) -> Vec<*const c_void> {
+    content.windows.iter().map(addr).collect()
+}
+
+pub(crate) fn application_ptrs(content: &'static MockContent) -> Vec<*const c_void> {
+    content.applications.iter().map(addr).collect()
+}
+
+/// Pointer to the application owning a mock window, if any
+pub(crate) fn owning_application(ptr: *const c_void) -> Option<*const c_void> {
+    let (content, window) = find(ptr, |content| &content.windows)?;
+    let pid = window.owner_process_id?;
+    content
+        .applications
+        .iter()
+        .find(|app| app.process_id == pid)
+        .map(addr)
+}
+
+/// Returns `true` if `ptr` belongs to a mock object and must not reach the FFI
+pub(crate) fn is_mock(ptr: *const c_void) -> bool {
+    content(ptr).is_some()
+        || display(ptr).is_some()
+        || window(ptr).is_some()
+        || application(ptr).is_some()
+}
+
+/// Fail with an error naming `api` if `ptr` belongs to a mock object
+///
+/// Call before passing a display, window, application or content pointer to
+/// the FFI.
+pub(crate) fn reject_mock(ptr: *const c_void, api: &str) -> Result<(), SCError> {
+    if is_mock(ptr) {
+        return Err(SCError::invalid_config(format!(
+            "{api} cannot use mock content from screencapturekit::testing"
+        )));
+    }
+    Ok(())
+}
+
+/// Wrap `filter` in a picker result as if the user had picked it
+///
+/// The content rect and scale are read from the filter, so the
+/// [`SCPickerResult`](crate::content_sharing_picker::SCPickerResult)
+/// accessors can be exercised without presenting the picker UI. Unlike the
+/// mock content above, this needs a real filter and calls into the system.
+#[cfg(feature = "macos_14_0")]
+pub fn picker_result(
+    filter: &crate::stream::content_filter::SCContentFilter,
+) -> crate::content_sharing_picker::SCPickerResult {
+    let ptr = unsafe { crate::ffi::sc_picker_result_create_with_filter(filter.as_ptr()) };
+    crate::content_sharing_picker::SCPickerResult::from_ptr(ptr)
+}
@@ -0,0 +1,55 @@
+//! Mach absolute time conversion
+//!
+//! `mach_absolute_time` ticks are the clock domain shared by captured
+//! frames (see [`CMSampleBuffer::host_time`](crate::cm::CMSampleBuffer::host_time)),
+//! `AVFoundation` capture devices and Core Audio host times. The length of a
+//! tick depends on the machine: one nanosecond on Intel, 125/3 nanoseconds on
+//! Apple silicon.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+}
+
+extern "C" {
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> i32;
+}
+
+fn timebase() -> MachTimebaseInfo {
+    static TIMEBASE: OnceLock<MachTimebaseInfo> = OnceLock::new();
+    *TIMEBASE.get_or_init(|| {
+        let mut info = MachTimebaseInfo { numer: 0, denom: 0 };
+        let status = unsafe { mach_timebase_info(&mut info) };
+        if status != 0 || info.numer == 0 || info.denom == 0 {
+            MachTimebaseInfo { numer: 1, denom: 1 }
+        } else {
+            info
+        }
+    })
+}
+
+/// Convert a span of `mach_absolute_time` ticks to a [`Duration`]
+///
+/// Pass the difference of two timestamps to measure the time between them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::cm::CMSampleBuffer;
+/// use screencapturekit::utils::mach_time_to_duration;
+///
+/// fn frame_gap(previous: &CMSampleBuffer, current: &CMSampleBuffer) -> std::time::Duration {
+///     mach_time_to_duration(current.host_time().saturating_sub(previous.host_time()))
+/// }
+/// ```
+#[must_use]
+pub fn mach_time_to_duration(ticks: u64) -> Duration {
+    let timebase = timebase();
+    let nanos = u128::from(ticks) * u128::from(timebase.numer) / u128::from(timebase.denom);
+    u64::try_from(nanos).map_or(Duration::MAX, Duration::from_nanos)
+}
@@ -7,9 +7,18 @@
 //! - [`error`] - Error types and result aliases
 //! - [`ffi_string`] - FFI string retrieval utilities
 //! - [`four_char_code`] - Four-character code handling (used for pixel formats, codecs)
+//! - [`mach_time`] - Conversion of `mach_absolute_time` ticks to durations
+//! - [`os_version`] - Runtime macOS version detection
 //! - [`sync_completion`] - Completion utilities for async FFI callbacks
+//! - `timer` - Background threads that run a callback at a fixed interval
 
 pub mod error;
 pub mod ffi_string;
 pub mod four_char_code;
+pub mod mach_time;
+pub mod os_version;
 pub mod sync_completion;
+pub(crate) mod timer;
+
+pub use mach_time::mach_time_to_duration;
+pub use os_version::MacOSVersion;
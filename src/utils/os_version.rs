@@ -0,0 +1,65 @@
+//! Runtime macOS version detection
+//!
+//! The `macos_*` features decide which APIs are compiled in; this module tells
+//! which of them the running system actually supports.
+
+use std::fmt;
+
+/// Version of the running macOS
+///
+/// Versions compare component by component, so `MacOSVersion::new(14, 0, 0)`
+/// is less than `MacOSVersion::new(14, 2, 0)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use screencapturekit::utils::MacOSVersion;
+///
+/// let version = MacOSVersion::current();
+/// if version.is_at_least(14, 0) {
+///     println!("SCScreenshotManager is available on {version}");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacOSVersion {
+    /// Major version, e.g. 14 for macOS Sonoma
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+    /// Patch version
+    pub patch: u32,
+}
+
+impl MacOSVersion {
+    /// Create a version from its components
+    #[must_use]
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Get the version of the running system
+    #[must_use]
+    pub fn current() -> Self {
+        let mut major = 0;
+        let mut minor = 0;
+        let mut patch = 0;
+        unsafe { crate::ffi::sc_get_os_version(&mut major, &mut minor, &mut patch) };
+        Self::new(major, minor, patch)
+    }
+
+    /// Check whether this version is `major.minor` or later
+    #[must_use]
+    pub const fn is_at_least(&self, major: u32, minor: u32) -> bool {
+        self.major > major || (self.major == major && self.minor >= minor)
+    }
+}
+
+impl fmt::Display for MacOSVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
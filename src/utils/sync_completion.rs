@@ -23,6 +23,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Condvar, Mutex};
 use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 // ============================================================================
 // Synchronous Completion (blocking)
@@ -88,6 +89,30 @@ impl<T> SyncCompletion<T> {
             .unwrap_or_else(|| Err("Completion signaled without result".to_string()))
     }
 
+    /// Wait for the completion callback for at most `timeout`
+    ///
+    /// Returns `None` if the callback has not signaled completion in time. The
+    /// callback may still run later; its result is then dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal mutex is poisoned.
+    pub fn wait_timeout(self, timeout: Duration) -> Option<Result<T, String>> {
+        let (lock, cvar) = &*self.inner;
+        let (mut state, _) = cvar
+            .wait_timeout_while(lock.lock().unwrap(), timeout, |state| !state.completed)
+            .unwrap();
+        if !state.completed {
+            return None;
+        }
+        Some(
+            state
+                .result
+                .take()
+                .unwrap_or_else(|| Err("Completion signaled without result".to_string())),
+        )
+    }
+
     /// Signal successful completion with a value
     ///
     /// # Safety
@@ -0,0 +1,173 @@
+//! Background threads that run a callback at a fixed interval
+
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+type StopFlag = (Mutex<bool>, Condvar);
+
+/// Handle to a thread started by [`spawn_timer`]
+///
+/// The thread stops when the handle is dropped. A tick already in progress
+/// finishes first; use [`stop`](Self::stop) to wait for it.
+pub struct Timer {
+    stop: Arc<StopFlag>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Timer {
+    /// Stop the thread and wait for a tick in progress to finish
+    ///
+    /// Must not be called from inside `tick`.
+    pub fn stop(mut self) {
+        self.signal();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    fn signal(&self) {
+        let (stopped, wakeup) = &*self.stop;
+        *stopped.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wakeup.notify_all();
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.signal();
+    }
+}
+
+/// Call `tick` every `interval` on a new thread named `name`
+///
+/// The first tick happens one `interval` after the call. Ticks are scheduled
+/// from the start time rather than from the end of the previous tick, so a
+/// tick that takes less than `interval` does not push later ones back. Ticks
+/// missed because one ran long are skipped rather than fired in a burst: the
+/// next one follows a full `interval` later. `tick` is passed a function that
+/// reports whether the [`Timer`] has been dropped in the meantime, and returns
+/// `false` to end the thread.
+pub fn spawn_timer<F>(name: &str, interval: Duration, tick: F) -> std::io::Result<Timer>
+where
+    F: FnMut(&dyn Fn() -> bool) -> bool + Send + 'static,
+{
+    spawn_timer_after(name, interval, interval, tick)
+}
+
+/// Like [`spawn_timer`], but the first tick happens after `first` instead of
+/// one `interval`
+pub fn spawn_timer_after<F>(
+    name: &str,
+    first: Duration,
+    interval: Duration,
+    mut tick: F,
+) -> std::io::Result<Timer>
+where
+    F: FnMut(&dyn Fn() -> bool) -> bool + Send + 'static,
+{
+    let stop = Arc::new((Mutex::new(false), Condvar::new()));
+    let thread_stop = Arc::clone(&stop);
+    let thread = std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || run_timer(&thread_stop, first, interval, &mut tick))?;
+    Ok(Timer {
+        stop,
+        thread: Some(thread),
+    })
+}
+
+fn run_timer(
+    stop: &StopFlag,
+    first: Duration,
+    interval: Duration,
+    tick: &mut dyn FnMut(&dyn Fn() -> bool) -> bool,
+) {
+    let (stopped, wakeup) = stop;
+    let is_stopped = || stopped.lock().map_or(true, |stopped| *stopped);
+    let mut next_tick = Instant::now() + first;
+    loop {
+        let Ok(mut guard) = stopped.lock() else {
+            return;
+        };
+        while !*guard {
+            let now = Instant::now();
+            if now >= next_tick {
+                break;
+            }
+            guard = match wakeup.wait_timeout(guard, next_tick - now) {
+                Ok((guard, _)) => guard,
+                Err(_) => return,
+            };
+        }
+        if *guard {
+            return;
+        }
+        drop(guard);
+
+        if !tick(&is_stopped) {
+            return;
+        }
+        next_tick += interval;
+        // Behind schedule: restart it from now instead of catching up
+        let now = Instant::now();
+        if next_tick <= now {
+            next_tick = now + interval;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_stop_waits_for_the_tick_in_progress() {
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&ticks);
+        let timer = spawn_timer_after(
+            "test-timer",
+            Duration::ZERO,
+            Duration::from_secs(60),
+            move |_| {
+                std::thread::sleep(Duration::from_millis(50));
+                counter.fetch_add(1, Ordering::SeqCst);
+                true
+            },
+        )
+        .expect("Failed to spawn timer");
+
+        // The first tick starts right away; stopping waits until it is done
+        std::thread::sleep(Duration::from_millis(10));
+        timer.stop();
+        assert_eq!(ticks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_late_tick_does_not_fire_a_burst() {
+        let interval = Duration::from_millis(30);
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&ticks);
+        let timer = spawn_timer_after("test-timer", Duration::ZERO, interval, move |_| {
+            let mut ticks = recorded.lock().unwrap_or_else(PoisonError::into_inner);
+            ticks.push(Instant::now());
+            let count = ticks.len();
+            drop(ticks);
+            if count == 1 {
+                // Overrun several intervals
+                std::thread::sleep(interval * 5);
+            }
+            count < 3
+        })
+        .expect("Failed to spawn timer");
+
+        std::thread::sleep(interval * 10);
+        timer.stop();
+        let started = ticks.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        assert_eq!(started.len(), 3);
+        // The tick after the slow one waits a full interval, and so do later ones
+        assert!(started[1] - started[0] >= interval * 5 + interval / 2);
+        assert!(started[2] - started[1] >= interval / 2);
+    }
+}
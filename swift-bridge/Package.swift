@@ -78,7 +78,8 @@ let package = Package(
         // CoreGraphics framework bindings (CGRect, CGSize, CGPoint, CGImage)
         .target(
             name: "CoreGraphicsBridge",
-            path: "Sources/CoreGraphics"),
+            path: "Sources/CoreGraphics",
+            swiftSettings: swiftSettings),
         // IOSurface framework bindings
         .target(
             name: "IOSurfaceBridge",
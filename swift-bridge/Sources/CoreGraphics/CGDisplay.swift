@@ -62,6 +62,24 @@ public func cgDisplayCopyCurrentMode(
     return true
 }
 
+/// 获取显示器旋转角度（顺时针，度）
+@_cdecl("cg_display_rotation")
+public func cgDisplayRotation(_ displayID: UInt32) -> Double {
+    CGDisplayRotation(displayID)
+}
+
+/// 显示器是否处于镜像组中（无论作为镜像源还是镜像目标）
+@_cdecl("cg_display_is_in_mirror_set")
+public func cgDisplayIsInMirrorSet(_ displayID: UInt32) -> Bool {
+    CGDisplayIsInMirrorSet(displayID) != 0
+}
+
+/// 返回该显示器所镜像的源显示器 ID；未镜像其他显示器时返回 0（kCGNullDirectDisplay）
+@_cdecl("cg_display_mirrors_display")
+public func cgDisplayMirrorsDisplay(_ displayID: UInt32) -> UInt32 {
+    CGDisplayMirrorsDisplay(displayID)
+}
+
 /// 创建指定显示器的 CGImage（低版本截图备用）
 @_cdecl("cg_display_create_image")
 public func cgDisplayCreateImage(_ displayID: UInt32) -> OpaquePointer? {
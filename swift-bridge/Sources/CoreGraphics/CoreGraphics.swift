@@ -1,6 +1,8 @@
 // CoreGraphics Bridge - CGRect, CGSize, CGPoint, CGImage
 
 import CoreGraphics
+import CoreImage
+import CoreText
 import Foundation
 import ImageIO
 import UniformTypeIdentifiers
@@ -88,6 +90,31 @@ public func getCGImageHeight(_ image: OpaquePointer) -> Int {
     return cgImage.height
 }
 
+/// Name of the image's color space, e.g. `kCGColorSpaceSRGB`
+/// Fails for unnamed and device-dependent color spaces
+@_cdecl("cgimage_get_color_space_name")
+public func getCGImageColorSpaceName(_ image: OpaquePointer, _ buffer: UnsafeMutablePointer<CChar>, _ bufferSize: Int) -> Bool {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard bufferSize > 0,
+          let name = cgImage.colorSpace?.name as String?,
+          !name.hasPrefix("kCGColorSpaceDevice")
+    else {
+        return false
+    }
+    return name.withCString { src in
+        guard strlen(src) < bufferSize else { return false }
+        strcpy(buffer, src)
+        return true
+    }
+}
+
+@_cdecl("cgimage_get_size")
+public func getCGImageSize(_ image: OpaquePointer, _ outWidth: UnsafeMutablePointer<Int>, _ outHeight: UnsafeMutablePointer<Int>) {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    outWidth.pointee = cgImage.width
+    outHeight.pointee = cgImage.height
+}
+
 @_cdecl("cgimage_release")
 public func releaseCGImage(_ image: OpaquePointer) {
     Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).release()
@@ -144,6 +171,259 @@ public func freeCGImageData(_ ptr: UnsafeMutableRawPointer) {
     ptr.deallocate()
 }
 
+/// Creates an sRGB image from premultiplied RGBA bytes (the layout `cgimage_get_data`
+/// produces). The bytes are copied, so the caller keeps ownership of `data`.
+@_cdecl("cgimage_create_from_rgba")
+public func createCGImageFromRGBA(_ data: UnsafePointer<UInt8>, _ width: Int, _ height: Int) -> OpaquePointer? {
+    let bytesPerRow = width * 4
+    guard let colorSpace = CGColorSpace(name: CGColorSpace.sRGB),
+          let provider = CGDataProvider(data: Data(bytes: data, count: bytesPerRow * height) as CFData),
+          let image = CGImage(
+              width: width,
+              height: height,
+              bitsPerComponent: 8,
+              bitsPerPixel: 32,
+              bytesPerRow: bytesPerRow,
+              space: colorSpace,
+              bitmapInfo: CGBitmapInfo(rawValue: CGImageAlphaInfo.premultipliedLast.rawValue),
+              provider: provider,
+              decode: nil,
+              shouldInterpolate: false,
+              intent: .defaultIntent
+          )
+    else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(image).toOpaque())
+}
+
+// MARK: - Annotations
+
+/// Creates an sRGB bitmap context the size of `image` with the image already drawn, and
+/// flips the coordinate system so the origin is at the top-left like image pixels.
+func makeFlippedCanvas(for image: CGImage) -> CGContext? {
+    guard let colorSpace = CGColorSpace(name: CGColorSpace.sRGB),
+          let context = CGContext(
+              data: nil,
+              width: image.width,
+              height: image.height,
+              bitsPerComponent: 8,
+              bytesPerRow: 0,
+              space: colorSpace,
+              bitmapInfo: CGImageAlphaInfo.premultipliedLast.rawValue
+          )
+    else {
+        return nil
+    }
+    context.draw(image, in: CGRect(x: 0, y: 0, width: image.width, height: image.height))
+    context.translateBy(x: 0, y: CGFloat(image.height))
+    context.scaleBy(x: 1, y: -1)
+    return context
+}
+
+/// Draws one line of text with its top-left corner at `origin` on a flipped canvas
+private func drawText(_ text: String, at origin: CGPoint, color: CGColor, size: CGFloat, in context: CGContext) {
+    let font = CTFontCreateUIFontForLanguage(.system, size, nil) ?? CTFontCreateWithName("Helvetica" as CFString, size, nil)
+    let attributes: [CFString: Any] = [
+        kCTFontAttributeName: font,
+        kCTForegroundColorAttributeName: color,
+    ]
+    guard let attributed = CFAttributedStringCreate(nil, text as CFString, attributes as CFDictionary) else {
+        return
+    }
+    let line = CTLineCreateWithAttributedString(attributed)
+    var ascent: CGFloat = 0
+    CTLineGetTypographicBounds(line, &ascent, nil, nil)
+
+    // CoreText draws glyphs bottom-up, so undo the canvas flip for them
+    context.textMatrix = CGAffineTransform(scaleX: 1, y: -1)
+    context.textPosition = CGPoint(x: origin.x, y: origin.y + ascent)
+    CTLineDraw(line, context)
+}
+
+/// Renders annotations onto a copy of `image`.
+/// Kinds: 0 = rect outline, 1 = filled rect, 2 = line, 3 = text. Each operation has four
+/// geometry values (x, y, width, height or x0, y0, x1, y1), four RGBA components, a stroke
+/// width (font size for text) and a text pointer that is null for non-text operations.
+@_cdecl("cgimage_draw_annotations")
+public func drawCGImageAnnotations(
+    _ image: OpaquePointer,
+    _ kinds: UnsafePointer<Int32>,
+    _ geometry: UnsafePointer<Double>,
+    _ colors: UnsafePointer<Double>,
+    _ widths: UnsafePointer<Double>,
+    _ texts: UnsafePointer<UnsafePointer<CChar>?>,
+    _ count: Int
+) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let context = makeFlippedCanvas(for: cgImage), let colorSpace = context.colorSpace else {
+        return nil
+    }
+
+    for index in 0 ..< count {
+        let g = geometry + index * 4
+        let c = colors + index * 4
+        guard let color = CGColor(colorSpace: colorSpace, components: [c[0], c[1], c[2], c[3]]) else {
+            continue
+        }
+        let width = CGFloat(widths[index])
+
+        switch kinds[index] {
+        case 0:
+            context.setStrokeColor(color)
+            context.setLineWidth(width)
+            context.stroke(CGRect(x: g[0], y: g[1], width: g[2], height: g[3]))
+        case 1:
+            context.setFillColor(color)
+            context.fill(CGRect(x: g[0], y: g[1], width: g[2], height: g[3]))
+        case 2:
+            context.setStrokeColor(color)
+            context.setLineWidth(width)
+            context.setLineCap(.round)
+            context.strokeLineSegments(between: [CGPoint(x: g[0], y: g[1]), CGPoint(x: g[2], y: g[3])])
+        case 3:
+            guard let text = texts[index] else { continue }
+            drawText(String(cString: text), at: CGPoint(x: g[0], y: g[1]), color: color, size: width, in: context)
+        default:
+            continue
+        }
+    }
+
+    guard let result = context.makeImage() else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(result).toOpaque())
+}
+
+// MARK: - Redaction
+
+/// Returns `image` run through a blur (mode 0) or pixellate (mode 1) filter at full size.
+/// The input is clamped to its extent first so edges don't fade to transparent.
+private func filteredImage(_ image: CGImage, mode: Int32, amount: Double) -> CGImage? {
+    let input = CIImage(cgImage: image)
+    let filter: CIFilter?
+    switch mode {
+    case 0:
+        filter = CIFilter(name: "CIGaussianBlur", parameters: [kCIInputRadiusKey: amount])
+    case 1:
+        filter = CIFilter(name: "CIPixellate", parameters: [
+            kCIInputScaleKey: max(amount, 1),
+            kCIInputCenterKey: CIVector(x: 0, y: 0),
+        ])
+    default:
+        filter = nil
+    }
+    filter?.setValue(input.clampedToExtent(), forKey: kCIInputImageKey)
+    guard let output = filter?.outputImage?.cropped(to: input.extent) else {
+        return nil
+    }
+    return CIContext().createCGImage(output, from: input.extent)
+}
+
+/// Hides `count` rectangles (x, y, width, height in top-left pixel coordinates) of a copy
+/// of `image`. Modes: 0 = blur with radius `amount`, 1 = pixelate with block size `amount`,
+/// 2 = fill with the RGBA `color`.
+@_cdecl("cgimage_redact")
+public func redactCGImage(
+    _ image: OpaquePointer,
+    _ rects: UnsafePointer<Double>,
+    _ count: Int,
+    _ mode: Int32,
+    _ amount: Double,
+    _ color: UnsafePointer<Double>
+) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    guard let context = makeFlippedCanvas(for: cgImage), let colorSpace = context.colorSpace else {
+        return nil
+    }
+    let regions = (0 ..< count).map { index in
+        CGRect(x: rects[index * 4], y: rects[index * 4 + 1], width: rects[index * 4 + 2], height: rects[index * 4 + 3])
+    }
+
+    if mode == 2 {
+        guard let fill = CGColor(colorSpace: colorSpace, components: [color[0], color[1], color[2], color[3]]) else {
+            return nil
+        }
+        context.setFillColor(fill)
+        context.fill(regions)
+    } else if !regions.isEmpty {
+        guard let filtered = filteredImage(cgImage, mode: mode, amount: amount) else {
+            return nil
+        }
+        context.saveGState()
+        context.clip(to: regions)
+        // Draw unflipped so the filtered image lines up with the original
+        context.scaleBy(x: 1, y: -1)
+        context.translateBy(x: 0, y: -CGFloat(cgImage.height))
+        context.draw(filtered, in: CGRect(x: 0, y: 0, width: cgImage.width, height: cgImage.height))
+        context.restoreGState()
+    }
+
+    guard let result = context.makeImage() else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(result).toOpaque())
+}
+
+/// ImageIO options for a thumbnail no larger than `maxPixelSize` on its longer side
+private func thumbnailOptions(maxPixelSize: Int) -> CFDictionary {
+    [
+        kCGImageSourceCreateThumbnailFromImageAlways: true,
+        kCGImageSourceCreateThumbnailWithTransform: true,
+        kCGImageSourceShouldCacheImmediately: true,
+        kCGImageSourceThumbnailMaxPixelSize: maxPixelSize,
+    ] as CFDictionary
+}
+
+@_cdecl("cgimage_create_thumbnail")
+public func createCGImageThumbnail(_ image: OpaquePointer, _ maxPixelSize: Int) -> OpaquePointer? {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+
+    // The pixels are already decoded, so draw them straight into a smaller
+    // context rather than going through an ImageIO source
+    let longest = max(cgImage.width, cgImage.height)
+    guard longest > maxPixelSize else {
+        return OpaquePointer(Unmanaged.passRetained(cgImage).toOpaque())
+    }
+    let scale = Double(maxPixelSize) / Double(longest)
+    let width = max(1, Int((Double(cgImage.width) * scale).rounded()))
+    let height = max(1, Int((Double(cgImage.height) * scale).rounded()))
+
+    let colorSpace = cgImage.colorSpace.flatMap { $0.supportsOutput ? $0 : nil }
+        ?? CGColorSpace(name: CGColorSpace.sRGB)
+    guard let colorSpace,
+          let context = CGContext(
+              data: nil,
+              width: width,
+              height: height,
+              bitsPerComponent: 8,
+              bytesPerRow: 0,
+              space: colorSpace,
+              bitmapInfo: CGImageAlphaInfo.premultipliedLast.rawValue
+          )
+    else {
+        return nil
+    }
+    context.interpolationQuality = .high
+    context.draw(cgImage, in: CGRect(x: 0, y: 0, width: width, height: height))
+    guard let thumbnail = context.makeImage() else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(thumbnail).toOpaque())
+}
+
+@_cdecl("cgimage_create_thumbnail_from_file")
+public func createCGImageThumbnailFromFile(_ pathPtr: UnsafePointer<CChar>, _ maxPixelSize: Int) -> OpaquePointer? {
+    let url = URL(fileURLWithPath: String(cString: pathPtr))
+    // Without caching, only the downsampled image is ever decoded
+    guard let source = CGImageSourceCreateWithURL(url as CFURL, [kCGImageSourceShouldCache: false] as CFDictionary),
+          let thumbnail = CGImageSourceCreateThumbnailAtIndex(source, 0, thumbnailOptions(maxPixelSize: maxPixelSize))
+    else {
+        return nil
+    }
+    return OpaquePointer(Unmanaged.passRetained(thumbnail).toOpaque())
+}
+
 @_cdecl("cgimage_save_png")
 public func saveCGImageToPNG(_ image: OpaquePointer, _ pathPtr: UnsafePointer<CChar>) -> Bool {
     let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
@@ -158,40 +438,165 @@ public func saveCGImageToPNG(_ image: OpaquePointer, _ pathPtr: UnsafePointer<CC
     return CGImageDestinationFinalize(destination)
 }
 
-/// Save CGImage to file with specified format
-/// format: 0=PNG, 1=JPEG, 2=TIFF, 3=GIF, 4=BMP, 5=HEIC
-/// quality: 0.0-1.0 for lossy formats (JPEG, HEIC)
+/// Type identifier for a Rust `ImageFormat` id:
+/// 0=PNG, 1=JPEG, 2=TIFF, 3=GIF, 4=BMP, 5=HEIC, 6=WebP, 7=AVIF
+private func imageTypeIdentifier(forFormat format: Int32) -> String? {
+    switch format {
+    case 0: return UTType.png.identifier
+    case 1: return UTType.jpeg.identifier
+    case 2: return UTType.tiff.identifier
+    case 3: return UTType.gif.identifier
+    case 4: return UTType.bmp.identifier
+    case 5: return UTType.heic.identifier
+    case 6: return "org.webmproject.webp"
+    case 7: return "public.avif"
+    default: return nil
+    }
+}
+
+/// Whether ImageIO on the running system can write the given format
+@_cdecl("cgimage_destination_supports_format")
+public func cgimageDestinationSupportsFormat(_ format: Int32) -> Bool {
+    guard let identifier = imageTypeIdentifier(forFormat: format),
+          let supported = CGImageDestinationCopyTypeIdentifiers() as? [String]
+    else {
+        return false
+    }
+    return supported.contains(identifier)
+}
+
+/// Save CGImage to file with specified format (see `imageTypeIdentifier`)
+/// quality: 0.0-1.0 for lossy formats (JPEG, HEIC, WebP, AVIF)
 @_cdecl("cgimage_save_to_file")
 public func saveCGImageToFile(_ image: OpaquePointer, _ pathPtr: UnsafePointer<CChar>, _ format: Int32, _ quality: Float) -> Bool {
     let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
-    let path = String(cString: pathPtr)
-    let url = URL(fileURLWithPath: path)
+    return writeCGImage(cgImage, to: String(cString: pathPtr), format: format, quality: quality, metadata: [:])
+}
 
-    let utType: UTType
-    switch format {
-    case 0: utType = .png
-    case 1: utType = .jpeg
-    case 2: utType = .tiff
-    case 3: utType = .gif
-    case 4: utType = .bmp
-    case 5: utType = .heic
-    default: return false
+@_cdecl("cgimage_save_to_file_with_metadata")
+public func saveCGImageToFileWithMetadata(
+    _ image: OpaquePointer,
+    _ pathPtr: UnsafePointer<CChar>,
+    _ format: Int32,
+    _ quality: Float,
+    _ captureTime: Double,
+    _ descriptionPtr: UnsafePointer<CChar>?,
+    _ dpi: Double
+) -> Bool {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+
+    var metadata: [CFString: Any] = [:]
+    var tiff: [CFString: Any] = [:]
+    var exif: [CFString: Any] = [:]
+    var png: [CFString: Any] = [:]
+
+    if captureTime.isFinite {
+        // EXIF/TIFF date strings have no time zone and are read as local time
+        let formatter = DateFormatter()
+        formatter.locale = Locale(identifier: "en_US_POSIX")
+        formatter.dateFormat = "yyyy:MM:dd HH:mm:ss"
+        let date = Date(timeIntervalSince1970: captureTime)
+        let stamp = formatter.string(from: date)
+        tiff[kCGImagePropertyTIFFDateTime] = stamp
+        exif[kCGImagePropertyExifDateTimeOriginal] = stamp
+        exif[kCGImagePropertyExifDateTimeDigitized] = stamp
+        png[kCGImagePropertyPNGCreationTime] = stamp
     }
 
-    guard let destination = CGImageDestinationCreateWithURL(url as CFURL, utType.identifier as CFString, 1, nil) else {
+    if let descriptionPtr = descriptionPtr {
+        let description = String(cString: descriptionPtr)
+        tiff[kCGImagePropertyTIFFImageDescription] = description
+        exif[kCGImagePropertyExifUserComment] = description
+        png[kCGImagePropertyPNGDescription] = description
+    }
+
+    if dpi > 0 {
+        metadata[kCGImagePropertyDPIWidth] = dpi
+        metadata[kCGImagePropertyDPIHeight] = dpi
+    }
+
+    if !tiff.isEmpty { metadata[kCGImagePropertyTIFFDictionary] = tiff }
+    if !exif.isEmpty { metadata[kCGImagePropertyExifDictionary] = exif }
+    if !png.isEmpty { metadata[kCGImagePropertyPNGDictionary] = png }
+
+    return writeCGImage(cgImage, to: String(cString: pathPtr), format: format, quality: quality, metadata: metadata)
+}
+
+private func writeCGImage(_ cgImage: CGImage, to path: String, format: Int32, quality: Float, metadata: [CFString: Any]) -> Bool {
+    let url = URL(fileURLWithPath: path)
+
+    guard let identifier = imageTypeIdentifier(forFormat: format),
+          let destination = CGImageDestinationCreateWithURL(url as CFURL, identifier as CFString, 1, nil)
+    else {
         return false
     }
 
+    var properties = metadata
     // Set quality for lossy formats
-    var properties: [CFString: Any]? = nil
-    if format == 1 || format == 5 { // JPEG or HEIC
-        properties = [kCGImageDestinationLossyCompressionQuality: quality]
+    if [1, 5, 6, 7].contains(format) { // JPEG, HEIC, WebP or AVIF
+        properties[kCGImageDestinationLossyCompressionQuality] = quality
     }
 
-    CGImageDestinationAddImage(destination, cgImage, properties as CFDictionary?)
+    CGImageDestinationAddImage(destination, cgImage, properties.isEmpty ? nil : properties as CFDictionary)
     return CGImageDestinationFinalize(destination)
 }
 
+/// Save an image without losing its extended dynamic range
+///
+/// For HDR sources, HEIC, AVIF and JPEG get an SDR base image plus an ISO HDR
+/// gain map on macOS 15+. TIFF keeps the extended-range pixels and color
+/// space as they are, and extended-range PNG is re-encoded as 16-bit PQ.
+/// Other formats, gain-map formats on older systems and SDR sources are
+/// written unchanged.
+@_cdecl("cgimage_save_hdr")
+public func saveCGImageHDR(_ image: OpaquePointer, _ pathPtr: UnsafePointer<CChar>, _ format: Int32, _ quality: Float) -> Bool {
+    let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
+    let path = String(cString: pathPtr)
+
+    let colorSpace = cgImage.colorSpace
+    let extendedRange = colorSpace.map(CGColorSpaceUsesExtendedRange) ?? false
+    // PQ and HLG images are HDR without needing values above 1.0
+    let isHDR = extendedRange || (colorSpace.map(CGColorSpaceUsesITUR_2100TF) ?? false)
+    guard isHDR else {
+        return writeCGImage(cgImage, to: path, format: format, quality: quality, metadata: [:])
+    }
+
+    switch format {
+    case 0 where extendedRange: // PNG: no extended range, so encode the highlights in PQ
+        guard let pqImage = convertToPQ(cgImage) else { return false }
+        return writeCGImage(pqImage, to: path, format: format, quality: quality, metadata: [:])
+    case 1, 5, 7: // JPEG, HEIC, AVIF
+        var metadata: [CFString: Any] = [:]
+        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+        if #available(macOS 15.0, *) {
+            metadata[kCGImageDestinationEncodeRequest] = kCGImageDestinationEncodeToISOHDR
+        }
+        #endif
+        return writeCGImage(cgImage, to: path, format: format, quality: quality, metadata: metadata)
+    default:
+        return writeCGImage(cgImage, to: path, format: format, quality: quality, metadata: [:])
+    }
+}
+
+/// Redraw an image into a 16-bit Rec. 2100 PQ bitmap
+private func convertToPQ(_ image: CGImage) -> CGImage? {
+    guard let colorSpace = CGColorSpace(name: CGColorSpace.itur_2100_PQ),
+          let context = CGContext(
+              data: nil,
+              width: image.width,
+              height: image.height,
+              bitsPerComponent: 16,
+              bytesPerRow: 0,
+              space: colorSpace,
+              bitmapInfo: CGImageAlphaInfo.premultipliedLast.rawValue | CGBitmapInfo.byteOrder16Little.rawValue
+          )
+    else {
+        return nil
+    }
+    context.draw(image, in: CGRect(x: 0, y: 0, width: image.width, height: image.height))
+    return context.makeImage()
+}
+
 @_cdecl("cgimage_hash")
 public func cgimageHash(_ image: OpaquePointer) -> Int {
     let cgImage = Unmanaged<CGImage>.fromOpaque(UnsafeRawPointer(image)).takeUnretainedValue()
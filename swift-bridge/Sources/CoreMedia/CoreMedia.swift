@@ -30,13 +30,28 @@ public func cm_sample_buffer_get_image_buffer(_ sampleBuffer: UnsafeMutableRawPo
     return Unmanaged.passRetained(imageBuffer).toOpaque()
 }
 
+/// Returns the backing surface without retaining it; it is only valid while the sample buffer is
+@_cdecl("cm_sample_buffer_peek_iosurface")
+public func cm_sample_buffer_peek_iosurface(_ sampleBuffer: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer? {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+    guard let imageBuffer = CMSampleBufferGetImageBuffer(buffer),
+          let ioSurface = CVPixelBufferGetIOSurface(imageBuffer)
+    else {
+        return nil
+    }
+    return Unmanaged.passUnretained(ioSurface.takeUnretainedValue()).toOpaque()
+}
+
 @_cdecl("cm_sample_buffer_get_frame_status")
 public func cm_sample_buffer_get_frame_status(_ sampleBuffer: UnsafeMutableRawPointer) -> Int32 {
     let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
 
+    // The attachment is an NSNumber, which doesn't bridge to SCFrameStatus
+    // directly; raw values this SDK doesn't know are reported as missing
     guard let attachments = CMSampleBufferGetSampleAttachmentsArray(buffer, createIfNecessary: false) as? [[CFString: Any]],
           let firstAttachment = attachments.first,
-          let status = firstAttachment[SCStreamFrameInfo.status.rawValue as CFString] as? SCFrameStatus
+          let rawStatus = firstAttachment[SCStreamFrameInfo.status.rawValue as CFString] as? Int,
+          let status = SCFrameStatus(rawValue: rawStatus)
     else {
         return -1
     }
@@ -59,6 +74,20 @@ public func cm_sample_buffer_get_display_time(_ sampleBuffer: UnsafeMutableRawPo
     return true
 }
 
+/// Per-output delivery counter attached by the stream output handler.
+/// Must match `sequenceNumberAttachmentKey` in the ScreenCaptureKit bridge.
+@_cdecl("cm_sample_buffer_get_sequence_number")
+public func cm_sample_buffer_get_sequence_number(_ sampleBuffer: UnsafeMutableRawPointer, _ outValue: UnsafeMutablePointer<UInt64>) -> Bool {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+
+    guard let number = CMGetAttachment(buffer, key: "ScreenCaptureKitRsSequenceNumber" as CFString, attachmentModeOut: nil) as? NSNumber else {
+        return false
+    }
+
+    outValue.pointee = number.uint64Value
+    return true
+}
+
 @_cdecl("cm_sample_buffer_get_scale_factor")
 public func cm_sample_buffer_get_scale_factor(_ sampleBuffer: UnsafeMutableRawPointer, _ outValue: UnsafeMutablePointer<Float64>) -> Bool {
     let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
@@ -233,6 +262,18 @@ public func cm_sample_buffer_get_presentation_timestamp(_ sampleBuffer: UnsafeMu
     outEpoch.pointee = time.epoch
 }
 
+/// Presentation timestamp in mach_absolute_time units, or 0 if the buffer has none.
+/// ScreenCaptureKit stamps samples against the host time clock, so this is exact.
+@_cdecl("cm_sample_buffer_get_host_time")
+public func cm_sample_buffer_get_host_time(_ sampleBuffer: UnsafeMutableRawPointer) -> UInt64 {
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+    let time = CMSampleBufferGetPresentationTimeStamp(buffer)
+    guard time.isValid, time.isNumeric else {
+        return 0
+    }
+    return CMClockConvertHostTimeToSystemUnits(time)
+}
+
 @_cdecl("cm_sample_buffer_get_decode_timestamp")
 public func cm_sample_buffer_get_decode_timestamp(
     _ sampleBuffer: UnsafeMutableRawPointer,
@@ -746,6 +787,20 @@ public func cm_format_description_get_audio_format_flags(_ formatDescription: Un
     return asbd.pointee.mFormatFlags
 }
 
+/// Copies the whole AudioStreamBasicDescription; fails for non-audio descriptions
+@_cdecl("cm_format_description_get_audio_stream_basic_description")
+public func cm_format_description_get_audio_stream_basic_description(
+    _ formatDescription: UnsafeMutableRawPointer,
+    _ outDescription: UnsafeMutablePointer<AudioStreamBasicDescription>
+) -> Bool {
+    let desc = Unmanaged<CMFormatDescription>.fromOpaque(formatDescription).takeUnretainedValue()
+    guard let asbd = CMAudioFormatDescriptionGetStreamBasicDescription(desc) else {
+        return false
+    }
+    outDescription.pointee = asbd.pointee
+    return true
+}
+
 // MARK: - CMSampleBuffer Creation
 
 @_cdecl("cm_sample_buffer_create_for_image_buffer")
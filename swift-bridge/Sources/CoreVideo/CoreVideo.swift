@@ -1,5 +1,6 @@
 // CoreVideo Bridge - CVPixelBuffer, CVPixelBufferPool
 
+import CoreGraphics
 import CoreMedia
 import CoreVideo
 import Foundation
@@ -280,6 +281,26 @@ public func cv_pixel_buffer_get_extended_pixels(
                                    extraRowsOnBottom)
 }
 
+@_cdecl("cv_pixel_buffer_is_hdr")
+public func cv_pixel_buffer_is_hdr(_ pixelBuffer: UnsafeMutableRawPointer) -> Bool {
+    let buffer = Unmanaged<CVPixelBuffer>.fromOpaque(pixelBuffer).takeUnretainedValue()
+
+    // Transfer function attachment set by the producer (PQ or HLG means HDR)
+    if let transfer = CVBufferCopyAttachment(buffer, kCVImageBufferTransferFunctionKey, nil) as? String {
+        if transfer == (kCVImageBufferTransferFunction_SMPTE_ST_2084_PQ as String)
+            || transfer == (kCVImageBufferTransferFunction_ITU_R_2100_HLG as String)
+        {
+            return true
+        }
+    }
+
+    // Otherwise fall back to the color space, which may carry the transfer function
+    if let colorSpace = CVImageBufferGetColorSpace(buffer)?.takeUnretainedValue() {
+        return CGColorSpaceUsesITUR_2100TF(colorSpace)
+    }
+    return false
+}
+
 // MARK: - CVPixelBufferPool APIs
 
 @_cdecl("cv_pixel_buffer_pool_create")
@@ -4,10 +4,8 @@ import Foundation
 
 // MARK: - Dispatch Queue Management
 
-@_cdecl("dispatch_queue_create")
-public func createDispatchQueue(_ label: UnsafePointer<CChar>, _ qos: Int32) -> UnsafeMutableRawPointer {
-    let labelStr = String(cString: label)
-    let qosClass: DispatchQoS = switch qos {
+private func dispatchQoS(_ qos: Int32) -> DispatchQoS {
+    switch qos {
     case 0: .background
     case 1: .utility
     case 2: .default
@@ -15,8 +13,38 @@ public func createDispatchQueue(_ label: UnsafePointer<CChar>, _ qos: Int32) ->
     case 4: .userInteractive
     default: .default
     }
+}
+
+@_cdecl("dispatch_queue_create")
+public func createDispatchQueue(_ label: UnsafePointer<CChar>, _ qos: Int32) -> UnsafeMutableRawPointer {
+    let labelStr = String(cString: label)
+    let queue = DispatchQueue(label: labelStr, qos: dispatchQoS(qos))
+    return Unmanaged.passRetained(queue).toOpaque()
+}
+
+/// Creates a serial queue with an autorelease frequency (0 = inherit, 1 = work item,
+/// 2 = never) and an optional target queue
+@_cdecl("dispatch_queue_create_with_attributes")
+public func createDispatchQueueWithAttributes(
+    _ label: UnsafePointer<CChar>,
+    _ qos: Int32,
+    _ autoreleaseFrequency: Int32,
+    _ target: UnsafeMutableRawPointer?
+) -> UnsafeMutableRawPointer {
+    let labelStr = String(cString: label)
+    let frequency: DispatchQueue.AutoreleaseFrequency = switch autoreleaseFrequency {
+    case 1: .workItem
+    case 2: .never
+    default: .inherit
+    }
+    let targetQueue = target.map { Unmanaged<DispatchQueue>.fromOpaque($0).takeUnretainedValue() }
 
-    let queue = DispatchQueue(label: labelStr, qos: qosClass)
+    let queue = DispatchQueue(
+        label: labelStr,
+        qos: dispatchQoS(qos),
+        autoreleaseFrequency: frequency,
+        target: targetQueue
+    )
     return Unmanaged.passRetained(queue).toOpaque()
 }
 
@@ -53,6 +53,24 @@ public func io_surface_is_in_use(_ surface: UnsafeMutableRawPointer) -> Bool {
     return IOSurfaceIsInUse(ioSurface)
 }
 
+@_cdecl("io_surface_increment_use_count")
+public func io_surface_increment_use_count(_ surface: UnsafeMutableRawPointer) {
+    let ioSurface = Unmanaged<IOSurface>.fromOpaque(surface).takeUnretainedValue()
+    IOSurfaceIncrementUseCount(ioSurface)
+}
+
+@_cdecl("io_surface_decrement_use_count")
+public func io_surface_decrement_use_count(_ surface: UnsafeMutableRawPointer) {
+    let ioSurface = Unmanaged<IOSurface>.fromOpaque(surface).takeUnretainedValue()
+    IOSurfaceDecrementUseCount(ioSurface)
+}
+
+@_cdecl("io_surface_get_use_count")
+public func io_surface_get_use_count(_ surface: UnsafeMutableRawPointer) -> Int32 {
+    let ioSurface = Unmanaged<IOSurface>.fromOpaque(surface).takeUnretainedValue()
+    return IOSurfaceGetUseCount(ioSurface)
+}
+
 @_cdecl("io_surface_release")
 public func io_surface_release(_ surface: UnsafeMutableRawPointer) {
     Unmanaged<IOSurface>.fromOpaque(surface).release()
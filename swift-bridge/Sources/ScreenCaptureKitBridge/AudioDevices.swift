@@ -112,3 +112,37 @@ public func getDefaultInputDeviceName(buffer: UnsafeMutablePointer<CChar>?, buff
         return true
     }
 }
+
+/// Get the distinct sample rate/channel count pairs an audio input device supports
+///
+/// Pass a null `deviceId` for the default device. Writes up to `capacity` pairs and
+/// returns the total number, so a first call with a capacity of 0 sizes the buffers.
+@_cdecl("sc_audio_get_input_device_formats")
+public func getInputDeviceFormats(
+    deviceId: UnsafePointer<CChar>?,
+    sampleRates: UnsafeMutablePointer<Double>?,
+    channelCounts: UnsafeMutablePointer<Int32>?,
+    capacity: Int
+) -> Int {
+    let device = deviceId.map { AVCaptureDevice(uniqueID: String(cString: $0)) } ?? AVCaptureDevice.default(for: .audio)
+    guard let device else { return 0 }
+
+    var formats: [(sampleRate: Double, channels: Int32)] = []
+    for format in device.formats {
+        guard let asbd = CMAudioFormatDescriptionGetStreamBasicDescription(format.formatDescription)?.pointee else {
+            continue
+        }
+        let entry = (sampleRate: asbd.mSampleRate, channels: Int32(asbd.mChannelsPerFrame))
+        if !formats.contains(where: { $0 == entry }) {
+            formats.append(entry)
+        }
+    }
+
+    if let sampleRates, let channelCounts {
+        for (i, format) in formats.prefix(max(capacity, 0)).enumerated() {
+            sampleRates[i] = format.sampleRate
+            channelCounts[i] = format.channels
+        }
+    }
+    return formats.count
+}
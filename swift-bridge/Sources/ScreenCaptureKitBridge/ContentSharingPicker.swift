@@ -344,7 +344,6 @@ public func showContentSharingPickerForStream(
     _ userData: UnsafeMutableRawPointer?
 ) {
     let configBox: Box<SCContentSharingPickerConfiguration> = unretained(config)
-    let scStream: SCStream = unretained(streamPtr)
 
     DispatchQueue.main.async {
         NSApp.setActivationPolicy(.regular)
@@ -359,6 +358,9 @@ public func showContentSharingPickerForStream(
         let observer = PickerObserverWithResult(callback: callback, userData: userData)
         currentObserver = observer
 
+        // After an automatic restart the pointer still names the failed stream
+        let scStream = resolveStream(streamPtr)
+
         picker.isActive = true
         picker.add(observer)
         picker.setConfiguration(configBox.value, for: scStream)
@@ -415,7 +417,6 @@ public func showContentSharingPickerForStreamUsingStyle(
     _ userData: UnsafeMutableRawPointer?
 ) {
     let configBox: Box<SCContentSharingPickerConfiguration> = unretained(config)
-    let scStream: SCStream = unretained(streamPtr)
 
     let contentStyle: SCShareableContentStyle = switch style {
     case 1: .window
@@ -437,6 +438,9 @@ public func showContentSharingPickerForStreamUsingStyle(
         let observer = PickerObserverWithResult(callback: callback, userData: userData)
         currentObserver = observer
 
+        // After an automatic restart the pointer still names the failed stream
+        let scStream = resolveStream(streamPtr)
+
         picker.isActive = true
         picker.add(observer)
         picker.setConfiguration(configBox.value, for: scStream)
@@ -523,6 +527,14 @@ public func getPickerResultApplicationAt(_ result: OpaquePointer, _ index: Int)
     return ScreenCaptureKitBridge.retain(r.applications[index])
 }
 
+/// Wrap a filter in a picker result without presenting the picker (used by tests)
+@available(macOS 14.0, *)
+@_cdecl("sc_picker_result_create_with_filter")
+public func createPickerResultWithFilter(_ filter: OpaquePointer) -> OpaquePointer {
+    let f: SCContentFilter = unretained(filter)
+    return ScreenCaptureKitBridge.retain(PickerResult(filter: f))
+}
+
 @available(macOS 14.0, *)
 @_cdecl("sc_picker_result_release")
 public func releasePickerResult(_ result: OpaquePointer) {
@@ -77,6 +77,37 @@ public func initializeCoreGraphics() {
     _ = CGMainDisplayID()
 }
 
+/// Classify the console session as active (0), locked (1) or login window (2)
+@_cdecl("sc_session_state")
+public func getSessionState() -> Int32 {
+    guard let session = CGSessionCopyCurrentDictionary() as? [String: Any] else {
+        // No window server session at all; nothing can be captured
+        return 2
+    }
+    let onConsole = session["kCGSSessionOnConsoleKey"] as? Bool ?? false
+    let loginDone = session["kCGSessionLoginDoneKey"] as? Bool ?? false
+    if !onConsole || !loginDone {
+        return 2
+    }
+    if session["CGSSessionScreenIsLocked"] as? Bool ?? false {
+        return 1
+    }
+    return 0
+}
+
+/// Version of the running macOS, for picking APIs at runtime
+@_cdecl("sc_get_os_version")
+public func getOSVersion(
+    _ outMajor: UnsafeMutablePointer<UInt32>,
+    _ outMinor: UnsafeMutablePointer<UInt32>,
+    _ outPatch: UnsafeMutablePointer<UInt32>
+) {
+    let version = ProcessInfo.processInfo.operatingSystemVersion
+    outMajor.pointee = UInt32(version.majorVersion)
+    outMinor.pointee = UInt32(version.minorVersion)
+    outPatch.pointee = UInt32(version.patchVersion)
+}
+
 // MARK: - Error Types
 
 /// Strongly typed errors for the ScreenCaptureKit bridge
@@ -1,6 +1,7 @@
 // Recording Output APIs (macOS 15.0+)
 // Stub implementation for macOS < 15.0
 
+import AVFoundation
 import Foundation
 import ScreenCaptureKit
 
@@ -21,6 +22,10 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
         var finishedCallback: RecordingFinishedCallback?
         var context: UnsafeMutableRawPointer?
         weak var outputRef: AnyObject?
+        var orientation: RecordingOrientation?
+        var configuration: SCRecordingOutputConfiguration?
+        /// Set while the output is recorded by a `FanOutRecorder` instead of the system
+        var fanOut: FanOutRecorder?
 
         func recordingOutputDidStartRecording(_: SCRecordingOutput) {
             if let cb = startedCallback {
@@ -36,12 +41,249 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
         }
 
         func recordingOutputDidFinishRecording(_: SCRecordingOutput) {
+            if let orientation {
+                do {
+                    try orientation.apply()
+                } catch {
+                    if let cb = failedCallback {
+                        let errorCode = extractStreamErrorCode(error)
+                        error.localizedDescription.withCString { cb(context, errorCode, $0) }
+                    }
+                    return
+                }
+            }
             if let cb = finishedCallback {
                 cb(context)
             }
         }
     }
 
+    /// Playback rotation for a recording, applied to the finished file
+    ///
+    /// `SCRecordingOutput` writes the file itself and exposes no track
+    /// transform, so the rotation is stored on the configuration and written
+    /// into the movie header once recording finishes.
+    @available(macOS 15.0, *)
+    private final class RecordingOrientation {
+        private static var associationKey: UInt8 = 0
+
+        let degrees: Int32
+        var url: URL?
+        var fileType: AVFileType = .mp4
+
+        init(degrees: Int32) {
+            self.degrees = degrees
+        }
+
+        static func get(from config: SCRecordingOutputConfiguration) -> RecordingOrientation? {
+            objc_getAssociatedObject(config, &associationKey) as? RecordingOrientation
+        }
+
+        static func set(_ orientation: RecordingOrientation?, on config: SCRecordingOutputConfiguration) {
+            objc_setAssociatedObject(config, &associationKey, orientation, .OBJC_ASSOCIATION_RETAIN)
+        }
+
+        /// Snapshot of the configuration's orientation for one recording
+        static func forRecording(with config: SCRecordingOutputConfiguration) -> RecordingOrientation? {
+            guard let stored = get(from: config), stored.degrees != 0 else { return nil }
+            let orientation = RecordingOrientation(degrees: stored.degrees)
+            orientation.url = config.outputURL
+            orientation.fileType = config.outputFileType
+            return orientation
+        }
+
+        func apply() throws {
+            guard let url else { return }
+            let movie = AVMutableMovie(url: url, options: nil)
+            for track in movie.tracks(withMediaType: .video) {
+                track.preferredTransform = transform(for: track.naturalSize)
+            }
+            try movie.writeHeader(to: url, fileType: fileType, options: .addMovieHeaderToDestination)
+        }
+
+        private func transform(for size: CGSize) -> CGAffineTransform {
+            switch degrees {
+            case 90: return CGAffineTransform(a: 0, b: 1, c: -1, d: 0, tx: size.height, ty: 0)
+            case 180: return CGAffineTransform(a: -1, b: 0, c: 0, d: -1, tx: size.width, ty: size.height)
+            case 270: return CGAffineTransform(a: 0, b: -1, c: 1, d: 0, tx: 0, ty: size.width)
+            default: return .identity
+            }
+        }
+    }
+
+    /// Records a stream's samples with `AVAssetWriter` on behalf of an
+    /// `SCRecordingOutput` the stream could not attach natively
+    ///
+    /// Reports through the output's delegate like a native recording, so
+    /// callbacks, orientation and the recorded duration/size behave the same.
+    /// Audio samples that arrive before the first video frame are dropped.
+    @available(macOS 15.0, *)
+    final class FanOutRecorder: NSObject, SCStreamOutput {
+        let queue = DispatchQueue(label: "com.screencapturekit-rs.fan-out-recorder")
+        let capturesAudio: Bool
+        private let configuration: SCRecordingOutputConfiguration
+        private weak var output: SCRecordingOutput?
+        private weak var delegate: RecordingDelegate?
+        private var writer: AVAssetWriter?
+        private var videoInput: AVAssetWriterInput?
+        private var audioInput: AVAssetWriterInput?
+        private var failed = false
+        private var startTime = CMTime.invalid
+        private var lastTime = CMTime.invalid
+        private let lock = NSLock()
+
+        fileprivate init(output: SCRecordingOutput, configuration: SCRecordingOutputConfiguration, delegate: RecordingDelegate, capturesAudio: Bool) {
+            self.output = output
+            self.configuration = configuration
+            self.delegate = delegate
+            self.capturesAudio = capturesAudio
+        }
+
+        var recordedDuration: CMTime {
+            lock.lock()
+            defer { lock.unlock() }
+            guard startTime.isValid, lastTime.isValid else { return .zero }
+            return CMTimeSubtract(lastTime, startTime)
+        }
+
+        var recordedFileSize: Int64 {
+            guard let path = configuration.outputURL?.path,
+                  let size = try? FileManager.default.attributesOfItem(atPath: path)[.size] as? NSNumber
+            else {
+                return 0
+            }
+            return size.int64Value
+        }
+
+        func stream(_: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
+            guard !failed, sampleBuffer.isValid else { return }
+            switch type {
+            case .screen:
+                // Idle frames carry no image
+                guard let pixelBuffer = CMSampleBufferGetImageBuffer(sampleBuffer) else { return }
+                if writer == nil {
+                    start(with: pixelBuffer, at: sampleBuffer.presentationTimeStamp)
+                }
+                append(sampleBuffer, to: videoInput)
+                lock.lock()
+                lastTime = sampleBuffer.presentationTimeStamp
+                lock.unlock()
+            case .audio:
+                append(sampleBuffer, to: audioInput)
+            default:
+                break
+            }
+        }
+
+        private func start(with pixelBuffer: CVPixelBuffer, at time: CMTime) {
+            guard let url = configuration.outputURL else {
+                fail(SCBridgeError.configurationError("Recording output has no output URL"))
+                return
+            }
+            do {
+                let writer = try AVAssetWriter(outputURL: url, fileType: configuration.outputFileType)
+                let video = AVAssetWriterInput(mediaType: .video, outputSettings: [
+                    AVVideoCodecKey: configuration.videoCodecType,
+                    AVVideoWidthKey: CVPixelBufferGetWidth(pixelBuffer),
+                    AVVideoHeightKey: CVPixelBufferGetHeight(pixelBuffer),
+                ])
+                video.expectsMediaDataInRealTime = true
+                writer.add(video)
+                if capturesAudio {
+                    let audio = AVAssetWriterInput(mediaType: .audio, outputSettings: [
+                        AVFormatIDKey: kAudioFormatMPEG4AAC,
+                        AVNumberOfChannelsKey: 2,
+                        AVSampleRateKey: 48000,
+                    ])
+                    audio.expectsMediaDataInRealTime = true
+                    writer.add(audio)
+                    audioInput = audio
+                }
+                guard writer.startWriting() else {
+                    throw writer.error ?? SCBridgeError.streamError("Failed to start writing \(url.path)")
+                }
+                writer.startSession(atSourceTime: time)
+                self.writer = writer
+                videoInput = video
+            } catch {
+                fail(error)
+                return
+            }
+            lock.lock()
+            startTime = time
+            lock.unlock()
+            if let output, let delegate {
+                delegate.recordingOutputDidStartRecording(output)
+            }
+        }
+
+        private func append(_ sampleBuffer: CMSampleBuffer, to input: AVAssetWriterInput?) {
+            guard let input, let writer, writer.status == .writing, input.isReadyForMoreMediaData else { return }
+            if !input.append(sampleBuffer) {
+                fail(writer.error ?? SCBridgeError.streamError("Failed to append a sample to the recording"))
+            }
+        }
+
+        private func fail(_ error: Error) {
+            failed = true
+            writer?.cancelWriting()
+            if let output, let delegate {
+                delegate.recordingOutput(output, didFailWithError: error)
+            }
+        }
+
+        /// Finishes the file once the recorder has been removed from the stream
+        func finish() {
+            queue.async { [self] in
+                guard !failed else { return }
+                guard let writer else {
+                    fail(SCBridgeError.streamError("Recording stopped before any frame was captured"))
+                    return
+                }
+                videoInput?.markAsFinished()
+                audioInput?.markAsFinished()
+                writer.finishWriting { [self] in
+                    queue.async { [self] in
+                        guard writer.status == .completed else {
+                            fail(writer.error ?? SCBridgeError.streamError("Failed to finish the recording"))
+                            return
+                        }
+                        if let output, let delegate {
+                            delegate.recordingOutputDidFinishRecording(output)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates a recorder for an output the stream could not attach natively
+    @available(macOS 15.0, *)
+    func makeFanOutRecorder(for output: SCRecordingOutput, capturesAudio: Bool) -> FanOutRecorder? {
+        delegateStorageLock.lock()
+        let delegate = delegateStorage[ObjectIdentifier(output)]
+        delegateStorageLock.unlock()
+        guard let delegate, let configuration = delegate.configuration else { return nil }
+        let recorder = FanOutRecorder(output: output, configuration: configuration, delegate: delegate, capturesAudio: capturesAudio)
+        delegate.fanOut = recorder
+        return recorder
+    }
+
+    /// Forgets the recorder once the output is recorded natively again
+    @available(macOS 15.0, *)
+    func clearFanOutRecorder(for output: SCRecordingOutput) {
+        delegateStorageLock.lock()
+        delegateStorage[ObjectIdentifier(output)]?.fanOut = nil
+        delegateStorageLock.unlock()
+    }
+
+    @available(macOS 15.0, *)
+    private func fanOutRecorder(for output: SCRecordingOutput) -> FanOutRecorder? {
+        delegateStorageLock.lock()
+        defer { delegateStorageLock.unlock() }
+        return delegateStorage[ObjectIdentifier(output)]?.fanOut
+    }
+
     // Storage for delegate to prevent deallocation
     @available(macOS 15.0, *)
     private var delegateStorage: [ObjectIdentifier: RecordingDelegate] = [:]
@@ -122,6 +364,20 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
         }
     }
 
+    @available(macOS 15.0, *)
+    @_cdecl("sc_recording_output_configuration_set_orientation")
+    public func setRecordingOutputOrientation(_ config: OpaquePointer, _ degrees: Int32) {
+        let box: Box<SCRecordingOutputConfiguration> = unretained(config)
+        RecordingOrientation.set(degrees == 0 ? nil : RecordingOrientation(degrees: degrees), on: box.value)
+    }
+
+    @available(macOS 15.0, *)
+    @_cdecl("sc_recording_output_configuration_get_orientation")
+    public func getRecordingOutputOrientation(_ config: OpaquePointer) -> Int32 {
+        let box: Box<SCRecordingOutputConfiguration> = unretained(config)
+        return RecordingOrientation.get(from: box.value)?.degrees ?? 0
+    }
+
     @available(macOS 15.0, *)
     @_cdecl("sc_recording_output_configuration_get_available_video_codecs_count")
     public func getRecordingOutputAvailableVideoCodecsCount(_ config: OpaquePointer) -> Int {
@@ -180,6 +436,8 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
     public func createRecordingOutput(_ config: OpaquePointer) -> OpaquePointer? {
         let box: Box<SCRecordingOutputConfiguration> = unretained(config)
         let delegate = RecordingDelegate()
+        delegate.configuration = box.value
+        delegate.orientation = RecordingOrientation.forRecording(with: box.value)
         let output = SCRecordingOutput(configuration: box.value, delegate: delegate)
 
         // Store delegate to prevent deallocation
@@ -205,6 +463,8 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
         delegate.finishedCallback = finishedCallback
         delegate.context = context
 
+        delegate.configuration = box.value
+        delegate.orientation = RecordingOrientation.forRecording(with: box.value)
         let output = SCRecordingOutput(configuration: box.value, delegate: delegate)
 
         // Store delegate to prevent deallocation
@@ -218,7 +478,7 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
     @_cdecl("sc_recording_output_get_recorded_duration")
     public func getRecordingOutputRecordedDuration(_ output: OpaquePointer, _ value: UnsafeMutablePointer<Int64>, _ timescale: UnsafeMutablePointer<Int32>) {
         let o: SCRecordingOutput = unretained(output)
-        let duration = o.recordedDuration
+        let duration = fanOutRecorder(for: o)?.recordedDuration ?? o.recordedDuration
         value.pointee = duration.value
         timescale.pointee = duration.timescale
     }
@@ -227,7 +487,7 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
     @_cdecl("sc_recording_output_get_recorded_file_size")
     public func getRecordingOutputRecordedFileSize(_ output: OpaquePointer) -> Int64 {
         let o: SCRecordingOutput = unretained(output)
-        return Int64(o.recordedFileSize)
+        return fanOutRecorder(for: o)?.recordedFileSize ?? Int64(o.recordedFileSize)
     }
 
     @available(macOS 15.0, *)
@@ -271,6 +531,12 @@ public typealias RecordingFinishedCallback = @convention(c) (UnsafeMutableRawPoi
     @_cdecl("sc_recording_output_configuration_get_output_file_type")
     public func getRecordingOutputFileType(_: OpaquePointer?) -> Int32 { 0 }
 
+    @_cdecl("sc_recording_output_configuration_set_orientation")
+    public func setRecordingOutputOrientation(_: OpaquePointer?, _: Int32) {}
+
+    @_cdecl("sc_recording_output_configuration_get_orientation")
+    public func getRecordingOutputOrientation(_: OpaquePointer?) -> Int32 { 0 }
+
     @_cdecl("sc_recording_output_configuration_get_available_video_codecs_count")
     public func getRecordingOutputAvailableVideoCodecsCount(_: OpaquePointer?) -> Int { 0 }
 
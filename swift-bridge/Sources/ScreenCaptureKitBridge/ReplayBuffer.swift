@@ -0,0 +1,140 @@
+// Replay Buffer APIs - in-memory frame history and movie export
+
+import AVFoundation
+import CoreMedia
+import CoreVideo
+import Foundation
+
+// MARK: - Frame Copier
+
+/// Copies captured frames out of ScreenCaptureKit's surface pool.
+///
+/// The capture pool only holds `queueDepth` surfaces, so keeping delivered buffers alive
+/// for several seconds would stall the stream. Buffered frames are copied into a private
+/// pool instead and the original surface goes straight back to ScreenCaptureKit.
+final class ReplayFrameCopier {
+    private let conformer = PixelBufferConformer()
+    private let attributes = PixelBufferAttributes(
+        metalCompatible: false,
+        openGLCompatible: false,
+        extendedLeft: 0,
+        extendedTop: 0,
+        extendedRight: 0,
+        extendedBottom: 0
+    )
+    // A replay buffer may be attached to more than one stream
+    private let lock = NSLock()
+
+    func copy(_ sampleBuffer: CMSampleBuffer) -> CMSampleBuffer? {
+        lock.lock()
+        defer { lock.unlock() }
+        return conformer.copy(sampleBuffer, to: attributes)
+    }
+}
+
+@_cdecl("sc_replay_copier_create")
+public func createReplayCopier() -> OpaquePointer {
+    retain(ReplayFrameCopier())
+}
+
+@_cdecl("sc_replay_copier_release")
+public func releaseReplayCopier(_ copier: OpaquePointer) {
+    release(copier)
+}
+
+/// Returns a retained copy of the frame, or nil if it has no image buffer
+@_cdecl("sc_replay_copier_copy_sample_buffer")
+public func replayCopierCopySampleBuffer(_ copier: OpaquePointer, _ sampleBuffer: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer? {
+    let frameCopier: ReplayFrameCopier = unretained(copier)
+    let buffer = Unmanaged<CMSampleBuffer>.fromOpaque(sampleBuffer).takeUnretainedValue()
+    guard let copy = frameCopier.copy(buffer) else { return nil }
+    return Unmanaged.passRetained(copy as AnyObject).toOpaque()
+}
+
+// MARK: - Movie Export
+
+/// Encodes buffered frames to an H.264 movie. The file type follows the path extension
+/// (`.mp4`, otherwise QuickTime); timestamps are rebased so the movie starts at zero.
+@_cdecl("sc_replay_write_movie")
+public func writeReplayMovie(
+    _ samples: UnsafePointer<UnsafeMutableRawPointer>,
+    _ count: Int,
+    _ path: UnsafePointer<CChar>,
+    _ context: UnsafeMutableRawPointer?,
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
+) {
+    func fail(_ message: String) {
+        SCBridgeError.recordingError(message).description.withCString { callback(context, false, $0) }
+    }
+
+    // Retain the frames for the duration of the asynchronous write
+    let buffers: [CMSampleBuffer] = (0 ..< count).map {
+        Unmanaged<CMSampleBuffer>.fromOpaque(samples[$0]).takeUnretainedValue()
+    }
+    guard let first = buffers.first, let firstImage = CMSampleBufferGetImageBuffer(first) else {
+        fail("No frames to save")
+        return
+    }
+
+    let url = URL(fileURLWithPath: String(cString: path))
+    try? FileManager.default.removeItem(at: url)
+    let fileType: AVFileType = url.pathExtension.lowercased() == "mp4" ? .mp4 : .mov
+
+    let writer: AVAssetWriter
+    do {
+        writer = try AVAssetWriter(outputURL: url, fileType: fileType)
+    } catch {
+        fail(error.localizedDescription)
+        return
+    }
+
+    let settings: [String: Any] = [
+        AVVideoCodecKey: AVVideoCodecType.h264,
+        AVVideoWidthKey: CVPixelBufferGetWidth(firstImage),
+        AVVideoHeightKey: CVPixelBufferGetHeight(firstImage),
+    ]
+    let input = AVAssetWriterInput(mediaType: .video, outputSettings: settings)
+    input.expectsMediaDataInRealTime = false
+    let adaptor = AVAssetWriterInputPixelBufferAdaptor(assetWriterInput: input, sourcePixelBufferAttributes: nil)
+
+    guard writer.canAdd(input) else {
+        fail("Cannot add video input to asset writer")
+        return
+    }
+    writer.add(input)
+    guard writer.startWriting() else {
+        fail(writer.error?.localizedDescription ?? "Failed to start writing")
+        return
+    }
+
+    let start = CMSampleBufferGetPresentationTimeStamp(first)
+    writer.startSession(atSourceTime: .zero)
+
+    let queue = DispatchQueue(label: "com.screencapturekit.replay")
+    var index = 0
+    var finished = false
+    input.requestMediaDataWhenReady(on: queue) {
+        guard !finished else { return }
+        while input.isReadyForMoreMediaData, index < buffers.count {
+            let buffer = buffers[index]
+            index += 1
+            guard let image = CMSampleBufferGetImageBuffer(buffer) else { continue }
+            let time = CMTimeSubtract(CMSampleBufferGetPresentationTimeStamp(buffer), start)
+            if !adaptor.append(image, withPresentationTime: time) {
+                break
+            }
+        }
+
+        if index >= buffers.count || writer.status == .failed {
+            finished = true
+            input.markAsFinished()
+            writer.finishWriting {
+                if writer.status == .completed {
+                    callback(context, true, nil)
+                } else {
+                    fail(writer.error?.localizedDescription ?? "Failed to finish writing")
+                }
+            }
+        }
+    }
+}
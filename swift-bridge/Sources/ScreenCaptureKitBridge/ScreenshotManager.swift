@@ -1,11 +1,37 @@
 // Screenshot Manager APIs (macOS 14.0+)
 
+import AppKit
 import CoreGraphics
+import CoreGraphicsBridge
 import CoreMedia
 import Foundation
 import ScreenCaptureKit
 import UniformTypeIdentifiers
 
+// MARK: - Menu Bar Metrics
+
+/// Height in points of the menu bar strip at the top of a display.
+/// On notched displays the menu bar is taller than the status bar thickness,
+/// so the screen's reserved area and safe-area inset are preferred; the status
+/// bar thickness is only used when the menu bar is hidden or the screen unknown.
+@_cdecl("sc_display_menu_bar_height")
+public func scDisplayMenuBarHeight(_ displayID: UInt32) -> Double {
+    let screenNumberKey = NSDeviceDescriptionKey("NSScreenNumber")
+    var height: CGFloat = 0
+    if let screen = NSScreen.screens.first(where: {
+        ($0.deviceDescription[screenNumberKey] as? NSNumber)?.uint32Value == displayID
+    }) {
+        height = screen.frame.maxY - screen.visibleFrame.maxY
+        if #available(macOS 12.0, *) {
+            height = max(height, screen.safeAreaInsets.top)
+        }
+    }
+    if height <= 0 {
+        height = NSStatusBar.system.thickness
+    }
+    return Double(height)
+}
+
 // MARK: - Screenshot Manager (macOS 14.0+)
 
 @available(macOS 14.0, *)
@@ -59,6 +85,53 @@ public func captureScreenshotSampleBuffer(
     }
 }
 
+// MARK: - Capture and save
+
+/// Writes a captured image with the same encoder `CGImage::save` uses; a `dpi` of 0 writes no DPI
+private func saveCapturedImage(_ image: CGImage, to path: String, format: Int32, quality: Float, dpi: Double) -> Bool {
+    let pointer = OpaquePointer(Unmanaged.passUnretained(image).toOpaque())
+    return path.withCString { saveCGImageToFileWithMetadata(pointer, $0, format, quality, .nan, nil, dpi) }
+}
+
+@available(macOS 14.0, *)
+@_cdecl("sc_screenshot_manager_capture_and_save")
+public func captureScreenshotAndSave(
+    _ contentFilter: OpaquePointer,
+    _ config: OpaquePointer,
+    _ pathPtr: UnsafePointer<CChar>,
+    _ format: Int32,
+    _ quality: Float,
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+    _ userData: UnsafeMutableRawPointer?
+) {
+    let filter: SCContentFilter = unretained(contentFilter)
+    let configuration: SCStreamConfiguration = unretained(config)
+    let path = String(cString: pathPtr)
+
+    Task {
+        do {
+            let image = try await SCScreenshotManager.captureImage(
+                contentFilter: filter,
+                configuration: configuration
+            )
+            // Same density `capture_image` attaches on the Rust side
+            var dpi = 72 * Double(filter.pointPixelScale)
+            if #available(macOS 14.2, *), filter.contentRect.width > 0 {
+                dpi = 72 * Double(image.width) / filter.contentRect.width
+            }
+            guard saveCapturedImage(image, to: path, format: format, quality: quality, dpi: dpi) else {
+                let bridgeError = SCBridgeError.screenshotError("Failed to write screenshot to \(path)")
+                bridgeError.description.withCString { callback(userData, false, $0) }
+                return
+            }
+            callback(userData, true, nil)
+        } catch {
+            let bridgeError = SCBridgeError.screenshotError(error.localizedDescription)
+            bridgeError.description.withCString { callback(userData, false, $0) }
+        }
+    }
+}
+
 // MARK: - Capture image in rect (macOS 15.2+)
 
 #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
@@ -102,6 +175,59 @@ public func captureScreenshotSampleBuffer(
     }
 #endif
 
+#if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+    @_cdecl("sc_screenshot_manager_capture_image_in_rect_and_save")
+    public func captureScreenshotInRectAndSave(
+        _ x: Double,
+        _ y: Double,
+        _ width: Double,
+        _ height: Double,
+        _ pathPtr: UnsafePointer<CChar>,
+        _ format: Int32,
+        _ quality: Float,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ userData: UnsafeMutableRawPointer?
+    ) {
+        guard #available(macOS 15.2, *) else {
+            let bridgeError = SCBridgeError.screenshotError("captureImageInRect requires macOS 15.2+")
+            bridgeError.description.withCString { callback(userData, false, $0) }
+            return
+        }
+        let rect = CGRect(x: x, y: y, width: width, height: height)
+        let path = String(cString: pathPtr)
+        Task {
+            do {
+                let image = try await SCScreenshotManager.captureImage(in: rect)
+                guard saveCapturedImage(image, to: path, format: format, quality: quality, dpi: 0) else {
+                    let bridgeError = SCBridgeError.screenshotError("Failed to write screenshot to \(path)")
+                    bridgeError.description.withCString { callback(userData, false, $0) }
+                    return
+                }
+                callback(userData, true, nil)
+            } catch {
+                let bridgeError = SCBridgeError.screenshotError(error.localizedDescription)
+                bridgeError.description.withCString { callback(userData, false, $0) }
+            }
+        }
+    }
+#else
+    @_cdecl("sc_screenshot_manager_capture_image_in_rect_and_save")
+    public func captureScreenshotInRectAndSave(
+        _: Double,
+        _: Double,
+        _: Double,
+        _: Double,
+        _: UnsafePointer<CChar>,
+        _: Int32,
+        _: Float,
+        _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+        _ userData: UnsafeMutableRawPointer?
+    ) {
+        let bridgeError = SCBridgeError.screenshotError("captureImageInRect requires macOS 15.2+")
+        bridgeError.description.withCString { callback(userData, false, $0) }
+    }
+#endif
+
 // MARK: - SCScreenshotConfiguration (macOS 26.0+)
 
 #if SCREENCAPTUREKIT_HAS_MACOS26_SDK
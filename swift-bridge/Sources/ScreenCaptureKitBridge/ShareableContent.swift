@@ -274,6 +274,22 @@ public func getShareableContentDisplaysCount(_ content: OpaquePointer) -> Int {
     return sc.displays.count
 }
 
+@_cdecl("sc_shareable_content_get_summary")
+public func getShareableContentSummary(
+    _ content: OpaquePointer,
+    _ outDisplays: UnsafeMutablePointer<Int>,
+    _ outWindows: UnsafeMutablePointer<Int>,
+    _ outApplications: UnsafeMutablePointer<Int>,
+    _ outOnScreenWindows: UnsafeMutablePointer<Int>
+) {
+    let sc: SCShareableContent = unretained(content)
+    let windows = sc.windows
+    outDisplays.pointee = sc.displays.count
+    outWindows.pointee = windows.count
+    outApplications.pointee = sc.applications.count
+    outOnScreenWindows.pointee = windows.reduce(0) { $0 + ($1.isOnScreen ? 1 : 0) }
+}
+
 @_cdecl("sc_shareable_content_get_display_at")
 public func getShareableContentDisplay(_ content: OpaquePointer, _ index: Int) -> OpaquePointer? {
     let sc: SCShareableContent = unretained(content)
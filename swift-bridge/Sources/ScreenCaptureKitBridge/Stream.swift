@@ -1,5 +1,6 @@
 // Stream Control APIs - SCContentFilter, SCStream
 
+import AppKit
 import CoreGraphics
 import CoreImage
 import CoreMedia
@@ -13,6 +14,7 @@ import ScreenCaptureKit
 public func createContentFilterWithDesktopIndependentWindow(_ window: OpaquePointer) -> OpaquePointer {
     let scWindow: SCWindow = unretained(window)
     let filter = SCContentFilter(desktopIndependentWindow: scWindow)
+    FilterRecipe.set(.window(scWindow), on: filter)
     return retain(filter)
 }
 
@@ -31,6 +33,7 @@ public func createContentFilterWithDisplayExcludingWindows(
         }
     }
     let filter = SCContentFilter(display: scDisplay, excludingWindows: excludedWindows)
+    FilterRecipe.set(.excludingWindows(scDisplay, excludedWindows), on: filter)
     return retain(filter)
 }
 
@@ -49,6 +52,7 @@ public func createContentFilterWithDisplayIncludingWindows(
         }
     }
     let filter = SCContentFilter(display: scDisplay, including: includedWindows)
+    FilterRecipe.set(.includingWindows(scDisplay, includedWindows), on: filter)
     return retain(filter)
 }
 
@@ -76,6 +80,7 @@ public func createContentFilterWithDisplayIncludingApplicationsExceptingWindows(
         }
     }
     let filter = SCContentFilter(display: scDisplay, including: includedApps, exceptingWindows: exceptedWindows)
+    FilterRecipe.set(.includingApplications(scDisplay, includedApps, exceptingWindows: exceptedWindows), on: filter)
     return retain(filter)
 }
 
@@ -103,9 +108,83 @@ public func createContentFilterWithDisplayExcludingApplicationsExceptingWindows(
         }
     }
     let filter = SCContentFilter(display: scDisplay, excludingApplications: excludedApps, exceptingWindows: exceptedWindows)
+    FilterRecipe.set(.excludingApplications(scDisplay, excludedApps, exceptingWindows: exceptedWindows), on: filter)
     return retain(filter)
 }
 
+/// How a content filter was built, so it can be rebuilt with more windows hidden
+///
+/// `SCContentFilter` doesn't expose the windows it excludes, so the inputs of
+/// every filter created above are kept alongside it.
+private final class FilterRecipe {
+    enum Kind {
+        case window(SCWindow)
+        case excludingWindows(SCDisplay, [SCWindow])
+        case includingWindows(SCDisplay, [SCWindow])
+        case includingApplications(SCDisplay, [SCRunningApplication], exceptingWindows: [SCWindow])
+        case excludingApplications(SCDisplay, [SCRunningApplication], exceptingWindows: [SCWindow])
+    }
+
+    let kind: Kind
+
+    private static var associationKey: UInt8 = 0
+
+    init(_ kind: Kind) {
+        self.kind = kind
+    }
+
+    static func get(from filter: SCContentFilter) -> FilterRecipe? {
+        objc_getAssociatedObject(filter, &associationKey) as? FilterRecipe
+    }
+
+    static func set(_ kind: Kind, on filter: SCContentFilter) {
+        objc_setAssociatedObject(filter, &associationKey, FilterRecipe(kind), .OBJC_ASSOCIATION_RETAIN)
+    }
+}
+
+/// A copy of `filter` that doesn't capture `hidden`, or nil if the filter
+/// wasn't created by this bridge or can't leave out one of the windows
+private func contentFilter(_ filter: SCContentFilter, hiding hidden: [SCWindow]) -> SCContentFilter? {
+    guard let recipe = FilterRecipe.get(from: filter) else { return nil }
+    let hiddenIDs = Set(hidden.map(\.windowID))
+    let visible: ([SCWindow]) -> [SCWindow] = { $0.filter { !hiddenIDs.contains($0.windowID) } }
+
+    let kind: FilterRecipe.Kind
+    let rebuilt: SCContentFilter
+    switch recipe.kind {
+    case let .window(window):
+        // Only this window is captured, so every other window is already hidden
+        return hiddenIDs.contains(window.windowID) ? nil : filter
+    case let .excludingWindows(display, windows):
+        let excluded = visible(windows) + hidden
+        kind = .excludingWindows(display, excluded)
+        rebuilt = SCContentFilter(display: display, excludingWindows: excluded)
+    case let .includingWindows(display, windows):
+        let included = visible(windows)
+        kind = .includingWindows(display, included)
+        rebuilt = SCContentFilter(display: display, including: included)
+    case let .includingApplications(display, apps, excepting):
+        let excepted = visible(excepting) + hidden
+        kind = .includingApplications(display, apps, exceptingWindows: excepted)
+        rebuilt = SCContentFilter(display: display, including: apps, exceptingWindows: excepted)
+    case let .excludingApplications(display, apps, excepting):
+        // Windows of applications that are captured can't be left out one by one
+        let excludedPIDs = Set(apps.map(\.processID))
+        let allExcluded = hidden.allSatisfy { window in
+            window.owningApplication.map { excludedPIDs.contains($0.processID) } ?? false
+        }
+        guard allExcluded else { return nil }
+        let excepted = visible(excepting)
+        kind = .excludingApplications(display, apps, exceptingWindows: excepted)
+        rebuilt = SCContentFilter(display: display, excludingApplications: apps, exceptingWindows: excepted)
+    }
+    if #available(macOS 14.2, *) {
+        rebuilt.includeMenuBar = filter.includeMenuBar
+    }
+    FilterRecipe.set(kind, on: rebuilt)
+    return rebuilt
+}
+
 @_cdecl("sc_content_filter_retain")
 public func retainContentFilter(_ filter: OpaquePointer) -> OpaquePointer {
     let f: SCContentFilter = unretained(filter)
@@ -288,19 +367,32 @@ public func getContentFilterIncludeMenuBar(_ filter: OpaquePointer) -> Bool {
 
 private class StreamDelegateWrapper: NSObject, SCStreamDelegate {
     let errorCallback: @convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void
-    let streamPtr: OpaquePointer
+    var streamPtr: OpaquePointer
     var activeCallback: (@convention(c) (OpaquePointer) -> Void)?
     var inactiveCallback: (@convention(c) (OpaquePointer) -> Void)?
+    weak var restartState: StreamRestartState?
 
     init(streamPtr: OpaquePointer, errorCallback: @escaping @convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void) {
         self.streamPtr = streamPtr
         self.errorCallback = errorCallback
     }
 
-    func stream(_: SCStream, didStopWithError error: Error) {
+    func stream(_ stream: SCStream, didStopWithError error: Error) {
         let errorCode = extractStreamErrorCode(error)
         let errorMsg = error.localizedDescription
-        errorMsg.withCString { errorCallback(streamPtr, errorCode, $0) }
+        guard let restartState, let restartCallback = restartState.restartCallback(after: errorCode) else {
+            errorMsg.withCString { errorCallback(streamPtr, errorCode, $0) }
+            return
+        }
+        let streamPtr = streamPtr
+        let errorCallback = errorCallback
+        Task {
+            if await restartState.restart(replacing: stream) {
+                errorMsg.withCString { restartCallback(streamPtr, errorCode, $0) }
+            } else {
+                errorMsg.withCString { errorCallback(streamPtr, errorCode, $0) }
+            }
+        }
     }
 
     #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
@@ -316,16 +408,23 @@ private class StreamDelegateWrapper: NSObject, SCStreamDelegate {
     #endif
 }
 
+/// Sample buffer attachment holding the per-output delivery counter.
+/// Must match the key read by `cm_sample_buffer_get_sequence_number`.
+private let sequenceNumberAttachmentKey = "ScreenCaptureKitRsSequenceNumber" as CFString
+
 private class StreamOutputHandler: NSObject, SCStreamOutput {
     let sampleBufferCallback: @convention(c) (OpaquePointer, OpaquePointer, Int32) -> Void
     let streamPtr: OpaquePointer
+    private let conformer = PixelBufferConformer()
+    // Samples for one output arrive on a single serial queue, so no lock is needed
+    private var nextSequenceNumber: UInt64 = 0
 
     init(streamPtr: OpaquePointer, sampleBufferCallback: @escaping @convention(c) (OpaquePointer, OpaquePointer, Int32) -> Void) {
         self.streamPtr = streamPtr
         self.sampleBufferCallback = sampleBufferCallback
     }
 
-    func stream(_: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
+    func stream(_ stream: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
         // Use rawValue comparison to avoid SDK availability issues
         // .screen = 0, .audio = 1, .microphone = 2 (macOS 15+)
         let outputType: Int32 = if type == .screen {
@@ -335,15 +434,394 @@ private class StreamOutputHandler: NSObject, SCStreamOutput {
         } else {
             1 // audio
         }
+        var sampleBuffer = sampleBuffer
+        if type == .screen, let attributes = PixelBufferAttributes.get(from: stream) {
+            sampleBuffer = conformer.conform(sampleBuffer, to: attributes)
+        }
+        CMSetAttachment(
+            sampleBuffer,
+            key: sequenceNumberAttachmentKey,
+            value: NSNumber(value: nextSequenceNumber),
+            attachmentMode: kCMAttachmentMode_ShouldNotPropagate
+        )
+        nextSequenceNumber &+= 1
         // IMPORTANT: passRetained() is used here to retain the CMSampleBuffer for Rust
         // The Rust side will release it when CMSampleBuffer is dropped
-        sampleBufferCallback(streamPtr, OpaquePointer(Unmanaged.passRetained(sampleBuffer as AnyObject).toOpaque()), outputType)
+        let retainedBuffer = OpaquePointer(Unmanaged.passRetained(sampleBuffer as AnyObject).toOpaque())
+        if MainThreadDelivery.get(from: stream)?.contains(outputType) == true {
+            let callback = sampleBufferCallback
+            let streamPtr = streamPtr
+            DispatchQueue.main.async {
+                callback(streamPtr, retainedBuffer, outputType)
+            }
+        } else {
+            sampleBufferCallback(streamPtr, retainedBuffer, outputType)
+        }
+    }
+}
+
+/// Output types whose callbacks are forwarded to the main queue.
+///
+/// Attached to the `SCStream` as an associated object so the setting lives exactly as
+/// long as the stream, independent of which handlers are currently registered.
+final class MainThreadDelivery: NSObject {
+    private var types: Set<Int32> = []
+    private let lock = NSLock()
+
+    private static var associationKey: UInt8 = 0
+
+    func contains(_ type: Int32) -> Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        return types.contains(type)
+    }
+
+    func set(_ type: Int32, enabled: Bool) {
+        lock.lock()
+        defer { lock.unlock() }
+        if enabled {
+            types.insert(type)
+        } else {
+            types.remove(type)
+        }
+    }
+
+    static func get(from stream: SCStream) -> MainThreadDelivery? {
+        objc_getAssociatedObject(stream, &associationKey) as? MainThreadDelivery
+    }
+
+    static func getOrCreate(on stream: SCStream) -> MainThreadDelivery {
+        if let existing = get(from: stream) {
+            return existing
+        }
+        let delivery = MainThreadDelivery()
+        attach(delivery, to: stream)
+        return delivery
+    }
+
+    static func attach(_ delivery: MainThreadDelivery, to stream: SCStream) {
+        objc_setAssociatedObject(stream, &associationKey, delivery, .OBJC_ASSOCIATION_RETAIN)
+    }
+}
+
+@_cdecl("sc_stream_set_main_thread_delivery")
+public func setStreamMainThreadDelivery(_ stream: OpaquePointer, _ type: Int32, _ enabled: Bool) {
+    let scStream: SCStream = resolveStream(stream)
+    MainThreadDelivery.getOrCreate(on: scStream).set(type, enabled: enabled)
+}
+
+@_cdecl("sc_stream_get_main_thread_delivery")
+public func getStreamMainThreadDelivery(_ stream: OpaquePointer, _ type: Int32) -> Bool {
+    let scStream: SCStream = resolveStream(stream)
+    return MainThreadDelivery.get(from: scStream)?.contains(type) ?? false
+}
+
+/// Copies video frames into buffers with the requested `PixelBufferAttributes`.
+///
+/// ScreenCaptureKit delivers IOSurface-backed buffers, which already satisfy Metal and
+/// OpenGL compatibility, so the copy only happens when a buffer does not meet the
+/// requirements (for example when extended pixels are requested).
+/// Each instance must only be used from one serial queue at a time.
+final class PixelBufferConformer {
+    private struct PoolKey: Equatable {
+        let width: Int
+        let height: Int
+        let format: OSType
+        let attributes: ObjectIdentifier
+    }
+
+    private var pool: CVPixelBufferPool?
+    private var poolKey: PoolKey?
+
+    func conform(_ sampleBuffer: CMSampleBuffer, to attributes: PixelBufferAttributes) -> CMSampleBuffer {
+        guard let source = CMSampleBufferGetImageBuffer(sampleBuffer),
+              !attributes.isSatisfied(by: source),
+              let conformed = copy(sampleBuffer, to: attributes)
+        else {
+            return sampleBuffer
+        }
+        return conformed
+    }
+
+    /// Always copies the frame into this conformer's own pool, releasing the capture surface.
+    func copy(_ sampleBuffer: CMSampleBuffer, to attributes: PixelBufferAttributes) -> CMSampleBuffer? {
+        guard let source = CMSampleBufferGetImageBuffer(sampleBuffer),
+              let copy = copyPixelBuffer(source, attributes: attributes)
+        else {
+            return nil
+        }
+        return makeSampleBuffer(copy, from: sampleBuffer)
+    }
+
+    private func copyPixelBuffer(_ source: CVPixelBuffer, attributes: PixelBufferAttributes) -> CVPixelBuffer? {
+        let key = PoolKey(
+            width: CVPixelBufferGetWidth(source),
+            height: CVPixelBufferGetHeight(source),
+            format: CVPixelBufferGetPixelFormatType(source),
+            attributes: ObjectIdentifier(attributes)
+        )
+        if pool == nil || poolKey != key {
+            var poolAttributes = attributes.cvAttributes
+            poolAttributes[kCVPixelBufferWidthKey] = key.width
+            poolAttributes[kCVPixelBufferHeightKey] = key.height
+            poolAttributes[kCVPixelBufferPixelFormatTypeKey] = key.format
+            var newPool: CVPixelBufferPool?
+            CVPixelBufferPoolCreate(nil, nil, poolAttributes as CFDictionary, &newPool)
+            pool = newPool
+            poolKey = key
+        }
+        guard let pool else { return nil }
+
+        var destination: CVPixelBuffer?
+        guard CVPixelBufferPoolCreatePixelBuffer(nil, pool, &destination) == kCVReturnSuccess,
+              let destination
+        else {
+            return nil
+        }
+
+        CVPixelBufferLockBaseAddress(source, .readOnly)
+        CVPixelBufferLockBaseAddress(destination, [])
+        defer {
+            CVPixelBufferUnlockBaseAddress(destination, [])
+            CVPixelBufferUnlockBaseAddress(source, .readOnly)
+        }
+
+        if CVPixelBufferIsPlanar(source) {
+            for plane in 0 ..< CVPixelBufferGetPlaneCount(source) {
+                copyRows(
+                    from: CVPixelBufferGetBaseAddressOfPlane(source, plane),
+                    sourceStride: CVPixelBufferGetBytesPerRowOfPlane(source, plane),
+                    to: CVPixelBufferGetBaseAddressOfPlane(destination, plane),
+                    destinationStride: CVPixelBufferGetBytesPerRowOfPlane(destination, plane),
+                    rows: CVPixelBufferGetHeightOfPlane(source, plane)
+                )
+            }
+        } else {
+            copyRows(
+                from: CVPixelBufferGetBaseAddress(source),
+                sourceStride: CVPixelBufferGetBytesPerRow(source),
+                to: CVPixelBufferGetBaseAddress(destination),
+                destinationStride: CVPixelBufferGetBytesPerRow(destination),
+                rows: CVPixelBufferGetHeight(source)
+            )
+        }
+
+        // Keep color space and other buffer-level attachments
+        CVBufferPropagateAttachments(source, destination)
+        return destination
+    }
+
+    private func copyRows(
+        from source: UnsafeMutableRawPointer?,
+        sourceStride: Int,
+        to destination: UnsafeMutableRawPointer?,
+        destinationStride: Int,
+        rows: Int
+    ) {
+        guard let source, let destination else { return }
+        let rowBytes = min(sourceStride, destinationStride)
+        for row in 0 ..< rows {
+            memcpy(destination + row * destinationStride, source + row * sourceStride, rowBytes)
+        }
+    }
+
+    private func makeSampleBuffer(_ pixelBuffer: CVPixelBuffer, from original: CMSampleBuffer) -> CMSampleBuffer? {
+        var formatDescription: CMVideoFormatDescription?
+        guard CMVideoFormatDescriptionCreateForImageBuffer(
+            allocator: nil,
+            imageBuffer: pixelBuffer,
+            formatDescriptionOut: &formatDescription
+        ) == noErr, let formatDescription else {
+            return nil
+        }
+
+        var timing = CMSampleTimingInfo()
+        CMSampleBufferGetSampleTimingInfo(original, at: 0, timingInfoOut: &timing)
+
+        var result: CMSampleBuffer?
+        guard CMSampleBufferCreateReadyWithImageBuffer(
+            allocator: nil,
+            imageBuffer: pixelBuffer,
+            formatDescription: formatDescription,
+            sampleTiming: &timing,
+            sampleBufferOut: &result
+        ) == noErr, let result else {
+            return nil
+        }
+
+        // Carry over the SCStreamFrameInfo attachments (status, content rect, dirty rects, ...)
+        if let sourceAttachments = (CMSampleBufferGetSampleAttachmentsArray(original, createIfNecessary: false) as? [NSDictionary])?.first,
+           let destinationArray = CMSampleBufferGetSampleAttachmentsArray(result, createIfNecessary: true),
+           CFArrayGetCount(destinationArray) > 0
+        {
+            let destinationAttachments = unsafeBitCast(CFArrayGetValueAtIndex(destinationArray, 0), to: NSMutableDictionary.self)
+            destinationAttachments.addEntries(from: sourceAttachments as! [AnyHashable: Any])
+        }
+        return result
     }
 }
 
+/// Everything needed to rebuild a stream after a fatal error.
+///
+/// Attached to the `SCStream` handed to Rust. Rust keeps using that pointer
+/// after an automatic restart, so the stream entry points go through
+/// `resolveStream`, which returns the replacement once one exists.
+private final class StreamRestartState: NSObject {
+    private struct Output {
+        let type: Int32
+        let outputType: SCStreamOutputType
+        let handler: StreamOutputHandler
+        let queue: DispatchQueue
+    }
+
+    private var filter: SCContentFilter
+    private var configuration: SCStreamConfiguration
+    private var delegate: StreamDelegateWrapper
+    private var outputs: [Output] = []
+    private var replacement: SCStream?
+    private var callback: (@convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void)?
+    private let lock = NSLock()
+
+    private static var associationKey: UInt8 = 0
+
+    init(filter: SCContentFilter, configuration: SCStreamConfiguration, delegate: StreamDelegateWrapper) {
+        self.filter = filter
+        self.configuration = configuration
+        self.delegate = delegate
+        super.init()
+        delegate.restartState = self
+    }
+
+    static func get(from stream: SCStream) -> StreamRestartState? {
+        objc_getAssociatedObject(stream, &associationKey) as? StreamRestartState
+    }
+
+    static func set(_ state: StreamRestartState, on stream: SCStream) {
+        objc_setAssociatedObject(stream, &associationKey, state, .OBJC_ASSOCIATION_RETAIN)
+    }
+
+    var current: SCStream? {
+        lock.lock()
+        defer { lock.unlock() }
+        return replacement
+    }
+
+    /// The filter the stream was last given
+    var currentFilter: SCContentFilter {
+        lock.lock()
+        defer { lock.unlock() }
+        return filter
+    }
+
+    /// The configuration the stream was last given
+    var currentConfiguration: SCStreamConfiguration {
+        lock.lock()
+        defer { lock.unlock() }
+        return configuration
+    }
+
+    func setEnabled(_ enabled: Bool, callback: @escaping @convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void) {
+        lock.lock()
+        defer { lock.unlock() }
+        self.callback = enabled ? callback : nil
+    }
+
+    /// The callback to report a restart with, or nil if this error should just be forwarded
+    func restartCallback(after errorCode: Int32) -> (@convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void)? {
+        // The user ended the capture from the system UI; bringing it back would override them
+        if Int(errorCode) == SCStreamError.Code.userStopped.rawValue {
+            return nil
+        }
+        lock.lock()
+        defer { lock.unlock() }
+        return callback
+    }
+
+    func update(filter: SCContentFilter) {
+        lock.lock()
+        defer { lock.unlock() }
+        self.filter = filter
+    }
+
+    func update(configuration: SCStreamConfiguration) {
+        lock.lock()
+        defer { lock.unlock() }
+        self.configuration = configuration
+    }
+
+    /// Records an output to move to the replacement stream. A type can have
+    /// several outputs, one per Rust handler, and all of them are kept.
+    func addOutput(_ handler: StreamOutputHandler, type: Int32, outputType: SCStreamOutputType, queue: DispatchQueue) {
+        lock.lock()
+        defer { lock.unlock() }
+        outputs.append(Output(type: type, outputType: outputType, handler: handler, queue: queue))
+    }
+
+    func removeOutput(_ handler: StreamOutputHandler) {
+        lock.lock()
+        defer { lock.unlock() }
+        outputs.removeAll { $0.handler === handler }
+    }
+
+    /// Builds a new stream from the stored filter and configuration, moves the
+    /// outputs over and starts it. The failed stream is left stopped.
+    func restart(replacing failed: SCStream) async -> Bool {
+        lock.lock()
+        let filter = filter
+        let configuration = configuration
+        let outputs = outputs
+        let streamPtr = delegate.streamPtr
+        let errorCallback = delegate.errorCallback
+        lock.unlock()
+
+        let replacementDelegate = StreamDelegateWrapper(streamPtr: streamPtr, errorCallback: errorCallback)
+        replacementDelegate.restartState = self
+        let stream = SCStream(filter: filter, configuration: configuration, delegate: replacementDelegate)
+        PixelBufferAttributes.set(PixelBufferAttributes.get(from: configuration), on: stream)
+        if let delivery = MainThreadDelivery.get(from: failed) {
+            MainThreadDelivery.attach(delivery, to: stream)
+        }
+
+        do {
+            for output in outputs {
+                try stream.addStreamOutput(output.handler, type: output.outputType, sampleHandlerQueue: output.queue)
+            }
+            try await stream.startCapture()
+        } catch {
+            return false
+        }
+        for output in outputs {
+            try? failed.removeStreamOutput(output.handler, type: output.outputType)
+        }
+
+        lock.lock()
+        replacement = stream
+        delegate = replacementDelegate
+        lock.unlock()
+        return true
+    }
+}
+
+/// The stream currently doing the capturing for a pointer handed to Rust
+func resolveStream(_ stream: OpaquePointer) -> SCStream {
+    let original: SCStream = unretained(stream)
+    return resolveStream(original)
+}
+
+private func resolveStream(_ original: SCStream) -> SCStream {
+    StreamRestartState.get(from: original)?.current ?? original
+}
+
+private func restartState(for stream: OpaquePointer) -> StreamRestartState? {
+    let original: SCStream = unretained(stream)
+    return StreamRestartState.get(from: original)
+}
+
 // Registry to store handlers associated with streams
+/// Outputs added for each stream and type; Rust adds one per type and fans
+/// each sample out to its handlers
 private class HandlerRegistry {
-    private var handlers: [String: StreamOutputHandler] = [:]
+    private var handlers: [String: [StreamOutputHandler]] = [:]
     private let lock = NSLock()
 
     private func key(for stream: OpaquePointer, type: Int32) -> String {
@@ -353,19 +831,25 @@ private class HandlerRegistry {
     func store(_ handler: StreamOutputHandler, for stream: OpaquePointer, type: Int32) {
         lock.lock()
         defer { lock.unlock() }
-        handlers[key(for: stream, type: type)] = handler
+        handlers[key(for: stream, type: type), default: []].append(handler)
     }
 
+    /// The most recently added output; every output of a type forwards to the
+    /// same Rust callback, so any of them can be removed
     func get(for stream: OpaquePointer, type: Int32) -> StreamOutputHandler? {
         lock.lock()
         defer { lock.unlock() }
-        return handlers[key(for: stream, type: type)]
+        return handlers[key(for: stream, type: type)]?.last
     }
 
-    func remove(for stream: OpaquePointer, type: Int32) {
+    func remove(_ handler: StreamOutputHandler, for stream: OpaquePointer, type: Int32) {
         lock.lock()
         defer { lock.unlock() }
-        handlers.removeValue(forKey: key(for: stream, type: type))
+        let key = key(for: stream, type: type)
+        handlers[key]?.removeAll { $0 === handler }
+        if handlers[key]?.isEmpty == true {
+            handlers.removeValue(forKey: key)
+        }
     }
 }
 
@@ -421,24 +905,69 @@ private class SingleFrameHandler: NSObject, SCStreamOutput {
 
 private class SingleFrameSampleBufferHandler: NSObject, SCStreamOutput {
     private var continuation: CheckedContinuation<CMSampleBuffer, Error>?
+    private var pendingError: Error?
     private let lock = NSLock()
+    private var framesToSkip: Int
+    private let requireComplete: Bool
+
+    /// - Parameters:
+    ///   - skipFrames: Number of accepted frames to discard before returning one
+    ///   - requireComplete: Only accept frames with status `.complete` and an image buffer
+    init(skipFrames: Int = 0, requireComplete: Bool = false) {
+        framesToSkip = skipFrames
+        self.requireComplete = requireComplete
+    }
 
     func nextSampleBuffer() async throws -> CMSampleBuffer {
         try await withCheckedThrowingContinuation { cont in
             lock.lock()
+            if let error = pendingError {
+                lock.unlock()
+                cont.resume(throwing: error)
+                return
+            }
             continuation = cont
             lock.unlock()
         }
     }
 
+    /// Fails the pending wait, or the next one if it has not started yet
+    func cancel(with error: Error) {
+        lock.lock()
+        let cont = continuation
+        continuation = nil
+        if cont == nil { pendingError = error }
+        lock.unlock()
+        cont?.resume(throwing: error)
+    }
+
     func stream(_: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
         guard type == .screen else { return }
+        if requireComplete, !Self.isComplete(sampleBuffer) { return }
+
+        lock.lock()
+        let skip = framesToSkip > 0
+        if skip { framesToSkip -= 1 }
+        lock.unlock()
+        if skip { return }
 
         // Retain the sample buffer for Rust side
         // Rust will release it when CMSampleBuffer is dropped
         resumeOnce(with: sampleBuffer)
     }
 
+    /// Whether the frame carries new content; idle, blank and warm-up frames do not
+    private static func isComplete(_ sampleBuffer: CMSampleBuffer) -> Bool {
+        guard CMSampleBufferGetImageBuffer(sampleBuffer) != nil,
+              let attachments = CMSampleBufferGetSampleAttachmentsArray(sampleBuffer, createIfNecessary: false) as? [[CFString: Any]],
+              let rawStatus = attachments.first?[SCStreamFrameInfo.status.rawValue as CFString] as? Int,
+              let status = SCFrameStatus(rawValue: rawStatus)
+        else {
+            return false
+        }
+        return status == .complete
+    }
+
     private func resumeOnce(with sampleBuffer: CMSampleBuffer) {
         lock.lock()
         let cont = continuation
@@ -471,8 +1000,15 @@ public func createStream(
     let delegate = StreamDelegateWrapper(streamPtr: streamPtr, errorCallback: errorCallback)
 
     let stream = SCStream(filter: scFilter, configuration: scConfig, delegate: delegate)
+    PixelBufferAttributes.set(PixelBufferAttributes.get(from: scConfig), on: stream)
     let actualStreamPtr = retain(stream)
 
+    // Report errors against the pointer Rust keys its delegates by, and keep
+    // the delegate alive for as long as the stream
+    delegate.streamPtr = actualStreamPtr
+    let recipe = StreamRestartState(filter: scFilter, configuration: duplicateStreamConfiguration(scConfig), delegate: delegate)
+    StreamRestartState.set(recipe, on: stream)
+
     return actualStreamPtr
 }
 
@@ -482,7 +1018,7 @@ public func addStreamOutput(
     _ type: Int32,
     _ sampleBufferCallback: @escaping @convention(c) (OpaquePointer, OpaquePointer, Int32) -> Void
 ) -> Bool {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     let handler = StreamOutputHandler(streamPtr: stream, sampleBufferCallback: sampleBufferCallback)
     handlerRegistry.store(handler, for: stream, type: type)
 
@@ -508,8 +1044,10 @@ public func addStreamOutput(
 
     do {
         try scStream.addStreamOutput(handler, type: outputType, sampleHandlerQueue: queue)
+        restartState(for: stream)?.addOutput(handler, type: type, outputType: outputType, queue: queue)
         return true
     } catch {
+        handlerRegistry.remove(handler, for: stream, type: type)
         return false
     }
 }
@@ -521,7 +1059,7 @@ public func addStreamOutputWithQueue(
     _ sampleBufferCallback: @escaping @convention(c) (OpaquePointer, OpaquePointer, Int32) -> Void,
     _ dispatchQueue: OpaquePointer?
 ) -> Bool {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     let handler = StreamOutputHandler(streamPtr: stream, sampleBufferCallback: sampleBufferCallback)
     handlerRegistry.store(handler, for: stream, type: type)
 
@@ -550,8 +1088,10 @@ public func addStreamOutputWithQueue(
 
     do {
         try scStream.addStreamOutput(handler, type: outputType, sampleHandlerQueue: queue)
+        restartState(for: stream)?.addOutput(handler, type: type, outputType: outputType, queue: queue)
         return true
     } catch {
+        handlerRegistry.remove(handler, for: stream, type: type)
         return false
     }
 }
@@ -561,7 +1101,7 @@ public func removeStreamOutput(
     _ stream: OpaquePointer,
     _ type: Int32
 ) -> Bool {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     guard let handler = handlerRegistry.get(for: stream, type: type) else { return false }
 
     let outputType: SCStreamOutputType
@@ -583,7 +1123,8 @@ public func removeStreamOutput(
 
     do {
         try scStream.removeStreamOutput(handler, type: outputType)
-        handlerRegistry.remove(for: stream, type: type)
+        handlerRegistry.remove(handler, for: stream, type: type)
+        restartState(for: stream)?.removeOutput(handler)
         return true
     } catch {
         return false
@@ -603,7 +1144,7 @@ public func startStreamCapture(
     _ context: UnsafeMutableRawPointer?,
     _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
 ) {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     Task {
         do {
             try await scStream.startCapture()
@@ -626,7 +1167,7 @@ public func stopStreamCapture(
     _ context: UnsafeMutableRawPointer?,
     _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
 ) {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     Task {
         do {
             try await scStream.stopCapture()
@@ -683,6 +1224,23 @@ public func scStreamCaptureSampleBuffer(
     _ configPtr: OpaquePointer,
     _ callback: @escaping @convention(c) (OpaquePointer?, UnsafePointer<CChar>?, UnsafeMutableRawPointer?) -> Void,
     _ userData: UnsafeMutableRawPointer?
+) {
+    // Wait for the first frame however long it takes
+    scStreamCaptureSampleBufferStable(filterPtr, configPtr, 0, false, .infinity, callback, userData)
+}
+
+/// Like `sc_stream_capture_sample_buffer`, but discards the first `skipFrames` frames and,
+/// when `requireComplete` is set, any frame that is not `.complete`. Fails after `timeout`
+/// seconds without a frame; pass `.infinity` to wait indefinitely
+@_cdecl("sc_stream_capture_sample_buffer_stable")
+public func scStreamCaptureSampleBufferStable(
+    _ filterPtr: OpaquePointer,
+    _ configPtr: OpaquePointer,
+    _ skipFrames: UInt32,
+    _ requireComplete: Bool,
+    _ timeout: Double,
+    _ callback: @escaping @convention(c) (OpaquePointer?, UnsafePointer<CChar>?, UnsafeMutableRawPointer?) -> Void,
+    _ userData: UnsafeMutableRawPointer?
 ) {
     let filter: SCContentFilter = unretained(filterPtr)
     let config: SCStreamConfiguration = unretained(configPtr)
@@ -690,7 +1248,7 @@ public func scStreamCaptureSampleBuffer(
     Task {
         do {
             let stream = SCStream(filter: filter, configuration: config, delegate: nil)
-            let handler = SingleFrameSampleBufferHandler()
+            let handler = SingleFrameSampleBufferHandler(skipFrames: Int(skipFrames), requireComplete: requireComplete)
             let queue = DispatchQueue(label: "com.screencapturekit.singleframe.samplebuffer", qos: .userInitiated)
             try stream.addStreamOutput(handler, type: .screen, sampleHandlerQueue: queue)
 
@@ -702,6 +1260,12 @@ public func scStreamCaptureSampleBuffer(
             }
 
             try await stream.startCapture()
+            // The caller gives up after the timeout; stop the stream instead of waiting forever
+            if timeout.isFinite {
+                DispatchQueue.global().asyncAfter(deadline: .now() + timeout) {
+                    handler.cancel(with: SCBridgeError.streamError("Timed out waiting for a frame"))
+                }
+            }
             let sampleBuffer = try await handler.nextSampleBuffer()
 
             // Retain the sample buffer for Rust side
@@ -729,11 +1293,20 @@ public func updateStreamContentFilter(
     _ context: UnsafeMutableRawPointer?,
     _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
 ) {
-    let scStream: SCStream = unretained(stream)
+    let scStream: SCStream = resolveStream(stream)
     let scFilter: SCContentFilter = unretained(filter)
+    let applied: SCContentFilter
+    do {
+        applied = try recordingExclusions(for: stream)?.filterForUpdate(scFilter) ?? scFilter
+    } catch {
+        SCBridgeError.from(error).description.withCString { callback(context, false, $0) }
+        return
+    }
     Task {
         do {
-            try await scStream.updateContentFilter(scFilter)
+            try await scStream.updateContentFilter(applied)
+            restartState(for: stream)?.update(filter: applied)
+            recordingExclusions(for: stream)?.filterDidUpdate(scFilter)
             callback(context, true, nil)
         } catch {
             let bridgeError = SCBridgeError.streamError(error.localizedDescription)
@@ -756,11 +1329,13 @@ public func updateStreamConfiguration(
     _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void
 ) {
     if #available(macOS 14.0, *) {
-        let scStream: SCStream = unretained(stream)
+        let scStream: SCStream = resolveStream(stream)
         let scConfig: SCStreamConfiguration = unretained(config)
         Task {
             do {
                 try await scStream.updateConfiguration(scConfig)
+                PixelBufferAttributes.set(PixelBufferAttributes.get(from: scConfig), on: scStream)
+                restartState(for: stream)?.update(configuration: duplicateStreamConfiguration(scConfig))
                 callback(context, true, nil)
             } catch {
                 let bridgeError = SCBridgeError.configurationError(error.localizedDescription)
@@ -779,7 +1354,7 @@ public func updateStreamConfiguration(
 @_cdecl("sc_stream_get_synchronization_clock")
 public func getStreamSynchronizationClock(_ stream: OpaquePointer) -> OpaquePointer? {
     if #available(macOS 13.0, *) {
-        let s: SCStream = unretained(stream)
+        let s: SCStream = resolveStream(stream)
         if let clock = s.synchronizationClock {
             // CMClock is a CoreFoundation type, retain and return it
             return OpaquePointer(Unmanaged.passRetained(clock as AnyObject).toOpaque())
@@ -788,6 +1363,148 @@ public func getStreamSynchronizationClock(_ stream: OpaquePointer) -> OpaquePoin
     return nil
 }
 
+// MARK: - Stream: Sleep/Wake Handling
+
+/// Stops a stream before the system sleeps and restarts it after wake.
+/// Holds the stream weakly so enabling auto-pause never extends its lifetime.
+private class SleepWakeObserver {
+    weak var stream: SCStream?
+    let streamPtr: OpaquePointer
+    let callback: @convention(c) (OpaquePointer, Int32, Bool) -> Void
+    private var tokens: [NSObjectProtocol] = []
+    private var pausedForSleep = false
+
+    init(stream: SCStream, streamPtr: OpaquePointer, callback: @escaping @convention(c) (OpaquePointer, Int32, Bool) -> Void) {
+        self.stream = stream
+        self.streamPtr = streamPtr
+        self.callback = callback
+
+        let center = NSWorkspace.shared.notificationCenter
+        tokens.append(center.addObserver(forName: NSWorkspace.willSleepNotification, object: nil, queue: nil) { [weak self] _ in
+            self?.willSleep()
+        })
+        tokens.append(center.addObserver(forName: NSWorkspace.didWakeNotification, object: nil, queue: nil) { [weak self] _ in
+            self?.didWake()
+        })
+    }
+
+    deinit {
+        let center = NSWorkspace.shared.notificationCenter
+        for token in tokens {
+            center.removeObserver(token)
+        }
+    }
+
+    private func willSleep() {
+        guard let original = stream else { return }
+        let stream = resolveStream(original)
+        // The system only waits briefly after willSleep, so stop synchronously with a timeout
+        let semaphore = DispatchSemaphore(value: 0)
+        let result = StopResult()
+        Task {
+            do {
+                try await stream.stopCapture()
+                result.stopped = true
+            } catch {}
+            semaphore.signal()
+        }
+        _ = semaphore.wait(timeout: .now() + 2)
+        pausedForSleep = result.stopped
+        callback(streamPtr, 0, result.stopped)
+    }
+
+    private func didWake() {
+        // Capture wasn't running before sleep, so there is nothing to resume or report
+        guard let original = stream, pausedForSleep else { return }
+        let stream = resolveStream(original)
+        let filter = StreamRestartState.get(from: original)?.currentFilter
+        pausedForSleep = false
+        Task {
+            do {
+                // Displays can be disconnected or rearranged while asleep
+                let content = try await SCShareableContent.current
+                guard filter.map({ contentFilter($0, isAvailableIn: content) }) ?? !content.displays.isEmpty else {
+                    throw SCBridgeError.contentUnavailable("Captured content is gone after wake")
+                }
+                try await stream.startCapture()
+                callback(streamPtr, 1, true)
+            } catch {
+                callback(streamPtr, 1, false)
+            }
+        }
+    }
+}
+
+/// Whether the display or windows `filter` captures are still part of `content`
+private func contentFilter(_ filter: SCContentFilter, isAvailableIn content: SCShareableContent) -> Bool {
+    let displayIDs = Set(content.displays.map(\.displayID))
+    let windowIDs = Set(content.windows.map(\.windowID))
+    guard let recipe = FilterRecipe.get(from: filter) else {
+        // Filters from the content picker have no recipe; ask the filter itself
+        #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+            if #available(macOS 15.2, *) {
+                if filter.style == .window {
+                    return filter.includedWindows.contains { windowIDs.contains($0.windowID) }
+                }
+                return !displayIDs.isEmpty && filter.includedDisplays.allSatisfy { displayIDs.contains($0.displayID) }
+            }
+        #endif
+        // Nothing to compare against; any display will do
+        return !displayIDs.isEmpty
+    }
+    switch recipe.kind {
+    case let .window(window):
+        return windowIDs.contains(window.windowID)
+    case let .excludingWindows(display, _),
+         let .includingWindows(display, _),
+         let .includingApplications(display, _, _),
+         let .excludingApplications(display, _, _):
+        return displayIDs.contains(display.displayID)
+    }
+}
+
+private final class StopResult {
+    var stopped = false
+}
+
+private let sleepWakeObservers = NSMutableDictionary()
+private let sleepWakeLock = NSLock()
+
+@_cdecl("sc_stream_set_auto_pause_on_sleep")
+public func setStreamAutoPauseOnSleep(
+    _ stream: OpaquePointer,
+    _ enabled: Bool,
+    _ callback: @escaping @convention(c) (OpaquePointer, Int32, Bool) -> Void
+) {
+    let key = NSNumber(value: UInt(bitPattern: stream))
+    sleepWakeLock.lock()
+    defer { sleepWakeLock.unlock() }
+    if enabled {
+        let scStream: SCStream = unretained(stream)
+        sleepWakeObservers[key] = SleepWakeObserver(stream: scStream, streamPtr: stream, callback: callback)
+    } else {
+        sleepWakeObservers.removeObject(forKey: key)
+    }
+}
+
+/// Returns a copy of the configuration the stream is currently using
+@_cdecl("sc_stream_copy_configuration")
+public func copyCurrentStreamConfiguration(_ stream: OpaquePointer) -> OpaquePointer? {
+    guard let configuration = restartState(for: stream)?.currentConfiguration else { return nil }
+    return retain(duplicateStreamConfiguration(configuration))
+}
+
+// MARK: - Stream: Auto Restart
+
+@_cdecl("sc_stream_set_auto_restart")
+public func setStreamAutoRestart(
+    _ stream: OpaquePointer,
+    _ enabled: Bool,
+    _ callback: @escaping @convention(c) (OpaquePointer, Int32, UnsafePointer<CChar>) -> Void
+) {
+    restartState(for: stream)?.setEnabled(enabled, callback: callback)
+}
+
 @_cdecl("sc_stream_retain")
 public func retainStream(_ stream: OpaquePointer) -> OpaquePointer {
     let s: SCStream = unretained(stream)
@@ -799,19 +1516,242 @@ public func releaseStream(_ stream: OpaquePointer) {
     release(stream)
 }
 
+// MARK: - Stream: windows hidden while recording
+
+/// Windows left out of a stream's content while a recording output is attached
+private final class RecordingExclusions {
+    private var windows: [SCWindow] = []
+    /// The filter to go back to once the last recording output is removed;
+    /// set only while the windows are hidden
+    private var previewFilter: SCContentFilter?
+    private var recordings = 0
+    private let lock = NSLock()
+
+    private static var associationKey: UInt8 = 0
+
+    static func get(from stream: SCStream) -> RecordingExclusions? {
+        objc_getAssociatedObject(stream, &associationKey) as? RecordingExclusions
+    }
+
+    static func attach(to stream: SCStream) -> RecordingExclusions {
+        if let existing = get(from: stream) {
+            return existing
+        }
+        let exclusions = RecordingExclusions()
+        objc_setAssociatedObject(stream, &associationKey, exclusions, .OBJC_ASSOCIATION_RETAIN)
+        return exclusions
+    }
+
+    private static func cannotHide(_ window: SCWindow) -> SCBridgeError {
+        .configurationError("The stream's content filter cannot leave out window \(window.windowID)")
+    }
+
+    /// Adds a window, returning the filter to switch to if a recording is running
+    func add(_ window: SCWindow, current: SCContentFilter) throws -> SCContentFilter? {
+        lock.lock()
+        defer { lock.unlock() }
+        guard !windows.contains(where: { $0.windowID == window.windowID }) else { return nil }
+        guard let hidden = contentFilter(previewFilter ?? current, hiding: windows + [window]) else {
+            throw Self.cannotHide(window)
+        }
+        windows.append(window)
+        return previewFilter == nil ? nil : hidden
+    }
+
+    /// Counts a recording output about to be added, returning the filter to
+    /// switch to if it is the first one. Call `didHide(restoring:)` once the
+    /// stream has switched.
+    func recordingWillStart(current: SCContentFilter) throws -> SCContentFilter? {
+        lock.lock()
+        defer { lock.unlock() }
+        recordings += 1
+        guard recordings == 1, !windows.isEmpty else { return nil }
+        guard let hidden = contentFilter(current, hiding: windows) else {
+            recordings -= 1
+            throw Self.cannotHide(windows[0])
+        }
+        return hidden
+    }
+
+    /// Records that the stream now leaves the windows out, and that `preview`
+    /// is the filter to restore when the last recording stops
+    func didHide(restoring preview: SCContentFilter) {
+        lock.lock()
+        defer { lock.unlock() }
+        if recordings > 0 {
+            previewFilter = preview
+        }
+    }
+
+    /// Counts a recording output removed (or one that failed to be added),
+    /// returning the filter to restore if it was the last one
+    func recordingDidStop() -> SCContentFilter? {
+        lock.lock()
+        defer { lock.unlock() }
+        recordings = max(recordings - 1, 0)
+        guard recordings == 0 else { return nil }
+        defer { previewFilter = nil }
+        return previewFilter
+    }
+
+    /// The filter to apply in place of `filter` while the windows are hidden
+    func filterForUpdate(_ filter: SCContentFilter) throws -> SCContentFilter {
+        lock.lock()
+        defer { lock.unlock() }
+        guard previewFilter != nil, !windows.isEmpty else { return filter }
+        guard let hidden = contentFilter(filter, hiding: windows) else {
+            throw Self.cannotHide(windows[0])
+        }
+        return hidden
+    }
+
+    /// Records that the stream switched to the hidden version of `filter`,
+    /// so `filter` is what gets restored when the recording stops
+    func filterDidUpdate(_ filter: SCContentFilter) {
+        lock.lock()
+        defer { lock.unlock() }
+        if previewFilter != nil {
+            previewFilter = filter
+        }
+    }
+}
+
+private func recordingExclusions(for stream: OpaquePointer) -> RecordingExclusions? {
+    let original: SCStream = unretained(stream)
+    return RecordingExclusions.get(from: original)
+}
+
+/// Switches the capturing stream to `filter` and keeps it for restarts
+private func applyContentFilter(_ filter: SCContentFilter, to stream: OpaquePointer) async throws {
+    try await resolveStream(stream).updateContentFilter(filter)
+    restartState(for: stream)?.update(filter: filter)
+}
+
+@_cdecl("sc_stream_exclude_window_during_recording")
+public func excludeStreamWindowDuringRecording(
+    _ stream: OpaquePointer,
+    _ window: OpaquePointer,
+    _ callback: @escaping @convention(c) (UnsafeMutableRawPointer?, Bool, UnsafePointer<CChar>?) -> Void,
+    _ context: UnsafeMutableRawPointer?
+) {
+    let original: SCStream = unretained(stream)
+    let scWindow: SCWindow = unretained(window)
+    guard let current = restartState(for: stream)?.currentFilter else {
+        SCBridgeError.streamError("Stream has no content filter").description.withCString { callback(context, false, $0) }
+        return
+    }
+    Task {
+        do {
+            if let filter = try RecordingExclusions.attach(to: original).add(scWindow, current: current) {
+                try await applyContentFilter(filter, to: stream)
+            }
+            callback(context, true, nil)
+        } catch {
+            SCBridgeError.from(error).description.withCString { callback(context, false, $0) }
+        }
+    }
+}
+
 // MARK: - Recording Output (macOS 15.0+)
 
 #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
     // Full implementation for macOS 15 SDK
 
+    /// Recording outputs attached to a stream, by how they are recorded
+    ///
+    /// The system may refuse a second recording output on a stream. Outputs
+    /// added after that are recorded from the stream's own samples by a
+    /// `FanOutRecorder`.
+    @available(macOS 15.0, *)
+    private final class AttachedRecordingOutputs {
+        private var native: Set<ObjectIdentifier> = []
+        private var fannedOut: [ObjectIdentifier: FanOutRecorder] = [:]
+        private let lock = NSLock()
+
+        private static var associationKey: UInt8 = 0
+
+        static func get(from stream: SCStream) -> AttachedRecordingOutputs? {
+            objc_getAssociatedObject(stream, &associationKey) as? AttachedRecordingOutputs
+        }
+
+        static func attach(to stream: SCStream) -> AttachedRecordingOutputs {
+            if let existing = get(from: stream) {
+                return existing
+            }
+            let outputs = AttachedRecordingOutputs()
+            objc_setAssociatedObject(stream, &associationKey, outputs, .OBJC_ASSOCIATION_RETAIN)
+            return outputs
+        }
+
+        var hasNative: Bool {
+            lock.lock()
+            defer { lock.unlock() }
+            return !native.isEmpty
+        }
+
+        func addNative(_ output: SCRecordingOutput) {
+            lock.lock()
+            defer { lock.unlock() }
+            native.insert(ObjectIdentifier(output))
+        }
+
+        func removeNative(_ output: SCRecordingOutput) {
+            lock.lock()
+            defer { lock.unlock() }
+            native.remove(ObjectIdentifier(output))
+        }
+
+        func addFannedOut(_ recorder: FanOutRecorder, for output: SCRecordingOutput) {
+            lock.lock()
+            defer { lock.unlock() }
+            fannedOut[ObjectIdentifier(output)] = recorder
+        }
+
+        func removeFannedOut(for output: SCRecordingOutput) -> FanOutRecorder? {
+            lock.lock()
+            defer { lock.unlock() }
+            return fannedOut.removeValue(forKey: ObjectIdentifier(output))
+        }
+
+        func isFannedOut(_ output: SCRecordingOutput) -> Bool {
+            lock.lock()
+            defer { lock.unlock() }
+            return fannedOut[ObjectIdentifier(output)] != nil
+        }
+    }
+
     @available(macOS 15.0, *)
     private func addRecordingOutputImpl(
         _ stream: OpaquePointer,
         _ recordingOutput: OpaquePointer
     ) throws {
-        let s: SCStream = unretained(stream)
+        let original: SCStream = unretained(stream)
+        let s: SCStream = resolveStream(original)
         let rec: SCRecordingOutput = unretained(recordingOutput)
-        try s.addRecordingOutput(rec)
+        let attached = AttachedRecordingOutputs.attach(to: original)
+        do {
+            try s.addRecordingOutput(rec)
+            attached.addNative(rec)
+            clearFanOutRecorder(for: rec)
+        } catch {
+            // Only a refused second output is recorded from the stream's samples
+            let capturesAudio = restartState(for: stream)?.currentConfiguration.capturesAudio ?? false
+            guard attached.hasNative,
+                  let recorder = makeFanOutRecorder(for: rec, capturesAudio: capturesAudio)
+            else {
+                throw error
+            }
+            try s.addStreamOutput(recorder, type: .screen, sampleHandlerQueue: recorder.queue)
+            if capturesAudio {
+                do {
+                    try s.addStreamOutput(recorder, type: .audio, sampleHandlerQueue: recorder.queue)
+                } catch {
+                    try? s.removeStreamOutput(recorder, type: .screen)
+                    throw error
+                }
+            }
+            attached.addFannedOut(recorder, for: rec)
+        }
     }
 
     @available(macOS 15.0, *)
@@ -819,9 +1759,28 @@ public func releaseStream(_ stream: OpaquePointer) {
         _ stream: OpaquePointer,
         _ recordingOutput: OpaquePointer
     ) throws {
-        let s: SCStream = unretained(stream)
+        let original: SCStream = unretained(stream)
+        let s: SCStream = resolveStream(original)
         let rec: SCRecordingOutput = unretained(recordingOutput)
+        let attached = AttachedRecordingOutputs.get(from: original)
+        if let recorder = attached?.removeFannedOut(for: rec) {
+            try? s.removeStreamOutput(recorder, type: .screen)
+            if recorder.capturesAudio {
+                try? s.removeStreamOutput(recorder, type: .audio)
+            }
+            recorder.finish()
+            return
+        }
         try s.removeRecordingOutput(rec)
+        attached?.removeNative(rec)
+    }
+
+    @_cdecl("sc_stream_recording_output_is_fanned_out")
+    public func isStreamRecordingOutputFannedOut(_ stream: OpaquePointer, _ recordingOutput: OpaquePointer) -> Bool {
+        guard #available(macOS 15.0, *) else { return false }
+        let original: SCStream = unretained(stream)
+        let rec: SCRecordingOutput = unretained(recordingOutput)
+        return AttachedRecordingOutputs.get(from: original)?.isFannedOut(rec) ?? false
     }
 
     @_cdecl("sc_stream_add_recording_output")
@@ -832,11 +1791,41 @@ public func releaseStream(_ stream: OpaquePointer) {
         _ context: UnsafeMutableRawPointer?
     ) {
         if #available(macOS 15.0, *) {
-            do {
-                try addRecordingOutputImpl(stream, recordingOutput)
-                callback(context, true, nil)
-            } catch {
-                error.localizedDescription.withCString { callback(context, false, $0) }
+            guard let exclusions = recordingExclusions(for: stream),
+                  let current = restartState(for: stream)?.currentFilter
+            else {
+                do {
+                    try addRecordingOutputImpl(stream, recordingOutput)
+                    callback(context, true, nil)
+                } catch {
+                    error.localizedDescription.withCString { callback(context, false, $0) }
+                }
+                return
+            }
+            // Hide the windows before the recording starts so its first frames leave them out
+            Task {
+                do {
+                    if let hidden = try exclusions.recordingWillStart(current: current) {
+                        do {
+                            try await applyContentFilter(hidden, to: stream)
+                        } catch {
+                            _ = exclusions.recordingDidStop()
+                            throw error
+                        }
+                        exclusions.didHide(restoring: current)
+                    }
+                    do {
+                        try addRecordingOutputImpl(stream, recordingOutput)
+                    } catch {
+                        if let preview = exclusions.recordingDidStop() {
+                            try? await applyContentFilter(preview, to: stream)
+                        }
+                        throw error
+                    }
+                    callback(context, true, nil)
+                } catch {
+                    SCBridgeError.from(error).description.withCString { callback(context, false, $0) }
+                }
             }
         } else {
             let bridgeError = SCBridgeError.configurationError("addRecordingOutput requires macOS 15.0 or later")
@@ -854,9 +1843,21 @@ public func releaseStream(_ stream: OpaquePointer) {
         if #available(macOS 15.0, *) {
             do {
                 try removeRecordingOutputImpl(stream, recordingOutput)
-                callback(context, true, nil)
             } catch {
                 error.localizedDescription.withCString { callback(context, false, $0) }
+                return
+            }
+            guard let preview = recordingExclusions(for: stream)?.recordingDidStop() else {
+                callback(context, true, nil)
+                return
+            }
+            Task {
+                do {
+                    try await applyContentFilter(preview, to: stream)
+                    callback(context, true, nil)
+                } catch {
+                    SCBridgeError.from(error).description.withCString { callback(context, false, $0) }
+                }
             }
         } else {
             let bridgeError = SCBridgeError.configurationError("removeRecordingOutput requires macOS 15.0 or later")
@@ -867,6 +1868,11 @@ public func releaseStream(_ stream: OpaquePointer) {
 #else
     // Stub implementation for older SDKs (macOS < 15 SDK)
 
+    @_cdecl("sc_stream_recording_output_is_fanned_out")
+    public func isStreamRecordingOutputFannedOut(_: OpaquePointer, _: OpaquePointer) -> Bool {
+        false
+    }
+
     @_cdecl("sc_stream_add_recording_output")
     public func addRecordingOutput(
         _: OpaquePointer,
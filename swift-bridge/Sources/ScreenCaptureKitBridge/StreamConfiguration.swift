@@ -2,6 +2,7 @@
 
 import CoreGraphics
 import CoreMedia
+import CoreVideo
 import Foundation
 import ScreenCaptureKit
 
@@ -23,6 +24,64 @@ public func releaseStreamConfiguration(_ config: OpaquePointer) {
     release(config)
 }
 
+@_cdecl("sc_stream_configuration_copy")
+public func copyStreamConfiguration(_ config: OpaquePointer) -> OpaquePointer {
+    let c: SCStreamConfiguration = unretained(config)
+    return retain(duplicateStreamConfiguration(c))
+}
+
+/// Returns an independent copy of `source`.
+///
+/// `SCStreamConfiguration` doesn't adopt `NSCopying`, so each property the
+/// bridge exposes is copied by name, along with the settings this bridge
+/// attaches to the configuration. Add new properties here when adding their
+/// setters.
+func duplicateStreamConfiguration(_ source: SCStreamConfiguration) -> SCStreamConfiguration {
+    let copy = SCStreamConfiguration()
+    copy.width = source.width
+    copy.height = source.height
+    copy.minimumFrameInterval = source.minimumFrameInterval
+    copy.pixelFormat = source.pixelFormat
+    copy.scalesToFit = source.scalesToFit
+    copy.sourceRect = source.sourceRect
+    copy.destinationRect = source.destinationRect
+    copy.queueDepth = source.queueDepth
+    copy.showsCursor = source.showsCursor
+    copy.backgroundColor = source.backgroundColor
+    copy.colorSpaceName = source.colorSpaceName
+    copy.colorMatrix = source.colorMatrix
+    copy.capturesAudio = source.capturesAudio
+    copy.sampleRate = source.sampleRate
+    copy.channelCount = source.channelCount
+    copy.excludesCurrentProcessAudio = source.excludesCurrentProcessAudio
+    if #available(macOS 14.0, *) {
+        copy.preservesAspectRatio = source.preservesAspectRatio
+        copy.captureResolution = source.captureResolution
+        copy.shouldBeOpaque = source.shouldBeOpaque
+        copy.ignoreShadowsDisplay = source.ignoreShadowsDisplay
+        copy.ignoreShadowsSingleWindow = source.ignoreShadowsSingleWindow
+        copy.ignoreGlobalClipDisplay = source.ignoreGlobalClipDisplay
+        copy.ignoreGlobalClipSingleWindow = source.ignoreGlobalClipSingleWindow
+        copy.capturesShadowsOnly = source.capturesShadowsOnly
+        copy.presenterOverlayPrivacyAlertSetting = source.presenterOverlayPrivacyAlertSetting
+        copy.streamName = source.streamName
+    }
+    if #available(macOS 14.2, *) {
+        copy.includeChildWindows = source.includeChildWindows
+    }
+    #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
+        if #available(macOS 15.0, *) {
+            copy.captureMicrophone = source.captureMicrophone
+            copy.microphoneCaptureDeviceID = source.microphoneCaptureDeviceID
+            copy.captureDynamicRange = source.captureDynamicRange
+            copy.showMouseClicks = source.showMouseClicks
+        }
+    #endif
+    PixelBufferAttributes.set(PixelBufferAttributes.get(from: source), on: copy)
+    ColorRangePreference.set(ColorRangePreference.get(from: source), on: copy)
+    return copy
+}
+
 @_cdecl("sc_stream_configuration_set_width")
 public func setStreamConfigurationWidth(_ config: OpaquePointer, _ width: Int) {
     let scConfig: SCStreamConfiguration = unretained(config)
@@ -138,7 +197,7 @@ public func getStreamConfigurationQueueDepth(_ config: OpaquePointer) -> Int {
 @_cdecl("sc_stream_configuration_set_pixel_format")
 public func setStreamConfigurationPixelFormat(_ config: OpaquePointer, _ format: UInt32) {
     let scConfig: SCStreamConfiguration = unretained(config)
-    scConfig.pixelFormat = format
+    scConfig.pixelFormat = ColorRangePreference.get(from: scConfig)?.apply(to: format) ?? format
 }
 
 @_cdecl("sc_stream_configuration_get_pixel_format")
@@ -161,6 +220,17 @@ public func setStreamConfigurationColorSpaceName(_ config: OpaquePointer, _ name
     scConfig.colorSpaceName = colorSpaceName as CFString
 }
 
+@_cdecl("sc_stream_configuration_get_color_space_name")
+public func getStreamConfigurationColorSpaceName(_ config: OpaquePointer, _ buffer: UnsafeMutablePointer<CChar>, _ bufferSize: Int) -> Bool {
+    let scConfig: SCStreamConfiguration = unretained(config)
+    let name = scConfig.colorSpaceName as String
+    guard !name.isEmpty, let cString = name.cString(using: .utf8), cString.count < bufferSize else {
+        return false
+    }
+    buffer.initialize(from: cString, count: cString.count)
+    return true
+}
+
 @_cdecl("sc_stream_configuration_set_should_be_opaque")
 public func setStreamConfigurationShouldBeOpaque(_ config: OpaquePointer, _ shouldBeOpaque: Bool) {
     let scConfig: SCStreamConfiguration = unretained(config)
@@ -623,6 +693,110 @@ public func getStreamConfigurationIgnoreGlobalClipSingleWindow(_ config: OpaqueP
     return false
 }
 
+// MARK: - Pixel buffer attributes
+
+/// CoreVideo attributes requested for delivered pixel buffers.
+///
+/// `SCStreamConfiguration` has no property for these, so they are attached to the
+/// configuration as an associated object and enforced by the stream output handler.
+final class PixelBufferAttributes: NSObject {
+    let metalCompatible: Bool
+    let openGLCompatible: Bool
+    let extendedLeft: Int
+    let extendedTop: Int
+    let extendedRight: Int
+    let extendedBottom: Int
+
+    private static var associationKey: UInt8 = 0
+
+    init(metalCompatible: Bool, openGLCompatible: Bool, extendedLeft: Int, extendedTop: Int, extendedRight: Int, extendedBottom: Int) {
+        self.metalCompatible = metalCompatible
+        self.openGLCompatible = openGLCompatible
+        self.extendedLeft = extendedLeft
+        self.extendedTop = extendedTop
+        self.extendedRight = extendedRight
+        self.extendedBottom = extendedBottom
+    }
+
+    var isEmpty: Bool {
+        !metalCompatible && !openGLCompatible
+            && extendedLeft == 0 && extendedTop == 0 && extendedRight == 0 && extendedBottom == 0
+    }
+
+    /// Attributes dictionary for `CVPixelBufferPoolCreate`
+    var cvAttributes: [CFString: Any] {
+        var attrs: [CFString: Any] = [kCVPixelBufferIOSurfacePropertiesKey: [:] as CFDictionary]
+        if metalCompatible { attrs[kCVPixelBufferMetalCompatibilityKey] = true }
+        if openGLCompatible { attrs[kCVPixelBufferOpenGLCompatibilityKey] = true }
+        if extendedLeft > 0 { attrs[kCVPixelBufferExtendedPixelsLeftKey] = extendedLeft }
+        if extendedTop > 0 { attrs[kCVPixelBufferExtendedPixelsTopKey] = extendedTop }
+        if extendedRight > 0 { attrs[kCVPixelBufferExtendedPixelsRightKey] = extendedRight }
+        if extendedBottom > 0 { attrs[kCVPixelBufferExtendedPixelsBottomKey] = extendedBottom }
+        return attrs
+    }
+
+    /// Whether a delivered buffer already meets these requirements (no copy needed).
+    /// IOSurface-backed buffers can be wrapped as Metal textures and GL textures directly.
+    func isSatisfied(by pixelBuffer: CVPixelBuffer) -> Bool {
+        if metalCompatible || openGLCompatible, CVPixelBufferGetIOSurface(pixelBuffer) == nil {
+            return false
+        }
+        var left = 0, right = 0, top = 0, bottom = 0
+        CVPixelBufferGetExtendedPixels(pixelBuffer, &left, &right, &top, &bottom)
+        return left >= extendedLeft && right >= extendedRight && top >= extendedTop && bottom >= extendedBottom
+    }
+
+    static func get(from object: AnyObject) -> PixelBufferAttributes? {
+        objc_getAssociatedObject(object, &associationKey) as? PixelBufferAttributes
+    }
+
+    static func set(_ attributes: PixelBufferAttributes?, on object: AnyObject) {
+        objc_setAssociatedObject(object, &associationKey, attributes, .OBJC_ASSOCIATION_RETAIN)
+    }
+}
+
+@_cdecl("sc_stream_configuration_set_pixel_buffer_attributes")
+public func setStreamConfigurationPixelBufferAttributes(
+    _ config: OpaquePointer,
+    _ metalCompatible: Bool,
+    _ openGLCompatible: Bool,
+    _ extendedLeft: Int,
+    _ extendedTop: Int,
+    _ extendedRight: Int,
+    _ extendedBottom: Int
+) {
+    let scConfig: SCStreamConfiguration = unretained(config)
+    let attributes = PixelBufferAttributes(
+        metalCompatible: metalCompatible,
+        openGLCompatible: openGLCompatible,
+        extendedLeft: extendedLeft,
+        extendedTop: extendedTop,
+        extendedRight: extendedRight,
+        extendedBottom: extendedBottom
+    )
+    PixelBufferAttributes.set(attributes.isEmpty ? nil : attributes, on: scConfig)
+}
+
+@_cdecl("sc_stream_configuration_get_pixel_buffer_attributes")
+public func getStreamConfigurationPixelBufferAttributes(
+    _ config: OpaquePointer,
+    _ metalCompatible: UnsafeMutablePointer<Bool>,
+    _ openGLCompatible: UnsafeMutablePointer<Bool>,
+    _ extendedLeft: UnsafeMutablePointer<Int>,
+    _ extendedTop: UnsafeMutablePointer<Int>,
+    _ extendedRight: UnsafeMutablePointer<Int>,
+    _ extendedBottom: UnsafeMutablePointer<Int>
+) {
+    let scConfig: SCStreamConfiguration = unretained(config)
+    let attributes = PixelBufferAttributes.get(from: scConfig)
+    metalCompatible.pointee = attributes?.metalCompatible ?? false
+    openGLCompatible.pointee = attributes?.openGLCompatible ?? false
+    extendedLeft.pointee = attributes?.extendedLeft ?? 0
+    extendedTop.pointee = attributes?.extendedTop ?? 0
+    extendedRight.pointee = attributes?.extendedRight ?? 0
+    extendedBottom.pointee = attributes?.extendedBottom ?? 0
+}
+
 // MARK: - Preset-based configuration (macOS 15.0+)
 
 #if SCREENCAPTUREKIT_HAS_MACOS15_SDK
@@ -651,3 +825,48 @@ public func getStreamConfigurationIgnoreGlobalClipSingleWindow(_ config: OpaqueP
         retain(SCStreamConfiguration())
     }
 #endif
+
+// MARK: - Color Range
+
+/// YCbCr range requested independently of the pixel format.
+///
+/// ScreenCaptureKit derives the range from the pixel format (`420v` vs `420f`), so the
+/// preference is attached to the configuration and applied whenever a YCbCr format is set.
+final class ColorRangePreference: NSObject {
+    let fullRange: Bool
+
+    private static var associationKey: UInt8 = 0
+
+    init(fullRange: Bool) {
+        self.fullRange = fullRange
+    }
+
+    /// Maps a YCbCr format to its variant with the requested range; RGB formats are unchanged
+    func apply(to format: OSType) -> OSType {
+        switch format {
+        case kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange, kCVPixelFormatType_420YpCbCr8BiPlanarFullRange:
+            fullRange ? kCVPixelFormatType_420YpCbCr8BiPlanarFullRange : kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange
+        case kCVPixelFormatType_420YpCbCr10BiPlanarVideoRange, kCVPixelFormatType_420YpCbCr10BiPlanarFullRange:
+            fullRange ? kCVPixelFormatType_420YpCbCr10BiPlanarFullRange : kCVPixelFormatType_420YpCbCr10BiPlanarVideoRange
+        default:
+            format
+        }
+    }
+
+    static func get(from object: AnyObject) -> ColorRangePreference? {
+        objc_getAssociatedObject(object, &associationKey) as? ColorRangePreference
+    }
+
+    static func set(_ preference: ColorRangePreference?, on object: AnyObject) {
+        objc_setAssociatedObject(object, &associationKey, preference, .OBJC_ASSOCIATION_RETAIN)
+    }
+}
+
+/// Sets the requested range (0 = video, 1 = full) and converts the current pixel format
+@_cdecl("sc_stream_configuration_set_color_range")
+public func setStreamConfigurationColorRange(_ config: OpaquePointer, _ range: Int32) {
+    let scConfig: SCStreamConfiguration = unretained(config)
+    let preference = ColorRangePreference(fullRange: range == 1)
+    ColorRangePreference.set(preference, on: scConfig)
+    scConfig.pixelFormat = preference.apply(to: scConfig.pixelFormat)
+}
@@ -1,5 +1,7 @@
 //! `AudioBuffer` and `AudioBufferList` tests
 
+mod common;
+
 use screencapturekit::cm::AudioBuffer;
 
 #[test]
@@ -25,3 +27,32 @@ fn test_audio_buffer_hash() {
     fn assert_hash_impl<T: std::hash::Hash>() {}
     assert_hash_impl::<AudioBuffer>();
 }
+
+#[test]
+fn test_audio_channels_rejects_video_sample() {
+    use screencapturekit::error::SCError;
+
+    let sample = common::plain_video_sample();
+
+    assert!(matches!(
+        sample.audio_channels(),
+        Err(SCError::InvalidBuffer(_))
+    ));
+}
+
+#[test]
+fn test_audio_channels_to_f32() {
+    use screencapturekit::cm::{AudioChannels, AudioSampleFormat};
+
+    let channels = AudioChannels::I16(vec![vec![i16::MIN, 0, 16_384]]);
+    assert_eq!(channels.sample_format(), AudioSampleFormat::I16);
+    assert_eq!(channels.channel_count(), 1);
+    assert_eq!(channels.frame_count(), 3);
+    assert_eq!(channels.to_f32(), vec![vec![-1.0, 0.0, 0.5]]);
+
+    let channels = AudioChannels::I32(vec![vec![i32::MIN, 0], vec![1 << 30, 0]]);
+    assert_eq!(channels.sample_format(), AudioSampleFormat::I32);
+    assert_eq!(channels.channel_count(), 2);
+    assert_eq!(channels.frame_count(), 2);
+    assert_eq!(channels.to_f32(), vec![vec![-1.0, 0.0], vec![0.5, 0.0]]);
+}
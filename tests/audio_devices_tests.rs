@@ -26,3 +26,63 @@ fn test_default_device() {
         println!("No default audio input device");
     }
 }
+
+#[test]
+fn test_supported_formats() {
+    let Some(device) = AudioInputDevice::default_device() else {
+        println!("No default audio input device");
+        return;
+    };
+    let formats = device.supported_formats();
+    println!("{}: {formats:?}", device.name);
+    for format in &formats {
+        assert!(format.sample_rate > 0);
+        assert!(format.channels > 0);
+    }
+}
+
+#[test]
+fn test_validate_microphone_format() {
+    use screencapturekit::cg::CGSize;
+    use screencapturekit::prelude::*;
+
+    // Without the microphone only the system audio format is checked
+    let config = SCStreamConfiguration::new()
+        .with_size(CGSize::new(640.0, 480.0))
+        .with_captures_audio(true)
+        .with_sample_rate(48000);
+    assert!(config.validate().is_ok());
+
+    let config = SCStreamConfiguration::new()
+        .with_size(CGSize::new(640.0, 480.0))
+        .with_captures_microphone(true)
+        .with_microphone_capture_device_id("no-such-device");
+    if !config.captures_microphone() {
+        println!("Microphone capture needs macOS 15.0+");
+        return;
+    }
+    assert!(matches!(
+        config.validate(),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+
+    let Some(device) = AudioInputDevice::default_device() else {
+        return;
+    };
+    let Some(format) = device.supported_formats().first().copied() else {
+        return;
+    };
+    let config = SCStreamConfiguration::new()
+        .with_size(CGSize::new(640.0, 480.0))
+        .with_captures_microphone(true)
+        .with_microphone_capture_device_id(&device.id)
+        .with_sample_rate(format.sample_rate)
+        .with_channel_count(format.channels);
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_sample_rate(1)
+        .validate()
+        .expect_err("no microphone records at 1 Hz");
+    assert!(err.to_string().contains(&format.to_string()));
+}
@@ -3,6 +3,7 @@
 #![allow(clippy::float_cmp)]
 
 use screencapturekit::cg::{CGPoint, CGRect, CGSize};
+use screencapturekit::cg_display::CGDisplay;
 
 #[test]
 fn test_cgpoint_new() {
@@ -180,6 +181,17 @@ fn test_cgrect_is_empty() {
     assert!(!valid.is_empty());
 }
 
+#[test]
+fn test_cgrect_contains_point() {
+    let rect = CGRect::new(10.0, 20.0, 100.0, 50.0);
+    assert!(rect.contains_point(CGPoint::new(10.0, 20.0)));
+    assert!(rect.contains_point(CGPoint::new(60.0, 45.0)));
+    assert!(!rect.contains_point(CGPoint::new(110.0, 45.0)));
+    assert!(!rect.contains_point(CGPoint::new(60.0, 70.0)));
+    assert!(!rect.contains_point(CGPoint::new(9.9, 45.0)));
+    assert!(!CGRect::new(0.0, 0.0, 0.0, 10.0).contains_point(CGPoint::zero()));
+}
+
 #[test]
 fn test_cgrect_display() {
     let rect = CGRect::new(10.0, 20.0, 100.0, 200.0);
@@ -235,3 +247,20 @@ fn test_cg_types_clone() {
     let cloned_rect = rect;
     assert_eq!(rect, cloned_rect);
 }
+
+#[test]
+fn test_cg_display_mirror_source_implies_mirrored() {
+    let Ok(ids) = CGDisplay::active_displays() else {
+        return;
+    };
+    for id in ids {
+        let display = CGDisplay::new(id);
+        if let Some(source) = display.mirror_source() {
+            assert!(display.is_mirrored());
+            assert_ne!(source, display);
+        }
+    }
+
+    // An invalid display id mirrors nothing
+    assert_eq!(CGDisplay::new(0).mirror_source(), None);
+}
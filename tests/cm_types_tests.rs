@@ -100,3 +100,145 @@ fn test_cmsample_timing_default() {
 
     assert!(timing.is_valid());
 }
+
+#[test]
+fn test_cvpixelbuffer_is_hdr_sdr_buffer() {
+    use screencapturekit::cm::CVPixelBuffer;
+
+    // A freshly created BGRA buffer has no HDR transfer function attached
+    let buffer = CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+    assert!(!buffer.is_hdr());
+}
+
+#[test]
+fn test_sample_buffer_retained_outlives_original() {
+    use screencapturekit::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
+
+    let pixel_buffer =
+        CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(
+        &pixel_buffer,
+        CMTime::new(3, 30),
+        CMTime::new(1, 30),
+    )
+    .expect("Failed to create sample buffer");
+
+    let kept = sample.retained();
+    assert_eq!(kept, sample);
+
+    // Simulate the capture callback returning while a consumer thread holds the frame
+    drop(sample);
+    let handle = std::thread::spawn(move || {
+        assert!(kept.is_valid());
+        kept.presentation_timestamp().value
+    });
+    assert_eq!(handle.join().unwrap(), 3);
+}
+
+#[test]
+fn test_sample_buffer_sequence_number_outside_stream() {
+    use screencapturekit::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
+
+    let pixel_buffer =
+        CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(
+        &pixel_buffer,
+        CMTime::new(0, 30),
+        CMTime::new(1, 30),
+    )
+    .expect("Failed to create sample buffer");
+
+    // Only buffers delivered by a stream are numbered
+    assert_eq!(sample.sequence_number(), None);
+}
+
+#[test]
+fn test_sample_buffer_to_raw_frame() {
+    use screencapturekit::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
+
+    let pixel_buffer =
+        CVPixelBuffer::create(64, 48, 0x4247_5241).expect("Failed to create pixel buffer");
+    let sample = CMSampleBuffer::create_for_image_buffer(
+        &pixel_buffer,
+        CMTime::new(7, 30),
+        CMTime::new(1, 30),
+    )
+    .expect("Failed to create sample buffer");
+
+    let guard = sample.to_raw_frame().expect("BGRA frame should be flat");
+    // The guard keeps the pixels alive on its own
+    drop(sample);
+    let frame = guard.frame();
+    assert_eq!((frame.width, frame.height), (64, 48));
+    assert_eq!(frame.pixel_format, 0x4247_5241);
+    assert!(frame.bytes_per_row >= 64 * 4);
+    assert_eq!(frame.data_len, frame.bytes_per_row as usize * 48);
+    assert!(!frame.data_ptr.is_null());
+    assert_eq!(guard.as_slice().len(), frame.data_len);
+    assert_eq!((frame.pts_value, frame.pts_timescale), (7, 30));
+
+    // Planar formats have no single contiguous range to describe
+    let planar = CVPixelBuffer::create(64, 48, 0x3432_3076).expect("Failed to create pixel buffer");
+    let sample =
+        CMSampleBuffer::create_for_image_buffer(&planar, CMTime::new(0, 30), CMTime::new(1, 30))
+            .expect("Failed to create sample buffer");
+    assert!(sample.to_raw_frame().is_none());
+}
+
+#[test]
+fn test_cvpixelbuffer_is_black() {
+    use screencapturekit::cm::CVPixelBuffer;
+
+    let buffer = CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+    {
+        let mut guard = buffer
+            .lock_base_address(false)
+            .expect("Failed to lock pixel buffer");
+        let len = buffer.bytes_per_row() * buffer.height();
+        unsafe { std::ptr::write_bytes(guard.base_address_mut(), 0, len) };
+    }
+    assert!(buffer.is_black());
+
+    {
+        let mut guard = buffer
+            .lock_base_address(false)
+            .expect("Failed to lock pixel buffer");
+        let len = buffer.bytes_per_row() * buffer.height();
+        unsafe { std::ptr::write_bytes(guard.base_address_mut(), 0xff, len) };
+    }
+    assert!(!buffer.is_black());
+}
+
+#[test]
+fn test_cmtime_ordering() {
+    use std::cmp::Ordering;
+
+    let a = CMTime::new(1, 30);
+    let b = CMTime::new(2, 60);
+    let later = CMTime::new(600, 1000);
+    assert_eq!(a.compare(&b), Some(Ordering::Equal));
+    assert_ne!(a, b);
+    assert!(a < later && b < later);
+    assert!(CMTime::new(-1, 1) < CMTime::ZERO);
+
+    // Special values sit at the ends of the timeline
+    assert!(CMTime::negative_infinity() < CMTime::new(i64::MIN, 1));
+    assert!(CMTime::new(i64::MAX, 1) < CMTime::indefinite());
+    assert!(CMTime::indefinite() < CMTime::positive_infinity());
+
+    // Invalid times don't order against anything else
+    assert_eq!(CMTime::INVALID.partial_cmp(&a), None);
+    assert_eq!(a.partial_cmp(&CMTime::INVALID), None);
+
+    assert_eq!(a.min(later), a);
+    assert_eq!(a.max(later), later);
+    assert_eq!(a.min(b), a);
+    assert_eq!(CMTime::INVALID.max(later), later);
+    assert_eq!(later.min(CMTime::INVALID), later);
+
+    let mut times = [later, CMTime::positive_infinity(), b, CMTime::ZERO, a];
+    times.sort_by(|x, y| x.partial_cmp(y).expect("valid times"));
+    assert_eq!(times[0], CMTime::ZERO);
+    assert_eq!(times[3], later);
+    assert!(times[4].is_positive_infinity());
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by the integration tests
+
+use screencapturekit::cm::{CMSampleBuffer, CMTime, CVPixelBuffer};
+
+/// A 64x64 BGRA frame wrapped in a sample buffer, without any of the
+/// attachments a stream adds
+pub fn plain_video_sample() -> CMSampleBuffer {
+    let pixel_buffer =
+        CVPixelBuffer::create(64, 64, 0x4247_5241).expect("Failed to create pixel buffer");
+    CMSampleBuffer::create_for_image_buffer(&pixel_buffer, CMTime::new(0, 30), CMTime::new(1, 30))
+        .expect("Failed to create sample buffer")
+}
@@ -161,6 +161,43 @@ fn test_zero_dimensions() {
     assert_eq!(config.height(), 0);
 }
 
+#[test]
+fn test_validate() {
+    use screencapturekit::error::SCError;
+    use screencapturekit::stream::configuration::MAX_OUTPUT_DIMENSION;
+
+    let valid = || {
+        SCStreamConfiguration::new()
+            .with_width(1920)
+            .with_height(1080)
+    };
+    assert!(valid().validate().is_ok());
+
+    let zero_height = valid().with_height(0);
+    assert!(matches!(
+        zero_height.validate(),
+        Err(SCError::InvalidConfiguration(_))
+    ));
+    assert!(valid()
+        .with_width(MAX_OUTPUT_DIMENSION + 2)
+        .validate()
+        .is_err());
+
+    // Audio settings are only checked when audio is captured
+    assert!(valid().with_sample_rate(44100).validate().is_ok());
+    let audio = || valid().with_captures_audio(true);
+    for rate in [8000, 16000, 24000, 48000] {
+        assert!(audio().with_sample_rate(rate).validate().is_ok());
+    }
+    let err = audio()
+        .with_sample_rate(44100)
+        .validate()
+        .expect_err("44.1 kHz is not a ScreenCaptureKit rate");
+    assert!(err.to_string().contains("44100"));
+    assert!(audio().with_channel_count(6).validate().is_err());
+    assert!(audio().with_channel_count(1).validate().is_ok());
+}
+
 #[test]
 fn test_large_dimensions() {
     let config = SCStreamConfiguration::new()
@@ -208,6 +245,22 @@ fn test_configuration_clone() {
     assert_eq!(config1.height(), config2.height());
 }
 
+#[test]
+fn test_configuration_deep_copy() {
+    let config1 = SCStreamConfiguration::new()
+        .with_width(1920)
+        .with_height(1080);
+
+    let mut config2 = config1.deep_copy();
+    assert_eq!(config2.width(), 1920);
+    assert_eq!(config2.height(), 1080);
+    assert_ne!(config1, config2);
+
+    config2.set_width(640);
+    assert_eq!(config2.width(), 640);
+    assert_eq!(config1.width(), 1920);
+}
+
 #[test]
 fn test_configuration_equality() {
     let config1 = SCStreamConfiguration::new();
@@ -382,8 +435,37 @@ fn test_excludes_current_process_audio() {
 
 #[test]
 fn test_color_space_name() {
-    let _config = SCStreamConfiguration::new().with_color_space_name("kCGColorSpaceSRGB");
-    // No getter, just verify no crash
+    let config = SCStreamConfiguration::new().with_color_space_name("kCGColorSpaceSRGB");
+    assert_eq!(
+        config.color_space_name().as_deref(),
+        Some("kCGColorSpaceSRGB")
+    );
+}
+
+#[test]
+fn test_color_space() {
+    use screencapturekit::stream::configuration::ColorSpace;
+
+    let names = [
+        (ColorSpace::SRGB, "kCGColorSpaceSRGB"),
+        (ColorSpace::DisplayP3, "kCGColorSpaceDisplayP3"),
+        (ColorSpace::ExtendedSRGB, "kCGColorSpaceExtendedSRGB"),
+        (
+            ColorSpace::ExtendedLinearSRGB,
+            "kCGColorSpaceExtendedLinearSRGB",
+        ),
+        (ColorSpace::Rec2020, "kCGColorSpaceITUR_2020"),
+    ];
+    for (color_space, name) in names {
+        assert_eq!(color_space.name(), name);
+        assert_eq!(color_space.to_string(), name);
+        assert_eq!(ColorSpace::from_name(name), Some(color_space));
+        let config = SCStreamConfiguration::new().with_color_space(color_space);
+        assert_eq!(config.color_space(), Some(color_space));
+        assert_eq!(config.color_space_name().as_deref(), Some(name));
+    }
+    assert_eq!(ColorSpace::default(), ColorSpace::SRGB);
+    assert_eq!(ColorSpace::from_name("kCGColorSpaceGenericGray"), None);
 }
 
 #[test]
@@ -457,3 +539,163 @@ fn test_screenshot_configuration() {
     assert!(!config.shows_cursor());
     assert!(!config.captures_audio());
 }
+
+#[test]
+fn test_dimension_alignment() {
+    assert_eq!(PixelFormat::BGRA.width_alignment(), 1);
+    assert_eq!(PixelFormat::l10r.width_alignment(), 1);
+    assert_eq!(PixelFormat::YCbCr_420v.width_alignment(), 2);
+    assert_eq!(PixelFormat::YCbCr_420f.height_alignment(), 2);
+
+    // Odd sizes are rounded up when a YCbCr format is already set...
+    let config = SCStreamConfiguration::new()
+        .with_pixel_format(PixelFormat::YCbCr_420v)
+        .with_width(1921)
+        .with_height(1081);
+    assert_eq!(config.effective_dimensions(), (1922, 1082));
+
+    // ...and when the format is set afterwards
+    let config = SCStreamConfiguration::new()
+        .with_width(641)
+        .with_height(479)
+        .with_pixel_format(PixelFormat::YCbCr_420f);
+    assert_eq!(config.effective_dimensions(), (642, 480));
+
+    let config = SCStreamConfiguration::new()
+        .with_width(641)
+        .with_height(479);
+    assert_eq!(config.effective_dimensions(), (641, 479));
+
+    // The setters report the rounding through the getters
+    let mut config = SCStreamConfiguration::new().with_pixel_format(PixelFormat::YCbCr_420v);
+    config.set_width(1279).set_height(719);
+    assert_eq!((config.width(), config.height()), (1280, 720));
+
+    // Rounding up would overflow, so the largest aligned value is used
+    assert_eq!(
+        PixelFormat::YCbCr_420f.align_dimensions(u32::MAX, u32::MAX),
+        (u32::MAX - 1, u32::MAX - 1)
+    );
+    assert_eq!(
+        PixelFormat::BGRA.align_dimensions(u32::MAX, 0),
+        (u32::MAX, 0)
+    );
+}
+
+#[test]
+fn test_frame_pacing() {
+    use screencapturekit::cg_display::CGDisplay;
+    use screencapturekit::shareable_content::SCShareableContent;
+    use screencapturekit::stream::configuration::FramePacing;
+
+    let config = SCStreamConfiguration::new().with_frame_pacing(FramePacing::Fixed(24));
+    assert_eq!(config.fps(), 24);
+
+    // Out-of-range fixed rates are clamped to a valid interval
+    let config = SCStreamConfiguration::new().with_frame_pacing(FramePacing::Fixed(0));
+    assert_eq!(config.fps(), 1);
+    let interval = FramePacing::Fixed(0).frame_interval();
+    assert_eq!((interval.value, interval.timescale), (1, 1));
+    let interval = FramePacing::Fixed(u32::MAX).frame_interval();
+    assert_eq!(interval.timescale, i32::MAX);
+
+    let interval = FramePacing::DisplayLink.frame_interval();
+    assert_eq!(interval.value, 1000);
+    assert!(interval.timescale > 0);
+
+    let config = SCStreamConfiguration::new().with_frame_pacing(FramePacing::DisplayLink);
+    let stored = config.minimum_frame_interval();
+    println!(
+        "Display link interval: {}/{}",
+        stored.value, stored.timescale
+    );
+    assert!(config.fps() > 0);
+
+    // Pacing to a specific display uses that display's mode
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    for display in content.displays() {
+        let pacing = FramePacing::for_display(&display);
+        assert_eq!(pacing, FramePacing::Display(display.display_id()));
+        let expected = CGDisplay::new(display.display_id())
+            .display_mode()
+            .unwrap_or_default()
+            .frame_interval();
+        assert_eq!(pacing.frame_interval(), expected);
+    }
+    // An unknown display falls back to 60 Hz
+    let interval = FramePacing::Display(u32::MAX).frame_interval();
+    assert_eq!((interval.value, interval.timescale), (1000, 60_000));
+}
+
+#[test]
+fn test_display_mode_frame_interval() {
+    use screencapturekit::cg_display::{CGDisplay, DisplayMode};
+
+    // A mode without a refresh rate falls back to 60 Hz
+    let interval = DisplayMode::default().frame_interval();
+    assert_eq!(interval.value, 1000);
+    assert_eq!(interval.timescale, 60_000);
+
+    let mode = CGDisplay::active_displays()
+        .ok()
+        .and_then(|ids| ids.first().copied())
+        .and_then(|id| CGDisplay::new(id).display_mode())
+        .unwrap_or_default();
+    let config = SCStreamConfiguration::new().with_minimum_frame_interval(&mode.frame_interval());
+    let stored = config.minimum_frame_interval();
+    println!(
+        "Refresh rate {} Hz -> interval {}/{}",
+        mode.refresh_rate(),
+        stored.value,
+        stored.timescale
+    );
+    assert!(mode.frame_interval().timescale > 0);
+}
+
+#[test]
+fn test_content_fit() {
+    use screencapturekit::cg::CGSize;
+    use screencapturekit::stream::configuration::ContentFitMode;
+
+    let source = CGSize::new(1920.0, 1080.0);
+    let base = || {
+        SCStreamConfiguration::new()
+            .with_width(400)
+            .with_height(400)
+    };
+
+    let config = base().with_content_fit(ContentFitMode::Stretch, source);
+    assert!(config.scales_to_fit());
+    assert!(!config.preserves_aspect_ratio());
+
+    let config = base().with_content_fit(ContentFitMode::Contain, source);
+    assert!(config.scales_to_fit());
+    assert!(config.source_rect().is_empty());
+
+    // Cover crops a centered square out of the wide source
+    let config = base().with_content_fit(ContentFitMode::Cover, source);
+    let crop = config.source_rect();
+    assert!((crop.x - 420.0).abs() < f64::EPSILON);
+    assert!(crop.y.abs() < f64::EPSILON);
+    assert!((crop.width - 1080.0).abs() < f64::EPSILON);
+    assert!((crop.height - 1080.0).abs() < f64::EPSILON);
+
+    // A tall output crops the top and bottom instead
+    let config = SCStreamConfiguration::new()
+        .with_width(1000)
+        .with_height(1000)
+        .with_content_fit(ContentFitMode::Cover, CGSize::new(800.0, 1200.0));
+    let crop = config.source_rect();
+    assert!((crop.y - 200.0).abs() < f64::EPSILON);
+    assert!((crop.height - 800.0).abs() < f64::EPSILON);
+
+    // Switching away from Cover drops its crop
+    for mode in [ContentFitMode::Contain, ContentFitMode::Stretch] {
+        let mut config = base().with_content_fit(ContentFitMode::Cover, source);
+        assert!(!config.source_rect().is_empty());
+        config.set_content_fit(mode, source);
+        assert!(config.source_rect().is_empty(), "{mode:?}");
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Tests for `SCStreamConfiguration` and related types
 
-use screencapturekit::stream::configuration::{PixelFormat, SCStreamConfiguration};
+use screencapturekit::stream::configuration::{ColorRange, PixelFormat, SCStreamConfiguration};
 
 #[test]
 fn test_default_configuration() {
@@ -32,6 +32,19 @@ fn test_set_dimensions_multiple() {
     assert_eq!(config.height(), 720);
 }
 
+#[test]
+fn test_set_size() {
+    use screencapturekit::cg::CGSize;
+
+    let config = SCStreamConfiguration::new().with_size(CGSize::new(1279.6, 720.2));
+    assert_eq!(config.width(), 1280);
+    assert_eq!(config.height(), 720);
+    assert_eq!(config.size(), CGSize::new(1280.0, 720.0));
+
+    let config = SCStreamConfiguration::new().with_size(CGSize::new(-10.0, 0.0));
+    assert_eq!((config.width(), config.height()), (0, 0));
+}
+
 #[test]
 fn test_set_pixel_format() {
     let formats = [
@@ -140,12 +153,53 @@ fn test_channel_counts() {
     }
 }
 
+#[test]
+fn test_color_range() {
+    let mut config = SCStreamConfiguration::new().with_pixel_format(PixelFormat::YCbCr_420v);
+    assert_eq!(config.color_range(), ColorRange::Video);
+
+    // Switching the range converts the configured YCbCr format
+    config.set_color_range(ColorRange::Full);
+    assert_eq!(config.pixel_format(), PixelFormat::YCbCr_420f);
+
+    // ...and applies to formats set afterwards
+    config.set_pixel_format(PixelFormat::YCbCr_420v);
+    assert_eq!(config.pixel_format(), PixelFormat::YCbCr_420f);
+    config.set_color_range(ColorRange::Video);
+    config.set_pixel_format(PixelFormat::YCbCr_420f);
+    assert_eq!(config.color_range(), ColorRange::Video);
+
+    // RGB formats are always full range
+    config.set_pixel_format(PixelFormat::BGRA);
+    assert_eq!(config.pixel_format(), PixelFormat::BGRA);
+    assert_eq!(config.color_range(), ColorRange::Full);
+}
+
 #[test]
 fn test_pixel_format_equality() {
     assert_eq!(PixelFormat::BGRA, PixelFormat::BGRA);
     assert_ne!(PixelFormat::BGRA, PixelFormat::YCbCr_420v);
 }
 
+#[test]
+fn test_pixel_format_from_raw() {
+    use screencapturekit::FourCharCode;
+
+    for format in [
+        PixelFormat::BGRA,
+        PixelFormat::l10r,
+        PixelFormat::YCbCr_420v,
+        PixelFormat::YCbCr_420f,
+    ] {
+        let raw = FourCharCode::from(format).as_u32();
+        assert_eq!(PixelFormat::from_raw(raw), Some(format));
+    }
+    // Unknown codes are not silently treated as BGRA
+    let unknown = FourCharCode::from_bytes(*b"y420").as_u32();
+    assert_eq!(PixelFormat::from_raw(unknown), None);
+    assert_eq!(PixelFormat::from(unknown), PixelFormat::BGRA);
+}
+
 #[test]
 fn test_pixel_format_in_collections() {
     use std::collections::HashSet;
@@ -389,6 +443,29 @@ fn test_ignore_global_clip_builder_pattern() {
     assert_eq!(config.width(), 1920);
 }
 
+#[test]
+#[cfg(feature = "macos_14_0")]
+fn test_clean_window_capture() {
+    let mut config = SCStreamConfiguration::new().with_clean_window_capture(true);
+    assert!(config.ignores_shadows_single_window());
+    assert!(config.ignores_shadows_display());
+    assert!(config.ignore_global_clip_single_window());
+    assert!(config.ignore_global_clip_display());
+    assert!(config.clean_window_capture());
+
+    config.set_clean_window_capture(false);
+    assert!(!config.ignores_shadows_single_window());
+    assert!(!config.ignores_shadows_display());
+    assert!(!config.ignore_global_clip_single_window());
+    assert!(!config.ignore_global_clip_display());
+    assert!(!config.clean_window_capture());
+
+    // Any one flag left off means the capture isn't clean
+    config.set_clean_window_capture(true);
+    config.set_ignore_global_clip_display(false);
+    assert!(!config.clean_window_capture());
+}
+
 #[test]
 #[cfg(feature = "macos_15_0")]
 fn test_preset_configuration() {
@@ -457,3 +534,123 @@ fn test_preserves_aspect_ratio_builder() {
 
     assert!(config.preserves_aspect_ratio());
 }
+
+#[test]
+fn test_pixel_buffer_attributes() {
+    use screencapturekit::stream::configuration::PixelBufferAttributes;
+
+    let config = SCStreamConfiguration::new();
+    assert!(config.pixel_buffer_attributes().is_empty());
+
+    let attributes = PixelBufferAttributes::new()
+        .with_metal_compatibility(true)
+        .with_opengl_compatibility(true)
+        .with_extended_pixels(1, 2, 16, 4);
+    let mut config = config.with_pixel_buffer_attributes(attributes);
+
+    let read_back = config.pixel_buffer_attributes();
+    assert_eq!(read_back, attributes);
+    assert!(read_back.metal_compatible());
+    assert!(read_back.opengl_compatible());
+    assert_eq!(read_back.extended_pixels(), (1, 2, 16, 4));
+
+    // Empty attributes clear the request
+    config.set_pixel_buffer_attributes(PixelBufferAttributes::default());
+    assert!(config.pixel_buffer_attributes().is_empty());
+}
+
+#[test]
+fn test_source_rect_in_display() {
+    use screencapturekit::cg::CGRect;
+    use screencapturekit::shareable_content::SCShareableContent;
+
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let bounds = display.frame();
+
+    // A rect inside the display is passed through unchanged
+    let config = SCStreamConfiguration::new()
+        .with_source_rect_in_display(&display, CGRect::new(10.0, 20.0, 100.0, 50.0));
+    assert_eq!(config.source_rect(), CGRect::new(10.0, 20.0, 100.0, 50.0));
+
+    // A rect hanging off the bottom-right corner is clipped to the display
+    let config = SCStreamConfiguration::new().with_source_rect_in_display(
+        &display,
+        CGRect::new(bounds.width - 10.0, bounds.height - 10.0, 100.0, 100.0),
+    );
+    let rect = config.source_rect();
+    assert!((rect.width - 10.0).abs() < f64::EPSILON);
+    assert!((rect.height - 10.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_with_scale() {
+    use screencapturekit::shareable_content::SCShareableContent;
+    use screencapturekit::stream::configuration::MAX_OUTPUT_DIMENSION;
+
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+
+    let native = SCStreamConfiguration::new().with_scale(&display, 1.0);
+    assert!(native.width() > 0 && native.height() > 0);
+
+    let half = SCStreamConfiguration::new().with_scale(&display, 0.5);
+    assert!(half.width().abs_diff(native.width() / 2) <= 1);
+    assert!(half.height().abs_diff(native.height() / 2) <= 1);
+
+    // Oversized results shrink to the limit on the longer side
+    let huge = SCStreamConfiguration::new().with_scale(&display, 100.0);
+    assert_eq!(huge.width().max(huge.height()), MAX_OUTPUT_DIMENSION);
+
+    // Invalid scales leave the size alone
+    let unchanged = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480)
+        .with_scale(&display, f64::NAN)
+        .with_scale(&display, -1.0);
+    assert_eq!((unchanged.width(), unchanged.height()), (640, 480));
+}
+
+#[test]
+fn test_audio_only_configuration() {
+    let config = SCStreamConfiguration::audio_only();
+
+    assert!(config.captures_audio());
+    assert_eq!(config.sample_rate(), 48000);
+    assert_eq!(config.channel_count(), 2);
+    assert!(!config.shows_cursor());
+    assert_eq!(config.width(), 2);
+    assert_eq!(config.height(), 2);
+    assert_eq!(config.fps(), 1);
+}
+
+#[test]
+fn test_audio_defaults_and_combined_config() {
+    use screencapturekit::stream::configuration::AudioConfig;
+
+    let config = SCStreamConfiguration::new().with_captures_audio(true);
+    assert!(config.sample_rate() > 0);
+    assert!(config.channel_count() > 0);
+
+    // Values set before enabling audio are kept
+    let config = SCStreamConfiguration::new()
+        .with_sample_rate(16000)
+        .with_channel_count(1)
+        .with_captures_audio(true);
+    assert_eq!(config.sample_rate(), 16000);
+    assert_eq!(config.channel_count(), 1);
+
+    let config = SCStreamConfiguration::new().with_audio(AudioConfig::default());
+    assert!(config.captures_audio());
+    assert_eq!(config.sample_rate(), 48000);
+    assert_eq!(config.channel_count(), 2);
+    assert!(!config.captures_microphone());
+}
@@ -1,8 +1,11 @@
 //! `SCContentFilter` tests
 
 use screencapturekit::cg::CGRect;
-use screencapturekit::shareable_content::SCShareableContent;
-use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::error::SCError;
+use screencapturekit::shareable_content::{SCDisplay, SCShareableContent, SCWindow};
+use screencapturekit::stream::configuration::SCStreamConfiguration;
+use screencapturekit::stream::content_filter::{SCContentFilter, SendFilter};
+use screencapturekit::stream::SCStreamType;
 
 // Initialize CoreGraphics to prevent CGS_REQUIRE_INIT crashes in CI
 fn cg_init_for_headless_ci() {
@@ -59,6 +62,157 @@ fn test_content_filter_exclude_windows() {
     }
 }
 
+#[test]
+fn test_content_filter_exclude_window_ids() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let ids: Vec<u32> = content
+        .windows()
+        .iter()
+        .take(2)
+        .map(SCWindow::window_id)
+        .collect();
+
+    // IDs survive a content refresh, and unknown IDs are ignored
+    let refreshed = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &refreshed.displays()[0];
+    let mut with_stale = ids.clone();
+    with_stale.push(u32::MAX);
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_window_ids(&refreshed, &with_stale)
+        .build();
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+
+    #[cfg(feature = "macos_15_2")]
+    {
+        let included: Vec<u32> = filter
+            .included_windows()
+            .iter()
+            .map(SCWindow::window_id)
+            .collect();
+        for id in &ids {
+            assert!(!included.contains(id), "window {id} was not excluded");
+        }
+    }
+
+    // Combined with reference-based exclusion, in either order
+    let windows = refreshed.windows();
+    let window_refs: Vec<&_> = windows.iter().skip(2).take(1).collect();
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_window_ids(&refreshed, &ids)
+        .exclude_windows(&window_refs)
+        .build();
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+
+    #[cfg(feature = "macos_15_2")]
+    {
+        let included: Vec<u32> = filter
+            .included_windows()
+            .iter()
+            .map(SCWindow::window_id)
+            .collect();
+        let excluded = ids
+            .iter()
+            .copied()
+            .chain(window_refs.iter().map(|w| w.window_id()));
+        for id in excluded {
+            assert!(!included.contains(&id), "window {id} was not excluded");
+        }
+    }
+}
+
+#[test]
+fn test_content_filter_exclude_app_bundle_ids() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    // Applications that are not running are skipped
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_app_bundle_ids(&content, &["com.apple.dock", "com.example.not-running"])
+        .build();
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+
+    #[cfg(feature = "macos_15_2")]
+    assert!(filter
+        .included_applications()
+        .iter()
+        .all(|app| app.bundle_identifier() != "com.apple.dock"));
+
+    // Combined with reference-based exclusion, in either order
+    let applications = content.applications();
+    let app_refs: Vec<&_> = applications.iter().take(1).collect();
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_app_bundle_ids(&content, &["com.apple.notificationcenterui"])
+        .exclude_applications(&app_refs, &[])
+        .build();
+
+    #[cfg(feature = "macos_15_2")]
+    {
+        use screencapturekit::shareable_content::SCRunningApplication;
+
+        let included: Vec<String> = filter
+            .included_applications()
+            .iter()
+            .map(SCRunningApplication::bundle_identifier)
+            .collect();
+        let excluded = app_refs
+            .iter()
+            .map(|app| app.bundle_identifier())
+            .chain(std::iter::once(
+                "com.apple.notificationcenterui".to_string(),
+            ));
+        for bundle_id in excluded {
+            assert!(
+                !included.contains(&bundle_id),
+                "{bundle_id} was not excluded"
+            );
+        }
+    }
+    #[cfg(not(feature = "macos_15_2"))]
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+}
+
+#[test]
+#[cfg(feature = "macos_15_2")]
+fn test_content_filter_exclude_app_bundle_ids_keeps_excluded_windows() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let windows = content.windows();
+
+    // One window to exclude directly, and another application to exclude by bundle ID
+    let Some(window) = windows.iter().find(|w| w.owning_application().is_some()) else {
+        return;
+    };
+    let owner = window.owning_application().map(|app| app.process_id());
+    let Some(other_app) = windows
+        .iter()
+        .filter_map(SCWindow::owning_application)
+        .find(|app| Some(app.process_id()) != owner)
+    else {
+        return;
+    };
+    let bundle_id = other_app.bundle_identifier();
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[window])
+        .exclude_app_bundle_ids(&content, &[bundle_id.as_str()])
+        .build();
+
+    let included = filter.included_windows();
+    assert!(included.iter().all(|w| w.window_id() != window.window_id()));
+    assert!(included.iter().all(|w| {
+        w.owning_application()
+            .map_or(true, |app| app.bundle_identifier() != bundle_id)
+    }));
+}
+
 #[test]
 fn test_content_filter_include_windows() {
     cg_init_for_headless_ci();
@@ -78,6 +232,56 @@ fn test_content_filter_include_windows() {
     }
 }
 
+#[test]
+fn test_content_filter_desktop_only() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .desktop_only(true)
+        .build();
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+
+    // Toggling back yields a plain display capture
+    let plain = SCContentFilter::builder()
+        .display(display)
+        .desktop_only(true)
+        .desktop_only(false)
+        .build();
+    assert_eq!(plain.stream_type(), SCStreamType::Display);
+
+    #[cfg(feature = "macos_15_2")]
+    {
+        let displays = filter.included_displays();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].display_id(), display.display_id());
+        assert!(filter.included_windows().is_empty());
+
+        let displays = plain.included_displays();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].display_id(), display.display_id());
+    }
+
+    // `false` only undoes `true`, so included windows stay included
+    if let Some(window) = content.windows().first() {
+        let windows = SCContentFilter::builder()
+            .display(display)
+            .include_windows(&[window])
+            .desktop_only(false)
+            .build();
+        assert_eq!(windows.stream_type(), SCStreamType::Display);
+
+        #[cfg(feature = "macos_15_2")]
+        {
+            let included = windows.included_windows();
+            assert_eq!(included.len(), 1);
+            assert_eq!(included[0].window_id(), window.window_id());
+        }
+    }
+}
+
 #[test]
 fn test_content_filter_include_applications() {
     cg_init_for_headless_ci();
@@ -242,6 +446,31 @@ fn test_content_filter_style() {
     ));
 }
 
+#[test]
+fn test_content_filter_stream_type() {
+    use screencapturekit::stream::SCStreamType;
+    cg_init_for_headless_ci();
+
+    assert_eq!(SCStreamType::from(0), SCStreamType::Window);
+    assert_eq!(SCStreamType::from(1), SCStreamType::Display);
+    assert_eq!(SCStreamType::from(-1), SCStreamType::Unknown);
+    assert_eq!(SCStreamType::Unknown.to_string(), "Unknown");
+
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    // Unknown before macOS 14.0
+    assert!(matches!(
+        filter.stream_type(),
+        SCStreamType::Display | SCStreamType::Unknown
+    ));
+}
+
 #[test]
 #[cfg(feature = "macos_14_0")]
 fn test_content_filter_style_window() {
@@ -413,3 +642,83 @@ fn test_shareable_content_style_from_i32() {
         SCShareableContentStyle::None
     ); // Unknown
 }
+
+#[test]
+fn test_filter_config_display_are_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SCContentFilter>();
+    assert_send_sync::<SCStreamConfiguration>();
+    assert_send_sync::<SCDisplay>();
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    // Build a filter on a worker thread and use it back on this one
+    let (filter, config) = std::thread::spawn(move || {
+        let filter = SCContentFilter::builder().display(&display).build();
+        let config = SCStreamConfiguration::new().with_width(640);
+        (filter, config)
+    })
+    .join()
+    .expect("worker thread panicked");
+
+    assert_eq!(config.width(), 640);
+    drop(filter);
+    println!("✓ Filter, configuration and display cross threads");
+}
+
+#[test]
+fn test_send_filter_hands_over_the_only_handle() {
+    fn assert_send<T: Send>() {}
+    assert_send::<SendFilter>();
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+
+    let filter = SCContentFilter::builder()
+        .display(&display)
+        .exclude_windows(&[])
+        .build_send();
+    let filter = std::thread::spawn(move || {
+        let mut filter = filter.into_inner();
+        filter.set_include_menu_bar(true);
+        filter
+    })
+    .join()
+    .expect("worker thread panicked");
+
+    assert_eq!(filter.stream_type(), SCStreamType::Display);
+}
+
+#[test]
+fn test_content_filter_for_window_id() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+
+    assert!(content.window_with_id(u32::MAX).is_none());
+    let result = SCContentFilter::for_window_id(u32::MAX);
+    assert!(matches!(result, Err(SCError::WindowNotFound(_))));
+
+    if let Some(window) = content.windows().first() {
+        let id = window.window_id();
+        let found = content.window_with_id(id).expect("window should be found");
+        assert_eq!(found.window_id(), id);
+
+        // The window may close between the two fetches
+        if let Ok(filter) = SCContentFilter::for_window_id(id) {
+            assert!(format!("{filter:?}").contains("SCContentFilter"));
+        }
+    }
+}
@@ -78,6 +78,29 @@ fn test_picker_api_availability() {
     println!("✓ Content sharing picker API available on macOS 14.0+");
 }
 
+#[test]
+#[cfg(feature = "testing")]
+fn test_picker_result_recommended_configuration() {
+    use screencapturekit::shareable_content::SCShareableContent;
+    use screencapturekit::stream::content_filter::SCContentFilter;
+    use screencapturekit::testing;
+
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let result = testing::picker_result(&filter);
+    let (width, height) = result.pixel_size();
+    assert!(width > 0 && height > 0);
+
+    let config = result.recommended_configuration();
+    assert_eq!(config.width(), width);
+    assert_eq!(config.height(), height);
+}
+
 // MARK: - Async Picker Tests
 
 #[test]
@@ -139,3 +139,97 @@ fn test_stream_activity_default_implementations() {
     delegate.stream_did_become_active();
     delegate.stream_did_become_inactive();
 }
+
+#[test]
+fn test_stream_callbacks_sleep_wake() {
+    use screencapturekit::stream::delegate_trait::StreamCallbacks;
+
+    struct MinimalDelegate;
+    impl SCStreamDelegateTrait for MinimalDelegate {}
+
+    let paused = Arc::new(AtomicBool::new(false));
+    let resumed = Arc::new(AtomicBool::new(false));
+    let paused_clone = paused.clone();
+    let resumed_clone = resumed.clone();
+
+    let delegate = StreamCallbacks::new()
+        .on_sleep(move |p| paused_clone.store(p, Ordering::SeqCst))
+        .on_wake(move |r| resumed_clone.store(r, Ordering::SeqCst));
+
+    delegate.stream_will_sleep(true);
+    delegate.stream_did_wake(true);
+
+    assert!(paused.load(Ordering::SeqCst));
+    assert!(resumed.load(Ordering::SeqCst));
+
+    // Default implementations are no-ops
+    MinimalDelegate.stream_will_sleep(false);
+    MinimalDelegate.stream_did_wake(false);
+}
+
+#[test]
+fn test_stream_callbacks_restart() {
+    use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    use std::sync::Mutex;
+
+    struct MinimalDelegate;
+    impl SCStreamDelegateTrait for MinimalDelegate {}
+
+    let received = Arc::new(Mutex::new(None));
+    let received_clone = received.clone();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let stopped_clone = stopped.clone();
+
+    let delegate = StreamCallbacks::new()
+        .on_restart(move |e| *received_clone.lock().unwrap() = Some(e.to_string()))
+        .on_stop(move |_| stopped_clone.store(true, Ordering::SeqCst));
+
+    delegate.did_restart(SCError::StreamError("display disconnected".to_string()));
+
+    let message = received.lock().unwrap().clone().expect("restart callback");
+    assert!(message.contains("display disconnected"));
+    // A successful restart is not a stop
+    assert!(!stopped.load(Ordering::SeqCst));
+
+    MinimalDelegate.did_restart(SCError::StreamError("ignored".to_string()));
+}
+
+#[test]
+fn test_stream_callbacks_handler_panic() {
+    use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    use std::sync::Mutex;
+
+    let received = Arc::new(Mutex::new(None));
+    let received_clone = received.clone();
+
+    let delegate = StreamCallbacks::new()
+        .on_handler_panic(move |msg| *received_clone.lock().unwrap() = Some(msg));
+
+    delegate.output_handler_did_panic("boom".to_string());
+    assert_eq!(received.lock().unwrap().as_deref(), Some("boom"));
+}
+
+#[test]
+fn test_stream_callbacks_format_negotiated() {
+    use screencapturekit::stream::configuration::PixelFormat;
+    use screencapturekit::stream::delegate_trait::StreamCallbacks;
+    use std::sync::Mutex;
+
+    struct MinimalDelegate;
+    impl SCStreamDelegateTrait for MinimalDelegate {}
+
+    let received = Arc::new(Mutex::new(None));
+    let received_clone = received.clone();
+
+    let delegate = StreamCallbacks::new().on_format_negotiated(move |requested, actual| {
+        *received_clone.lock().unwrap() = Some((requested, actual));
+    });
+
+    delegate.did_negotiate_format(PixelFormat::l10r, PixelFormat::BGRA);
+    assert_eq!(
+        *received.lock().unwrap(),
+        Some((PixelFormat::l10r, PixelFormat::BGRA))
+    );
+
+    MinimalDelegate.did_negotiate_format(PixelFormat::l10r, PixelFormat::BGRA);
+}
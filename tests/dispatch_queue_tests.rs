@@ -1,6 +1,6 @@
 //! Tests for dispatch queue functionality
 
-use screencapturekit::dispatch_queue::{DispatchQoS, DispatchQueue};
+use screencapturekit::dispatch_queue::{DispatchAutoreleaseFrequency, DispatchQoS, DispatchQueue};
 
 #[test]
 fn test_dispatch_queue_creation() {
@@ -47,3 +47,76 @@ fn test_dispatch_queue_drop() {
         let _queue = DispatchQueue::new("com.test.queue", DispatchQoS::Default);
     } // Queue should be dropped here without issues
 }
+
+#[test]
+fn test_dispatch_queue_autorelease_frequency() {
+    let queue = DispatchQueue::new("com.test.queue", DispatchQoS::UserInteractive);
+    assert_eq!(
+        queue.autorelease_frequency(),
+        DispatchAutoreleaseFrequency::Inherit
+    );
+
+    let queue = queue.with_autorelease_frequency(DispatchAutoreleaseFrequency::WorkItem);
+    assert!(!queue.as_ptr().is_null());
+    assert_eq!(queue.label(), "com.test.queue");
+    assert_eq!(queue.qos(), DispatchQoS::UserInteractive);
+    assert_eq!(
+        queue.autorelease_frequency(),
+        DispatchAutoreleaseFrequency::WorkItem
+    );
+
+    // Attributes survive cloning
+    let cloned = queue.clone();
+    assert_eq!(cloned.as_ptr(), queue.as_ptr());
+    assert_eq!(
+        cloned.autorelease_frequency(),
+        DispatchAutoreleaseFrequency::WorkItem
+    );
+}
+
+#[test]
+fn test_dispatch_queue_with_target() {
+    let shared = DispatchQueue::new("com.test.shared", DispatchQoS::UserInitiated);
+    let screen = DispatchQueue::new("com.test.screen", DispatchQoS::Default)
+        .with_target(&shared)
+        .with_autorelease_frequency(DispatchAutoreleaseFrequency::WorkItem);
+    assert!(!screen.as_ptr().is_null());
+    assert_ne!(screen.as_ptr(), shared.as_ptr());
+
+    // The target may be dropped before the queue that uses it
+    drop(shared);
+    assert!(format!("{screen:?}").contains("com.test.shared"));
+}
+
+#[test]
+fn test_dispatch_queue_pool() {
+    use screencapturekit::dispatch_queue::DispatchQueuePool;
+
+    let pool = DispatchQueuePool::new();
+    assert!(pool.is_empty());
+
+    let first = pool.get("com.test.pool", DispatchQoS::UserInteractive);
+    let second = pool.get("com.test.pool", DispatchQoS::Background);
+    assert_eq!(first.as_ptr(), second.as_ptr());
+    assert_eq!(second.qos(), DispatchQoS::UserInteractive);
+
+    let other = pool.get("com.test.pool.other", DispatchQoS::Default);
+    assert_ne!(other.as_ptr(), first.as_ptr());
+    assert_eq!(pool.len(), 2);
+
+    // Handed-out queues outlive their removal from the pool
+    assert!(pool.remove("com.test.pool").is_some());
+    assert!(!first.as_ptr().is_null());
+    let replaced = pool.get("com.test.pool", DispatchQoS::Default);
+    assert_ne!(replaced.as_ptr(), first.as_ptr());
+
+    let custom = DispatchQueue::new("com.test.pool.custom", DispatchQoS::Utility)
+        .with_autorelease_frequency(DispatchAutoreleaseFrequency::WorkItem);
+    assert!(pool.insert(custom.clone()).is_none());
+    assert_eq!(
+        pool.get("com.test.pool.custom", DispatchQoS::Default)
+            .as_ptr(),
+        custom.as_ptr()
+    );
+    assert!(format!("{pool:?}").contains("com.test.pool.custom"));
+}
@@ -1,6 +1,10 @@
 //! `CMFormatDescription` tests
 
-use screencapturekit::cm::{codec_types, media_types, CMFormatDescription};
+mod common;
+
+use screencapturekit::cm::{
+    codec_types, media_types, AudioCodecType, CMFormatDescription, MediaType, VideoCodecType,
+};
 use screencapturekit::FourCharCode;
 
 #[test]
@@ -17,6 +21,62 @@ fn test_media_type_constants() {
     assert_eq!(media_types::TIMECODE, FourCharCode::from_bytes(*b"tmcd"));
 }
 
+#[test]
+fn test_media_type_enum() {
+    let known = [
+        (media_types::VIDEO, MediaType::Video),
+        (media_types::AUDIO, MediaType::Audio),
+        (media_types::MUXED, MediaType::Muxed),
+        (media_types::TEXT, MediaType::Text),
+        (media_types::CLOSED_CAPTION, MediaType::ClosedCaption),
+        (media_types::METADATA, MediaType::Metadata),
+        (media_types::TIMECODE, MediaType::Timecode),
+    ];
+    for (code, media_type) in known {
+        assert_eq!(MediaType::from(code), media_type);
+        assert_eq!(media_type.four_char_code(), code);
+    }
+
+    let unknown = FourCharCode::from_bytes(*b"abcd");
+    assert_eq!(MediaType::from(unknown), MediaType::Raw(unknown));
+    assert_eq!(MediaType::Raw(unknown).four_char_code(), unknown);
+    assert_eq!(MediaType::Raw(unknown).to_string(), "abcd");
+    assert_eq!(MediaType::ClosedCaption.to_string(), "Closed Caption");
+}
+
+#[test]
+fn test_codec_type_enums() {
+    assert_eq!(
+        VideoCodecType::from(codec_types::H264),
+        VideoCodecType::H264
+    );
+    assert_eq!(
+        VideoCodecType::from(codec_types::HEVC),
+        VideoCodecType::HEVC
+    );
+    assert_eq!(
+        VideoCodecType::from(codec_types::HEVC_2),
+        VideoCodecType::HEVC
+    );
+    assert_eq!(
+        VideoCodecType::from(codec_types::PRORES_422_HQ),
+        VideoCodecType::ProRes422HQ
+    );
+    assert!(VideoCodecType::ProRes422Proxy.is_prores());
+    assert!(VideoCodecType::ProRes4444XQ.is_prores());
+    assert!(!VideoCodecType::H264.is_prores());
+
+    let bgra = FourCharCode::from_bytes(*b"BGRA");
+    assert_eq!(VideoCodecType::from(bgra), VideoCodecType::Raw(bgra));
+
+    assert_eq!(
+        AudioCodecType::from(codec_types::LPCM),
+        AudioCodecType::LinearPCM
+    );
+    assert_eq!(AudioCodecType::from(codec_types::AAC), AudioCodecType::AAC);
+    assert_eq!(AudioCodecType::from(bgra), AudioCodecType::Raw(bgra));
+}
+
 #[test]
 fn test_codec_type_constants() {
     // Video codecs
@@ -77,3 +137,42 @@ fn test_codec_type_in_collections() {
     assert!(codecs.contains(&codec_types::HEVC));
     assert!(!codecs.contains(&codec_types::JPEG));
 }
+
+#[test]
+fn test_audio_stream_basic_description_flags() {
+    use screencapturekit::cm::AudioStreamBasicDescription;
+
+    let asbd = AudioStreamBasicDescription {
+        sample_rate: 48_000.0,
+        format_id: codec_types::LPCM,
+        format_flags: AudioStreamBasicDescription::FLAG_IS_FLOAT
+            | AudioStreamBasicDescription::FLAG_IS_PACKED
+            | AudioStreamBasicDescription::FLAG_IS_NON_INTERLEAVED,
+        bytes_per_packet: 4,
+        frames_per_packet: 1,
+        bytes_per_frame: 4,
+        channels: 2,
+        bits_per_channel: 32,
+    };
+    assert!(asbd.is_linear_pcm());
+    assert!(asbd.is_float());
+    assert!(!asbd.is_signed_integer());
+    assert!(!asbd.is_big_endian());
+    assert!(asbd.is_non_interleaved());
+
+    let format = screencapturekit::cm::AudioFormat::from(asbd);
+    assert!((format.sample_rate - 48_000.0).abs() < f64::EPSILON);
+    assert_eq!(format.channels, 2);
+    assert!(!format.interleaved);
+}
+
+#[test]
+fn test_audio_stream_basic_description_of_video() {
+    let sample = common::plain_video_sample();
+
+    let format = sample
+        .format_description()
+        .expect("Video sample has a format description");
+    assert!(format.is_video());
+    assert_eq!(format.audio_stream_basic_description(), None);
+}
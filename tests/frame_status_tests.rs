@@ -1,5 +1,7 @@
 //! Tests for `SCFrameStatus`
 
+mod common;
+
 use screencapturekit::cm::SCFrameStatus;
 use std::collections::HashSet;
 
@@ -36,6 +38,24 @@ fn test_frame_status_is_complete() {
     assert!(!SCFrameStatus::Stopped.is_complete());
 }
 
+#[test]
+fn test_frame_status_is_idle() {
+    assert!(SCFrameStatus::Idle.is_idle());
+    assert!(!SCFrameStatus::Complete.is_idle());
+    assert!(!SCFrameStatus::Blank.is_idle());
+    assert!(!SCFrameStatus::Stopped.is_idle());
+}
+
+#[test]
+fn test_frame_status_is_droppable() {
+    assert!(!SCFrameStatus::Complete.is_droppable());
+    assert!(SCFrameStatus::Idle.is_droppable());
+    assert!(SCFrameStatus::Blank.is_droppable());
+    assert!(SCFrameStatus::Suspended.is_droppable());
+    assert!(!SCFrameStatus::Started.is_droppable());
+    assert!(SCFrameStatus::Stopped.is_droppable());
+}
+
 #[test]
 fn test_frame_status_display() {
     assert_eq!(format!("{}", SCFrameStatus::Complete), "Complete");
@@ -210,3 +230,37 @@ fn test_frame_status_result_usage() {
     assert!(check_status(SCFrameStatus::Idle).is_err());
     assert!(check_status(SCFrameStatus::Blank).is_err());
 }
+
+#[test]
+fn test_frame_status_missing_attachment() {
+    let sample = common::plain_video_sample();
+
+    // Buffers not produced by a stream carry no status attachment
+    assert_eq!(sample.frame_status(), None);
+    assert!(!sample.is_idle_frame());
+    assert!(!sample.is_droppable_frame());
+}
+
+#[test]
+fn test_frame_dirty_rects_missing_attachment() {
+    let sample = common::plain_video_sample();
+
+    // No attachment means no change information, not an error
+    assert_eq!(sample.dirty_rects(), None);
+    assert!(sample.dirty_pixel_rects().is_empty());
+}
+
+#[test]
+fn test_frame_scale_missing_attachment() {
+    let sample = common::plain_video_sample();
+
+    assert_eq!(sample.scale_factor(), None);
+    assert_eq!(sample.content_scale(), None);
+}
+
+#[test]
+fn test_frame_content_rect_missing_attachment() {
+    let sample = common::plain_video_sample();
+
+    assert_eq!(sample.content_rect(), None);
+}
@@ -441,6 +441,28 @@ fn test_iosurface_backed_buffer() {
     }
 }
 
+#[test]
+fn test_shareable_content_headless_graceful() {
+    // Deliberately no CoreGraphics pre-initialization: get() must handle it
+    match SCShareableContent::get() {
+        Ok(content) => {
+            // On headless runners this is an empty, but valid, result
+            assert_eq!(content.is_empty(), content.displays().is_empty());
+            if content.is_empty() {
+                assert!(content.windows().is_empty());
+                assert!(content.applications().is_empty());
+                let cloned = content.clone();
+                assert!(cloned.is_empty());
+                let _ = format!("{content:?} {cloned}");
+            }
+        }
+        Err(e) => {
+            // Only a permission failure is acceptable when displays exist
+            eprintln!("Shareable content unavailable: {e}");
+        }
+    }
+}
+
 #[test]
 fn test_shareable_content_below_window() {
     // Get shareable content to find a reference window
@@ -479,6 +501,35 @@ fn test_shareable_content_below_window() {
     }
 }
 
+#[test]
+fn test_shareable_content_get_with_options() {
+    use screencapturekit::shareable_content::{SCShareableContentOptions, SCWindow};
+
+    let options = SCShareableContentOptions::default().on_screen_windows_only(true);
+    let on_screen = match SCShareableContent::get_with_options(options) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Skipping test: {e}");
+            return;
+        }
+    };
+
+    assert!(on_screen.windows().iter().all(SCWindow::is_on_screen));
+    println!("Found {} on-screen windows", on_screen.windows().len());
+}
+
+#[test]
+fn test_session_state() {
+    use screencapturekit::shareable_content::SessionState;
+
+    let state = SCShareableContent::session_state();
+    println!("Session state: {state}");
+    assert!(SessionState::Active.is_capturable());
+    assert!(!SessionState::Locked.is_capturable());
+    assert!(!SessionState::LoginWindow.is_capturable());
+    assert_eq!(SessionState::LoginWindow.to_string(), "Login Window");
+}
+
 #[test]
 fn test_shareable_content_above_window() {
     // Get shareable content to find a reference window
@@ -517,6 +568,49 @@ fn test_shareable_content_above_window() {
     }
 }
 
+#[test]
+fn test_shareable_content_z_order_query() {
+    let content = match SCShareableContent::get() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Skipping test: {e}");
+            return;
+        }
+    };
+
+    let windows = content.windows();
+    if windows.is_empty() {
+        eprintln!("Skipping test: no windows available");
+        return;
+    }
+
+    let reference_window = &windows[0];
+
+    match SCShareableContent::query()
+        .above(reference_window)
+        .exclude_desktop_windows(true)
+        .fetch()
+    {
+        Ok(above) => println!("Found {} windows above via query", above.windows().len()),
+        Err(e) => eprintln!("Above query failed (may be expected): {e}"),
+    }
+
+    match SCShareableContent::query()
+        .below(reference_window)
+        .exclude_desktop_windows(true)
+        .fetch()
+    {
+        Ok(below) => println!("Found {} windows below via query", below.windows().len()),
+        Err(e) => eprintln!("Below query failed (may be expected): {e}"),
+    }
+
+    // Without a reference window the query behaves like `get()`
+    let all = SCShareableContent::query()
+        .fetch()
+        .expect("Unreferenced query should succeed when get() does");
+    assert!(!all.windows().is_empty());
+}
+
 #[cfg(feature = "macos_14_4")]
 #[test]
 fn test_shareable_content_current_process() {
@@ -537,3 +631,52 @@ fn test_shareable_content_current_process() {
         }
     }
 }
+
+#[test]
+fn test_on_surface_delivers_frames() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠️  Screen recording permission not granted - skipping test");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠️  No displays available - skipping test");
+        return;
+    };
+
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+    let filter = SCContentFilter::builder().display(&display).build();
+    let mut stream = SCStream::new(&filter, &config);
+
+    // Report sizes to the test thread, where a failed assert fails the test,
+    // and keep one surface past its callback by taking a use on it
+    let (sizes_tx, sizes_rx) = std::sync::mpsc::channel();
+    let kept = Arc::new(Mutex::new(None));
+    let kept_in_handler = kept.clone();
+    stream
+        .on_surface(SCStreamOutputType::Screen, move |surface| {
+            let _ = sizes_tx.send((surface.width(), surface.height()));
+            let mut kept = kept_in_handler.lock().unwrap();
+            if kept.is_none() {
+                surface.increment_use_count();
+                *kept = Some(surface.clone());
+            }
+        })
+        .expect("Failed to add surface handler");
+
+    stream.start_capture().expect("Failed to start capture");
+    let first = sizes_rx.recv_timeout(Duration::from_secs(5));
+    stream.stop_capture().expect("Failed to stop capture");
+
+    let first = first.expect("No surface delivered");
+    assert_eq!(first, (640, 480));
+    for size in sizes_rx.try_iter() {
+        assert_eq!(size, (640, 480));
+    }
+
+    let surface = kept.lock().unwrap().take().expect("No surface kept");
+    assert!(surface.use_count() >= 1);
+    assert!(surface.is_in_use());
+    surface.decrement_use_count();
+}
@@ -0,0 +1,24 @@
+//! Mach absolute time conversion tests
+
+use std::time::Duration;
+
+use screencapturekit::utils::mach_time_to_duration;
+
+#[test]
+fn test_mach_time_to_duration_zero() {
+    assert_eq!(mach_time_to_duration(0), Duration::ZERO);
+}
+
+#[test]
+fn test_mach_time_to_duration_scales_linearly() {
+    // A tick is at least a nanosecond on every supported machine
+    let three_ms = mach_time_to_duration(3_000_000);
+    assert!(three_ms >= Duration::from_millis(3));
+    assert_eq!(mach_time_to_duration(6_000_000), three_ms * 2);
+}
+
+#[test]
+fn test_mach_time_to_duration_does_not_overflow() {
+    let max = mach_time_to_duration(u64::MAX);
+    assert!(max >= mach_time_to_duration(u64::MAX / 2));
+}
@@ -0,0 +1,307 @@
+//! Mock shareable content tests (`testing` feature)
+
+#![cfg(feature = "testing")]
+
+use std::sync::Mutex;
+
+use screencapturekit::cg::CGRect;
+use screencapturekit::error::SCError;
+use screencapturekit::screenshot_manager::SCScreenshotManager;
+use screencapturekit::shareable_content::{
+    SCShareableContent, SCShareableContentOptions, SessionState,
+};
+use screencapturekit::stream::content_filter::SCContentFilter;
+use screencapturekit::testing::{self, MockApplication, MockContent, MockDisplay, MockWindow};
+
+// The mock is process-wide, so tests that install one take turns
+static MOCK_LOCK: Mutex<()> = Mutex::new(());
+
+fn sample_content() -> MockContent {
+    MockContent {
+        displays: vec![
+            MockDisplay::new(1, 2560, 1440),
+            MockDisplay::new(2, 1920, 1080).with_frame(CGRect::new(2560.0, 0.0, 1920.0, 1080.0)),
+        ],
+        windows: vec![
+            MockWindow::new(100, "Editor").with_owner(501),
+            MockWindow::new(101, "Hidden")
+                .with_owner(502)
+                .with_on_screen(false),
+            MockWindow::new(102, "Orphan"),
+        ],
+        applications: vec![
+            MockApplication::new(501, "Editor", "com.example.editor"),
+            MockApplication::new(502, "Helper", "com.example.helper"),
+        ],
+    }
+}
+
+#[test]
+fn test_mock_content_is_returned() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(sample_content());
+    assert!(testing::is_mock_active());
+
+    let content = SCShareableContent::get().expect("mock content");
+    assert!(!content.is_empty());
+    let displays = content.displays();
+    assert_eq!(displays.len(), 2);
+    assert_eq!(displays[0].display_id(), 1);
+    assert_eq!(displays[1].width(), 1920);
+    assert!((displays[1].frame().x - 2560.0).abs() < f64::EPSILON);
+
+    let windows = content.windows();
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows[0].title().as_deref(), Some("Editor"));
+    assert!(!windows[1].is_on_screen());
+    assert!(windows[2].owning_application().is_none());
+
+    let apps = content.applications();
+    assert_eq!(apps.len(), 2);
+    assert_eq!(apps[1].bundle_identifier(), "com.example.helper");
+
+    let summary = content.summary();
+    assert_eq!(summary.display_count, 2);
+    assert_eq!(summary.window_count, 3);
+    assert_eq!(summary.on_screen_window_count, 2);
+    assert_eq!(summary.application_count, 2);
+
+    let owner = content
+        .window_with_id(101)
+        .and_then(|window| window.owning_application())
+        .expect("owner");
+    assert_eq!(owner.process_id(), 502);
+    assert!(content.window_with_id(999).is_none());
+
+    // Options don't filter mock content
+    let options = SCShareableContentOptions::default().on_screen_windows_only(true);
+    let filtered = SCShareableContent::get_with_options(options).expect("mock content");
+    assert_eq!(filtered.windows().len(), 3);
+
+    // Content without displays is empty, as on a headless machine
+    testing::set_mock_content(MockContent {
+        windows: vec![MockWindow::new(7, "Offscreen")],
+        ..MockContent::default()
+    });
+    let headless = SCShareableContent::get().expect("mock content");
+    assert!(headless.is_empty());
+    assert_eq!(
+        headless.window_with_id(7).and_then(|window| window.title()),
+        Some("Offscreen".to_string())
+    );
+
+    testing::clear_mock_content();
+    assert!(!testing::is_mock_active());
+}
+
+#[test]
+fn test_mock_wrappers_outlive_mock() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(sample_content());
+    let content = SCShareableContent::get().expect("mock content");
+    let window = content.windows()[0].clone();
+    let copy = content.clone();
+    testing::clear_mock_content();
+
+    // Wrappers taken from a mock stay usable after it is removed
+    assert_eq!(window.window_id(), 100);
+    assert_eq!(window, copy.windows()[0]);
+    assert_eq!(copy.displays().len(), 2);
+    drop(copy);
+    drop(content);
+}
+
+#[test]
+fn test_watch_reports_content_changes() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(sample_content());
+
+    assert!(SCShareableContent::watch(Duration::ZERO, |_| {}).is_err());
+
+    let (sender, receiver) = mpsc::channel();
+    let watcher = SCShareableContent::watch(Duration::from_millis(10), move |delta| {
+        let _ = sender.send(delta);
+    })
+    .expect("watch");
+
+    let mut changed = sample_content();
+    changed.windows.retain(|window| window.window_id != 101);
+    changed
+        .windows
+        .push(MockWindow::new(103, "Settings").with_owner(503));
+    changed.applications.push(MockApplication::new(
+        503,
+        "Settings",
+        "com.example.settings",
+    ));
+    testing::set_mock_content(changed);
+
+    let delta = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("content delta");
+    assert_eq!(delta.added_windows, vec![103]);
+    assert_eq!(delta.removed_windows, vec![101]);
+    assert_eq!(delta.added_applications, vec![503]);
+    assert!(delta.removed_applications.is_empty());
+    assert!(!delta.is_empty());
+
+    watcher.stop();
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_window_source_rect() {
+    use screencapturekit::stream::configuration::SCStreamConfiguration;
+
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(MockContent {
+        windows: vec![
+            MockWindow::new(7, "Player").with_frame(CGRect::new(300.0, 200.0, 640.0, 480.0))
+        ],
+        ..MockContent::default()
+    });
+    let content = SCShareableContent::get().expect("mock content");
+    let window = &content.windows()[0];
+
+    // Window-local rects inside the window are used as they are
+    let config = SCStreamConfiguration::new()
+        .with_window_source_rect(window, CGRect::new(0.0, 40.0, 640.0, 360.0));
+    assert_eq!(config.source_rect(), CGRect::new(0.0, 40.0, 640.0, 360.0));
+
+    // Rects hanging off the window are clipped to its size, not its screen position
+    let config = SCStreamConfiguration::new()
+        .with_window_source_rect(window, CGRect::new(600.0, -20.0, 100.0, 100.0));
+    assert_eq!(config.source_rect(), CGRect::new(600.0, 0.0, 40.0, 80.0));
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_window_is_likely_protected() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    // (title, owning bundle ID, expected)
+    let cases: &[(&str, &str, bool)] = &[
+        // Streaming apps are flagged whatever the title
+        ("Home", "com.netflix.Netflix", true),
+        ("", "com.apple.TV", true),
+        // Browsers only when the title names a service as a whole word
+        ("Netflix", "com.apple.Safari", true),
+        ("Watch - Netflix", "com.google.Chrome", true),
+        ("Max | Stream Movies", "org.mozilla.firefox", true),
+        ("Disney+ Hotstar", "com.brave.Browser", true),
+        ("(Prime Video)", "com.microsoft.edgemac", true),
+        ("Maximum Overdrive - Wikipedia", "com.apple.Safari", false),
+        ("Hulu2 fan forum", "com.apple.Safari", false),
+        ("NetflixAndChill", "com.google.Chrome", false),
+        ("Inbox", "com.apple.Safari", false),
+        // Other applications never match on the title
+        ("Netflix", "com.example.editor", false),
+    ];
+
+    let mut windows = Vec::new();
+    let mut applications = Vec::new();
+    for (i, (title, bundle_id, _)) in cases.iter().enumerate() {
+        let id = u32::try_from(i).unwrap() + 1;
+        let pid = i32::try_from(i).unwrap() + 1000;
+        windows.push(MockWindow::new(id, *title).with_owner(pid));
+        applications.push(MockApplication::new(pid, "App", *bundle_id));
+    }
+    testing::set_mock_content(MockContent {
+        windows,
+        applications,
+        ..MockContent::default()
+    });
+
+    let content = SCShareableContent::get().expect("mock content");
+    for (window, (title, bundle_id, expected)) in content.windows().iter().zip(cases) {
+        assert_eq!(
+            window.is_likely_protected(),
+            *expected,
+            "{title:?} in {bundle_id}"
+        );
+    }
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_reinstalling_equal_content_reuses_it() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(sample_content());
+    let first = SCShareableContent::get().expect("mock content");
+    testing::set_mock_content(sample_content());
+    let second = SCShareableContent::get().expect("mock content");
+
+    // Wrappers compare by identity, so equal pointers mean nothing new was leaked
+    assert_eq!(first, second);
+    assert_eq!(first.windows()[0], second.windows()[0]);
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_capture_apis_reject_mocks() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    testing::set_mock_content(sample_content());
+    let content = SCShareableContent::get().expect("mock content");
+    let window = &content.windows()[0];
+    let display = &content.displays()[0];
+
+    let above = SCShareableContent::query().above(window).fetch();
+    assert!(matches!(above, Err(SCError::InvalidConfiguration(_))));
+    let filter = SCContentFilter::for_window_id(window.window_id());
+    assert!(matches!(filter, Err(SCError::InvalidConfiguration(_))));
+    let menu_bar = SCScreenshotManager::capture_menu_bar(display);
+    assert!(matches!(menu_bar, Err(SCError::InvalidConfiguration(_))));
+
+    let build = std::panic::catch_unwind(|| {
+        let _ = SCContentFilter::builder().display(display).build();
+    });
+    assert!(
+        build.is_err(),
+        "building a filter from a mock display must panic"
+    );
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_session_state_transitions() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    // Unlocked, then the screen locks, then the user switches away and back
+    let transitions = [
+        (SessionState::Active, true),
+        (SessionState::Locked, false),
+        (SessionState::LoginWindow, false),
+        (SessionState::Active, true),
+    ];
+    for (state, capturable) in transitions {
+        testing::set_mock_session_state(state);
+        let reported = SCShareableContent::session_state();
+        assert_eq!(reported, state);
+        assert_eq!(reported.is_capturable(), capturable, "{state}");
+    }
+
+    testing::clear_mock_session_state();
+}
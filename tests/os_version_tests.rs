@@ -0,0 +1,23 @@
+//! Runtime macOS version tests
+
+use screencapturekit::utils::MacOSVersion;
+
+#[test]
+fn test_current_version_is_supported() {
+    // The crate requires macOS 12.3 or later
+    let version = MacOSVersion::current();
+    assert!(version.is_at_least(12, 3), "unexpected version {version}");
+}
+
+#[test]
+fn test_version_comparison() {
+    let sonoma = MacOSVersion::new(14, 2, 1);
+    assert!(sonoma.is_at_least(14, 2));
+    assert!(sonoma.is_at_least(13, 5));
+    assert!(!sonoma.is_at_least(14, 3));
+    assert!(!sonoma.is_at_least(15, 0));
+
+    assert!(MacOSVersion::new(14, 0, 0) < sonoma);
+    assert!(MacOSVersion::new(26, 0, 0) > sonoma);
+    assert_eq!(sonoma.to_string(), "14.2.1");
+}
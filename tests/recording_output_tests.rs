@@ -154,6 +154,30 @@ fn test_recording_output_file_type() {
     assert_eq!(config.output_file_type(), SCRecordingOutputFileType::MOV);
 }
 
+#[test]
+fn test_recording_output_orientation() {
+    use screencapturekit::recording_output::SCRecordingOrientation;
+
+    let config = SCRecordingOutputConfiguration::new();
+    assert_eq!(config.orientation(), SCRecordingOrientation::Up);
+
+    let config = config.with_orientation(SCRecordingOrientation::Right);
+    assert_eq!(config.orientation(), SCRecordingOrientation::Right);
+    assert_eq!(config.orientation().degrees(), 90);
+
+    let config = config.with_orientation(SCRecordingOrientation::Up);
+    assert_eq!(config.orientation(), SCRecordingOrientation::Up);
+
+    assert_eq!(
+        SCRecordingOrientation::from_degrees(270.0),
+        SCRecordingOrientation::Left
+    );
+    assert_eq!(
+        SCRecordingOrientation::from_degrees(-180.0),
+        SCRecordingOrientation::Down
+    );
+}
+
 #[test]
 fn test_recording_output_available_codecs_count() {
     let config = SCRecordingOutputConfiguration::new();
@@ -170,6 +194,21 @@ fn test_recording_output_available_file_types_count() {
     println!("Available file types: {count}");
 }
 
+#[test]
+fn test_recording_output_supported_formats() {
+    use screencapturekit::recording_output;
+
+    let config = SCRecordingOutputConfiguration::new();
+    assert_eq!(
+        recording_output::supported_codecs(),
+        config.available_video_codecs()
+    );
+    assert_eq!(
+        recording_output::supported_file_types(),
+        config.available_output_file_types()
+    );
+}
+
 #[test]
 fn test_recording_output_recorded_duration() {
     let config = SCRecordingOutputConfiguration::new();
@@ -316,3 +355,95 @@ fn test_recording_output_file_type_array_matches_count() {
     // The array length should match the count
     assert_eq!(file_types.len(), count);
 }
+
+#[test]
+fn test_exclude_window_during_recording() {
+    use screencapturekit::prelude::*;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+
+    let err = stream
+        .exclude_window_during_recording(u32::MAX)
+        .expect_err("no window has this ID");
+    assert!(matches!(err, SCError::InvalidConfiguration(_)));
+
+    if let Some(window) = content.windows().first() {
+        stream
+            .exclude_window_during_recording(window.window_id())
+            .expect("display filters can leave out any window");
+        // Excluding the same window twice is not an error
+        stream
+            .exclude_window_during_recording(window.window_id())
+            .expect("window already excluded");
+    }
+}
+
+#[test]
+fn test_multiple_recording_outputs() {
+    use screencapturekit::prelude::*;
+    use screencapturekit::recording_output::{RecordingOutputMode, SCRecordingOutputCodec};
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let stream = SCStream::new(&filter, &SCStreamConfiguration::new());
+
+    let dir = std::env::temp_dir();
+    let master = SCRecordingOutputConfiguration::new()
+        .with_output_url(&dir.join("sck_multi_master.mov"))
+        .with_video_codec(SCRecordingOutputCodec::HEVC);
+    let proxy = SCRecordingOutputConfiguration::new()
+        .with_output_url(&dir.join("sck_multi_proxy.mp4"))
+        .with_video_codec(SCRecordingOutputCodec::H264);
+    let (Some(master), Some(proxy)) = (
+        SCRecordingOutput::new(&master),
+        SCRecordingOutput::new(&proxy),
+    ) else {
+        println!("⚠ Skipping - recording output unavailable");
+        return;
+    };
+
+    let master = stream
+        .add_recording_output_with_token(&master)
+        .expect("first output is always recorded by the system");
+    assert_eq!(master.mode(), RecordingOutputMode::Native);
+    let proxy = stream
+        .add_recording_output_with_token(&proxy)
+        .expect("second output is recorded natively or fanned out");
+    println!("Second recording output mode: {:?}", proxy.mode());
+
+    // Without the master the system holds at most one output (a native
+    // proxy), which it has already shown it accepts alongside another, so a
+    // replacement is native whichever mode the proxy got
+    stream
+        .remove_recording_output(master.output())
+        .expect("remove master");
+    let replacement = SCRecordingOutputConfiguration::new()
+        .with_output_url(&dir.join("sck_multi_replacement.mov"))
+        .with_video_codec(SCRecordingOutputCodec::HEVC);
+    let replacement = SCRecordingOutput::new(&replacement).expect("recording output");
+    let replacement = stream
+        .add_recording_output_with_token(&replacement)
+        .expect("replacement output");
+    assert_eq!(replacement.mode(), RecordingOutputMode::Native);
+
+    stream
+        .remove_recording_output(proxy.output())
+        .expect("remove proxy");
+    stream
+        .remove_recording_output(replacement.output())
+        .expect("remove replacement");
+}
@@ -2,7 +2,11 @@
 
 #![cfg(feature = "macos_14_0")]
 
-use screencapturekit::screenshot_manager::{CGImage, SCScreenshotManager};
+use screencapturekit::cg::CGSize;
+use screencapturekit::error::SCError;
+use screencapturekit::screenshot_manager::{
+    supported_image_formats, CGImage, ImageFormat, SCScreenshotManager, TimeLapse,
+};
 use screencapturekit::shareable_content::SCShareableContent;
 use screencapturekit::stream::configuration::SCStreamConfiguration;
 use screencapturekit::stream::content_filter::SCContentFilter;
@@ -21,6 +25,37 @@ fn test_screenshot_manager_type() {
     let _ = SCScreenshotManager;
 }
 
+#[test]
+fn test_image_format_checked_quality() {
+    assert_eq!(
+        ImageFormat::jpeg_checked(0.0).unwrap(),
+        ImageFormat::Jpeg(0.0)
+    );
+    assert_eq!(
+        ImageFormat::heic_checked(1.0).unwrap(),
+        ImageFormat::Heic(1.0)
+    );
+
+    for quality in [-0.1, 1.01, 80.0, f32::NAN] {
+        assert!(matches!(
+            ImageFormat::jpeg_checked(quality),
+            Err(SCError::InvalidConfiguration(_))
+        ));
+        assert!(ImageFormat::heic_checked(quality).is_err());
+    }
+}
+
+#[test]
+fn test_supported_image_formats() {
+    let formats = supported_image_formats();
+    // PNG and JPEG can be written on every supported macOS release
+    assert!(formats.contains(&ImageFormat::Png));
+    assert!(formats.contains(&ImageFormat::Jpeg(1.0)));
+    for format in &formats {
+        assert!(!format.extension().is_empty());
+    }
+}
+
 #[test]
 fn test_capture_image() {
     cg_init_for_headless_ci();
@@ -45,6 +80,154 @@ fn test_capture_image() {
     // Note: May fail if screen recording permission not granted
 }
 
+#[test]
+fn test_capture_best() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    if let Ok(image) = SCScreenshotManager::capture_best(&filter, &config) {
+        assert_eq!((image.width(), image.height()), (640, 480));
+    }
+    // Note: May fail if screen recording permission not granted
+}
+
+#[test]
+fn test_capture_menu_bar() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    if let Ok(image) = SCScreenshotManager::capture_menu_bar(display) {
+        // A full-width strip, much shorter than the display
+        assert!(image.width() > 0);
+        assert!(image.height() > 0);
+        assert!(image.height() * 10 < image.width());
+    }
+    // Note: May fail if screen recording permission not granted
+}
+
+#[test]
+fn test_capture_application() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let Some(app) = content
+        .windows()
+        .into_iter()
+        .filter(|w| w.is_on_screen() && w.window_layer() == 0)
+        .find_map(|w| w.owning_application())
+    else {
+        return;
+    };
+
+    if let Ok(image) = SCScreenshotManager::capture_application(&app, display) {
+        // The whole display, at native density
+        let size = image.size();
+        assert!(size.width >= display.frame().width);
+        assert!(size.height >= display.frame().height);
+    }
+    // Note: May fail if screen recording permission not granted, or if the
+    // app's windows are on another display
+}
+
+#[test]
+fn test_capture_application_without_windows_on_display() {
+    use screencapturekit::shareable_content::SCWindow;
+
+    cg_init_for_headless_ci();
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        println!("⚠ No displays available");
+        return;
+    };
+    // Background apps and agents have no on-screen window anywhere
+    let on_screen_pids: Vec<i32> = content
+        .windows()
+        .iter()
+        .filter(|window| window.is_on_screen())
+        .filter_map(SCWindow::owning_application)
+        .map(|app| app.process_id())
+        .collect();
+    let Some(app) = content
+        .applications()
+        .into_iter()
+        .find(|app| !on_screen_pids.contains(&app.process_id()))
+    else {
+        println!("⚠ Skipping - every app has an on-screen window");
+        return;
+    };
+
+    // Decided from the given content, before any capture is attempted
+    let err = SCScreenshotManager::capture_application_in(&content, &app, &display)
+        .expect_err("app without windows was captured");
+    assert!(
+        matches!(&err, SCError::ScreenshotError(message) if message.contains("has no windows on display")),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_capture_and_save() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let path = std::env::temp_dir().join("sck_capture_and_save.png");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+    if SCScreenshotManager::capture_and_save(&filter, &config, path, ImageFormat::Png).is_ok() {
+        let saved = CGImage::thumbnail_from_file(path, 1000).expect("Saved file is an image");
+        assert_eq!((saved.width(), saved.height()), (320, 240));
+        let _ = std::fs::remove_file(path);
+    }
+    // Note: May fail if screen recording permission not granted
+
+    let err = SCScreenshotManager::capture_and_save(
+        &filter,
+        &config,
+        "/nonexistent/dir/shot.png",
+        ImageFormat::Png,
+    );
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_capture_window_at_point() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let Some(target) = content
+        .windows()
+        .into_iter()
+        .find(|w| w.is_on_screen() && w.window_layer() == 0 && !w.frame().is_empty())
+    else {
+        return;
+    };
+
+    let point = target.frame().center();
+    if let Ok((window, image)) = SCScreenshotManager::capture_window_at_point(point) {
+        // Another window may cover the target, but the hit window always
+        // contains the point and is a normal window
+        assert!(window.frame().contains_point(point));
+        assert_eq!(window.window_layer(), 0);
+        assert!(image.width() > 0 && image.height() > 0);
+    }
+    // Note: May fail if screen recording permission not granted
+}
+
 #[test]
 fn test_capture_sample_buffer() {
     cg_init_for_headless_ci();
@@ -68,6 +251,49 @@ fn test_capture_sample_buffer() {
     }
 }
 
+#[test]
+fn test_capture_sample_buffer_stable() {
+    use screencapturekit::cm::SCFrameStatus;
+    use screencapturekit::screenshot_manager::{
+        capture_sample_buffer_with_stream_stable, StableCaptureOptions,
+    };
+
+    let options = StableCaptureOptions::complete().with_skip_frames(1);
+    assert_eq!(
+        options,
+        StableCaptureOptions {
+            skip_frames: 1,
+            require_complete: true,
+            timeout: std::time::Duration::from_secs(5),
+        }
+    );
+
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    let result = capture_sample_buffer_with_stream_stable(&filter, &config, options);
+    // Note: May fail if screen recording permission not granted
+    if let Ok(buffer) = result {
+        assert!(buffer.image_buffer().is_some());
+        assert_eq!(buffer.frame_status(), Some(SCFrameStatus::Complete));
+    }
+
+    // No stream starts and delivers a frame within a millisecond
+    let options = options.with_timeout(std::time::Duration::from_millis(1));
+    let result = capture_sample_buffer_with_stream_stable(&filter, &config, options);
+    assert!(matches!(result, Err(SCError::Timeout(_))), "{result:?}");
+}
+
 #[test]
 fn test_cgimage_send_sync() {
     fn assert_send<T: Send>() {}
@@ -76,6 +302,156 @@ fn test_cgimage_send_sync() {
     assert_sync::<CGImage>();
 }
 
+#[test]
+fn test_cgimage_from_rgba_round_trip() {
+    let (width, height) = (8, 4);
+    let pixels: Vec<u8> = (0..32_u8).flat_map(|i| [i * 7, i * 3, 200, 255]).collect();
+
+    let image = CGImage::from_rgba(width, height, &pixels).expect("Failed to create image");
+    assert_eq!(image.width(), width);
+    assert_eq!(image.height(), height);
+    assert_eq!(image.size(), CGSize::new(8.0, 4.0));
+    // rgba_data() renders in the device color space, so allow for rounding in the conversion
+    let read_back = image.rgba_data().expect("Failed to read pixels");
+    assert_eq!(read_back.len(), pixels.len());
+    assert!(read_back
+        .iter()
+        .zip(&pixels)
+        .all(|(actual, expected)| actual.abs_diff(*expected) <= 2));
+
+    assert!(CGImage::from_rgba(0, height, &[]).is_err());
+    assert!(CGImage::from_rgba(width, height, &pixels[..pixels.len() - 1]).is_err());
+}
+
+#[test]
+fn test_cgimage_thumbnail() {
+    let (width, height) = (400, 200);
+    let pixels = vec![128_u8; width * height * 4];
+    let mut image = CGImage::from_rgba(width, height, &pixels).expect("Failed to create image");
+    image.set_dpi(144.0);
+
+    let thumbnail = image.thumbnail(100).expect("Failed to create thumbnail");
+    assert_eq!((thumbnail.width(), thumbnail.height()), (100, 50));
+    assert!((thumbnail.dpi().unwrap() - 36.0).abs() < 0.01);
+    // A flat image stays flat, in the same color space
+    let thumbnail_pixels = thumbnail.rgba_data().expect("Failed to read pixels");
+    assert!(thumbnail_pixels.iter().all(|&byte| byte == 128));
+
+    // Thin images keep at least one pixel on the short side
+    let strip = CGImage::from_rgba(400, 1, &pixels[..400 * 4]).expect("Failed to create image");
+    let strip = strip.thumbnail(100).expect("Failed to create thumbnail");
+    assert_eq!((strip.width(), strip.height()), (100, 1));
+
+    // Images that already fit are not enlarged
+    let full = image.thumbnail(1000).expect("Failed to create thumbnail");
+    assert_eq!((full.width(), full.height()), (width, height));
+
+    assert!(image.thumbnail(0).is_err());
+
+    let path = std::env::temp_dir().join("sck_thumbnail_source.png");
+    let path = path.to_str().unwrap();
+    image.save_png(path).expect("Failed to save image");
+    let from_file = CGImage::thumbnail_from_file(path, 50).expect("Failed to load thumbnail");
+    assert_eq!((from_file.width(), from_file.height()), (50, 25));
+    let _ = std::fs::remove_file(path);
+
+    assert!(CGImage::thumbnail_from_file("/nonexistent/image.png", 50).is_err());
+}
+
+#[test]
+fn test_cgimage_draw() {
+    use screencapturekit::cg::{CGColor, CGPoint, CGRect};
+    use screencapturekit::screenshot_manager::DrawOp;
+
+    let (width, height) = (64, 32);
+    let white = vec![255_u8; width * height * 4];
+    let image = CGImage::from_rgba(width, height, &white).expect("Failed to create image");
+
+    let annotated = image
+        .draw(&[
+            DrawOp::FilledRect {
+                rect: CGRect::new(0.0, 0.0, 8.0, 8.0),
+                color: CGColor::RED,
+            },
+            DrawOp::Rect {
+                rect: CGRect::new(20.0, 4.0, 20.0, 20.0),
+                color: CGColor::BLUE,
+                width: 2.0,
+            },
+            DrawOp::Line {
+                from: CGPoint::new(0.0, 31.0),
+                to: CGPoint::new(63.0, 31.0),
+                color: CGColor::BLACK,
+                width: 1.0,
+            },
+            DrawOp::Text {
+                text: "Hi".to_string(),
+                origin: CGPoint::new(44.0, 2.0),
+                color: CGColor::BLACK,
+                size: 12.0,
+            },
+        ])
+        .expect("Failed to draw");
+    assert_eq!(annotated.width(), width);
+    assert_eq!(annotated.height(), height);
+
+    // Origin is the top-left corner: the filled rect covers the first pixel
+    let pixels = annotated.rgba_data().expect("Failed to read pixels");
+    assert!(pixels[0] > 200 && pixels[1] < 50 && pixels[2] < 50);
+    // The source image is unchanged
+    let source = image.rgba_data().expect("Failed to read pixels");
+    assert!(source.iter().all(|&value| value >= 253));
+
+    let invalid = DrawOp::Text {
+        text: "a\0b".to_string(),
+        origin: CGPoint::zero(),
+        color: CGColor::BLACK,
+        size: 12.0,
+    };
+    assert!(image.draw(&[invalid]).is_err());
+}
+
+#[test]
+fn test_cgimage_redact() {
+    use screencapturekit::cg::{CGColor, CGRect};
+    use screencapturekit::screenshot_manager::RedactMode;
+
+    // Vertical stripes, so blurring and pixelating change pixel values
+    let (width, height) = (32, 32);
+    let pixels: Vec<u8> = (0..width * height)
+        .flat_map(|i| {
+            if i % 2 == 0 {
+                [255, 255, 255, 255]
+            } else {
+                [0, 0, 0, 255]
+            }
+        })
+        .collect();
+    let image = CGImage::from_rgba(width, height, &pixels).expect("Failed to create image");
+    let region = [CGRect::new(0.0, 0.0, 16.0, 16.0)];
+
+    let solid = image
+        .redact(&region, RedactMode::Solid(CGColor::RED))
+        .expect("Failed to redact");
+    let data = solid.rgba_data().expect("Failed to read pixels");
+    assert!(data[0] > 200 && data[1] < 50, "top-left pixel is covered");
+    let outside = (31 * width + 31) * 4;
+    assert!(data[outside] < 50, "pixels outside the region are kept");
+
+    for mode in [RedactMode::Blur(4.0), RedactMode::Pixelate(8)] {
+        let redacted = image.redact(&region, mode).expect("Failed to redact");
+        assert_eq!(redacted.width(), width);
+        assert_eq!(redacted.height(), height);
+        let data = redacted.rgba_data().expect("Failed to read pixels");
+        // Neighbouring stripes inside the region no longer alternate
+        let (first, second) = (data[4 * 4], data[5 * 4]);
+        assert!(first.abs_diff(second) < 200, "{mode:?} left stripes intact");
+    }
+
+    // Nothing to redact yields an unchanged copy
+    assert!(image.redact(&[], RedactMode::Blur(4.0)).is_ok());
+}
+
 #[test]
 fn test_cgimage_rgba_data() {
     cg_init_for_headless_ci();
@@ -100,6 +476,395 @@ fn test_cgimage_rgba_data() {
     }
 }
 
+#[test]
+fn test_cgimage_content_hash_stable() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100);
+
+    if let Ok(image) = SCScreenshotManager::capture_image(&filter, &config) {
+        if let (Ok(a), Ok(b)) = (image.content_hash(), image.content_hash()) {
+            assert_eq!(a, b);
+        }
+    }
+}
+
+/// Decode a PNG and return its text chunks by keyword and its pixel density
+fn read_png_metadata(
+    path: &str,
+) -> (
+    std::collections::HashMap<String, String>,
+    Option<png::PixelDimensions>,
+) {
+    let file = std::fs::File::open(path).expect("Failed to open PNG");
+    let mut reader = png::Decoder::new(std::io::BufReader::new(file))
+        .read_info()
+        .expect("Failed to read PNG header");
+    // Text chunks may also follow the image data
+    let mut pixels = vec![0; reader.output_buffer_size().expect("PNG too large")];
+    reader
+        .next_frame(&mut pixels)
+        .expect("Failed to decode PNG");
+    reader.finish().expect("Failed to read trailing PNG chunks");
+
+    let info = reader.info();
+    let mut text = std::collections::HashMap::new();
+    for chunk in &info.uncompressed_latin1_text {
+        text.insert(chunk.keyword.clone(), chunk.text.clone());
+    }
+    for chunk in &info.compressed_latin1_text {
+        text.insert(chunk.keyword.clone(), chunk.get_text().expect("bad zTXt"));
+    }
+    for chunk in &info.utf8_text {
+        text.insert(chunk.keyword.clone(), chunk.get_text().expect("bad iTXt"));
+    }
+    (text, info.pixel_dims)
+}
+
+#[test]
+fn test_save_with_metadata() {
+    use screencapturekit::screenshot_manager::ImageMetadata;
+
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100);
+
+    let metadata = ImageMetadata::new()
+        .with_capture_time(std::time::SystemTime::now())
+        .with_description(format!("display {}", display.display_id()))
+        .with_dpi(144.0);
+    assert_eq!(metadata.dpi(), Some(144.0));
+    assert!(metadata.capture_time().is_some());
+
+    if let Ok(image) = SCScreenshotManager::capture_image(&filter, &config) {
+        let path = std::env::temp_dir().join(format!("sck_metadata_{}.png", std::process::id()));
+        let path = path.to_str().expect("temp path is valid UTF-8");
+        image
+            .save_with_metadata(path, ImageFormat::Png, &metadata)
+            .expect("Failed to save image with metadata");
+        let (text, pixel_dims) = read_png_metadata(path);
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(
+            text.get("Description").map(String::as_str),
+            metadata.description()
+        );
+
+        // Written as "yyyy:MM:dd HH:mm:ss" in local time
+        let stamp = text.get("Creation Time").expect("capture time not written");
+        assert_eq!(stamp.len(), 19, "unexpected capture time {stamp:?}");
+        for (i, c) in stamp.chars().enumerate() {
+            match i {
+                4 | 7 | 13 | 16 => assert_eq!(c, ':', "unexpected capture time {stamp:?}"),
+                10 => assert_eq!(c, ' ', "unexpected capture time {stamp:?}"),
+                _ => assert!(c.is_ascii_digit(), "unexpected capture time {stamp:?}"),
+            }
+        }
+
+        // 144 dpi is stored as pixels per meter
+        let dims = pixel_dims.expect("DPI not written");
+        assert_eq!(dims.unit, png::Unit::Meter);
+        assert!((5669..=5670).contains(&dims.xppu), "xppu = {}", dims.xppu);
+        assert_eq!(dims.xppu, dims.yppu);
+
+        // Null bytes in the description are rejected before reaching the encoder
+        let bad = ImageMetadata::new().with_description("a\0b");
+        assert!(image
+            .save_with_metadata(path, ImageFormat::Png, &bad)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_save_hdr() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100);
+
+    if let Ok(image) = SCScreenshotManager::capture_image(&filter, &config) {
+        // SDR input goes through every path too
+        for format in [ImageFormat::Heic(0.9), ImageFormat::Tiff, ImageFormat::Png] {
+            let path = std::env::temp_dir().join(format!(
+                "sck_hdr_{}.{}",
+                std::process::id(),
+                format.extension()
+            ));
+            let path = path.to_str().expect("temp path is valid UTF-8");
+            image
+                .save_hdr(path, format)
+                .expect("Failed to save HDR image");
+            assert!(std::fs::metadata(path).is_ok_and(|m| m.len() > 0));
+            let _ = std::fs::remove_file(path);
+        }
+
+        assert!(image.save_hdr("/tmp/a\0b.png", ImageFormat::Png).is_err());
+    }
+}
+
+/// Bit depth from the IHDR chunk of a PNG file
+fn png_bit_depth(path: &str) -> u8 {
+    let bytes = std::fs::read(path).expect("Failed to read PNG");
+    assert_eq!(&bytes[12..16], b"IHDR");
+    bytes[24]
+}
+
+#[test]
+fn test_save_hdr_leaves_sdr_images_alone() {
+    use screencapturekit::screenshot_manager::CGImage;
+
+    let pixels: Vec<u8> = (0..16u8)
+        .flat_map(|i| [i * 16, 255 - i * 16, 128, 255])
+        .collect();
+    let image = CGImage::from_rgba(4, 4, &pixels).expect("Failed to create image");
+
+    let dir = std::env::temp_dir();
+    let plain = dir.join(format!("sck_sdr_plain_{}.png", std::process::id()));
+    let hdr = dir.join(format!("sck_sdr_hdr_{}.png", std::process::id()));
+    let plain = plain.to_str().expect("temp path is valid UTF-8");
+    let hdr = hdr.to_str().expect("temp path is valid UTF-8");
+    image.save(plain, ImageFormat::Png).expect("Failed to save");
+    image
+        .save_hdr(hdr, ImageFormat::Png)
+        .expect("Failed to save HDR");
+
+    // No conversion to 16-bit PQ: the file is what save writes
+    assert_eq!(png_bit_depth(hdr), 8);
+    assert_eq!(std::fs::read(hdr).unwrap(), std::fs::read(plain).unwrap());
+    let _ = std::fs::remove_file(plain);
+    let _ = std::fs::remove_file(hdr);
+}
+
+#[test]
+#[cfg(feature = "macos_26_0")]
+fn test_save_hdr_keeps_extended_range() {
+    use screencapturekit::screenshot_manager::{
+        SCScreenshotConfiguration, SCScreenshotDynamicRange,
+    };
+
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+    let config = SCScreenshotConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_dynamic_range(SCScreenshotDynamicRange::HDR);
+
+    let Some(image) = SCScreenshotManager::capture_screenshot(&filter, &config)
+        .ok()
+        .and_then(|output| output.hdr_image())
+    else {
+        println!("⚠ Skipping - no HDR screenshot available");
+        return;
+    };
+    let color_space = image.color_space_name().unwrap_or_default();
+    if !color_space.contains("Extended") {
+        println!("⚠ Skipping - HDR screenshot is not extended range ({color_space})");
+        return;
+    }
+
+    let dir = std::env::temp_dir();
+    let png = dir.join(format!("sck_hdr_extended_{}.png", std::process::id()));
+    let heic = dir.join(format!("sck_hdr_extended_{}.heic", std::process::id()));
+    let png = png.to_str().expect("temp path is valid UTF-8");
+    let heic = heic.to_str().expect("temp path is valid UTF-8");
+
+    // PNG keeps the highlights as 16-bit PQ
+    image
+        .save_hdr(png, ImageFormat::Png)
+        .expect("Failed to save PNG");
+    assert_eq!(png_bit_depth(png), 16);
+
+    // HEIC carries an ISO 21496-1 gain map next to the SDR base image
+    image
+        .save_hdr(heic, ImageFormat::Heic(0.9))
+        .expect("Failed to save HEIC");
+    let bytes = std::fs::read(heic).expect("Failed to read HEIC");
+    let urn = b"urn:iso:std:iso:ts:21496:-1";
+    assert!(
+        bytes.windows(urn.len()).any(|window| window == urn),
+        "no gain map in {heic}"
+    );
+
+    let _ = std::fs::remove_file(png);
+    let _ = std::fs::remove_file(heic);
+}
+
+#[test]
+fn test_cgimage_color_space_name() {
+    use screencapturekit::screenshot_manager::CGImage;
+
+    let image = CGImage::from_rgba(2, 2, &[0u8; 16]).expect("Failed to create image");
+    assert_eq!(
+        image.color_space_name().as_deref(),
+        Some("kCGColorSpaceSRGB")
+    );
+}
+
+#[test]
+fn test_capture_burst() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100);
+
+    let interval = std::time::Duration::from_millis(50);
+    let empty = SCScreenshotManager::capture_burst(&filter, &config, 0, interval);
+    assert!(empty.is_ok_and(|frames| frames.is_empty()));
+
+    let started = std::time::Instant::now();
+    let (frames, failure) =
+        SCScreenshotManager::capture_burst_partial(&filter, &config, 3, interval);
+    match failure {
+        None => {
+            assert_eq!(frames.len(), 3);
+            assert!(started.elapsed() >= interval * 2);
+        }
+        Some((index, e)) => {
+            assert_eq!(index, frames.len());
+            println!("⚠ Burst stopped at frame {index}: {e}");
+        }
+    }
+}
+
+#[test]
+fn test_cgimage_dpi() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(100)
+        .with_height(100);
+
+    if let Ok(mut image) = SCScreenshotManager::capture_image(&filter, &config) {
+        // Captures are tagged with the backing resolution
+        assert!(image.dpi().is_some_and(|dpi| dpi > 0.0));
+
+        assert!(image.scale_factor() > 0.0);
+
+        image.set_dpi(144.0);
+        assert_eq!(image.dpi(), Some(144.0));
+        assert!((image.scale_factor() - 2.0).abs() < f64::EPSILON);
+
+        let path = std::env::temp_dir().join(format!("sck_dpi_{}.png", std::process::id()));
+        let path = path.to_str().expect("temp path is valid UTF-8");
+        image.save_png(path).expect("Failed to save image with DPI");
+        let _ = std::fs::remove_file(path);
+
+        image.set_dpi(0.0);
+        assert_eq!(image.dpi(), None);
+        assert!((image.scale_factor() - 1.0).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn test_timelapse_start_stop() {
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(160)
+        .with_height(120);
+
+    let dir = std::env::temp_dir().join(format!("sck_timelapse_{}", std::process::id()));
+    let handle = TimeLapse::new(&filter, &config, std::time::Duration::from_millis(100))
+        .output_dir(&dir)
+        .format(ImageFormat::Jpeg(0.5))
+        .skip_unchanged(true)
+        .start()
+        .expect("Failed to start time-lapse");
+
+    std::thread::sleep(std::time::Duration::from_millis(350));
+    let frames = handle.stop();
+
+    // Frames are only written when screen recording permission is granted
+    let written = std::fs::read_dir(&dir).map_or(0, Iterator::count);
+    assert_eq!(frames, written);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // A zero interval would capture in a tight loop
+    let result = TimeLapse::new(&filter, &config, std::time::Duration::ZERO)
+        .output_dir(&dir)
+        .start();
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+    assert!(!dir.exists());
+}
+
+#[tokio::test]
+#[cfg(feature = "async")]
+async fn test_async_capture_image() {
+    use screencapturekit::async_api;
+
+    cg_init_for_headless_ci();
+    let content = SCShareableContent::get().expect("Failed to get shareable content");
+    let display = &content.displays()[0];
+
+    let filter = SCContentFilter::builder()
+        .display(display)
+        .exclude_windows(&[])
+        .build();
+
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    // Note: May fail if screen recording permission not granted
+    if let Ok(image) = async_api::capture_image(&filter, &config).await {
+        assert!(image.width() > 0);
+        assert!(image.height() > 0);
+    }
+}
+
 // MARK: - New Screenshot Features (macOS 15.2+)
 
 #[test]
@@ -236,3 +236,1078 @@ fn test_stream_debug_display() {
 
     println!("✓ Debug and Display traits work");
 }
+
+#[test]
+fn test_stream_manager_lifecycle() {
+    use screencapturekit::stream::StreamManager;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = content.displays()[0].clone();
+    let mut manager = StreamManager::with_content(content);
+    assert!(manager.is_empty());
+
+    let filter = SCContentFilter::builder().display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+    let first = manager
+        .add_stream(&filter, &config, |_sample, _type| {})
+        .expect("Failed to add stream");
+    let second = manager
+        .add_stream(&filter, &config, |_sample, _type| {})
+        .expect("Failed to add stream");
+
+    assert_ne!(first, second);
+    assert_eq!(manager.ids(), vec![first, second]);
+    assert!(manager.stream(first).is_some());
+    assert!(!manager.is_running(first));
+
+    if manager.start_all().is_ok() {
+        assert!(manager.is_running(first));
+        assert!(manager.is_running(second));
+        manager.stop_all().expect("Failed to stop streams");
+        assert!(!manager.is_running(first));
+    }
+
+    assert!(manager.remove_stream(second).is_some());
+    assert_eq!(manager.len(), 1);
+    println!("✓ StreamManager lifecycle works");
+}
+
+#[test]
+fn test_stream_manager_handlers_only_see_their_stream() {
+    use screencapturekit::stream::StreamManager;
+    use std::sync::mpsc;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = content.displays()[0].clone();
+    let mut manager = StreamManager::with_content(content);
+    let filter = SCContentFilter::builder().display(&display).build();
+
+    // Each stream gets its own output width, so a frame's width tells which stream it came from
+    let mut receivers = Vec::new();
+    for width in [320, 640] {
+        let config = SCStreamConfiguration::new()
+            .with_width(width)
+            .with_height(240);
+        let (tx, rx) = mpsc::channel();
+        manager
+            .add_stream(&filter, &config, move |sample: CMSampleBuffer, _| {
+                if let Some(pixel_buffer) = sample.image_buffer() {
+                    let _ = tx.send(pixel_buffer.width());
+                }
+            })
+            .expect("Failed to add stream");
+        receivers.push((width as usize, rx));
+    }
+
+    if manager.start_all().is_err() {
+        println!("⚠ Skipping - streams could not be started");
+        return;
+    }
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    manager.stop_all().expect("Failed to stop streams");
+
+    for (width, rx) in receivers {
+        let widths: Vec<usize> = rx.try_iter().collect();
+        assert!(
+            widths.iter().all(|&w| w == width),
+            "handler for {width}px stream received frames of widths {widths:?}"
+        );
+    }
+    println!("✓ StreamManager handlers only receive their own stream's frames");
+}
+
+#[test]
+fn test_stream_panic_policy() {
+    use screencapturekit::stream::PanicPolicy;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::default();
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.panic_policy(), PanicPolicy::RemoveHandler);
+
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    stream.set_panic_policy(PanicPolicy::Ignore);
+    assert_eq!(stream.panic_policy(), PanicPolicy::Ignore);
+
+    // Clones share the policy
+    let cloned = stream.clone();
+    assert_eq!(cloned.panic_policy(), PanicPolicy::Ignore);
+
+    println!("✓ Panic policy set");
+}
+
+#[test]
+fn test_remove_output_handler_for_type_stops_callbacks() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let frames = Arc::new(AtomicUsize::new(0));
+    let frames_clone = frames.clone();
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert!(!stream.remove_output_handler_for_type(SCStreamOutputType::Screen));
+    stream.add_output_handler(
+        move |_, _| {
+            frames_clone.fetch_add(1, Ordering::SeqCst);
+        },
+        SCStreamOutputType::Screen,
+    );
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while frames.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    assert!(stream.remove_output_handler_for_type(SCStreamOutputType::Screen));
+    assert!(!stream.remove_output_handler_for_type(SCStreamOutputType::Screen));
+
+    let after_remove = frames.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(frames.load(Ordering::SeqCst), after_remove);
+
+    let _ = stream.stop_capture();
+    println!("✓ No callbacks after removing handlers ({after_remove} frames before)");
+}
+
+#[test]
+fn test_remove_each_of_several_output_handlers() {
+    use std::time::{Duration, Instant};
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let mut stream = SCStream::new(&filter, &config);
+    let first = stream
+        .add_output_handler(|_, _| {}, SCStreamOutputType::Screen)
+        .expect("first handler");
+    let second = stream
+        .add_output_handler(|_, _| {}, SCStreamOutputType::Screen)
+        .expect("second handler");
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while stream.diagnostics().frames_delivered == 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    // Both handlers share one native output, which goes with the second...
+    assert!(stream.remove_output_handler(first, SCStreamOutputType::Screen));
+    assert!(stream.remove_output_handler(second, SCStreamOutputType::Screen));
+    assert!(!stream.remove_output_handler(second, SCStreamOutputType::Screen));
+
+    // ...and once they are, no samples reach the stream at all
+    std::thread::sleep(Duration::from_millis(200));
+    let seen = |stream: &SCStream| {
+        let diagnostics = stream.diagnostics();
+        diagnostics.frames_delivered + diagnostics.frames_dropped
+    };
+    let after_remove = seen(&stream);
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(seen(&stream), after_remove);
+
+    let _ = stream.stop_capture();
+}
+
+#[test]
+fn test_same_type_handlers_receive_each_frame_once() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_fps(30);
+
+    let mut stream = SCStream::new(&filter, &config);
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let times = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&times);
+        stream
+            .add_output_handler(
+                move |sample: CMSampleBuffer, _| {
+                    sink.lock().unwrap().push(sample.presentation_timestamp());
+                },
+                SCStreamOutputType::Screen,
+            )
+            .expect("Failed to add handler");
+        received.push(times);
+    }
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(1));
+    stream.stop_capture().expect("Failed to stop capture");
+
+    let received: Vec<Vec<CMTime>> = received
+        .iter()
+        .map(|times| times.lock().unwrap().clone())
+        .collect();
+    for times in &received {
+        // Strictly increasing, so no frame reached a handler twice
+        assert!(
+            times.windows(2).all(|pair| pair[0] < pair[1]),
+            "duplicate or out-of-order frames: {times:?}"
+        );
+        assert_eq!(times, &received[0], "handlers saw different frames");
+    }
+    assert_eq!(
+        stream.diagnostics().frames_delivered,
+        received[0].len() as u64
+    );
+    println!("✓ {} frames, once per handler", received[0].len());
+}
+
+#[test]
+fn test_stream_diagnostics() {
+    use std::time::{Duration, Instant};
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    let mut stream = SCStream::new(&filter, &config);
+    let diagnostics = stream.diagnostics();
+    assert!(diagnostics.configuration.contains("640x480"));
+    assert!(!diagnostics.is_capturing);
+    assert_eq!(diagnostics.frames_delivered, 0);
+    assert_eq!(diagnostics.frames_dropped, 0);
+    assert!(diagnostics.last_frame_status.is_none());
+
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    assert!(stream.diagnostics().is_capturing);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while stream.diagnostics().frames_delivered == 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    stream.stop_capture().expect("Failed to stop capture");
+    let diagnostics = stream.diagnostics();
+    assert!(!diagnostics.is_capturing);
+    println!("{diagnostics}");
+}
+
+#[test]
+fn test_stream_wait_for_first_frame() {
+    use screencapturekit::error::SCError;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    let mut stream = SCStream::new(&filter, &config);
+    // Nothing to wait for before capture starts
+    assert!(stream
+        .wait_for_first_frame(Duration::from_millis(10))
+        .is_err());
+
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    match stream.wait_for_first_frame(Duration::from_secs(5)) {
+        Ok(()) => assert!(stream.diagnostics().frames_delivered > 0),
+        Err(SCError::Timeout(message)) => println!("⚠ {message}"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    stream.stop_capture().expect("Failed to stop capture");
+    assert!(stream
+        .wait_for_first_frame(Duration::from_millis(10))
+        .is_err());
+    println!("✓ wait_for_first_frame follows the capture state");
+}
+
+#[test]
+fn test_stream_heartbeat_repeats_idle_frames() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_fps(10)
+        .with_queue_depth(5);
+
+    let times = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&times);
+    let mut stream = SCStream::new(&filter, &config);
+    stream
+        .add_output_handler_with_heartbeat(
+            move |sample: CMSampleBuffer, _| {
+                if sample.frame_status() == Some(screencapturekit::cm::SCFrameStatus::Complete) {
+                    sink.lock().unwrap().push(sample.presentation_timestamp());
+                }
+            },
+            Duration::from_millis(100),
+        )
+        .expect("Failed to add heartbeat handler");
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(1));
+    stream.stop_capture().expect("Failed to stop capture");
+
+    let times = times.lock().unwrap().clone();
+    // Real frames and repeats together keep the timeline moving forward
+    assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+    if !times.is_empty() {
+        assert!(
+            times.len() >= 3,
+            "only {} frames in one second",
+            times.len()
+        );
+    }
+    println!("✓ {} frames with heartbeat", times.len());
+}
+
+#[test]
+fn test_stream_heartbeat_respects_panic_policy() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_queue_depth(5);
+
+    // The default policy disables a handler after its first panic, and
+    // repeats must not revive it
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let mut stream = SCStream::new(&filter, &config);
+    stream
+        .add_output_handler_with_heartbeat(
+            move |_sample: CMSampleBuffer, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                panic!("handler failure");
+            },
+            Duration::from_millis(50),
+        )
+        .expect("Failed to add heartbeat handler");
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_millis(500));
+    stream.stop_capture().expect("Failed to stop capture");
+
+    assert!(calls.load(Ordering::SeqCst) <= 1);
+    println!("✓ Heartbeat repeats follow the panic policy");
+}
+
+#[test]
+fn test_stream_heartbeat_rejects_zero_interval() {
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let mut stream = SCStream::new(&filter, &config);
+    let result =
+        stream.add_output_handler_with_heartbeat(|_sample: CMSampleBuffer, _| {}, Duration::ZERO);
+    assert!(matches!(result, Err(SCError::InvalidConfiguration(_))));
+
+    // The rejected handler was never registered, so a later one gets going
+    let id = stream
+        .add_output_handler_with_heartbeat(
+            |_sample: CMSampleBuffer, _| {},
+            Duration::from_millis(50),
+        )
+        .expect("Failed to add heartbeat handler");
+    assert!(stream.remove_output_handler(id, SCStreamOutputType::Screen));
+    println!("✓ Zero heartbeat interval rejected");
+}
+
+#[test]
+fn test_stream_dropped_due_to_backpressure() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480)
+        .with_fps(60)
+        .with_queue_depth(3);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.dropped_due_to_backpressure(), 0);
+
+    // A handler slower than the frame interval starves the surface pool
+    let complete = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&complete);
+    stream.add_output_handler(
+        move |sample: CMSampleBuffer, _| {
+            if sample.frame_status() == Some(screencapturekit::cm::SCFrameStatus::Complete) {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        },
+        SCStreamOutputType::Screen,
+    );
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(1));
+    stream.stop_capture().expect("Failed to stop capture");
+
+    let skipped = stream.dropped_due_to_backpressure();
+    assert_eq!(stream.diagnostics().dropped_due_to_backpressure, skipped);
+    // Every handler call overruns the 60 fps interval, so once more frames
+    // arrive than the pool holds, some presentation times must be skipped
+    let complete = complete.load(Ordering::SeqCst);
+    if complete <= 4 {
+        println!("⚠ Skipping - only {complete} complete frames (static screen?)");
+        return;
+    }
+    assert!(
+        skipped > 0,
+        "{complete} complete frames from a 50 ms handler at 60 fps but no drops counted"
+    );
+    println!("✓ {skipped} frames lost to backpressure");
+}
+
+#[test]
+fn test_stream_output_dimensions() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.output_dimensions(), None);
+
+    let (tx, rx) = mpsc::channel();
+    stream.add_output_handler(
+        move |sample: CMSampleBuffer, _| {
+            if let Some(pixel_buffer) = sample.image_buffer() {
+                let _ = tx.send((pixel_buffer.width(), pixel_buffer.height()));
+            }
+        },
+        SCStreamOutputType::Screen,
+    );
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    let Ok((width, height)) = rx.recv_timeout(Duration::from_secs(5)) else {
+        let _ = stream.stop_capture();
+        println!("⚠ No frames received");
+        return;
+    };
+    let _ = stream.stop_capture();
+
+    let dimensions = stream
+        .output_dimensions()
+        .expect("dimensions cached from the first frame");
+    assert_eq!(
+        (dimensions.0 as usize, dimensions.1 as usize),
+        (width, height)
+    );
+    println!(
+        "✓ Requested 640x480, negotiated {}x{}",
+        dimensions.0, dimensions.1
+    );
+}
+
+#[test]
+fn test_stream_audio_format() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480)
+        .with_captures_audio(true);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert_eq!(stream.audio_format(), None);
+
+    let (tx, rx) = mpsc::channel();
+    stream.add_output_handler(
+        move |sample: CMSampleBuffer, _| {
+            let asbd = sample
+                .format_description()
+                .and_then(|desc| desc.audio_stream_basic_description());
+            if let Some(asbd) = asbd {
+                let _ = tx.send(asbd);
+            }
+        },
+        SCStreamOutputType::Audio,
+    );
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+
+    let Ok(asbd) = rx.recv_timeout(Duration::from_secs(5)) else {
+        let _ = stream.stop_capture();
+        println!("⚠ No audio received");
+        return;
+    };
+    let _ = stream.stop_capture();
+
+    let format = stream
+        .audio_format()
+        .expect("format cached from the first audio buffer");
+    assert_eq!(format, asbd.into());
+    println!(
+        "✓ Audio format: {} Hz, {} channels, interleaved {}",
+        format.sample_rate, format.channels, format.interleaved
+    );
+}
+
+#[test]
+fn test_stream_stats_callback() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert!(stream.set_stats_callback(Duration::ZERO, |_| {}).is_err());
+
+    let (tx, rx) = mpsc::channel();
+    stream
+        .set_stats_callback(Duration::from_millis(50), move |stats| {
+            let _ = tx.send(stats);
+        })
+        .expect("Failed to set stats callback");
+    let stats = rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("Stats callback was not invoked");
+    assert!(!stats.is_capturing);
+    assert_eq!(stats.frames_delivered, 0);
+
+    stream.clear_stats_callback();
+    while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+    assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_stream_main_thread_delivery() {
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder()
+        .display(&display)
+        .exclude_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert!(!stream.delivers_on_main_thread(SCStreamOutputType::Screen));
+
+    stream.deliver_on_main_thread(SCStreamOutputType::Screen);
+    assert!(stream.delivers_on_main_thread(SCStreamOutputType::Screen));
+    assert!(!stream.delivers_on_main_thread(SCStreamOutputType::Audio));
+
+    // Shared with clones of the same native stream
+    let clone = stream.clone();
+    assert!(clone.delivers_on_main_thread(SCStreamOutputType::Screen));
+
+    stream.set_main_thread_delivery(SCStreamOutputType::Screen, false);
+    assert!(!stream.delivers_on_main_thread(SCStreamOutputType::Screen));
+}
+
+#[test]
+fn test_replay_buffer() {
+    use screencapturekit::stream::ReplayBuffer;
+    use std::time::Duration;
+
+    let replay = ReplayBuffer::new(Duration::from_secs(5));
+    assert_eq!(replay.duration(), Duration::from_secs(5));
+    assert!(replay.is_empty());
+    assert_eq!(replay.buffered_duration(), Duration::ZERO);
+
+    let path = std::env::temp_dir().join(format!("sck_replay_{}.mov", std::process::id()));
+    let path = path.to_str().expect("temp path is valid UTF-8");
+    assert!(replay.save_last(Duration::from_secs(1), path).is_err());
+
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder()
+        .display(&display)
+        .exclude_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let mut stream = SCStream::new(&filter, &config);
+    assert!(replay.attach(&mut stream).is_some());
+
+    if stream.start_capture().is_ok() {
+        std::thread::sleep(Duration::from_millis(500));
+        let _ = stream.stop_capture();
+        if !replay.is_empty() {
+            replay
+                .save_last(Duration::from_secs(1), path)
+                .expect("Failed to save replay");
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    replay.clear();
+    assert!(replay.is_empty());
+}
+
+#[test]
+fn test_stream_configuration_snapshot_and_batched_update() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let mut config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_fps(15);
+
+    let mut stream = SCStream::new(&filter, &config);
+
+    // The stream keeps the configuration as it was when created
+    config.set_width(640);
+    let current = stream.configuration().expect("stream configuration");
+    assert_eq!(current.width(), 320);
+    assert_eq!(current.height(), 240);
+    assert_eq!(current.fps(), 15);
+
+    // A closure that changes nothing never reaches the system
+    stream
+        .update(|changes| assert!(changes.is_empty()))
+        .expect("empty update");
+
+    // Changing the copy does not affect the stream
+    let mut copy = stream.configuration().expect("stream configuration");
+    copy.set_height(480);
+    assert_eq!(stream.configuration().map(|c| c.height()), Some(240));
+
+    // An applied batch replaces the stream's configuration in one go
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Screen);
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    let applied = stream.update(|changes| {
+        changes.width(640).height(480).fps(30);
+        assert!(!changes.is_empty());
+    });
+    if let Err(e) = applied {
+        println!("⚠ Skipping - live update not supported: {e}");
+        let _ = stream.stop_capture();
+        return;
+    }
+    let current = stream.configuration().expect("stream configuration");
+    assert_eq!((current.width(), current.height()), (640, 480));
+    assert_eq!(current.fps(), 30);
+    let _ = stream.stop_capture();
+}
+
+#[test]
+fn test_start_capture_rejects_audio_handler_without_captures_audio() {
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder().display(&display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240)
+        .with_captures_audio(false)
+        .with_captures_microphone(false);
+
+    let mut stream = SCStream::new(&filter, &config);
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Audio);
+    let err = stream
+        .start_capture()
+        .expect_err("audio handler without captures_audio");
+    assert!(matches!(err, SCError::InvalidConfiguration(_)));
+    assert!(err.to_string().contains("captures_audio"));
+
+    let mut stream = SCStream::new(&filter, &config);
+    stream.add_output_handler(|_, _| {}, SCStreamOutputType::Microphone);
+    let err = stream
+        .start_capture()
+        .expect_err("microphone handler without captures_microphone");
+    assert!(err.to_string().contains("captures_microphone"));
+}
+
+#[test]
+fn test_synced_capture() {
+    use screencapturekit::stream::SyncedCapture;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let Ok(content) = SCShareableContent::get() else {
+        println!("⚠ Skipping - no screen recording permission");
+        return;
+    };
+
+    if content.displays().is_empty() {
+        println!("⚠ No displays available");
+        return;
+    }
+
+    let display = &content.displays()[0];
+    let filter = SCContentFilter::builder().display(display).build();
+    let config = SCStreamConfiguration::new()
+        .with_width(640)
+        .with_height(480)
+        .with_queue_depth(8);
+
+    // Pairing needs an audio output
+    let result = SyncedCapture::new(&filter, &config, Duration::from_millis(100), |_, _| {});
+    assert!(result.is_err());
+
+    let config = config.with_captures_audio(true);
+    let pairs = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&pairs);
+    let capture = SyncedCapture::new(
+        &filter,
+        &config,
+        Duration::from_millis(100),
+        move |video, audio| {
+            sink.lock()
+                .unwrap()
+                .push((video.presentation_timestamp(), audio.len()));
+        },
+    )
+    .expect("Failed to create synced capture");
+    assert_eq!(capture.window(), Duration::from_millis(100));
+
+    if capture.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(1));
+    capture.stop_capture().expect("Failed to stop capture");
+
+    let pairs = pairs.lock().unwrap().clone();
+    // Frames are delivered in presentation order, each exactly once
+    assert!(
+        pairs.windows(2).all(|pair| pair[0].0 < pair[1].0),
+        "duplicate or out-of-order frames: {pairs:?}"
+    );
+    println!("✓ {} frames delivered with their audio", pairs.len());
+}
+
+#[test]
+fn test_output_handler_receives_surface() {
+    use screencapturekit::output::IOSurface;
+    use screencapturekit::stream::output_trait::SCStreamOutputTrait;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    enum Delivery {
+        Surface { surface: usize, pixels: usize },
+        Sample,
+    }
+
+    struct SurfaceCounter {
+        wants_surface: bool,
+        tx: mpsc::Sender<Delivery>,
+    }
+
+    impl SCStreamOutputTrait for SurfaceCounter {
+        fn did_output_sample_buffer(&self, _sample: CMSampleBuffer, _of_type: SCStreamOutputType) {
+            let _ = self.tx.send(Delivery::Sample);
+        }
+
+        fn wants_surface(&self) -> bool {
+            self.wants_surface
+        }
+
+        fn did_output_surface(
+            &self,
+            surface: IOSurface,
+            sample: CMSampleBuffer,
+            _of_type: SCStreamOutputType,
+        ) {
+            let pixels = sample.image_buffer().map_or(0, |buffer| buffer.width());
+            let _ = self.tx.send(Delivery::Surface {
+                surface: surface.width(),
+                pixels,
+            });
+        }
+    }
+
+    let Ok(content) = SCShareableContent::get() else {
+        return;
+    };
+    let Some(display) = content.displays().into_iter().next() else {
+        return;
+    };
+    let filter = SCContentFilter::builder()
+        .display(&display)
+        .exclude_windows(&[])
+        .build();
+    let config = SCStreamConfiguration::new()
+        .with_width(320)
+        .with_height(240);
+
+    let (surface_tx, surface_rx) = mpsc::channel();
+    let (sample_tx, sample_rx) = mpsc::channel();
+    let mut stream = SCStream::new(&filter, &config);
+    stream.add_output_handler(
+        SurfaceCounter {
+            wants_surface: true,
+            tx: surface_tx,
+        },
+        SCStreamOutputType::Screen,
+    );
+    // Without the opt-in every sample arrives through did_output_sample_buffer
+    stream.add_output_handler(
+        SurfaceCounter {
+            wants_surface: false,
+            tx: sample_tx,
+        },
+        SCStreamOutputType::Screen,
+    );
+
+    if stream.start_capture().is_err() {
+        println!("⚠ Skipping - capture could not start");
+        return;
+    }
+    let got_frame = stream.wait_for_first_frame(Duration::from_secs(2)).is_ok();
+    std::thread::sleep(Duration::from_millis(200));
+    stream.stop_capture().expect("Failed to stop capture");
+    drop(stream);
+
+    let mut surfaces = 0;
+    let mut others = 0;
+    for delivery in surface_rx.try_iter() {
+        match delivery {
+            Delivery::Surface { surface, pixels } => {
+                assert_eq!(surface, pixels, "surface and pixel buffer widths differ");
+                surfaces += 1;
+            }
+            Delivery::Sample => others += 1,
+        }
+    }
+    let plain: Vec<Delivery> = sample_rx.try_iter().collect();
+    assert!(plain
+        .iter()
+        .all(|delivery| matches!(delivery, Delivery::Sample)));
+    assert_eq!(plain.len(), surfaces + others);
+    // A complete frame always has a surface
+    if got_frame {
+        assert!(surfaces > 0, "no surface among {others} samples");
+    }
+    println!("✓ {surfaces} surface frames, {others} other samples");
+}
@@ -0,0 +1,98 @@
+//! Window selection tests (`testing` feature)
+
+#![cfg(feature = "testing")]
+
+use std::sync::Mutex;
+
+use screencapturekit::shareable_content::{
+    SCShareableContent, SCWindow, SavedWindow, WindowSelection,
+};
+use screencapturekit::testing::{self, MockApplication, MockContent, MockWindow};
+
+// The mock is process-wide, so tests that install one take turns
+static MOCK_LOCK: Mutex<()> = Mutex::new(());
+
+fn content_with(windows: Vec<MockWindow>) -> SCShareableContent {
+    testing::set_mock_content(MockContent {
+        displays: Vec::new(),
+        windows,
+        applications: vec![
+            MockApplication::new(501, "Editor", "com.example.editor"),
+            MockApplication::new(502, "Terminal", "com.example.terminal"),
+        ],
+    });
+    SCShareableContent::get().unwrap()
+}
+
+#[test]
+fn test_window_selection_records_windows() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let content = content_with(vec![
+        MockWindow::new(100, "main.rs").with_owner(501),
+        MockWindow::new(101, "Orphan"),
+    ]);
+
+    let selection = WindowSelection::from_windows(&content.windows());
+    assert_eq!(selection.len(), 2);
+    assert_eq!(
+        selection.windows[0],
+        SavedWindow {
+            window_id: 100,
+            bundle_id: Some("com.example.editor".to_string()),
+            title: Some("main.rs".to_string()),
+        }
+    );
+    assert_eq!(selection.windows[1].bundle_id, None);
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_window_selection_resolves_by_id_then_title() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let before = content_with(vec![
+        MockWindow::new(100, "main.rs").with_owner(501),
+        MockWindow::new(101, "zsh").with_owner(502),
+        MockWindow::new(102, "notes.md").with_owner(501),
+    ]);
+    let selection = WindowSelection::from_windows(&before.windows());
+
+    // The editor relaunched with new window IDs, and the terminal's old ID now
+    // belongs to an editor window
+    let after = content_with(vec![
+        MockWindow::new(101, "notes.md").with_owner(501),
+        MockWindow::new(200, "main.rs").with_owner(501),
+    ]);
+    let (windows, missing) = selection.resolve_with_missing(&after);
+    let ids: Vec<u32> = windows.iter().map(SCWindow::window_id).collect();
+    assert_eq!(ids, vec![200, 101]);
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].title.as_deref(), Some("zsh"));
+
+    assert_eq!(selection.resolve(&after).len(), 2);
+
+    testing::clear_mock_content();
+}
+
+#[test]
+fn test_window_selection_each_window_matches_once() {
+    let _guard = MOCK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let before = content_with(vec![
+        MockWindow::new(100, "Untitled").with_owner(501),
+        MockWindow::new(101, "Untitled").with_owner(501),
+    ]);
+    let selection = WindowSelection::from_windows(&before.windows());
+
+    let after = content_with(vec![MockWindow::new(300, "Untitled").with_owner(501)]);
+    let (windows, missing) = selection.resolve_with_missing(&after);
+    assert_eq!(windows.len(), 1);
+    assert_eq!(missing.len(), 1);
+
+    testing::clear_mock_content();
+}